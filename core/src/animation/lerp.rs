@@ -1,8 +1,8 @@
 use ribir_text::{Em, FontSize, Pixel, PIXELS_PER_EM};
 
 use crate::prelude::{
-  Angle, Box2D, Brush, Color, DevicePoint, DeviceRect, DeviceSize, DeviceVector, Point, Radius,
-  Rect, Size, Transform, Vector,
+  Angle, Box2D, Brush, Color, DashPattern, DevicePoint, DeviceRect, DeviceSize, DeviceVector,
+  Point, Radius, Rect, Size, Transform, Vector,
 };
 
 /// Linearly interpolate between two value.
@@ -158,6 +158,23 @@ impl Lerp for Brush {
   }
 }
 
+impl Lerp for DashPattern {
+  fn lerp(&self, to: &Self, factor: f32) -> Self {
+    if self.array.len() == to.array.len() {
+      let array = self
+        .array
+        .iter()
+        .zip(to.array.iter())
+        .map(|(from, to)| from.lerp(to, factor))
+        .collect::<Vec<_>>();
+      DashPattern::new(array, self.offset.lerp(&to.offset, factor))
+    } else {
+      // The patterns with different lengths can't interpolate.
+      to.clone()
+    }
+  }
+}
+
 impl Lerp for Transform {
   fn lerp(&self, to: &Self, factor: f32) -> Self {
     let m11 = self.m11.lerp(&to.m11, factor);
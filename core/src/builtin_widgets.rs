@@ -15,6 +15,8 @@ use ribir_algo::Sc;
 pub use theme::*;
 mod cursor;
 pub use cursor::*;
+mod draggable;
+pub use draggable::*;
 pub use winit::window::CursorIcon;
 mod margin;
 pub use margin::*;
@@ -113,6 +115,7 @@ pub struct FatObj<T> {
   has_focus: Option<State<HasFocus>>,
   mouse_hover: Option<State<MouseHover>>,
   pointer_pressed: Option<State<PointerPressed>>,
+  draggable: Option<State<Draggable>>,
   fitted_box: Option<State<FittedBox>>,
   box_decoration: Option<State<BoxDecoration>>,
   padding: Option<State<Padding>>,
@@ -179,6 +182,7 @@ impl<T> FatObj<T> {
       has_focus: self.has_focus,
       mouse_hover: self.mouse_hover,
       pointer_pressed: self.pointer_pressed,
+      draggable: self.draggable,
       fitted_box: self.fitted_box,
       box_decoration: self.box_decoration,
       padding: self.padding,
@@ -209,6 +213,7 @@ impl<T> FatObj<T> {
       && self.has_focus.is_none()
       && self.mouse_hover.is_none()
       && self.pointer_pressed.is_none()
+      && self.draggable.is_none()
       && self.fitted_box.is_none()
       && self.box_decoration.is_none()
       && self.padding.is_none()
@@ -325,6 +330,14 @@ impl<T> FatObj<T> {
       .get_or_insert_with(|| State::value(<_>::default()))
   }
 
+  /// Returns the `State<Draggable>` widget from the FatObj. If it doesn't
+  /// exist, a new one is created.
+  pub fn get_draggable_widget(&mut self) -> &mut State<Draggable> {
+    self
+      .draggable
+      .get_or_insert_with(|| State::value(<_>::default()))
+  }
+
   /// Returns the `State<Cursor>` widget from the FatObj. If it doesn't exist, a
   /// new one is created.
   pub fn get_cursor_widget(&mut self) -> &mut State<Cursor> {
@@ -502,6 +515,18 @@ impl<T> FatObj<T> {
     on_mixin!(self, on_pointer_cancel, f)
   }
 
+  /// Attaches a handler to the widget that is triggered when it captures a
+  /// pointer.
+  pub fn on_got_pointer_capture(mut self, f: impl FnMut(&mut PointerEvent) + 'static) -> Self {
+    on_mixin!(self, on_got_pointer_capture, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when the pointer it
+  /// captured is released.
+  pub fn on_lost_pointer_capture(mut self, f: impl FnMut(&mut PointerEvent) + 'static) -> Self {
+    on_mixin!(self, on_lost_pointer_capture, f)
+  }
+
   /// Attaches a handler to the widget that is triggered when a pointer device
   /// is moved into the hit test boundaries of an widget or one of its
   /// descendants.
@@ -584,6 +609,90 @@ impl<T> FatObj<T> {
     self
   }
 
+  /// Attaches a handler to the widget that is triggered when the pointers start
+  /// to drag over the widget.
+  pub fn on_pan_start(mut self, f: impl FnMut(&mut PanEvent) + 'static) -> Self {
+    on_mixin!(self, on_pan_start, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when the dragging
+  /// pointers move.
+  pub fn on_pan_update(mut self, f: impl FnMut(&mut PanEvent) + 'static) -> Self {
+    on_mixin!(self, on_pan_update, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when the dragging
+  /// pointers lift up, the event carries the velocity of the pointers.
+  pub fn on_pan_end(mut self, f: impl FnMut(&mut PanEvent) + 'static) -> Self {
+    on_mixin!(self, on_pan_end, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when two or more
+  /// pointers pinch on the widget.
+  pub fn on_scale(mut self, f: impl FnMut(&mut ScaleEvent) + 'static) -> Self {
+    on_mixin!(self, on_scale, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when two or more
+  /// pointers rotate on the widget.
+  pub fn on_rotate(mut self, f: impl FnMut(&mut RotateEvent) + 'static) -> Self {
+    on_mixin!(self, on_rotate, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when a pointer presses
+  /// on the widget for a while without moving.
+  pub fn on_long_press(mut self, f: impl FnMut(&mut LongPressEvent) + 'static) -> Self {
+    on_mixin!(self, on_long_press, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when a pointer drags
+  /// fast over the widget and lifts up.
+  pub fn on_swipe(mut self, f: impl FnMut(&mut SwipeEvent) + 'static) -> Self {
+    on_mixin!(self, on_swipe, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when a dragged widget
+  /// enters it.
+  pub fn on_drag_enter(mut self, f: impl FnMut(&mut DragEvent) + 'static) -> Self {
+    on_mixin!(self, on_drag_enter, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when a dragged widget
+  /// moves over it.
+  pub fn on_drag_over(mut self, f: impl FnMut(&mut DragEvent) + 'static) -> Self {
+    on_mixin!(self, on_drag_over, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when a dragged widget
+  /// leaves it, or the drag over it is dropped or cancelled.
+  pub fn on_drag_leave(mut self, f: impl FnMut(&mut DragEvent) + 'static) -> Self {
+    on_mixin!(self, on_drag_leave, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when a dragged widget
+  /// is dropped on it.
+  pub fn on_drop(mut self, f: impl FnMut(&mut DragEvent) + 'static) -> Self {
+    on_mixin!(self, on_drop, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when files dragged
+  /// from the operating system hover it.
+  pub fn on_file_hover(mut self, f: impl FnMut(&mut FileDropEvent) + 'static) -> Self {
+    on_mixin!(self, on_file_hover, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when the files
+  /// hovering it leave, are dropped or cancelled.
+  pub fn on_file_hover_end(mut self, f: impl FnMut(&mut FileDropEvent) + 'static) -> Self {
+    on_mixin!(self, on_file_hover_end, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when files dragged
+  /// from the operating system are dropped on it.
+  pub fn on_file_drop(mut self, f: impl FnMut(&mut FileDropEvent) + 'static) -> Self {
+    on_mixin!(self, on_file_drop, f)
+  }
+
   /// Attaches a handler to the widget that is triggered when the user rotates a
   /// wheel button on a pointing device (typically a mouse).
   pub fn on_wheel(mut self, f: impl FnMut(&mut WheelEvent) + 'static) -> Self {
@@ -756,11 +865,33 @@ impl<T> FatObj<T> {
     self.declare_builtin_init(v, Self::get_box_decoration_widget, |m, v| m.border_radius = v)
   }
 
+  /// Initializes the shadows of the widget.
+  pub fn box_shadow<const M: u8>(self, v: impl DeclareInto<Vec<BoxShadow>, M>) -> Self {
+    self.declare_builtin_init(v, Self::get_box_decoration_widget, |m, v| m.box_shadow = v)
+  }
+
+  /// Initializes the filter applied to the content behind the widget.
+  pub fn backdrop_filter<const M: u8>(
+    self, v: impl DeclareInto<Option<BackdropFilter>, M>,
+  ) -> Self {
+    self.declare_builtin_init(v, Self::get_box_decoration_widget, |m, v| m.backdrop_filter = v)
+  }
+
   /// Initializes the extra space within the widget.
   pub fn padding<const M: u8>(self, v: impl DeclareInto<EdgeInsets, M>) -> Self {
     self.declare_builtin_init(v, Self::get_padding_widget, |m, v| m.padding = v)
   }
 
+  /// Initializes the payload of the widget to make it draggable.
+  pub fn draggable<const M: u8>(self, v: impl DeclareInto<Option<DragData>, M>) -> Self {
+    self.declare_builtin_init(v, Self::get_draggable_widget, |m, v| m.draggable = v)
+  }
+
+  /// Initializes the widget follows the pointer while dragging the widget.
+  pub fn drag_preview<const M: u8>(self, v: impl DeclareInto<GenWidget, M>) -> Self {
+    self.declare_builtin_init(v, Self::get_draggable_widget, |m, v| m.drag_preview = Some(v))
+  }
+
   /// Initializes the cursor of the widget.
   pub fn cursor<const M: u8>(self, v: impl DeclareInto<CursorIcon, M>) -> Self {
     self.declare_builtin_init(v, Self::get_cursor_widget, |m, v| m.cursor = v)
@@ -899,6 +1030,7 @@ impl<'a> FatObj<Widget<'a>> {
           has_focus,
           mouse_hover,
           pointer_pressed,
+          draggable,
           cursor,
           margin,
           transform,
@@ -21,6 +21,11 @@ pub enum Align {
   /// [`HAlign::Stretch`]! if direction is horizontal and [`VAlign::Stretch`]!
   /// if direction is vertical.
   Stretch,
+  /// The children are aligned by the baseline of their first line of text, in
+  /// the cross axis of a horizontal layout. The children without a baseline
+  /// use their bottom edge as the baseline. Work as `Start` if the layout not
+  /// supports it.
+  Baseline,
 }
 
 /// A enum that describe how widget align to its box in x-axis.
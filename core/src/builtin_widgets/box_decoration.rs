@@ -10,6 +10,15 @@ pub struct BoxDecoration {
   /// The corners of this box are rounded by this `BorderRadius`. The round
   /// corner only work if the two borders beside it are same style.
   pub border_radius: Option<Radius>,
+  /// The shadows cast by the box, follow the corners of the box. The first
+  /// shadow is on the top, the outer shadows are painted below the
+  /// background, and the inner shadows are painted above the background but
+  /// below the border.
+  pub box_shadow: Vec<BoxShadow>,
+  /// The filter applied to the content painted behind the box, it follows the
+  /// corners of the box and is painted below the background, so a translucent
+  /// background looks like a frosted glass.
+  pub backdrop_filter: Option<BackdropFilter>,
 }
 
 impl Declare for BoxDecoration {
@@ -30,11 +39,20 @@ pub struct Border {
 pub struct BorderSide {
   pub color: Brush,
   pub width: f32,
+  /// The dash pattern of the side, a solid line if it's `None`.
+  pub dash: Option<DashPattern>,
 }
 
 impl BorderSide {
   #[inline]
-  pub fn new(width: f32, color: Brush) -> Self { Self { width, color } }
+  pub fn new(width: f32, color: Brush) -> Self { Self { width, color, dash: None } }
+
+  /// Convert the side to a dashed line.
+  #[inline]
+  pub fn with_dash(mut self, dash: DashPattern) -> Self {
+    self.dash = Some(dash);
+    self
+  }
 }
 
 impl Render for BoxDecoration {
@@ -48,6 +66,14 @@ impl Render for BoxDecoration {
     if !size.is_empty() {
       let rect = Rect::from_size(size);
       let painter = ctx.painter();
+      self.paint_shadows(painter, &rect, false);
+      if let Some(filter) = &self.backdrop_filter {
+        let path = match &self.border_radius {
+          Some(radius) => Path::rect_round(&rect, radius),
+          None => Path::rect(&rect),
+        };
+        painter.draw_backdrop_filter(path, filter);
+      }
       if let Some(ref background) = self.background {
         painter.set_brush(background.clone());
         if let Some(radius) = &self.border_radius {
@@ -57,12 +83,25 @@ impl Render for BoxDecoration {
         }
         painter.fill();
       }
+      self.paint_shadows(painter, &rect, true);
       self.paint_border(painter, &rect);
     }
   }
 }
 
 impl BoxDecoration {
+  fn paint_shadows(&self, painter: &mut Painter, rect: &Rect, inset: bool) {
+    let radius = self.border_radius.unwrap_or_default();
+    self
+      .box_shadow
+      .iter()
+      .rev()
+      .filter(|s| s.inset == inset)
+      .for_each(|s| {
+        painter.draw_box_shadow(rect, &radius, s);
+      });
+  }
+
   fn paint_border(&self, painter: &mut Painter, rect: &Rect) {
     if self.border.is_none() {
       return;
@@ -99,6 +138,7 @@ impl BoxDecoration {
 
     painter
       .set_line_width(border.top.width)
+      .set_dash(border.top.dash.clone())
       .set_brush(border.top.color.clone());
     painter.rect_round(
       &Rect::new(Point::new(min_x, min_y), Size::new(max_x - min_x, max_y - min_y)),
@@ -134,6 +174,7 @@ impl BoxDecoration {
         if border.is_visible() {
           painter
             .set_line_width(border.width)
+            .set_dash(border.dash.clone())
             .set_brush(border.color.clone());
           painter.begin_path(vertexs[edge.0] + *offset);
           painter.line_to(vertexs[edge.1] + *offset);
@@ -178,7 +219,7 @@ mod tests {
   use ribir_dev_helper::*;
 
   use super::*;
-  use crate::test_helper::*;
+  use crate::{reset_test_env, test_helper::*};
 
   #[test]
   fn default_value_is_none() {
@@ -191,10 +232,39 @@ mod tests {
     assert_eq!(w.read().border, None);
     assert_eq!(w.read().border_radius, None);
     assert_eq!(w.read().background, None);
+    assert!(w.read().box_shadow.is_empty());
+    assert_eq!(w.read().backdrop_filter, None);
 
     std::mem::forget(ctx);
   }
 
+  #[test]
+  fn dashed_border() {
+    reset_test_env!();
+
+    let w = fn_widget! {
+      let dash = DashPattern::new([40., 70.], 0.);
+      @MockBox {
+        size: Size::new(100., 100.),
+        border: Border::only_top(BorderSide::new(2., Color::BLACK.into()).with_dash(dash)),
+        margin: EdgeInsets::all(10.),
+      }
+    };
+    let mut wnd = TestWindow::new(w);
+    wnd.draw_frame();
+
+    let Frame { commands, .. } = wnd.take_last_frame().unwrap();
+    let bounds = commands
+      .iter()
+      .find_map(|cmd| match cmd {
+        PaintCommand::Path(p) => Some(p.paint_bounds),
+        _ => None,
+      })
+      .unwrap();
+    // Only the first dash is drawn, the rest of the side is in the gap.
+    assert_eq!(bounds.width(), 40.);
+  }
+
   const SIZE: Size = Size::new(100., 100.);
 
   widget_layout_test!(
@@ -19,6 +19,17 @@ impl Render for ConstrainedBox {
     ctx.assert_perform_single_child_layout(BoxClamp { min, max })
   }
 
+  fn intrinsic_size(&self, intrinsic: Intrinsic, extent: f32, ctx: &mut LayoutCtx) -> f32 {
+    let BoxClamp { min, max } = self.clamp;
+    let extent = extent
+      .min(intrinsic.extent_of(max))
+      .max(intrinsic.extent_of(min));
+    ctx
+      .children_max_intrinsic_size(intrinsic, extent)
+      .min(intrinsic.axis_of(max))
+      .max(intrinsic.axis_of(min))
+  }
+
   #[inline]
   fn only_sized_by_parent(&self) -> bool { false }
 
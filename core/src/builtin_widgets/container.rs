@@ -16,6 +16,11 @@ impl Render for Container {
     self.size
   }
 
+  #[inline]
+  fn intrinsic_size(&self, intrinsic: Intrinsic, _: f32, _: &mut LayoutCtx) -> f32 {
+    intrinsic.axis_of(self.size)
+  }
+
   #[inline]
   fn paint(&self, _: &mut PaintingCtx) {}
 
@@ -0,0 +1,73 @@
+use crate::prelude::*;
+
+/// A builtin widget that makes its child draggable. The child is dragged by
+/// panning it, the drop targets receive the payload by the `on_drop`
+/// listener.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let w = fn_widget! {
+///   @Row {
+///     @Container {
+///       size: Size::new(50., 50.),
+///       background: Color::RED,
+///       draggable: DragData::new("red"),
+///       drag_preview: fn_widget! {
+///         @Container { size: Size::new(50., 50.), background: Color::RED, opacity: 0.5 }
+///       },
+///     }
+///     @Container {
+///       size: Size::new(100., 100.),
+///       background: Color::BLUE,
+///       on_drop: move |e| println!("{:?} dropped", e.data::<&str>()),
+///     }
+///   }
+/// };
+/// App::run(w);
+/// ```
+#[derive(Default)]
+pub struct Draggable {
+  /// The payload of the drag, the widget is not draggable if it's `None`.
+  pub draggable: Option<DragData>,
+  /// The widget follows the pointer while dragging, nothing follows the
+  /// pointer if it's `None`.
+  pub drag_preview: Option<GenWidget>,
+}
+
+impl Declare for Draggable {
+  type Builder = FatObj<()>;
+  #[inline]
+  fn declarer() -> Self::Builder { FatObj::new(()) }
+}
+
+impl<'c> ComposeChild<'c> for Draggable {
+  type Child = Widget<'c>;
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    fn_widget! {
+      @$child {
+        on_pan_start: move |e| {
+          let this = $this;
+          let Some(data) = this.draggable.clone() else { return };
+          let preview = this.drag_preview.clone();
+          // Keep the pointer at the position it pressed on the preview.
+          let grab = (e.position() - e.offset).to_vector();
+          let wnd = e.window();
+          wnd
+            .drag_mgr
+            .start(e.current_target(), data, preview, grab, e.global_pos(), &wnd);
+        },
+        on_pan_update: move |e| {
+          e.window().drag_mgr.drag_to(e.current_target(), e.global_pos(), &e.window());
+        },
+        on_pan_end: move |e| e.window().drag_mgr.drop(e.current_target(), &e.window()),
+        on_disposed: move |e| {
+          e.window().drag_mgr.cancel(Some(e.current_target()), &e.window())
+        },
+      }
+    }
+    .into_widget()
+  }
+}
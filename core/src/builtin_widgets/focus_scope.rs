@@ -12,6 +12,13 @@ pub struct FocusScope {
   /// skip the whole subtree.
   #[declare(default)]
   pub can_focus: bool,
+
+  /// If true, the focus is trapped in the scope, the tab navigation cycles
+  /// within the descendants and never leaves the scope. It's useful for the
+  /// modal content, like a dialog.
+  /// Default value is false.
+  #[declare(default)]
+  pub trap: bool,
 }
 
 impl<'c> ComposeChild<'c> for FocusScope {
@@ -146,6 +153,48 @@ mod tests {
     }
   }
 
+  #[test]
+  fn tab_trap_scope() {
+    let _guard = unsafe { AppCtx::new_lock_scope() };
+
+    let size = Size::zero();
+    let widget = fn_widget! {
+      @MockMulti {
+        @MockBox { size, tab_index: 0i16 }
+        @FocusScope {
+          trap: true,
+          can_focus: true,
+          auto_focus: true,
+          @MockMulti {
+            @MockBox { size, tab_index: 0i16 }
+            @MockBox { size, tab_index: 0i16 }
+          }
+        }
+        @MockBox { size, tab_index: 0i16 }
+      }
+    };
+
+    let wnd = TestWindow::new(widget);
+    let mut focus_mgr = wnd.focus_mgr.borrow_mut();
+    let tree = wnd.tree();
+    focus_mgr.refresh_focus(tree);
+
+    let id0 = tree.content_root().first_child(tree).unwrap();
+    let scope = id0.next_sibling(tree).unwrap();
+    let scope_id1 = scope.first_child(tree).unwrap();
+    let scope_id2 = scope_id1.next_sibling(tree).unwrap();
+
+    assert_eq!(focus_mgr.focusing(), Some(scope));
+    focus_mgr.focus_next_widget(tree);
+    assert_eq!(focus_mgr.focusing(), Some(scope_id1));
+    focus_mgr.focus_next_widget(tree);
+    assert_eq!(focus_mgr.focusing(), Some(scope_id2));
+    focus_mgr.focus_next_widget(tree);
+    assert_eq!(focus_mgr.focusing(), Some(scope_id1));
+    focus_mgr.focus_prev_widget(tree);
+    assert_eq!(focus_mgr.focusing(), Some(scope_id2));
+  }
+
   #[test]
   fn focus_scope() {
     let _guard = unsafe { AppCtx::new_lock_scope() };
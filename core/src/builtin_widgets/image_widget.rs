@@ -1,10 +1,15 @@
-use crate::prelude::*;
+use crate::{prelude::*, ticker::FrameMsg};
 
 impl Render for Resource<PixelImage> {
   fn perform_layout(&self, _: BoxClamp, _: &mut LayoutCtx) -> Size {
     Size::new(self.width() as f32, self.height() as f32)
   }
 
+  fn intrinsic_size(&self, intrinsic: Intrinsic, _: f32, _: &mut LayoutCtx) -> f32 {
+    let size = if intrinsic.is_width() { self.width() } else { self.height() };
+    size as f32
+  }
+
   fn paint(&self, ctx: &mut PaintingCtx) {
     let size = ctx.box_size().unwrap();
     let box_rect = Rect::from_size(size);
@@ -15,3 +20,101 @@ impl Render for Resource<PixelImage> {
     }
   }
 }
+
+/// An animated image is played by the frame ticker of the window, it keeps the
+/// window drawing new frames while it's mounted.
+impl Compose for Resource<AnimatedImage> {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    fn_widget! {
+      let frame = Stateful::new($this.frames()[0].image.clone());
+      let mut guard = None;
+      if $this.is_animated() {
+        let wnd = ctx!().window();
+        let mut start = None;
+        let mut index = 0;
+        let u = wnd
+          .frame_tick_stream()
+          .subscribe(move |msg| {
+            if let FrameMsg::NewFrame(time) = msg {
+              let this = $this;
+              let start = *start.get_or_insert(time);
+              let new_index = this.frame_index_at(time - start);
+              if new_index != index {
+                index = new_index;
+                *$frame.write() = this.frames()[index].image.clone();
+              }
+            }
+          });
+        wnd.inc_running_animate();
+        guard = Some((u, wnd.id()));
+      }
+      @ $frame {
+        on_disposed: move |_| {
+          if let Some((u, wnd_id)) = guard {
+            u.unsubscribe();
+            if let Some(wnd) = AppCtx::get_window(wnd_id) {
+              wnd.dec_running_animate();
+            }
+          }
+        }
+      }
+    }
+    .into_widget()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{cell::Cell, rc::Rc};
+
+  use ribir_painter::image::ColorFormat;
+
+  use super::*;
+  #[cfg(target_arch = "wasm32")]
+  use crate::test_helper::wasm_bindgen_test;
+  use crate::{reset_test_env, test_helper::*};
+
+  fn frame(size: u32, delay: Duration) -> ImageFrame {
+    let data = vec![0; (size * size * 4) as usize];
+    let image = PixelImage::new(data.into(), size, size, ColorFormat::Rgba8);
+    ImageFrame { image: Resource::new(image), delay }
+  }
+
+  #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+  #[test]
+  fn play_animated_image() {
+    reset_test_env!();
+
+    let img = Resource::new(AnimatedImage::new(vec![
+      frame(10, Duration::from_millis(100)),
+      frame(20, Duration::from_secs(100)),
+    ]));
+    let (show, w_show) = split_value(true);
+    let mut wnd = TestWindow::new(fn_widget! {
+      let img = img.clone();
+      @ { pipe!(*$show).map(move |show| show.then(|| img.clone())) }
+    });
+    let start = Rc::new(Cell::new(None));
+    let c_start = start.clone();
+    wnd.frame_tick_stream().subscribe(move |msg| {
+      if let FrameMsg::NewFrame(time) = msg {
+        c_start.set(c_start.get().or(Some(time)));
+      }
+    });
+    wnd.draw_frame();
+    wnd.assert_root_size(Size::new(10., 10.));
+    assert!(wnd.need_draw());
+
+    // Tick the frame 150ms after the start by hand, rather than waiting for it.
+    let time = start.get().unwrap() + Duration::from_millis(150);
+    wnd
+      .frame_tick_stream()
+      .next(FrameMsg::NewFrame(time));
+    wnd.layout();
+    wnd.assert_root_size(Size::new(20., 20.));
+
+    *w_show.write() = false;
+    wnd.draw_frame();
+    assert!(!wnd.need_draw());
+  }
+}
@@ -35,6 +35,12 @@ impl Render for Margin {
     size + thickness
   }
 
+  fn intrinsic_size(&self, intrinsic: Intrinsic, extent: f32, ctx: &mut LayoutCtx) -> f32 {
+    let thickness = self.margin.thickness();
+    let extent = (extent - intrinsic.extent_of(thickness)).max(0.);
+    ctx.children_max_intrinsic_size(intrinsic, extent) + intrinsic.axis_of(thickness)
+  }
+
   #[inline]
   fn only_sized_by_parent(&self) -> bool { false }
 
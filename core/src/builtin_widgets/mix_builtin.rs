@@ -1,4 +1,8 @@
-use std::{cell::Cell, convert::Infallible};
+use std::{
+  cell::{Cell, RefCell},
+  convert::Infallible,
+  rc::Rc,
+};
 
 use rxrust::prelude::*;
 
@@ -45,6 +49,7 @@ pub type EventSubject = MutRefItemSubject<'static, Event, Infallible>;
 pub struct MixBuiltin {
   flags: Cell<BuiltinFlags>,
   subject: EventSubject,
+  gestures: RefCell<Option<Rc<RefCell<GestureHandlers>>>>,
 }
 
 impl Declare for MixBuiltin {
@@ -148,6 +153,14 @@ impl MixBuiltin {
     impl_event_callback!(self, Pointer, PointerCancel, PointerEvent, handler)
   }
 
+  pub fn on_got_pointer_capture(&self, handler: impl FnMut(&mut PointerEvent) + 'static) -> &Self {
+    impl_event_callback!(self, Pointer, GotPointerCapture, PointerEvent, handler)
+  }
+
+  pub fn on_lost_pointer_capture(&self, handler: impl FnMut(&mut PointerEvent) + 'static) -> &Self {
+    impl_event_callback!(self, Pointer, LostPointerCapture, PointerEvent, handler)
+  }
+
   pub fn on_pointer_enter(&self, handler: impl FnMut(&mut PointerEvent) + 'static) -> &Self {
     impl_event_callback!(self, Pointer, PointerEnter, PointerEvent, handler)
   }
@@ -212,6 +225,115 @@ impl MixBuiltin {
     self
   }
 
+  pub fn on_drag_enter(&self, handler: impl FnMut(&mut DragEvent) + 'static) -> &Self {
+    impl_event_callback!(self, Pointer, DragEnter, DragEvent, handler)
+  }
+
+  pub fn on_drag_over(&self, handler: impl FnMut(&mut DragEvent) + 'static) -> &Self {
+    impl_event_callback!(self, Pointer, DragOver, DragEvent, handler)
+  }
+
+  pub fn on_drag_leave(&self, handler: impl FnMut(&mut DragEvent) + 'static) -> &Self {
+    impl_event_callback!(self, Pointer, DragLeave, DragEvent, handler)
+  }
+
+  pub fn on_drop(&self, handler: impl FnMut(&mut DragEvent) + 'static) -> &Self {
+    impl_event_callback!(self, Pointer, Drop, DragEvent, handler)
+  }
+
+  pub fn on_file_hover(&self, handler: impl FnMut(&mut FileDropEvent) + 'static) -> &Self {
+    impl_event_callback!(self, Pointer, FileHover, FileDropEvent, handler)
+  }
+
+  pub fn on_file_hover_end(&self, handler: impl FnMut(&mut FileDropEvent) + 'static) -> &Self {
+    impl_event_callback!(self, Pointer, FileHoverEnd, FileDropEvent, handler)
+  }
+
+  pub fn on_file_drop(&self, handler: impl FnMut(&mut FileDropEvent) + 'static) -> &Self {
+    impl_event_callback!(self, Pointer, FileDrop, FileDropEvent, handler)
+  }
+
+  pub fn on_pan_start(&self, handler: impl FnMut(&mut PanEvent) + 'static) -> &Self {
+    self
+      .gesture_handlers()
+      .borrow_mut()
+      .pan_start
+      .push(Box::new(handler));
+    self
+  }
+
+  pub fn on_pan_update(&self, handler: impl FnMut(&mut PanEvent) + 'static) -> &Self {
+    self
+      .gesture_handlers()
+      .borrow_mut()
+      .pan_update
+      .push(Box::new(handler));
+    self
+  }
+
+  pub fn on_pan_end(&self, handler: impl FnMut(&mut PanEvent) + 'static) -> &Self {
+    self
+      .gesture_handlers()
+      .borrow_mut()
+      .pan_end
+      .push(Box::new(handler));
+    self
+  }
+
+  pub fn on_scale(&self, handler: impl FnMut(&mut ScaleEvent) + 'static) -> &Self {
+    self
+      .gesture_handlers()
+      .borrow_mut()
+      .scale
+      .push(Box::new(handler));
+    self
+  }
+
+  pub fn on_rotate(&self, handler: impl FnMut(&mut RotateEvent) + 'static) -> &Self {
+    self
+      .gesture_handlers()
+      .borrow_mut()
+      .rotate
+      .push(Box::new(handler));
+    self
+  }
+
+  pub fn on_long_press(&self, handler: impl FnMut(&mut LongPressEvent) + 'static) -> &Self {
+    self
+      .gesture_handlers()
+      .borrow_mut()
+      .long_press
+      .push(Box::new(handler));
+    self
+  }
+
+  pub fn on_swipe(&self, handler: impl FnMut(&mut SwipeEvent) + 'static) -> &Self {
+    self
+      .gesture_handlers()
+      .borrow_mut()
+      .swipe
+      .push(Box::new(handler));
+    self
+  }
+
+  /// The gesture handlers of the widget, the widget joins the gesture arena
+  /// when a pointer presses down on it.
+  fn gesture_handlers(&self) -> Rc<RefCell<GestureHandlers>> {
+    let mut gestures = self.gestures.borrow_mut();
+    let handlers = gestures.get_or_insert_with(|| {
+      let handlers = Rc::new(RefCell::new(GestureHandlers::default()));
+      let h = handlers.clone();
+      self.on_pointer_down(move |e| {
+        let wnd = e.window();
+        wnd
+          .gesture_arena
+          .join(e.current_target(), &h, &wnd);
+      });
+      handlers
+    });
+    handlers.clone()
+  }
+
   pub fn on_ime_pre_edit(&self, f: impl FnMut(&mut ImePreEditEvent) + 'static) -> &Self {
     impl_event_callback!(self, KeyBoard, ImePreEdit, ImePreEditEvent, f)
   }
@@ -381,8 +503,11 @@ fn x_times_tap_map_filter(
   x: usize, dur: Duration, capture: bool,
 ) -> impl FnMut(&mut Event) -> Option<&mut PointerEvent> {
   assert!(x > 0);
+  // Every finger touching down has a new pointer id, so the taps are counted by
+  // the pointer type rather than the pointer id.
   struct TapInfo {
-    pointer_id: PointerId,
+    pointer_type: PointerType,
+    is_primary: bool,
     stamps: Vec<Instant>,
   }
 
@@ -395,7 +520,7 @@ fn x_times_tap_map_filter(
     };
     let now = Instant::now();
     match &mut type_info {
-      Some(info) if info.pointer_id == e.id => {
+      Some(info) if info.pointer_type == e.point_type && info.is_primary == e.is_primary => {
         if info.stamps.len() + 1 == x {
           if now.duration_since(info.stamps[0]) <= dur {
             // emit x-tap event and reset the tap info
@@ -413,7 +538,11 @@ fn x_times_tap_map_filter(
         }
       }
       _ => {
-        type_info = Some(TapInfo { pointer_id: e.id, stamps: vec![now] });
+        type_info = Some(TapInfo {
+          pointer_type: e.point_type.clone(),
+          is_primary: e.is_primary,
+          stamps: vec![now],
+        });
         None
       }
     }
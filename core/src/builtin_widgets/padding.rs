@@ -54,6 +54,19 @@ impl Render for Padding {
   fn only_sized_by_parent(&self) -> bool { false }
 
   #[inline]
+  fn intrinsic_size(&self, intrinsic: Intrinsic, extent: f32, ctx: &mut LayoutCtx) -> f32 {
+    let Some(child) = ctx.single_child() else {
+      return 0.;
+    };
+    // The padding only works if the child has children, see `perform_layout`.
+    if child.first_child(ctx.tree).is_none() {
+      return ctx.child_intrinsic_size(child, intrinsic, extent);
+    }
+    let thickness = self.padding.thickness();
+    let extent = (extent - intrinsic.extent_of(thickness)).max(0.);
+    ctx.child_intrinsic_size(child, intrinsic, extent) + intrinsic.axis_of(thickness)
+  }
+
   fn paint(&self, _: &mut PaintingCtx) {}
 }
 
@@ -32,6 +32,9 @@ impl Declare for ScrollableWidget {
 impl<'c> ComposeChild<'c> for ScrollableWidget {
   type Child = Widget<'c>;
   fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    // The descendants can query the scrollable widget, for example, to scroll it
+    // when dragging near its edges.
+    let writer: Box<dyn Query> = Box::new(this.clone_writer());
     fn_widget! {
       let mut view = @UnconstrainedBox {
         dir: pipe!{
@@ -68,6 +71,7 @@ impl<'c> ComposeChild<'c> for ScrollableWidget {
       }
     }
     .into_widget()
+    .attach_data(writer)
   }
 }
 
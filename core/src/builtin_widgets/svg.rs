@@ -4,6 +4,11 @@ impl Render for Svg {
   #[inline]
   fn perform_layout(&self, _: BoxClamp, _: &mut LayoutCtx) -> Size { self.size }
 
+  #[inline]
+  fn intrinsic_size(&self, intrinsic: Intrinsic, _: f32, _: &mut LayoutCtx) -> f32 {
+    intrinsic.axis_of(self.size)
+  }
+
   #[inline]
   fn paint(&self, ctx: &mut PaintingCtx) {
     let painter = ctx.painter();
@@ -3,7 +3,7 @@ use ribir_geom::Size;
 use super::{WidgetCtx, WidgetCtxImpl};
 use crate::{
   widget::{BoxClamp, Layouter, WidgetTree},
-  widget_tree::WidgetId,
+  widget_tree::{Intrinsic, WidgetId},
 };
 
 /// A place to compute the render object's layout. Rather than holding  children
@@ -70,6 +70,69 @@ impl<'a> LayoutCtx<'a> {
     self.new_layouter(wid)
   }
 
+  /// Return the children of the widget in order.
+  pub fn children(&self) -> impl Iterator<Item = WidgetId> + '_ { self.id.children(self.tree) }
+
+  /// Return the layouter of the `child`, so the children can be laid out in
+  /// any order.
+  ///
+  /// # Panic
+  /// panic if `child` is not a child of this widget.
+  pub fn child_layouter(&mut self, child: WidgetId) -> Layouter<'_> {
+    assert_eq!(child.parent(self.tree), Some(self.id));
+    self.new_layouter(child)
+  }
+
+  /// Return the intrinsic size of the `child` without performing its layout,
+  /// see `Render::intrinsic_size`.
+  ///
+  /// # Panic
+  /// panic if `child` is not a child of this widget.
+  pub fn child_intrinsic_size(
+    &mut self, child: WidgetId, intrinsic: Intrinsic, extent: f32,
+  ) -> f32 {
+    assert_eq!(child.parent(self.tree), Some(self.id));
+    self.tree.intrinsic_size(child, intrinsic, extent)
+  }
+
+  /// Return the max intrinsic size of the children, zero if there is no child.
+  pub fn children_max_intrinsic_size(&mut self, intrinsic: Intrinsic, extent: f32) -> f32 {
+    let children: Vec<_> = self.children().collect();
+    children
+      .into_iter()
+      .map(|c| self.child_intrinsic_size(c, intrinsic, extent))
+      .fold(0., f32::max)
+  }
+
+  /// Set the distance from the top of this widget to the baseline of its
+  /// first line of text, the parent may use it to align the children.
+  pub fn set_baseline(&mut self, baseline: f32) {
+    self
+      .tree
+      .store
+      .layout_info_or_default(self.id)
+      .baseline = Some(baseline);
+  }
+
+  /// Return the distance from the top of the `widget` to the baseline of its
+  /// first line of text. If the `widget` has no baseline, it's the baseline of
+  /// its first child with one, along the first children.
+  ///
+  /// Return `None` if the `widget` not performed layout or no baseline found.
+  pub fn widget_baseline(&self, widget: WidgetId) -> Option<f32> {
+    let store = &self.tree.store;
+    let mut offset = 0.;
+    let mut id = widget;
+    loop {
+      let info = store.layout_info(id)?;
+      if let Some(baseline) = info.baseline {
+        return Some(offset + baseline);
+      }
+      id = id.first_child(self.tree)?;
+      offset += store.layout_box_position(id)?.y;
+    }
+  }
+
   /// Clear the child layout information, so the `child` will be force layout
   /// when call `[LayoutCtx::perform_child_layout]!` even if it has layout cache
   /// information with same input.
@@ -3,6 +3,7 @@ use std::{ptr::NonNull, rc::Rc};
 use ribir_geom::{Point, Rect, Size};
 
 use crate::{
+  events::PointerId,
   query::QueryRef,
   state::WriteRef,
   widget::{BoxClamp, WidgetTree},
@@ -84,6 +85,14 @@ pub trait WidgetCtx {
   fn query_write_of_widget<T: 'static>(&self, w: WidgetId) -> Option<WriteRef<T>>;
   /// Retrieve the window associated with this context.
   fn window(&self) -> Rc<Window>;
+  /// Capture the pointer to the widget, the following events of the pointer
+  /// are dispatched to the widget until the capture is released or the pointer
+  /// is lifted up or cancelled. Only a pressed pointer can be captured.
+  fn set_pointer_capture(&self, pointer: PointerId);
+  /// Release the pointer if it's captured by the widget.
+  fn release_pointer_capture(&self, pointer: PointerId);
+  /// Return if the pointer is captured by the widget.
+  fn has_pointer_capture(&self, pointer: PointerId) -> bool;
 }
 
 pub(crate) trait WidgetCtxImpl {
@@ -197,6 +206,30 @@ impl<T: WidgetCtxImpl> WidgetCtx for T {
   }
 
   fn window(&self) -> Rc<Window> { self.current_wnd() }
+
+  fn set_pointer_capture(&self, pointer: PointerId) {
+    self
+      .current_wnd()
+      .dispatcher
+      .borrow_mut()
+      .set_pointer_capture(pointer, self.id());
+  }
+
+  fn release_pointer_capture(&self, pointer: PointerId) {
+    self
+      .current_wnd()
+      .dispatcher
+      .borrow_mut()
+      .release_pointer_capture(pointer, self.id());
+  }
+
+  fn has_pointer_capture(&self, pointer: PointerId) -> bool {
+    self
+      .current_wnd()
+      .dispatcher
+      .borrow()
+      .has_pointer_capture(pointer, self.id())
+  }
 }
 
 macro_rules! define_widget_context {
@@ -21,6 +21,14 @@ mod ime_pre_edit;
 pub use ime_pre_edit::*;
 mod lifecycle;
 pub use lifecycle::*;
+mod drag_drop;
+mod file_drop;
+mod gesture;
+pub(crate) use drag_drop::DragManager;
+pub use drag_drop::{DragData, DragEvent};
+pub use file_drop::FileDropEvent;
+pub(crate) use gesture::{GestureArena, GestureHandlers};
+pub use gesture::{LongPressEvent, PanEvent, RotateEvent, ScaleEvent, SwipeDirection, SwipeEvent};
 
 pub(crate) mod focus_mgr;
 mod listener_impl_helper;
@@ -121,10 +129,37 @@ pub enum Event {
   PointerMove(PointerEvent),
   PointerMoveCapture(PointerEvent),
   PointerCancel(PointerEvent),
+  /// The got pointer capture event fires when a widget captures a pointer.
+  GotPointerCapture(PointerEvent),
+  /// The lost pointer capture event fires when the pointer captured by a
+  /// widget is released, include the pointer is lifted up or cancelled.
+  LostPointerCapture(PointerEvent),
   PointerEnter(PointerEvent),
   PointerLeave(PointerEvent),
   Tap(PointerEvent),
   TapCapture(PointerEvent),
+  /// The drag enter event fires when a dragged widget enters a widget, it
+  /// doesn't bubble.
+  DragEnter(DragEvent),
+  /// The drag over event fires every time the dragging pointer moves over a
+  /// widget.
+  DragOver(DragEvent),
+  /// The drag leave event fires when a dragged widget leaves a widget, and
+  /// after the drag is dropped or cancelled, it doesn't bubble.
+  DragLeave(DragEvent),
+  /// The drop event fires on the widget under the pointer when the dragged
+  /// widget is dropped.
+  Drop(DragEvent),
+  /// The file hover event fires when files dragged from the operating system
+  /// hover a widget, once for every file.
+  FileHover(FileDropEvent),
+  /// The file hover end event fires when the files hovering a widget leave
+  /// it, are dropped or cancelled.
+  FileHoverEnd(FileDropEvent),
+  /// The file drop event fires when files dragged from the operating system
+  /// are dropped on a widget. The files dropped together on the same widget
+  /// are batched in one event.
+  FileDrop(FileDropEvent),
   ImePreEdit(ImePreEditEvent),
   ImePreEditCapture(ImePreEditEvent),
   /// Firing the wheel event when the user rotates a wheel button on a pointing
@@ -189,10 +224,14 @@ impl std::ops::Deref for Event {
       | Event::PointerMove(e)
       | Event::PointerMoveCapture(e)
       | Event::PointerCancel(e)
+      | Event::GotPointerCapture(e)
+      | Event::LostPointerCapture(e)
       | Event::PointerEnter(e)
       | Event::PointerLeave(e)
       | Event::Tap(e)
       | Event::TapCapture(e) => e,
+      Event::DragEnter(e) | Event::DragOver(e) | Event::DragLeave(e) | Event::Drop(e) => e,
+      Event::FileHover(e) | Event::FileHoverEnd(e) | Event::FileDrop(e) => e,
       Event::ImePreEdit(e) | Event::ImePreEditCapture(e) => e,
       Event::Wheel(e) | Event::WheelCapture(e) => e,
       Event::Chars(e) | Event::CharsCapture(e) => e,
@@ -220,10 +259,14 @@ impl std::ops::DerefMut for Event {
       | Event::PointerMove(e)
       | Event::PointerMoveCapture(e)
       | Event::PointerCancel(e)
+      | Event::GotPointerCapture(e)
+      | Event::LostPointerCapture(e)
       | Event::PointerEnter(e)
       | Event::PointerLeave(e)
       | Event::Tap(e)
       | Event::TapCapture(e) => e,
+      Event::DragEnter(e) | Event::DragOver(e) | Event::DragLeave(e) | Event::Drop(e) => e,
+      Event::FileHover(e) | Event::FileHoverEnd(e) | Event::FileDrop(e) => e,
       Event::ImePreEdit(e) | Event::ImePreEditCapture(e) => e,
       Event::Wheel(e) | Event::WheelCapture(e) => e,
       Event::Chars(e) | Event::CharsCapture(e) => e,
@@ -243,10 +286,19 @@ impl Event {
       | Event::PointerMove(_)
       | Event::PointerMoveCapture(_)
       | Event::PointerCancel(_)
+      | Event::GotPointerCapture(_)
+      | Event::LostPointerCapture(_)
       | Event::PointerEnter(_)
       | Event::PointerLeave(_)
       | Event::Tap(_)
-      | Event::TapCapture(_) => BuiltinFlags::Pointer,
+      | Event::TapCapture(_)
+      | Event::DragEnter(_)
+      | Event::DragOver(_)
+      | Event::DragLeave(_)
+      | Event::Drop(_)
+      | Event::FileHover(_)
+      | Event::FileHoverEnd(_)
+      | Event::FileDrop(_) => BuiltinFlags::Pointer,
       Event::Wheel(_) | Event::WheelCapture(_) => BuiltinFlags::Wheel,
       Event::ImePreEdit(_)
       | Event::ImePreEditCapture(_)
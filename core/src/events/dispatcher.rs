@@ -1,6 +1,11 @@
-use std::rc::{Rc, Weak};
+use std::{
+  path::PathBuf,
+  rc::{Rc, Weak},
+};
 
-use winit::event::{DeviceId, ElementState, MouseButton, MouseScrollDelta, WindowEvent};
+use winit::event::{
+  DeviceId, ElementState, MouseButton, MouseScrollDelta, Touch, TouchPhase, WindowEvent,
+};
 
 use crate::{prelude::*, window::DelayEvent};
 
@@ -9,11 +14,32 @@ pub(crate) struct Dispatcher {
   pub(crate) info: DispatchInfo,
   pub(crate) entered_widgets: Vec<WidgetId>,
   pub(crate) pointer_down_uid: Option<WidgetId>,
+  /// The fingers touching the screen.
+  pub(crate) touches: Vec<TouchPointer>,
+  /// The last pointer id allocated to a finger.
+  last_touch_id: usize,
+  /// The widgets capturing the pointers, the events of a captured pointer are
+  /// dispatched to its capturing widget.
+  pointer_captures: Vec<(PointerId, WidgetId)>,
+  /// The files dragged from the operating system hovering the window.
+  hovered_files: Vec<PathBuf>,
+  /// The widget the hovering files are over.
+  file_hover_target: Option<WidgetId>,
 }
 
 impl Dispatcher {
   pub fn new() -> Self {
-    Self { wnd: Weak::new(), info: <_>::default(), entered_widgets: vec![], pointer_down_uid: None }
+    Self {
+      wnd: Weak::new(),
+      info: <_>::default(),
+      entered_widgets: vec![],
+      pointer_down_uid: None,
+      touches: vec![],
+      last_touch_id: PointerId::MOUSE.0,
+      pointer_captures: vec![],
+      hovered_files: vec![],
+      file_hover_target: None,
+    }
   }
 
   pub fn init(&mut self, wnd: &Rc<Window>) { self.wnd = Rc::downgrade(wnd); }
@@ -46,6 +72,12 @@ impl Dispatcher {
       }
       WindowEvent::CursorLeft { .. } => self.on_cursor_left(),
       WindowEvent::MouseWheel { delta, .. } => self.dispatch_wheel(delta, wnd_factor),
+      WindowEvent::Touch(touch) => self.dispatch_touch(touch, wnd_factor),
+      // The file events of winit carry no position, use the last known cursor
+      // position.
+      WindowEvent::HoveredFile(path) => self.dispatch_hovered_file(path, self.info.cursor_pos),
+      WindowEvent::HoveredFileCancelled => self.dispatch_hovered_file_cancelled(),
+      WindowEvent::DroppedFile(path) => self.dispatch_dropped_file(path, self.info.cursor_pos),
       _ => log::info!("not processed event {:?}", event),
     }
   }
@@ -55,6 +87,13 @@ impl Dispatcher {
     state: ElementState,
   ) {
     let wnd = self.window();
+    // The escape key cancels the drag rather than goes to the focused widget.
+    if key == VirtualKey::Named(NamedKey::Escape) && wnd.drag_mgr.is_dragging() {
+      if state == ElementState::Pressed {
+        wnd.add_delay_event(DelayEvent::DragCancel);
+      }
+      return;
+    }
     if let Some(focus_id) = wnd.focusing() {
       let event = KeyboardEvent::new(&wnd, focus_id, physical_key, key, is_repeat, location);
       match state {
@@ -84,11 +123,14 @@ impl Dispatcher {
 
   pub fn cursor_move_to(&mut self, position: Point) {
     self.info.cursor_pos = position;
+    if !self.hovered_files.is_empty() {
+      self.file_hover_move_to(position);
+    }
     self.pointer_enter_leave_dispatch();
-    if let Some(hit) = self.hit_widget() {
+    if let Some(target) = self.mouse_target() {
       self
         .window()
-        .add_delay_event(DelayEvent::PointerMove(hit));
+        .add_delay_event(DelayEvent::PointerMove(target, None));
     }
   }
 
@@ -116,25 +158,89 @@ impl Dispatcher {
           if self.info.mouse_button.1.is_empty() {
             self.info.mouse_button.0 = None;
             let wnd = self.window();
+            let capture = self.capture_target(PointerId::MOUSE);
             let mut dispatch = |tree: &WidgetTree| {
-              let hit = self.hit_widget()?;
-              wnd.add_delay_event(DelayEvent::PointerUp(hit));
+              let hit = self.hit_widget();
+              if let Some(target) = capture.or(hit) {
+                wnd.add_delay_event(DelayEvent::PointerUp(target, None));
+              }
 
               let tap_on = self
                 .pointer_down_uid
                 .take()?
-                .lowest_common_ancestor(hit, tree)?;
-              wnd.add_delay_event(DelayEvent::Tap(tap_on));
+                .lowest_common_ancestor(hit?, tree)?;
+              wnd.add_delay_event(DelayEvent::Tap(tap_on, None));
               Some(())
             };
 
             dispatch(wnd.tree());
+            self.release_capture(PointerId::MOUSE, None);
           }
         }
       };
     }
   }
 
+  /// A file is dragged over the window at `pos`, the operating system fires
+  /// once for every file.
+  pub fn dispatch_hovered_file(&mut self, path: PathBuf, pos: Point) {
+    let hit = self.hit_widget_at(pos);
+    if hit != self.file_hover_target {
+      self.file_hover_end();
+    }
+    self.hovered_files.push(path);
+    self.file_hover_target = hit;
+    self.file_hover_emit();
+  }
+
+  /// The hovering files move to `pos`, the target is changed if they leave it.
+  fn file_hover_move_to(&mut self, pos: Point) {
+    let hit = self.hit_widget_at(pos);
+    if hit != self.file_hover_target {
+      self.file_hover_end();
+      self.file_hover_target = hit;
+      self.file_hover_emit();
+    }
+  }
+
+  fn file_hover_emit(&self) {
+    if let Some(id) = self.file_hover_target {
+      let paths = self.hovered_files.clone();
+      self
+        .window()
+        .add_delay_event(DelayEvent::FileHover { id, paths });
+    }
+  }
+
+  pub fn dispatch_hovered_file_cancelled(&mut self) {
+    self.file_hover_end();
+    self.hovered_files.clear();
+  }
+
+  /// A file is dropped on the window at `pos`, the operating system fires once
+  /// for every file. The files dropped together on the same widget are
+  /// batched in one event.
+  pub fn dispatch_dropped_file(&mut self, path: PathBuf, pos: Point) {
+    self.file_hover_end();
+    self.hovered_files.retain(|p| p != &path);
+    let Some(id) = self.hit_widget_at(pos) else { return };
+    let wnd = self.window();
+    let mut events = wnd.delay_emitter.borrow_mut();
+    match events.back_mut() {
+      Some(DelayEvent::FileDrop { id: last, paths }) if *last == id => paths.push(path),
+      _ => events.push_back(DelayEvent::FileDrop { id, paths: vec![path] }),
+    }
+  }
+
+  fn file_hover_end(&mut self) {
+    if let Some(id) = self.file_hover_target.take() {
+      let paths = self.hovered_files.clone();
+      self
+        .window()
+        .add_delay_event(DelayEvent::FileHoverEnd { id, paths });
+    }
+  }
+
   pub fn dispatch_wheel(&mut self, delta: MouseScrollDelta, wnd_factor: f64) {
     if let Some(wid) = self.hit_widget() {
       let (delta_x, delta_y) = match delta {
@@ -151,13 +257,114 @@ impl Dispatcher {
     }
   }
 
+  /// Dispatch the touch of a finger as the pointer events. Every finger is a
+  /// pointer with its own id, and the first finger touching down when no other
+  /// finger is on the screen is the primary pointer.
+  ///
+  /// A finger is captured by the widget it touches down on, its following
+  /// events are dispatched to that widget, even if the finger moves out of it.
+  pub fn dispatch_touch(&mut self, touch: Touch, wnd_factor: f64) {
+    let Touch { phase, location, force, id: finger, .. } = touch;
+    let pos = location.to_logical::<f32>(wnd_factor);
+    let pos = Point::new(pos.x, pos.y);
+    let pressure = TouchPoint::pressure_of(force);
+    let wnd = self.window();
+
+    if phase == TouchPhase::Started {
+      self.last_touch_id += 1;
+      let point = TouchPoint {
+        id: PointerId(self.last_touch_id),
+        pos,
+        pressure,
+        is_primary: self.touches.is_empty(),
+        pressed: true,
+      };
+      let hit = self.hit_widget_at(pos);
+      self.touches.retain(|t| t.finger != finger);
+      self
+        .touches
+        .push(TouchPointer { finger, point, capture: hit });
+      if point.is_primary {
+        self.focus_on_pointer_down(hit);
+      }
+      if let Some(hit) = hit {
+        wnd.add_delay_event(DelayEvent::PointerDown(hit, Some(point)));
+      }
+      return;
+    }
+
+    let Some(idx) = self
+      .touches
+      .iter()
+      .position(|t| t.finger == finger)
+    else {
+      return;
+    };
+    let tree = wnd.tree();
+    let down = self.touches[idx]
+      .capture
+      .filter(|w| !w.is_dropped(tree));
+    let capture = self
+      .capture_target(self.touches[idx].point.id)
+      .or(down);
+    match phase {
+      TouchPhase::Moved => {
+        let point = &mut self.touches[idx].point;
+        point.pos = pos;
+        point.pressure = pressure;
+        let point = *point;
+        if let Some(target) = capture.or_else(|| self.hit_widget_at(pos)) {
+          wnd.add_delay_event(DelayEvent::PointerMove(target, Some(point)));
+        }
+      }
+      TouchPhase::Ended => {
+        let TouchPointer { mut point, .. } = self.touches.remove(idx);
+        point.pos = pos;
+        point.pressure = 0.;
+        point.pressed = false;
+        let hit = self.hit_widget_at(pos);
+        if let Some(target) = capture.or(hit) {
+          wnd.add_delay_event(DelayEvent::PointerUp(target, Some(point)));
+        }
+        let tap_on = down
+          .zip(hit)
+          .and_then(|(down, up)| down.lowest_common_ancestor(up, tree));
+        if let Some(tap_on) = tap_on {
+          wnd.add_delay_event(DelayEvent::Tap(tap_on, Some(point)));
+        }
+        self.release_capture(point.id, Some(point));
+      }
+      TouchPhase::Cancelled => {
+        let TouchPointer { mut point, .. } = self.touches.remove(idx);
+        point.pressure = 0.;
+        point.pressed = false;
+        if let Some(target) = capture {
+          wnd.add_delay_event(DelayEvent::PointerCancel(target, Some(point)));
+        }
+        self.release_capture(point.id, Some(point));
+      }
+      TouchPhase::Started => unreachable!(),
+    }
+  }
+
   fn bubble_pointer_down(&mut self) {
     let hit = self.hit_widget();
     self.pointer_down_uid = hit;
+    self.focus_on_pointer_down(hit);
+    if let Some(hit) = hit {
+      self
+        .window()
+        .add_delay_event(DelayEvent::PointerDown(hit, None));
+    }
+  }
+
+  /// Focus the nearest focus node of the widget pressed by the pointer, or
+  /// blur if it's not in any focus node.
+  fn focus_on_pointer_down(&self, hit: Option<WidgetId>) {
     let wnd = self.window();
     let tree = wnd.tree();
 
-    let nearest_focus = self.pointer_down_uid.and_then(|wid| {
+    let nearest_focus = hit.and_then(|wid| {
       wid.ancestors(tree).find(|id| {
         id.query_ref::<MixBuiltin>(tree)
           .map_or(false, |m| m.contain_flag(BuiltinFlags::Focus))
@@ -168,13 +375,98 @@ impl Dispatcher {
     } else {
       wnd.focus_mgr.borrow_mut().blur(tree);
     }
-    if let Some(hit) = hit {
-      wnd.add_delay_event(DelayEvent::PointerDown(hit));
+  }
+
+  /// Capture the pointer to the widget `wid`, only a pressed pointer can be
+  /// captured.
+  pub(crate) fn set_pointer_capture(&mut self, pointer: PointerId, wid: WidgetId) {
+    let Some(point) = self.pressed_pointer(pointer) else { return };
+    let wnd = self.window();
+    match self
+      .pointer_captures
+      .iter_mut()
+      .find(|(p, _)| *p == pointer)
+    {
+      Some((_, w)) if *w == wid => return,
+      Some((_, w)) => {
+        let old = std::mem::replace(w, wid);
+        if !old.is_dropped(wnd.tree()) {
+          wnd.add_delay_event(DelayEvent::LostPointerCapture(old, point));
+        }
+      }
+      None => self.pointer_captures.push((pointer, wid)),
+    }
+    wnd.add_delay_event(DelayEvent::GotPointerCapture(wid, point));
+    if pointer == PointerId::MOUSE {
+      self.pointer_enter_leave_dispatch();
     }
   }
 
+  /// Release the pointer if it's captured by the widget `wid`.
+  pub(crate) fn release_pointer_capture(&mut self, pointer: PointerId, wid: WidgetId) {
+    if self.has_pointer_capture(pointer, wid) {
+      let point = self.pressed_pointer(pointer).flatten();
+      self.release_capture(pointer, point);
+    }
+  }
+
+  pub(crate) fn has_pointer_capture(&self, pointer: PointerId, wid: WidgetId) -> bool {
+    self.pointer_captures.contains(&(pointer, wid))
+  }
+
+  /// The pointer state of the pressed pointer, `Some(None)` for the mouse.
+  fn pressed_pointer(&self, pointer: PointerId) -> Option<Option<TouchPoint>> {
+    if pointer == PointerId::MOUSE {
+      self.info.mouse_button.0.map(|_| None)
+    } else {
+      self
+        .touches
+        .iter()
+        .find(|t| t.point.id == pointer)
+        .map(|t| Some(t.point))
+    }
+  }
+
+  /// The widget captured the pointer, the capture of a disposed widget is
+  /// removed.
+  fn capture_target(&mut self, pointer: PointerId) -> Option<WidgetId> {
+    let idx = self
+      .pointer_captures
+      .iter()
+      .position(|(p, _)| *p == pointer)?;
+    let wid = self.pointer_captures[idx].1;
+    if wid.is_dropped(self.window().tree()) {
+      self.pointer_captures.remove(idx);
+      None
+    } else {
+      Some(wid)
+    }
+  }
+
+  fn release_capture(&mut self, pointer: PointerId, point: Option<TouchPoint>) {
+    let Some(wid) = self.capture_target(pointer) else { return };
+    self
+      .pointer_captures
+      .retain(|(p, _)| *p != pointer);
+    self
+      .window()
+      .add_delay_event(DelayEvent::LostPointerCapture(wid, point));
+    if pointer == PointerId::MOUSE {
+      self.pointer_enter_leave_dispatch();
+    }
+  }
+
+  /// The mouse events are dispatched to the widget capturing the mouse, or the
+  /// widget under the cursor.
+  fn mouse_target(&mut self) -> Option<WidgetId> {
+    self
+      .capture_target(PointerId::MOUSE)
+      .or_else(|| self.hit_widget())
+  }
+
   fn pointer_enter_leave_dispatch(&mut self) {
-    let new_hit = self.hit_widget();
+    // The widget capturing the mouse is the only one the mouse is over.
+    let new_hit = self.mouse_target();
     let wnd = self.window();
     let tree = wnd.tree();
 
@@ -197,13 +489,14 @@ impl Dispatcher {
     self.entered_widgets = new_hit.map_or(vec![], |wid| wid.ancestors(tree).collect::<Vec<_>>());
   }
 
-  fn hit_widget(&self) -> Option<WidgetId> {
+  fn hit_widget(&self) -> Option<WidgetId> { self.hit_widget_at(self.info.cursor_pos) }
+
+  pub(crate) fn hit_widget_at(&self, mut pos: Point) -> Option<WidgetId> {
     let mut hit_target = None;
     let wnd = self.window();
     let tree = wnd.tree();
 
     let mut w = Some(tree.root());
-    let mut pos = self.info.cursor_pos;
     while let Some(id) = w {
       let r = id.assert_get(tree);
       let ctx = HitTestCtx { id, tree: wnd.tree };
@@ -262,6 +555,8 @@ impl DispatchInfo {
 #[cfg(test)]
 mod tests {
 
+  use winit::dpi::LogicalPosition;
+
   use super::*;
   use crate::{reset_test_env, test_helper::*};
 
@@ -684,4 +979,201 @@ mod tests {
     let hit_1 = dispatcher.hit_widget();
     assert_eq!(hit_1, data.read().wid1);
   }
+
+  fn touch(wnd: &TestWindow, finger: u64, phase: TouchPhase, x: f32, y: f32) {
+    let location = LogicalPosition::new(x, y).to_physical(1.);
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::Touch(Touch {
+      device_id: unsafe { DeviceId::dummy() },
+      phase,
+      location,
+      force: None,
+      id: finger,
+    }));
+    wnd.run_frame_tasks();
+  }
+
+  #[test]
+  fn multi_touch() {
+    reset_test_env!();
+
+    let (records, w_records) = split_value(vec![]);
+    fn info(
+      name: &'static str, e: &PointerEvent,
+    ) -> (&'static str, WidgetId, PointerId, bool, Point, MouseButtons) {
+      (name, e.current_target(), e.id, e.is_primary, e.global_pos(), e.mouse_buttons())
+    }
+    let (left, w_left) = split_value(None);
+    let w = fn_widget! {
+      @MockMulti {
+        @MockBox {
+          size: Size::new(50., 50.),
+          on_mounted: move |e| *$w_left.write() = Some(e.current_target()),
+          on_pointer_down: move |e| $w_records.write().push(info("down", e)),
+          on_pointer_move: move |e| $w_records.write().push(info("move", e)),
+          on_pointer_up: move |e| $w_records.write().push(info("up", e)),
+        }
+        @MockBox { size: Size::new(50., 50.) }
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+    let left = left.read().unwrap();
+
+    touch(&wnd, 7, TouchPhase::Started, 10., 10.);
+    touch(&wnd, 8, TouchPhase::Started, 20., 20.);
+    // The first finger is captured by the left box after it moves out.
+    touch(&wnd, 7, TouchPhase::Moved, 80., 10.);
+    touch(&wnd, 7, TouchPhase::Ended, 80., 10.);
+    touch(&wnd, 8, TouchPhase::Ended, 20., 20.);
+
+    let records = records.read();
+    let first = records[0].2;
+    let second = records[1].2;
+    assert_ne!(first, second);
+    assert_ne!(first, PointerId::MOUSE);
+    let primary = MouseButtons::PRIMARY;
+    let none = MouseButtons::empty();
+    assert_eq!(
+      &*records,
+      &[
+        ("down", left, first, true, Point::new(10., 10.), primary),
+        ("down", left, second, false, Point::new(20., 20.), primary),
+        ("move", left, first, true, Point::new(80., 10.), primary),
+        ("up", left, first, true, Point::new(80., 10.), none),
+        ("up", left, second, false, Point::new(20., 20.), none),
+      ]
+    );
+  }
+
+  #[test]
+  fn touch_tap_and_cancel() {
+    reset_test_env!();
+
+    let (taps, w_taps) = split_value(0);
+    let (cancels, w_cancels) = split_value(0);
+    let w = fn_widget! {
+      @MockBox {
+        size: Size::new(50., 50.),
+        tab_index: 0i16,
+        on_tap: move |e| {
+          assert_eq!(e.point_type, PointerType::Touch);
+          *$w_taps.write() += 1;
+        },
+        on_pointer_cancel: move |_| *$w_cancels.write() += 1,
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+
+    touch(&wnd, 1, TouchPhase::Started, 10., 10.);
+    assert!(wnd.focus_mgr.borrow().focusing().is_some());
+    touch(&wnd, 1, TouchPhase::Ended, 20., 20.);
+    assert_eq!(*taps.read(), 1);
+
+    // No tap if the finger lifts out of the widget or is cancelled.
+    touch(&wnd, 1, TouchPhase::Started, 10., 10.);
+    touch(&wnd, 1, TouchPhase::Ended, 80., 80.);
+    touch(&wnd, 2, TouchPhase::Started, 10., 10.);
+    touch(&wnd, 2, TouchPhase::Cancelled, 10., 10.);
+    assert_eq!(*taps.read(), 1);
+    assert_eq!(*cancels.read(), 1);
+    assert!(wnd.dispatcher.borrow().touches.is_empty());
+  }
+
+  #[test]
+  fn mouse_pointer_capture() {
+    reset_test_env!();
+
+    let (records, w_records) = split_value(vec![]);
+    let w = fn_widget! {
+      @MockMulti {
+        @MockBox {
+          size: Size::new(50., 50.),
+          on_pointer_down: move |e| {
+            // Capture a released pointer is ignored.
+            e.set_pointer_capture(PointerId(99));
+            e.set_pointer_capture(e.id);
+            assert!(e.has_pointer_capture(e.id));
+          },
+          on_got_pointer_capture: move |_| $w_records.write().push("got"),
+          on_lost_pointer_capture: move |_| $w_records.write().push("lost"),
+          on_pointer_move: move |e| $w_records.write().push(
+            if e.position().x > 50. { "move out" } else { "move" }
+          ),
+          on_pointer_up: move |_| $w_records.write().push("up"),
+          on_pointer_leave: move |_| $w_records.write().push("leave"),
+        }
+        @MockBox {
+          size: Size::new(50., 50.),
+          on_pointer_enter: move |_| $w_records.write().push("right enter"),
+          on_pointer_move: move |_| $w_records.write().push("right move"),
+        }
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+
+    let device_id = unsafe { DeviceId::dummy() };
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::CursorMoved { device_id, position: (10, 10).into() });
+    wnd.process_mouse_input(device_id, ElementState::Pressed, MouseButton::Left);
+    wnd.run_frame_tasks();
+    assert_eq!(&*records.read(), &["move", "got"]);
+
+    // The captured mouse doesn't move over the right box.
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::CursorMoved { device_id, position: (80, 10).into() });
+    wnd.process_mouse_input(device_id, ElementState::Released, MouseButton::Left);
+    wnd.run_frame_tasks();
+    assert_eq!(&records.read()[2..], &["move out", "up", "lost", "leave", "right enter"]);
+    assert!(
+      wnd
+        .dispatcher
+        .borrow()
+        .pointer_captures
+        .is_empty()
+    );
+  }
+
+  #[test]
+  fn touch_pointer_capture() {
+    reset_test_env!();
+
+    let (records, w_records) = split_value(vec![]);
+    let (ids, w_ids) = split_value(vec![]);
+    let w = fn_widget! {
+      @MockMulti {
+        on_mounted: move |e| $w_ids.write().push(e.current_target()),
+        on_pointer_down: move |e| e.set_pointer_capture(e.id),
+        on_pointer_move: move |e| {
+          $w_records.write().push(("move", e.target()));
+          if e.global_pos().x > 50. {
+            e.release_pointer_capture(e.id);
+          }
+        },
+        on_lost_pointer_capture: move |e| $w_records.write().push(("lost", e.target())),
+        @MockBox {
+          size: Size::new(50., 50.),
+          on_mounted: move |e| $w_ids.write().push(e.current_target()),
+        }
+        @MockBox { size: Size::new(50., 50.) }
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+    let (parent, left) = (ids.read()[0], ids.read()[1]);
+
+    // The parent captures the finger instead of the box it touches down on.
+    touch(&wnd, 1, TouchPhase::Started, 10., 10.);
+    touch(&wnd, 1, TouchPhase::Moved, 20., 10.);
+    touch(&wnd, 1, TouchPhase::Moved, 80., 10.);
+    // After the capture released, the finger goes back to the box it touches.
+    touch(&wnd, 1, TouchPhase::Moved, 90., 10.);
+    touch(&wnd, 1, TouchPhase::Ended, 90., 10.);
+    assert_eq!(
+      &*records.read(),
+      &[("move", parent), ("move", parent), ("lost", parent), ("move", left)]
+    );
+  }
 }
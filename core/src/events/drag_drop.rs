@@ -0,0 +1,462 @@
+use std::{
+  any::Any,
+  cell::RefCell,
+  rc::{Rc, Weak},
+};
+
+use rxrust::prelude::*;
+
+use crate::{impl_common_event_deref, overlay::AutoClosePolicy, prelude::*, window::DelayEvent};
+
+/// The distance to the edge of a scrollable widget within which the dragging
+/// pointer scrolls it.
+const AUTO_SCROLL_EDGE: f32 = 32.;
+/// The max distance scrolled in every auto-scroll step.
+const AUTO_SCROLL_STEP: f32 = 16.;
+const AUTO_SCROLL_INTERVAL: Duration = Duration::from_millis(16);
+
+/// The payload carried by a drag, the drop targets downcast it to the type
+/// they accept.
+#[derive(Clone)]
+pub struct DragData(Rc<dyn Any>);
+
+impl DragData {
+  pub fn new<T: Any>(data: T) -> Self { Self(Rc::new(data)) }
+
+  /// Return the payload if it's of type `T`.
+  pub fn get<T: Any>(&self) -> Option<&T> { self.0.downcast_ref() }
+}
+
+impl std::fmt::Debug for DragData {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_tuple("DragData").finish()
+  }
+}
+
+/// The event fired to the drop targets when a widget is dragged over them.
+#[derive(Debug)]
+pub struct DragEvent {
+  data: DragData,
+  source: WidgetId,
+  pos: Point,
+  pub common: CommonEvent,
+}
+
+impl_common_event_deref!(DragEvent);
+
+impl DragEvent {
+  /// Return the payload of the drag if it's of type `T`.
+  #[inline]
+  pub fn data<T: Any>(&self) -> Option<&T> { self.data.get() }
+
+  #[inline]
+  pub fn drag_data(&self) -> &DragData { &self.data }
+
+  /// The widget being dragged.
+  #[inline]
+  pub fn source(&self) -> WidgetId { self.source }
+
+  /// The X, Y coordinate of the dragging pointer in global (window)
+  /// coordinates.
+  #[inline]
+  pub fn global_pos(&self) -> Point { self.pos }
+
+  /// The X, Y coordinate of the dragging pointer in current target widget.
+  #[inline]
+  pub fn position(&self) -> Point { self.map_from_global(self.pos) }
+}
+
+/// Traces the drag of the window, it hit-tests the widgets under the dragging
+/// pointer to fire the drag events, moves the preview and scrolls the
+/// scrollable widgets when the pointer is near their edges.
+#[derive(Default)]
+pub(crate) struct DragManager(RefCell<Option<DragSession>>);
+
+struct DragSession {
+  source: WidgetId,
+  data: DragData,
+  pos: Point,
+  /// The offset of the pointer to the top-left of the preview.
+  grab: Vector,
+  preview: Option<(Overlay, Stateful<Point>)>,
+  /// The widget under the pointer and its ancestors.
+  entered: Vec<WidgetId>,
+  _auto_scroll: SubscriptionGuard<BoxSubscription<'static>>,
+}
+
+impl DragManager {
+  pub(crate) fn is_dragging(&self) -> bool { self.0.borrow().is_some() }
+
+  pub(crate) fn start(
+    &self, source: WidgetId, data: DragData, preview: Option<GenWidget>, grab: Vector, pos: Point,
+    wnd: &Rc<Window>,
+  ) {
+    if self.is_dragging() {
+      return;
+    }
+
+    let preview = preview.map(|gen| {
+      let preview_pos = Stateful::new(pos - grab);
+      let overlay = Overlay::new(gen);
+      overlay.set_modal(false);
+      overlay.set_auto_close_policy(AutoClosePolicy::NONE);
+      let anchor = preview_pos.clone_watcher();
+      overlay.show_map(
+        move |w| {
+          let anchor = anchor.clone_watcher();
+          fn_widget! {
+            // The preview must not hide the drop targets from the hit test.
+            @IgnorePointer {
+              anchor: pipe!(Anchor::from_point(*$anchor)),
+              @ { w }
+            }
+          }
+          .into_widget()
+        },
+        wnd.clone(),
+      );
+      (overlay, preview_pos)
+    });
+
+    let weak = Rc::downgrade(wnd);
+    let auto_scroll = observable::interval(AUTO_SCROLL_INTERVAL, AppCtx::scheduler())
+      .subscribe(move |_| auto_scroll_tick(&weak));
+    let auto_scroll = BoxSubscription::new(auto_scroll).unsubscribe_when_dropped();
+    *self.0.borrow_mut() = Some(DragSession {
+      source,
+      data,
+      pos,
+      grab,
+      preview,
+      entered: vec![],
+      _auto_scroll: auto_scroll,
+    });
+    self.update(wnd);
+  }
+
+  /// Move the dragging pointer of the `source` to the `pos`.
+  pub(crate) fn drag_to(&self, source: WidgetId, pos: Point, wnd: &Window) {
+    {
+      let mut session = self.0.borrow_mut();
+      let Some(session) = session.as_mut().filter(|s| s.source == source) else { return };
+      session.pos = pos;
+      if let Some((_, preview_pos)) = &session.preview {
+        *preview_pos.write() = pos - session.grab;
+      }
+    }
+    self.update(wnd);
+  }
+
+  /// Drop the payload of the `source` to the widget under the pointer.
+  pub(crate) fn drop(&self, source: WidgetId, wnd: &Window) {
+    let dragging = self.0.borrow().as_ref().map(|s| s.source);
+    if dragging != Some(source) {
+      return;
+    }
+    let hit = self.hover(wnd);
+    let session = self.0.borrow_mut().take().unwrap();
+    if let Some(hit) = hit {
+      wnd.add_delay_event(DelayEvent::Drop(session.event(hit, wnd)));
+    }
+    session.finish(wnd);
+  }
+
+  /// Cancel the drag, or only cancel it if it's dragging the `source`.
+  pub(crate) fn cancel(&self, source: Option<WidgetId>, wnd: &Window) {
+    let session = {
+      let mut session = self.0.borrow_mut();
+      let matched = session
+        .as_ref()
+        .is_some_and(|s| source.is_none() || source == Some(s.source));
+      if matched { session.take() } else { None }
+    };
+    if let Some(session) = session {
+      session.finish(wnd);
+    }
+  }
+
+  /// Fire the drag over event to the widget under the dragging pointer.
+  fn update(&self, wnd: &Window) {
+    if let Some(hit) = self.hover(wnd) {
+      let session = self.0.borrow();
+      let session = session.as_ref().unwrap();
+      wnd.add_delay_event(DelayEvent::DragOver(session.event(hit, wnd)));
+    }
+  }
+
+  /// Hit-test the widget under the dragging pointer and fire the drag enter
+  /// and leave events if it changes.
+  fn hover(&self, wnd: &Window) -> Option<WidgetId> {
+    let mut session = self.0.borrow_mut();
+    let session = session.as_mut()?;
+    let tree = wnd.tree();
+    let hit = wnd.dispatcher.borrow().hit_widget_at(session.pos);
+
+    let old = session
+      .entered
+      .iter()
+      .find(|wid| !wid.is_dropped(tree))
+      .copied();
+    if old != hit {
+      if let Some(old) = old {
+        let up = hit.and_then(|w| w.lowest_common_ancestor(old, tree));
+        wnd.add_delay_event(DelayEvent::DragLeave { event: session.event(old, wnd), up });
+      }
+      if let Some(new) = hit {
+        let up = old.and_then(|o| o.lowest_common_ancestor(new, tree));
+        wnd.add_delay_event(DelayEvent::DragEnter { event: session.event(new, wnd), up });
+      }
+      session.entered = hit.map_or(vec![], |wid| wid.ancestors(tree).collect());
+    }
+    hit
+  }
+
+  /// Scroll the innermost scrollable widget under the pointer if the pointer
+  /// is near its edges, return if any widget scrolled.
+  fn auto_scroll(&self, wnd: &Window) -> bool {
+    let session = self.0.borrow();
+    let Some(session) = session.as_ref() else { return false };
+    let tree = wnd.tree();
+    let Some(&hit) = session.entered.first() else { return false };
+
+    let step = |pos: f32, len: f32| {
+      if pos < AUTO_SCROLL_EDGE {
+        -(AUTO_SCROLL_EDGE - pos.max(0.)) / AUTO_SCROLL_EDGE * AUTO_SCROLL_STEP
+      } else if pos > len - AUTO_SCROLL_EDGE {
+        (pos.min(len) - len + AUTO_SCROLL_EDGE) / AUTO_SCROLL_EDGE * AUTO_SCROLL_STEP
+      } else {
+        0.
+      }
+    };
+    for id in hit.ancestors(tree) {
+      let Some(size) = wnd.layout_size(id) else { continue };
+      let (x, y) = {
+        let Some(scroll) = id.query_ref::<ScrollableWidget>(tree) else { continue };
+        let pos = session.pos - wnd.map_to_global(Point::zero(), id);
+        let x = if scroll.is_x_scrollable() { step(pos.x, size.width) } else { 0. };
+        let y = if scroll.is_y_scrollable() { step(pos.y, size.height) } else { 0. };
+        let max = scroll.max_scrollable();
+        let scroll_pos = scroll.get_scroll_pos();
+        // Ignore the direction that can't scroll anymore, so the outer
+        // scrollable widget has a chance to scroll.
+        let x =
+          if (x < 0. && scroll_pos.x <= 0.) || (x > 0. && scroll_pos.x >= max.x) { 0. } else { x };
+        let y =
+          if (y < 0. && scroll_pos.y <= 0.) || (y > 0. && scroll_pos.y >= max.y) { 0. } else { y };
+        (x, y)
+      };
+      if x != 0. || y != 0. {
+        if let Some(mut scroll) = id.query_write::<ScrollableWidget>(tree) {
+          scroll.scroll(x, y);
+        }
+        return true;
+      }
+    }
+    false
+  }
+}
+
+fn auto_scroll_tick(wnd: &Weak<Window>) {
+  let Some(wnd) = wnd.upgrade() else { return };
+  // The widgets under the pointer change after scrolling.
+  if wnd.drag_mgr.auto_scroll(&wnd) {
+    wnd.drag_mgr.update(&wnd);
+  }
+}
+
+impl DragSession {
+  fn event(&self, target: WidgetId, wnd: &Window) -> DragEvent {
+    DragEvent {
+      data: self.data.clone(),
+      source: self.source,
+      pos: self.pos,
+      common: CommonEvent::new(target, wnd.tree),
+    }
+  }
+
+  /// Close the preview and leave the entered widgets.
+  fn finish(self, wnd: &Window) {
+    if let Some((overlay, _)) = &self.preview {
+      overlay.close();
+    }
+    let tree = wnd.tree();
+    if let Some(&old) = self
+      .entered
+      .iter()
+      .find(|wid| !wid.is_dropped(tree))
+    {
+      wnd.add_delay_event(DelayEvent::DragLeave { event: self.event(old, wnd), up: None });
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use winit::{
+    event::{DeviceId, ElementState, MouseButton, WindowEvent},
+    keyboard::{KeyCode, KeyLocation, NamedKey, PhysicalKey},
+  };
+
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  fn cursor_move(wnd: &TestWindow, x: f32, y: f32) {
+    let device_id = unsafe { DeviceId::dummy() };
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::CursorMoved { device_id, position: (x, y).into() });
+    wnd.run_frame_tasks();
+  }
+
+  fn mouse(wnd: &TestWindow, state: ElementState) {
+    let device_id = unsafe { DeviceId::dummy() };
+    wnd.process_mouse_input(device_id, state, MouseButton::Left);
+    wnd.run_frame_tasks();
+  }
+
+  fn drag_source() -> FatObj<Widget<'static>> {
+    FatObj::new(MockBox { size: Size::new(50., 50.) }.into_widget())
+      .draggable(DragData::new(7))
+      .drag_preview(|_: &mut BuildCtx| MockBox { size: Size::new(10., 10.) }.into_widget())
+  }
+
+  #[test]
+  fn drag_and_drop() {
+    reset_test_env!();
+
+    let (records, w_records) = split_value(vec![]);
+    let w = fn_widget! {
+      @MockMulti {
+        @ { drag_source() }
+        @MockBox {
+          size: Size::new(50., 50.),
+          on_drag_enter: move |_| $w_records.write().push("enter"),
+          on_drag_over: move |_| $w_records.write().push("over"),
+          on_drag_leave: move |_| $w_records.write().push("leave"),
+          on_drop: move |e| {
+            assert_eq!(e.data::<i32>(), Some(&7));
+            assert_eq!(e.position(), Point::new(20., 10.));
+            $w_records.write().push("drop");
+          },
+        }
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+
+    cursor_move(&wnd, 10., 10.);
+    mouse(&wnd, ElementState::Pressed);
+    cursor_move(&wnd, 30., 10.);
+    assert!(wnd.drag_mgr.is_dragging());
+    wnd.draw_frame();
+
+    // The preview under the pointer doesn't block the drop target.
+    cursor_move(&wnd, 70., 10.);
+    assert_eq!(&*records.read(), &["enter", "over"]);
+
+    mouse(&wnd, ElementState::Released);
+    assert!(!wnd.drag_mgr.is_dragging());
+    assert_eq!(&*records.read(), &["enter", "over", "drop", "leave"]);
+  }
+
+  #[test]
+  fn cancel_by_escape() {
+    reset_test_env!();
+
+    let (records, w_records) = split_value(vec![]);
+    let w = fn_widget! {
+      @MockMulti {
+        @ { drag_source() }
+        @MockBox {
+          size: Size::new(50., 50.),
+          on_drag_leave: move |_| $w_records.write().push("leave"),
+          on_drop: move |_| $w_records.write().push("drop"),
+        }
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+
+    cursor_move(&wnd, 10., 10.);
+    mouse(&wnd, ElementState::Pressed);
+    cursor_move(&wnd, 70., 10.);
+    assert!(wnd.drag_mgr.is_dragging());
+
+    wnd.processes_keyboard_event(
+      PhysicalKey::Code(KeyCode::Escape),
+      VirtualKey::Named(NamedKey::Escape),
+      false,
+      KeyLocation::Standard,
+      ElementState::Pressed,
+    );
+    wnd.run_frame_tasks();
+    assert!(!wnd.drag_mgr.is_dragging());
+
+    cursor_move(&wnd, 80., 10.);
+    mouse(&wnd, ElementState::Released);
+    assert_eq!(&*records.read(), &["leave"]);
+  }
+
+  #[test]
+  fn auto_scroll() {
+    reset_test_env!();
+
+    let (source, w_source) = split_value(None);
+    let w = fn_widget! {
+      @MockBox {
+        size: Size::new(100., 400.),
+        scrollable: Scrollable::Y,
+        @ {
+          let source = drag_source();
+          source.on_mounted(move |e| *$w_source.write() = Some(e.current_target()))
+        }
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+    let source = source.read().unwrap();
+
+    cursor_move(&wnd, 10., 10.);
+    mouse(&wnd, ElementState::Pressed);
+    cursor_move(&wnd, 70., 95.);
+    assert!(wnd.drag_mgr.is_dragging());
+
+    // Step the auto-scroll by hand rather than waiting for its interval.
+    assert!(wnd.drag_mgr.auto_scroll(&wnd));
+    wnd.draw_frame();
+    assert!(wnd.map_to_global(Point::zero(), source).y < 0.);
+
+    mouse(&wnd, ElementState::Released);
+  }
+
+  #[test]
+  fn no_tap_after_drag() {
+    reset_test_env!();
+
+    let (taps, w_taps) = split_value(vec![]);
+    let w = fn_widget! {
+      @MockMulti {
+        on_tap: move |_| $w_taps.write().push("parent"),
+        @ {
+          drag_source().on_tap(move |_| $w_taps.write().push("source"))
+        }
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+
+    // A click on the drag source is still a tap.
+    cursor_move(&wnd, 10., 10.);
+    mouse(&wnd, ElementState::Pressed);
+    mouse(&wnd, ElementState::Released);
+    assert_eq!(&*taps.read(), &["source", "parent"]);
+
+    // Released on the source after a drag, neither the source nor its ancestors
+    // are tapped.
+    mouse(&wnd, ElementState::Pressed);
+    cursor_move(&wnd, 30., 10.);
+    assert!(wnd.drag_mgr.is_dragging());
+    mouse(&wnd, ElementState::Released);
+    assert!(!wnd.drag_mgr.is_dragging());
+    assert_eq!(&*taps.read(), &["source", "parent"]);
+  }
+}
@@ -0,0 +1,154 @@
+use std::path::PathBuf;
+
+use crate::{impl_common_event_deref, prelude::*};
+
+/// The event fires when files are dragged from the operating system over a
+/// window, or dropped on it.
+#[derive(Debug)]
+pub struct FileDropEvent {
+  paths: Vec<PathBuf>,
+  pub common: CommonEvent,
+}
+
+impl_common_event_deref!(FileDropEvent);
+
+impl FileDropEvent {
+  #[inline]
+  pub fn new(paths: Vec<PathBuf>, id: WidgetId, wnd: &Window) -> Self {
+    Self { paths, common: CommonEvent::new(id, wnd.tree) }
+  }
+
+  /// The paths of the files. For the hover events, they are all the files
+  /// hovering the window; for the drop event, they are the files just dropped.
+  #[inline]
+  pub fn paths(&self) -> &[PathBuf] { &self.paths }
+}
+
+#[cfg(test)]
+mod tests {
+  use winit::event::{DeviceId, WindowEvent};
+
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  fn cursor_move(wnd: &TestWindow, x: f32, y: f32) {
+    let device_id = unsafe { DeviceId::dummy() };
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::CursorMoved { device_id, position: (x, y).into() });
+    wnd.run_frame_tasks();
+  }
+
+  fn file_target(records: &Stateful<Vec<String>>, name: &'static str) -> Widget<'static> {
+    let (hover, end, drop) =
+      (records.clone_writer(), records.clone_writer(), records.clone_writer());
+    FatObj::new(MockBox { size: Size::new(50., 50.) })
+      .on_file_hover(move |e| {
+        hover
+          .write()
+          .push(format!("{name} hover {}", e.paths().len()))
+      })
+      .on_file_hover_end(move |_| end.write().push(format!("{name} end")))
+      .on_file_drop(move |e| {
+        let files = e
+          .paths()
+          .iter()
+          .map(|p| p.display().to_string())
+          .collect::<Vec<_>>();
+        drop
+          .write()
+          .push(format!("{name} drop {}", files.join(" ")))
+      })
+      .into_widget()
+  }
+
+  fn two_targets(w_records: &Stateful<Vec<String>>) -> TestWindow {
+    let w_records = w_records.clone_writer();
+    let w = fn_widget! {
+      @MockMulti {
+        @ { file_target(&w_records, "a") }
+        @ { file_target(&w_records, "b") }
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+    wnd
+  }
+
+  #[test]
+  fn hover_and_drop() {
+    reset_test_env!();
+
+    let (records, w_records) = split_value(vec![]);
+    let wnd = two_targets(&w_records);
+
+    let pos = Point::new(10., 10.);
+    wnd.processes_hovered_file("x.txt".into(), pos);
+    wnd.processes_hovered_file("y.txt".into(), pos);
+    wnd.run_frame_tasks();
+    assert_eq!(&*records.read(), &["a hover 1", "a hover 2"]);
+
+    // The target changes while the files hovering.
+    let pos = Point::new(70., 10.);
+    wnd.processes_hovered_file("z.txt".into(), pos);
+    wnd.run_frame_tasks();
+    assert_eq!(&records.read()[2..], &["a end", "b hover 3"]);
+
+    // The files dropped together are in one event.
+    wnd.processes_dropped_file("x.txt".into(), pos);
+    wnd.processes_dropped_file("y.txt".into(), pos);
+    wnd.processes_dropped_file("z.txt".into(), pos);
+    wnd.run_frame_tasks();
+    assert_eq!(&records.read()[4..], &["b end", "b drop x.txt y.txt z.txt"]);
+  }
+
+  #[test]
+  #[allow(deprecated)]
+  fn hover_follow_cursor() {
+    reset_test_env!();
+
+    let (records, w_records) = split_value(vec![]);
+    let wnd = two_targets(&w_records);
+
+    cursor_move(&wnd, 10., 10.);
+    wnd.processes_native_event(WindowEvent::HoveredFile("x.txt".into()));
+    wnd.processes_native_event(WindowEvent::HoveredFile("y.txt".into()));
+    wnd.run_frame_tasks();
+    assert_eq!(&*records.read(), &["a hover 1", "a hover 2"]);
+
+    // Move between the targets while hovering, and in the same target.
+    cursor_move(&wnd, 70., 10.);
+    cursor_move(&wnd, 80., 20.);
+    assert_eq!(&records.read()[2..], &["a end", "b hover 2"]);
+    cursor_move(&wnd, 10., 20.);
+    assert_eq!(&records.read()[4..], &["b end", "a hover 2"]);
+
+    wnd.processes_native_event(WindowEvent::DroppedFile("x.txt".into()));
+    wnd.processes_native_event(WindowEvent::DroppedFile("y.txt".into()));
+    wnd.run_frame_tasks();
+    assert_eq!(&records.read()[6..], &["a end", "a drop x.txt y.txt"]);
+
+    // No file hovering, the cursor moves as usual.
+    cursor_move(&wnd, 70., 10.);
+    assert_eq!(records.read().len(), 8);
+  }
+
+  #[test]
+  fn hover_cancel() {
+    reset_test_env!();
+
+    let (records, w_records) = split_value(vec![]);
+    let w = fn_widget! { file_target(&w_records, "a") };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+
+    wnd.processes_hovered_file("x.txt".into(), Point::new(10., 10.));
+    wnd.processes_hovered_file_cancelled();
+    wnd.run_frame_tasks();
+    assert_eq!(&*records.read(), &["a hover 1", "a end"]);
+
+    // Nothing hits the position, the drop is ignored.
+    wnd.processes_dropped_file("x.txt".into(), Point::new(70., 70.));
+    wnd.run_frame_tasks();
+    assert_eq!(records.read().len(), 2);
+  }
+}
@@ -202,14 +202,22 @@ impl FocusManager {
     let mut node_id = focusing
       .and_then(|id| self.node_ids.get(&id))
       .copied();
+    // A focused trap scope steps into its descendants instead of its siblings.
     let mut scope_id = node_id
-      .and_then(|id| self.scope_id(id))
+      .filter(|id| self.is_trap_scope(*id))
+      .or_else(|| node_id.and_then(|id| self.scope_id(id)))
       .or(Some(self.root));
     loop {
       scope_id?;
       let next = self.focus_step_in_scope(scope_id.unwrap(), node_id, backward);
       if let Some(id) = next {
         return self.get(id).and_then(|n| n.wid);
+      } else if self.is_trap_scope(scope_id.unwrap()) {
+        // The focus never leaves a trap scope, restart from its first node.
+        let first = self.focus_step_in_scope(scope_id.unwrap(), None, backward);
+        return first
+          .and_then(|id| self.get(id).and_then(|n| n.wid))
+          .or(focusing);
       } else {
         node_id = scope_id;
         scope_id = self.scope_id(node_id.unwrap());
@@ -360,6 +368,14 @@ impl FocusManager {
       .unwrap_or_default()
   }
 
+  fn is_trap_scope(&self, scope_id: NodeId) -> bool {
+    scope_id != self.root
+      && self.assert_get(scope_id).has_focus_scope()
+      && self
+        .scope_property(self.get(scope_id).and_then(|n| n.wid))
+        .trap
+  }
+
   fn tab_index(&self, node_id: NodeId) -> i16 {
     let wnd = self.window();
     let tree = wnd.tree();
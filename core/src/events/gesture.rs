@@ -0,0 +1,765 @@
+use std::{
+  cell::RefCell,
+  f32::consts::PI,
+  rc::{Rc, Weak},
+};
+
+use rxrust::prelude::*;
+
+use crate::{impl_common_event_deref, prelude::*};
+
+/// The distance the pointers move before they are regarded as dragging rather
+/// than pressing.
+const GESTURE_SLOP: f32 = 8.;
+/// The angle in radians the pointers rotate before they are regarded as
+/// rotating.
+const ROTATE_SLOP: f32 = 0.1;
+const LONG_PRESS_DURATION: Duration = Duration::from_millis(500);
+/// The minimum speed in pixels per second of a swipe.
+const SWIPE_MIN_VELOCITY: f32 = 300.;
+/// The velocity is regarded as zero if the pointer stays longer than it before
+/// it lifts up.
+const VELOCITY_TIMEOUT: Duration = Duration::from_millis(100);
+
+macro_rules! impl_gesture_event {
+  ($($event:ident),*) => {
+    $(
+      impl_common_event_deref!($event);
+
+      impl $event {
+        /// The X, Y coordinate of the focal point of the pointers in global
+        /// (window) coordinates.
+        #[inline]
+        pub fn global_pos(&self) -> Point { self.focal }
+
+        /// The X, Y coordinate of the focal point of the pointers in current
+        /// target widget.
+        #[inline]
+        pub fn position(&self) -> Point { self.map_from_global(self.focal) }
+      }
+    )*
+  };
+}
+
+/// The event of dragging the pointers over a widget.
+#[derive(Debug)]
+pub struct PanEvent {
+  /// The distance the pointers moved since the last pan event.
+  pub delta: Vector,
+  /// The distance the pointers moved since they pressed down.
+  pub offset: Vector,
+  /// The velocity in pixels per second of the pointers, it's only available in
+  /// the pan end event.
+  pub velocity: Vector,
+  focal: Point,
+  pub common: CommonEvent,
+}
+
+/// The event of pinching two or more pointers.
+#[derive(Debug)]
+pub struct ScaleEvent {
+  /// The scale of the distance between the pointers since they pressed down.
+  pub scale: f32,
+  focal: Point,
+  pub common: CommonEvent,
+}
+
+/// The event of rotating two or more pointers.
+#[derive(Debug)]
+pub struct RotateEvent {
+  /// The clockwise angle the pointers rotated since they pressed down.
+  pub angle: Angle,
+  focal: Point,
+  pub common: CommonEvent,
+}
+
+/// The event of pressing a pointer on a widget for a while without moving.
+#[derive(Debug)]
+pub struct LongPressEvent {
+  focal: Point,
+  pub common: CommonEvent,
+}
+
+/// The event of a fast pan that lifts up.
+#[derive(Debug)]
+pub struct SwipeEvent {
+  pub direction: SwipeDirection,
+  /// The velocity in pixels per second of the pointer when it lifts up.
+  pub velocity: Vector,
+  focal: Point,
+  pub common: CommonEvent,
+}
+
+impl_gesture_event!(PanEvent, ScaleEvent, RotateEvent, LongPressEvent, SwipeEvent);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwipeDirection {
+  Left,
+  Right,
+  Up,
+  Down,
+}
+
+type Handlers<E> = Vec<Box<dyn FnMut(&mut E)>>;
+
+/// The gesture handlers of a widget.
+#[derive(Default)]
+pub(crate) struct GestureHandlers {
+  pub(crate) pan_start: Handlers<PanEvent>,
+  pub(crate) pan_update: Handlers<PanEvent>,
+  pub(crate) pan_end: Handlers<PanEvent>,
+  pub(crate) scale: Handlers<ScaleEvent>,
+  pub(crate) rotate: Handlers<RotateEvent>,
+  pub(crate) long_press: Handlers<LongPressEvent>,
+  pub(crate) swipe: Handlers<SwipeEvent>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GestureKind {
+  Pan,
+  Scale,
+  Rotate,
+  LongPress,
+  Swipe,
+}
+
+impl GestureHandlers {
+  fn kinds(&self) -> impl Iterator<Item = GestureKind> {
+    let pan = !self.pan_start.is_empty() || !self.pan_update.is_empty() || !self.pan_end.is_empty();
+    [
+      (GestureKind::Pan, pan),
+      (GestureKind::Scale, !self.scale.is_empty()),
+      (GestureKind::Rotate, !self.rotate.is_empty()),
+      (GestureKind::LongPress, !self.long_press.is_empty()),
+      (GestureKind::Swipe, !self.swipe.is_empty()),
+    ]
+    .into_iter()
+    .filter_map(|(kind, listened)| listened.then_some(kind))
+  }
+}
+
+/// The arena decides which gesture the pointers make, when the pointers press
+/// down on a widget, the recognizers of the widget and its ancestors join the
+/// arena. The first recognizer that recognizes its gesture wins, and the other
+/// recognizers are rejected, except the recognizers of the same widget, so a
+/// widget can be panned, scaled and rotated at the same time. The long press
+/// always excludes the other gestures.
+///
+/// The recognizers of the inner widgets join first, so they win if several
+/// recognizers recognize their gestures by the same pointer event. And the tap
+/// is not fired if any gesture is recognized.
+#[derive(Default)]
+pub(crate) struct GestureArena(RefCell<ArenaState>);
+
+#[derive(Default)]
+struct ArenaState {
+  /// A session begins when the first pointer presses down and ends when all
+  /// the pointers lift up.
+  session: usize,
+  pointers: Vec<TracedPointer>,
+  members: Vec<Member>,
+  /// Whether a gesture is recognized in the session.
+  claimed: bool,
+  last_focal: Point,
+  offset: Vector,
+  last_span: Option<f32>,
+  scale: f32,
+  last_angle: Option<f32>,
+  rotation: f32,
+  /// The velocity of the last lifted pointer.
+  velocity: Vector,
+  /// When the first pointer of the session pressed down.
+  pressed_at: Option<Instant>,
+  /// The time set by hand to trace the pointers, the real time is used if it's
+  /// `None`.
+  clock: Option<Instant>,
+}
+
+struct TracedPointer {
+  id: PointerId,
+  pos: Point,
+  velocity: Vector,
+  stamp: Instant,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum MemberState {
+  Pending,
+  Accepted,
+  Rejected,
+}
+
+struct Member {
+  kind: GestureKind,
+  target: WidgetId,
+  handlers: Rc<RefCell<GestureHandlers>>,
+  state: MemberState,
+}
+
+type Emission = Box<dyn FnOnce()>;
+
+impl Member {
+  fn compatible(&self, other: &Member) -> bool {
+    self.target == other.target
+      && self.kind != GestureKind::LongPress
+      && other.kind != GestureKind::LongPress
+  }
+}
+
+impl GestureArena {
+  /// Whether a gesture is recognized by the pointers, the tap of the pointers
+  /// should be ignored.
+  pub(crate) fn is_claimed(&self) -> bool { self.0.borrow().claimed }
+
+  pub(crate) fn pointer_down(&self, id: PointerId, pos: Point) {
+    let mut arena = self.0.borrow_mut();
+    if arena.pointers.is_empty() {
+      let session = arena.session + 1;
+      let clock = arena.clock;
+      *arena = ArenaState { session, scale: 1., clock, ..<_>::default() };
+      arena.pressed_at = Some(arena.now());
+    }
+    arena.pointers.retain(|p| p.id != id);
+    let stamp = arena.now();
+    arena
+      .pointers
+      .push(TracedPointer { id, pos, velocity: Vector::zero(), stamp });
+    arena.rebase();
+  }
+
+  /// The recognizers of the widget join the arena when a pointer presses down
+  /// on the widget or its descendants.
+  pub(crate) fn join(
+    &self, target: WidgetId, handlers: &Rc<RefCell<GestureHandlers>>, wnd: &Rc<Window>,
+  ) {
+    let mut arena = self.0.borrow_mut();
+    if arena.pointers.is_empty() {
+      return;
+    }
+    let kinds: Vec<_> = handlers.borrow().kinds().collect();
+    for kind in kinds {
+      let joined = arena
+        .members
+        .iter()
+        .any(|m| m.kind == kind && Rc::ptr_eq(&m.handlers, handlers));
+      if joined {
+        continue;
+      }
+      let mut member =
+        Member { kind, target, handlers: handlers.clone(), state: MemberState::Pending };
+      let excluded = arena
+        .members
+        .iter()
+        .any(|m| m.state == MemberState::Accepted && !m.compatible(&member));
+      if excluded {
+        member.state = MemberState::Rejected;
+      } else if kind == GestureKind::LongPress {
+        let (session, idx) = (arena.session, arena.members.len());
+        let wnd = Rc::downgrade(wnd);
+        let _ = observable::timer((), LONG_PRESS_DURATION, AppCtx::scheduler())
+          .subscribe(move |_| long_press_timeout(&wnd, session, idx));
+      }
+      arena.members.push(member);
+    }
+  }
+
+  pub(crate) fn pointer_move(&self, id: PointerId, pos: Point, wnd: &Window) {
+    let emissions = self.0.borrow_mut().move_pointer(id, pos, wnd);
+    emissions.into_iter().for_each(|f| f());
+  }
+
+  pub(crate) fn pointer_up(&self, id: PointerId, pos: Point, wnd: &Window) {
+    let mut emissions = self.0.borrow_mut().move_pointer(id, pos, wnd);
+    emissions.extend(self.0.borrow_mut().remove_pointer(id, wnd));
+    emissions.into_iter().for_each(|f| f());
+  }
+
+  pub(crate) fn pointer_cancel(&self, id: PointerId, wnd: &Window) {
+    let emissions = self.0.borrow_mut().remove_pointer(id, wnd);
+    emissions.into_iter().for_each(|f| f());
+  }
+}
+
+#[cfg(test)]
+impl GestureArena {
+  /// Advance the clock of the arena by hand, and recognize the long press if
+  /// its duration is reached.
+  pub(crate) fn advance(&self, dur: Duration, wnd: &Rc<Window>) {
+    let (session, long_presses) = {
+      let mut arena = self.0.borrow_mut();
+      let now = arena.now() + dur;
+      arena.clock = Some(now);
+      let due = arena
+        .pressed_at
+        .is_some_and(|at| now.duration_since(at) >= LONG_PRESS_DURATION);
+      let long_presses: Vec<_> = (0..arena.members.len())
+        .filter(|&idx| due && arena.members[idx].kind == GestureKind::LongPress)
+        .collect();
+      (arena.session, long_presses)
+    };
+    let wnd = Rc::downgrade(wnd);
+    for idx in long_presses {
+      long_press_timeout(&wnd, session, idx);
+    }
+  }
+}
+
+fn long_press_timeout(wnd: &Weak<Window>, session: usize, idx: usize) {
+  let Some(wnd) = wnd.upgrade() else { return };
+  let emission = {
+    let mut arena = wnd.gesture_arena.0.borrow_mut();
+    let pending = arena.session == session
+      && arena.pointers.len() == 1
+      && arena
+        .members
+        .get(idx)
+        .is_some_and(|m| m.state == MemberState::Pending);
+    if !pending {
+      return;
+    }
+    arena.accept(idx);
+    let focal = arena.focal();
+    let member = &arena.members[idx];
+    let e = LongPressEvent { focal, common: CommonEvent::new(member.target, wnd.tree) };
+    emit(&member.handlers, |h| &mut h.long_press, e)
+  };
+  emission();
+}
+
+fn emit<E: 'static>(
+  handlers: &Rc<RefCell<GestureHandlers>>,
+  list: impl Fn(&mut GestureHandlers) -> &mut Handlers<E> + 'static, mut e: E,
+) -> Emission {
+  let handlers = handlers.clone();
+  Box::new(move || {
+    let mut handlers = handlers.borrow_mut();
+    for h in list(&mut handlers).iter_mut() {
+      h(&mut e);
+    }
+  })
+}
+
+impl ArenaState {
+  fn now(&self) -> Instant { self.clock.unwrap_or_else(Instant::now) }
+
+  fn focal(&self) -> Point {
+    let sum = self
+      .pointers
+      .iter()
+      .fold(Vector::zero(), |sum, p| sum + p.pos.to_vector());
+    (sum / self.pointers.len().max(1) as f32).to_point()
+  }
+
+  fn span(&self) -> Option<f32> {
+    (self.pointers.len() >= 2).then(|| {
+      let focal = self.focal();
+      let sum: f32 = self
+        .pointers
+        .iter()
+        .map(|p| (p.pos - focal).length())
+        .sum();
+      sum / self.pointers.len() as f32
+    })
+  }
+
+  fn angle(&self) -> Option<f32> {
+    match &self.pointers[..] {
+      [first, second, ..] => Some(
+        (second.pos - first.pos)
+          .angle_from_x_axis()
+          .radians,
+      ),
+      _ => None,
+    }
+  }
+
+  /// Start to trace the gestures from the current pointers, so the gestures
+  /// don't jump when a pointer presses down or lifts up.
+  fn rebase(&mut self) {
+    self.last_focal = self.focal();
+    self.last_span = self.span();
+    self.last_angle = self.angle();
+  }
+
+  fn accept(&mut self, idx: usize) {
+    self.claimed = true;
+    self.members[idx].state = MemberState::Accepted;
+    let (accepted, others) = {
+      let (before, rest) = self.members.split_at_mut(idx);
+      let (accepted, after) = rest.split_first_mut().unwrap();
+      (accepted, before.iter_mut().chain(after.iter_mut()))
+    };
+    for m in others {
+      if m.state != MemberState::Rejected && !accepted.compatible(m) {
+        m.state = MemberState::Rejected;
+      }
+    }
+  }
+
+  fn recognized(&self, kind: GestureKind) -> bool {
+    match kind {
+      GestureKind::Pan => self.offset.length() > GESTURE_SLOP,
+      GestureKind::Swipe => self.pointers.len() == 1 && self.offset.length() > GESTURE_SLOP,
+      GestureKind::Scale => self
+        .last_span
+        .is_some_and(|span| (self.scale - 1.).abs() * span > GESTURE_SLOP),
+      GestureKind::Rotate => self.rotation.abs() > ROTATE_SLOP,
+      GestureKind::LongPress => false,
+    }
+  }
+
+  fn move_pointer(&mut self, id: PointerId, pos: Point, wnd: &Window) -> Vec<Emission> {
+    let now = self.now();
+    // Keep the velocity of the pointer if it lifts up without moving.
+    let Some(p) = self
+      .pointers
+      .iter_mut()
+      .find(|p| p.id == id && p.pos != pos)
+    else {
+      return vec![];
+    };
+    let dt = now
+      .duration_since(p.stamp)
+      .as_secs_f32()
+      .max(0.001);
+    p.velocity = (pos - p.pos) / dt;
+    p.pos = pos;
+    p.stamp = now;
+
+    let focal = self.focal();
+    let delta = focal - self.last_focal;
+    self.last_focal = focal;
+    self.offset += delta;
+    let (old_scale, old_rotation) = (self.scale, self.rotation);
+    if let (Some(last), Some(span)) = (self.last_span, self.span()) {
+      if last > 0. {
+        self.scale *= span / last;
+      }
+      self.last_span = Some(span);
+    }
+    if let (Some(last), Some(angle)) = (self.last_angle, self.angle()) {
+      // Keep the delta in (-PI, PI] to avoid jumping across the x-axis.
+      let mut delta = angle - last;
+      if delta > PI {
+        delta -= 2. * PI;
+      } else if delta <= -PI {
+        delta += 2. * PI;
+      }
+      self.rotation += delta;
+      self.last_angle = Some(angle);
+    }
+
+    let mut emissions = vec![];
+    for idx in 0..self.members.len() {
+      let m = &self.members[idx];
+      match m.state {
+        // The long press fails if the pointer moves or more pointers press down.
+        MemberState::Pending
+          if m.kind == GestureKind::LongPress
+            && (self.offset.length() > GESTURE_SLOP || self.pointers.len() > 1) =>
+        {
+          self.members[idx].state = MemberState::Rejected;
+        }
+        MemberState::Pending if self.recognized(m.kind) => {
+          self.accept(idx);
+          let m = &self.members[idx];
+          if m.kind == GestureKind::Pan {
+            let e = self.pan_event(m.target, self.offset, wnd);
+            emissions.push(emit(&m.handlers, |h| &mut h.pan_start, e));
+          }
+          emissions.extend(self.emit_change(idx, wnd, delta, true));
+        }
+        MemberState::Accepted => {
+          let changed = delta != Vector::zero();
+          let scaled = old_scale != self.scale;
+          let rotated = old_rotation != self.rotation;
+          let m = &self.members[idx];
+          let emitting = match m.kind {
+            GestureKind::Pan => changed,
+            GestureKind::Scale => scaled,
+            GestureKind::Rotate => rotated,
+            _ => false,
+          };
+          if emitting {
+            emissions.extend(self.emit_change(idx, wnd, delta, false));
+          }
+        }
+        _ => {}
+      }
+    }
+    emissions
+  }
+
+  fn emit_change(
+    &self, idx: usize, wnd: &Window, delta: Vector, started: bool,
+  ) -> Option<Emission> {
+    let m = &self.members[idx];
+    let common = CommonEvent::new(m.target, wnd.tree);
+    let focal = self.last_focal;
+    match m.kind {
+      // The pan start event already carries the offset.
+      GestureKind::Pan if started => None,
+      GestureKind::Pan => {
+        let e = PanEvent { delta, offset: self.offset, velocity: Vector::zero(), focal, common };
+        Some(emit(&m.handlers, |h| &mut h.pan_update, e))
+      }
+      GestureKind::Scale => {
+        let e = ScaleEvent { scale: self.scale, focal, common };
+        Some(emit(&m.handlers, |h| &mut h.scale, e))
+      }
+      GestureKind::Rotate => {
+        let e = RotateEvent { angle: Angle::radians(self.rotation), focal, common };
+        Some(emit(&m.handlers, |h| &mut h.rotate, e))
+      }
+      GestureKind::LongPress | GestureKind::Swipe => None,
+    }
+  }
+
+  fn pan_event(&self, target: WidgetId, delta: Vector, wnd: &Window) -> PanEvent {
+    PanEvent {
+      delta,
+      offset: self.offset,
+      velocity: Vector::zero(),
+      focal: self.last_focal,
+      common: CommonEvent::new(target, wnd.tree),
+    }
+  }
+
+  fn remove_pointer(&mut self, id: PointerId, wnd: &Window) -> Vec<Emission> {
+    let Some(idx) = self.pointers.iter().position(|p| p.id == id) else {
+      return vec![];
+    };
+    let p = self.pointers.remove(idx);
+    let still = self.now().duration_since(p.stamp) > VELOCITY_TIMEOUT;
+    self.velocity = if still { Vector::zero() } else { p.velocity };
+    if !self.pointers.is_empty() {
+      self.rebase();
+      return vec![];
+    }
+
+    // The session ends.
+    let mut emissions = vec![];
+    for m in self
+      .members
+      .iter()
+      .filter(|m| m.state == MemberState::Accepted)
+    {
+      match m.kind {
+        GestureKind::Pan => {
+          let mut e = self.pan_event(m.target, Vector::zero(), wnd);
+          e.velocity = self.velocity;
+          emissions.push(emit(&m.handlers, |h| &mut h.pan_end, e));
+        }
+        GestureKind::Swipe if self.velocity.length() >= SWIPE_MIN_VELOCITY => {
+          let Vector { x, y, .. } = self.velocity;
+          let direction = if x.abs() >= y.abs() {
+            if x > 0. { SwipeDirection::Right } else { SwipeDirection::Left }
+          } else if y > 0. {
+            SwipeDirection::Down
+          } else {
+            SwipeDirection::Up
+          };
+          let e = SwipeEvent {
+            direction,
+            velocity: self.velocity,
+            focal: self.last_focal,
+            common: CommonEvent::new(m.target, wnd.tree),
+          };
+          emissions.push(emit(&m.handlers, |h| &mut h.swipe, e));
+        }
+        _ => {}
+      }
+    }
+    self.members.clear();
+    emissions
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use winit::{
+    dpi::LogicalPosition,
+    event::{DeviceId, Touch, TouchPhase, WindowEvent},
+  };
+
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  fn touch(wnd: &TestWindow, finger: u64, phase: TouchPhase, x: f32, y: f32) {
+    let location = LogicalPosition::new(x, y).to_physical(1.);
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::Touch(Touch {
+      device_id: unsafe { DeviceId::dummy() },
+      phase,
+      location,
+      force: None,
+      id: finger,
+    }));
+    wnd.run_frame_tasks();
+  }
+
+  #[test]
+  fn pan() {
+    reset_test_env!();
+
+    let (records, w_records) = split_value(vec![]);
+    let w = fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        on_pan_start: move |e| $w_records.write().push(("start", e.delta, e.offset)),
+        on_pan_update: move |e| $w_records.write().push(("update", e.delta, e.offset)),
+        on_pan_end: move |e| $w_records.write().push(("end", e.delta, e.offset)),
+        on_tap: move |_| $w_records.write().push(("tap", Vector::zero(), Vector::zero())),
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+
+    touch(&wnd, 1, TouchPhase::Started, 10., 10.);
+    // Not far enough to pan.
+    touch(&wnd, 1, TouchPhase::Moved, 12., 10.);
+    assert!(records.read().is_empty());
+
+    touch(&wnd, 1, TouchPhase::Moved, 30., 10.);
+    touch(&wnd, 1, TouchPhase::Moved, 40., 15.);
+    touch(&wnd, 1, TouchPhase::Ended, 40., 15.);
+
+    // The tap is swallowed by the pan.
+    assert_eq!(
+      &*records.read(),
+      &[
+        ("start", Vector::new(20., 0.), Vector::new(20., 0.)),
+        ("update", Vector::new(10., 5.), Vector::new(30., 5.)),
+        ("end", Vector::zero(), Vector::new(30., 5.)),
+      ]
+    );
+  }
+
+  #[test]
+  fn scale_and_rotate() {
+    reset_test_env!();
+
+    let (scale, w_scale) = split_value(1.);
+    let (angle, w_angle) = split_value(Angle::zero());
+    let w = fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        on_scale: move |e| *$w_scale.write() = e.scale,
+        on_rotate: move |e| *$w_angle.write() = e.angle,
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+
+    touch(&wnd, 1, TouchPhase::Started, 40., 50.);
+    touch(&wnd, 2, TouchPhase::Started, 60., 50.);
+    touch(&wnd, 2, TouchPhase::Moved, 80., 50.);
+    assert_eq!(*scale.read(), 2.);
+    assert_eq!(*angle.read(), Angle::zero());
+
+    touch(&wnd, 2, TouchPhase::Moved, 40., 70.);
+    assert_eq!(*scale.read(), 1.);
+    assert!((angle.read().radians - PI / 2.).abs() < f32::EPSILON);
+
+    touch(&wnd, 1, TouchPhase::Ended, 40., 50.);
+    touch(&wnd, 2, TouchPhase::Ended, 40., 70.);
+  }
+
+  #[test]
+  fn long_press() {
+    reset_test_env!();
+
+    let (records, w_records) = split_value(vec![]);
+    let w = fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        on_long_press: move |e| $w_records.write().push(("long press", e.position())),
+        on_tap: move |e| $w_records.write().push(("tap", e.position())),
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+
+    let wait_long_press = || {
+      wnd
+        .gesture_arena
+        .advance(LONG_PRESS_DURATION, &wnd.0)
+    };
+
+    touch(&wnd, 1, TouchPhase::Started, 10., 10.);
+    wait_long_press();
+    touch(&wnd, 1, TouchPhase::Ended, 10., 10.);
+    // The tap is swallowed by the long press.
+    assert_eq!(&*records.read(), &[("long press", Point::new(10., 10.))]);
+
+    // Moving cancels the long press, and the pointer taps when it lifts up.
+    touch(&wnd, 1, TouchPhase::Started, 10., 10.);
+    touch(&wnd, 1, TouchPhase::Moved, 50., 10.);
+    wait_long_press();
+    touch(&wnd, 1, TouchPhase::Ended, 50., 10.);
+    assert_eq!(records.read().len(), 2);
+    assert_eq!(records.read()[1].0, "tap");
+  }
+
+  #[test]
+  fn swipe() {
+    reset_test_env!();
+
+    let (swipes, w_swipes) = split_value(vec![]);
+    let w = fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        on_swipe: move |e| $w_swipes.write().push(e.direction),
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+    // Freeze the clock of the arena, and move the pointers by hand.
+    let advance = |dur| wnd.gesture_arena.advance(dur, &wnd.0);
+    advance(Duration::ZERO);
+
+    touch(&wnd, 1, TouchPhase::Started, 50., 80.);
+    advance(Duration::from_millis(50));
+    touch(&wnd, 1, TouchPhase::Moved, 50., 20.);
+    touch(&wnd, 1, TouchPhase::Ended, 50., 20.);
+    assert_eq!(&*swipes.read(), &[SwipeDirection::Up]);
+
+    // The pointer stays before lifting up, it's not a swipe.
+    touch(&wnd, 1, TouchPhase::Started, 20., 50.);
+    advance(Duration::from_millis(50));
+    touch(&wnd, 1, TouchPhase::Moved, 80., 50.);
+    advance(VELOCITY_TIMEOUT + Duration::from_millis(20));
+    touch(&wnd, 1, TouchPhase::Ended, 80., 50.);
+    assert_eq!(swipes.read().len(), 1);
+  }
+
+  #[test]
+  fn inner_gesture_wins() {
+    reset_test_env!();
+
+    let (records, w_records) = split_value(vec![]);
+    let w = fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        on_pan_start: move |_| $w_records.write().push("outer pan"),
+        on_long_press: move |_| $w_records.write().push("outer long press"),
+        @MockBox {
+          size: Size::new(50., 50.),
+          on_pan_start: move |_| $w_records.write().push("inner pan"),
+        }
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+
+    touch(&wnd, 1, TouchPhase::Started, 10., 10.);
+    touch(&wnd, 1, TouchPhase::Moved, 40., 10.);
+    touch(&wnd, 1, TouchPhase::Ended, 40., 10.);
+
+    touch(&wnd, 1, TouchPhase::Started, 60., 60.);
+    touch(&wnd, 1, TouchPhase::Moved, 90., 60.);
+    touch(&wnd, 1, TouchPhase::Ended, 90., 60.);
+
+    assert_eq!(&*records.read(), &["inner pan", "outer pan"]);
+  }
+}
@@ -1,8 +1,21 @@
+use ribir_geom::Point;
+
 use super::CommonEvent;
-use crate::impl_common_event_deref;
+use crate::{context::WidgetCtx, impl_common_event_deref};
 mod from_mouse;
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct PointerId(usize);
+mod from_touch;
+pub(crate) use from_touch::*;
+
+/// The id of a pointer. The mouse always has the same id, and every finger
+/// touching the screen has its own id, which keeps the same until the finger
+/// lifts up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PointerId(pub(crate) usize);
+
+impl PointerId {
+  /// The id of the mouse pointer.
+  pub const MOUSE: PointerId = PointerId(0);
+}
 
 /// The pointer is a hardware-agnostic device that can target a specific set of
 /// screen coordinates. Having a single event model for pointers can simplify
@@ -44,10 +57,39 @@ pub struct PointerEvent {
   /// Indicates if the pointer represents the primary pointer of this pointer
   /// type.
   pub is_primary: bool,
+  /// The finger if the event is fired by a touch, the mouse events read the
+  /// cursor state from the dispatcher.
+  touch: Option<TouchPoint>,
 
   pub common: CommonEvent,
 }
 
+impl PointerEvent {
+  /// The X, Y coordinate of the pointer in global (window) coordinates.
+  pub fn global_pos(&self) -> Point {
+    self
+      .touch
+      .map_or_else(|| self.common.global_pos(), |t| t.pos)
+  }
+
+  /// The X, Y coordinate of the pointer in current target widget.
+  pub fn position(&self) -> Point { self.map_from_global(self.global_pos()) }
+
+  /// The buttons being depressed (if any) in current state, a finger touching
+  /// the screen is the primary button.
+  pub fn mouse_buttons(&self) -> MouseButtons {
+    match &self.touch {
+      Some(t) if t.pressed => MouseButtons::PRIMARY,
+      Some(_) => MouseButtons::empty(),
+      None => self.common.mouse_buttons(),
+    }
+  }
+
+  /// The button number that was pressed (if applicable) when the pointer event
+  /// was fired.
+  pub fn button_num(&self) -> u32 { self.mouse_buttons().bits().count_ones() }
+}
+
 bitflags! {
   #[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
   pub struct MouseButtons: u8 {
@@ -12,8 +12,7 @@ impl PointerEvent {
       .mouse_buttons()
       .is_empty();
     PointerEvent {
-      // todo: we need to trace the pressed pointer, how to generate pointer id, by device + button?
-      id: PointerId(0),
+      id: PointerId::MOUSE,
       width: 1.0,
       height: 1.0,
       pressure: if no_button { 0. } else { 0.5 },
@@ -22,6 +21,7 @@ impl PointerEvent {
       twist: 0.,
       point_type: PointerType::Mouse,
       is_primary: true,
+      touch: None,
       common: CommonEvent::new(target, wnd.tree),
     }
   }
@@ -0,0 +1,55 @@
+use winit::event::Force;
+
+use super::PointerId;
+use crate::prelude::*;
+
+/// A finger on the touch screen, the dispatcher traces it from touching down
+/// until it lifts up or is cancelled.
+#[derive(Debug)]
+pub(crate) struct TouchPointer {
+  /// The id of the finger given by the platform.
+  pub(crate) finger: u64,
+  pub(crate) point: TouchPoint,
+  /// The widget the finger touched down on, the following events of the
+  /// finger are dispatched to it.
+  pub(crate) capture: Option<WidgetId>,
+}
+
+/// The state of a finger when its event fired, the pointer events of the
+/// touch read it instead of the cursor state of the dispatcher.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TouchPoint {
+  pub(crate) id: PointerId,
+  /// The position of the finger in the window.
+  pub(crate) pos: Point,
+  pub(crate) pressure: f32,
+  pub(crate) is_primary: bool,
+  /// Whether the finger still touches the screen, it's false for the up and
+  /// the cancel events.
+  pub(crate) pressed: bool,
+}
+
+impl TouchPoint {
+  pub(crate) fn pressure_of(force: Option<Force>) -> f32 {
+    // Assume a medium pressure if the hardware can't detect it.
+    force.map_or(0.5, |f| f.normalized() as f32)
+  }
+}
+
+impl PointerEvent {
+  pub(crate) fn from_touch(target: WidgetId, point: &TouchPoint, wnd: &Window) -> Self {
+    PointerEvent {
+      id: point.id,
+      width: 1.0,
+      height: 1.0,
+      pressure: point.pressure,
+      tilt_x: 90.,
+      tilt_y: 90.,
+      twist: 0.,
+      point_type: PointerType::Touch,
+      is_primary: point.is_primary,
+      touch: Some(*point),
+      common: CommonEvent::new(target, wnd.tree),
+    }
+  }
+}
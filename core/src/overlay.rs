@@ -3,9 +3,7 @@ use std::{
   rc::{Rc, Weak},
 };
 
-use ribir_algo::Sc;
-
-use crate::prelude::*;
+use crate::{prelude::*, ticker::FrameMsg};
 
 /// Overlay let independent the widget "float" visual elements on top of
 /// other widgets by inserting them into the root stack of the widget stack.
@@ -32,7 +30,16 @@ use crate::prelude::*;
 /// App::run(w);
 /// ```
 #[derive(Clone)]
-pub struct Overlay(Sc<RefCell<InnerOverlay>>);
+pub struct Overlay(Rc<RefCell<InnerOverlay>>);
+
+/// A weak reference to an [`Overlay`], it doesn't keep the overlay alive.
+#[derive(Clone)]
+pub struct WeakOverlay(Weak<RefCell<InnerOverlay>>);
+
+impl WeakOverlay {
+  /// Return the overlay if it's still alive.
+  pub fn upgrade(&self) -> Option<Overlay> { self.0.upgrade().map(Overlay) }
+}
 
 bitflags! {
   #[derive(Clone, Copy)]
@@ -43,10 +50,112 @@ bitflags! {
   }
 }
 
+/// The side of the target widget to place an anchored overlay.
+///
+/// `Start` and `End` are the left and right sides of the target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlacementSide {
+  Top,
+  Bottom,
+  Start,
+  End,
+}
+
+/// How an anchored overlay is aligned with its target along the side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlacementAlign {
+  /// Align the left or top edges.
+  #[default]
+  Start,
+  Center,
+  /// Align the right or bottom edges.
+  End,
+}
+
+/// Describes where to place an anchored overlay around its target widget.
+///
+/// The overlay flips to the opposite side if it doesn't fit in the preferred
+/// side but fits better in the opposite side, and it shifts to stay in the
+/// window if it overflows.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Placement {
+  /// The preferred side of the target.
+  pub side: PlacementSide,
+  pub align: PlacementAlign,
+  /// The gap between the overlay and the target.
+  pub gap: f32,
+}
+
+impl Placement {
+  pub fn new(side: PlacementSide) -> Self { Self { side, align: PlacementAlign::Start, gap: 0. } }
+
+  pub fn with_align(mut self, align: PlacementAlign) -> Self {
+    self.align = align;
+    self
+  }
+
+  pub fn with_gap(mut self, gap: f32) -> Self {
+    self.gap = gap;
+    self
+  }
+
+  /// Return the side the overlay of `size` is placed, it's the preferred side
+  /// or the opposite side if the overlay fits better there.
+  pub fn fitting_side(&self, target: Rect, size: Size, bounds: Size) -> PlacementSide {
+    let space = |side| match side {
+      PlacementSide::Top => target.min_y() - self.gap,
+      PlacementSide::Bottom => bounds.height - target.max_y() - self.gap,
+      PlacementSide::Start => target.min_x() - self.gap,
+      PlacementSide::End => bounds.width - target.max_x() - self.gap,
+    };
+    let (need, opposite) = match self.side {
+      PlacementSide::Top => (size.height, PlacementSide::Bottom),
+      PlacementSide::Bottom => (size.height, PlacementSide::Top),
+      PlacementSide::Start => (size.width, PlacementSide::End),
+      PlacementSide::End => (size.width, PlacementSide::Start),
+    };
+    let preferred = space(self.side);
+    if preferred < need && space(opposite) > preferred { opposite } else { self.side }
+  }
+
+  /// Return the position of the overlay of `size`, to place it around the
+  /// `target` in the `bounds`.
+  pub fn place(&self, target: Rect, size: Size, bounds: Size) -> Point {
+    let align = |start: f32, length: f32, extent: f32| match self.align {
+      PlacementAlign::Start => start,
+      PlacementAlign::Center => start + (length - extent) / 2.,
+      PlacementAlign::End => start + length - extent,
+    };
+    let pos = match self.fitting_side(target, size, bounds) {
+      PlacementSide::Top => Point::new(
+        align(target.min_x(), target.width(), size.width),
+        target.min_y() - self.gap - size.height,
+      ),
+      PlacementSide::Bottom => {
+        Point::new(align(target.min_x(), target.width(), size.width), target.max_y() + self.gap)
+      }
+      PlacementSide::Start => Point::new(
+        target.min_x() - self.gap - size.width,
+        align(target.min_y(), target.height(), size.height),
+      ),
+      PlacementSide::End => {
+        Point::new(target.max_x() + self.gap, align(target.min_y(), target.height(), size.height))
+      }
+    };
+
+    // Shift the overlay to stay in the bounds.
+    Point::new(
+      pos.x.min(bounds.width - size.width).max(0.),
+      pos.y.min(bounds.height - size.height).max(0.),
+    )
+  }
+}
+
 struct InnerOverlay {
   gen: GenWidget,
   auto_close_policy: AutoClosePolicy,
   mask: Option<Brush>,
+  modal: bool,
   showing: Option<ShowingInfo>,
 }
 
@@ -60,14 +169,18 @@ impl Overlay {
   /// Create overlay from a function widget that may call many times.
   pub fn new(gen: impl Into<GenWidget>) -> Self {
     let gen = gen.into();
-    Self(Sc::new(RefCell::new(InnerOverlay {
+    Self(Rc::new(RefCell::new(InnerOverlay {
       gen,
       auto_close_policy: AutoClosePolicy::ESC | AutoClosePolicy::TAP_OUTSIDE,
       mask: None,
+      modal: true,
       showing: None,
     })))
   }
 
+  /// Create a weak reference to the overlay.
+  pub fn downgrade(&self) -> WeakOverlay { WeakOverlay(Rc::downgrade(&self.0)) }
+
   /// Return the overlay that the `ctx` belongs to if it is within an overlay.
   pub fn of(ctx: &impl WidgetCtx) -> Option<Self> {
     let wnd = ctx.window();
@@ -94,6 +207,17 @@ impl Overlay {
   /// Get the mask of the the background of the overlay used.  
   pub fn mask(&self) -> Option<Brush> { self.0.borrow().mask.clone() }
 
+  /// Set whether the overlay is modal, it's modal by default.
+  ///
+  /// A modal overlay covers the whole window and blocks the pointer events of
+  /// the widgets below it. A non-modal overlay only receives the pointer events
+  /// over its content, the others pass through to the widgets below, so the
+  /// mask and `AutoClosePolicy::TAP_OUTSIDE` not work for it.
+  pub fn set_modal(&self, modal: bool) { self.0.borrow_mut().modal = modal; }
+
+  /// Return whether the overlay is modal.
+  pub fn is_modal(&self) -> bool { self.0.borrow().modal }
+
   /// Show the overlay.
   pub fn show(&self, wnd: Rc<Window>) {
     if self.is_showing() {
@@ -169,6 +293,45 @@ impl Overlay {
     );
   }
 
+  /// Show the overlay anchored to the `target` widget, it's placed around the
+  /// target by the `placement`, and follows the target when the layout
+  /// changes. If the overlay is showing, nothing will happen.
+  pub fn show_anchored(&self, target: WidgetId, placement: Placement, wnd: Rc<Window>) {
+    if self.is_showing() {
+      return;
+    }
+    self.show_map(
+      move |w| {
+        fn_widget! {
+          let wnd = ctx!().window();
+          let mut w = @$w {};
+          let wid = w.lazy_id();
+          let u = wnd
+            .frame_tick_stream()
+            .filter(|msg| matches!(msg, FrameMsg::LayoutReady(_)))
+            .subscribe(move |_| {
+              if target.is_dropped(wnd.tree()) {
+                return;
+              }
+              let Some(size) = wnd.layout_size(wid.assert_id()) else { return };
+              let target = Rect::new(
+                wnd.map_to_global(Point::zero(), target),
+                wnd.layout_size(target).unwrap_or_default(),
+              );
+              let pos = placement.place(target, size, wnd.size());
+              let anchor = Anchor::from_point(pos);
+              if $w.anchor != anchor {
+                $w.write().anchor = anchor;
+              }
+            });
+          @$w { on_disposed: move |_| u.unsubscribe() }
+        }
+        .into_widget()
+      },
+      wnd,
+    );
+  }
+
   /// return whether the overlay is showing.
   pub fn is_showing(&self) -> bool { self.0.borrow().showing.is_some() }
 
@@ -192,29 +355,44 @@ impl Overlay {
 
   fn inner_show(&self, content: GenWidget, wnd: Rc<Window>) {
     let background = self.mask();
+    let modal = self.is_modal();
+    let close_on_esc = |e: &mut KeyboardEvent| {
+      if *e.key() == VirtualKey::Named(NamedKey::Escape) {
+        if let Some(overlay) = Overlay::of(&**e).filter(|o| {
+          o.auto_close_policy()
+            .contains(AutoClosePolicy::ESC)
+        }) {
+          overlay.close();
+        }
+      }
+    };
     let gen = fn_widget! {
-      @Container {
-        size: Size::new(f32::INFINITY, f32::INFINITY),
-        background: background.clone(),
-        on_tap: move |e| {
-          if e.target() == e.current_target() {
-            if let Some(overlay) = Overlay::of(&**e)
-              .filter(|o| o.auto_close_policy().contains(AutoClosePolicy::TAP_OUTSIDE))
-            {
-              overlay.close();
+      if modal {
+        @Container {
+          size: Size::new(f32::INFINITY, f32::INFINITY),
+          background: background.clone(),
+          on_tap: move |e| {
+            if e.target() == e.current_target() {
+              if let Some(overlay) = Overlay::of(&**e)
+                .filter(|o| o.auto_close_policy().contains(AutoClosePolicy::TAP_OUTSIDE))
+              {
+                overlay.close();
+              }
             }
-          }
-        },
-        on_key_down: move |e| {
-          if *e.key() == VirtualKey::Named(NamedKey::Escape) {
-            if let Some(overlay) = Overlay::of(&**e)
-              .filter(|o| o.auto_close_policy().contains(AutoClosePolicy::ESC))
-            {
-              overlay.close();
-            }
-          }
-        },
-        @ { content.gen_widget() }
+          },
+          on_key_down: close_on_esc,
+          @ { content.gen_widget() }
+        }
+        .into_widget()
+      } else {
+        @IgnorePointer {
+          ignore: false,
+          // Not a tab stop, the overlay should not take the focus.
+          tab_index: -1i16,
+          on_key_down: close_on_esc,
+          @ { content.gen_widget() }
+        }
+        .into_widget()
       }
     };
 
@@ -261,7 +439,7 @@ impl ShowingOverlays {
     self
       .0
       .borrow_mut()
-      .retain(|o| !Sc::ptr_eq(&o.0, &overlay.0))
+      .retain(|o| !Rc::ptr_eq(&o.0, &overlay.0))
   }
 
   fn showing_of(&self, ctx: &impl WidgetCtx) -> Option<Overlay> {
@@ -281,6 +459,12 @@ impl Default for ShowingOverlays {
 mod tests {
   use std::{cell::RefCell, rc::Rc};
 
+  use winit::{
+    dpi::LogicalPosition,
+    event::{DeviceId, ElementState, MouseButton, WindowEvent},
+  };
+
+  use super::{Placement, PlacementAlign, PlacementSide};
   use crate::{prelude::*, reset_test_env, test_helper::*};
 
   #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
@@ -335,4 +519,103 @@ mod tests {
     assert_eq!(*r_log.borrow(), &["mounted", "disposed"]);
     assert_eq!(wnd.tree().count(root), 3);
   }
+
+  #[test]
+  fn placement() {
+    let bounds = Size::new(100., 100.);
+    let target = Rect::new(Point::new(40., 10.), Size::new(20., 10.));
+    let size = Size::new(30., 30.);
+
+    let bottom = Placement::new(PlacementSide::Bottom).with_gap(2.);
+    assert_eq!(bottom.place(target, size, bounds), Point::new(40., 22.));
+
+    // No space on the top, flip to the bottom.
+    let top = Placement::new(PlacementSide::Top).with_align(PlacementAlign::Center);
+    assert_eq!(top.fitting_side(target, size, bounds), PlacementSide::Bottom);
+    assert_eq!(top.place(target, size, bounds), Point::new(35., 20.));
+
+    // Shift to stay in the bounds.
+    let end = Placement::new(PlacementSide::End).with_align(PlacementAlign::End);
+    let target = Rect::new(Point::new(50., 0.), Size::new(45., 10.));
+    assert_eq!(end.fitting_side(target, size, bounds), PlacementSide::Start);
+    assert_eq!(end.place(target, size, bounds), Point::new(20., 0.));
+  }
+
+  #[test]
+  fn anchored_overlay() {
+    reset_test_env!();
+
+    let widget = fn_widget! {
+      @MockBox {
+        size: Size::new(200., 200.),
+        @MockBox {
+          anchor: Anchor::left_top(50., 150.),
+          size: Size::new(40., 20.),
+        }
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(widget, Size::new(200., 200.));
+    wnd.draw_frame();
+    let target = {
+      let tree = wnd.tree();
+      // The box is the child of its anchor widget.
+      let anchor = tree.content_root().first_child(tree).unwrap();
+      anchor.first_child(tree).unwrap()
+    };
+
+    let overlay = Overlay::new(fn_widget! { @MockBox { size: Size::new(60., 60.) } });
+    overlay.show_anchored(target, Placement::new(PlacementSide::Bottom), wnd.0.clone());
+    wnd.draw_frame();
+
+    // There is not enough space below the target, so it flips to the top.
+    assert_eq!(
+      wnd
+        .layout_info_by_path(&[1, 0, 0, 0])
+        .unwrap()
+        .pos,
+      Point::new(50., 90.)
+    );
+  }
+
+  #[test]
+  fn non_modal_overlay() {
+    reset_test_env!();
+
+    let (tap, w_tap) = split_value(0);
+    let widget = fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        on_tap: move |_| *$w_tap.write() += 1,
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(widget, Size::new(100., 100.));
+    wnd.draw_frame();
+
+    let overlay = Overlay::new(fn_widget! { @MockBox { size: Size::new(10., 10.) } });
+    overlay.show(wnd.0.clone());
+    wnd.draw_frame();
+    tap_on(&wnd, 50., 50.);
+    wnd.draw_frame();
+    assert_eq!(*tap.read(), 0);
+    overlay.close();
+
+    overlay.set_modal(false);
+    overlay.show(wnd.0.clone());
+    wnd.draw_frame();
+    tap_on(&wnd, 50., 50.);
+    wnd.draw_frame();
+    assert_eq!(*tap.read(), 1);
+    assert!(overlay.is_showing());
+  }
+
+  fn tap_on(wnd: &Window, x: f32, y: f32) {
+    let device_id = unsafe { DeviceId::dummy() };
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::CursorMoved {
+      device_id,
+      position: LogicalPosition::new(x, y).to_physical(1.),
+    });
+    wnd.process_mouse_input(device_id, ElementState::Pressed, MouseButton::Left);
+    wnd.process_mouse_input(device_id, ElementState::Released, MouseButton::Left);
+  }
 }
@@ -48,6 +48,13 @@ where
   #[inline]
   fn only_sized_by_parent(&self) -> bool { self.proxy().only_sized_by_parent() }
 
+  #[inline]
+  fn intrinsic_size(&self, intrinsic: Intrinsic, extent: f32, ctx: &mut LayoutCtx) -> f32 {
+    self
+      .proxy()
+      .intrinsic_size(intrinsic, extent, ctx)
+  }
+
   #[inline]
   fn hit_test(&self, ctx: &HitTestCtx, pos: Point) -> HitTest { self.proxy().hit_test(ctx, pos) }
 
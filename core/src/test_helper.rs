@@ -259,6 +259,11 @@ impl Render for MockBox {
 
     self.size
   }
+
+  #[inline]
+  fn intrinsic_size(&self, intrinsic: Intrinsic, _: f32, _: &mut LayoutCtx) -> f32 {
+    intrinsic.axis_of(self.size)
+  }
   #[inline]
   fn only_sized_by_parent(&self) -> bool { true }
 
@@ -44,6 +44,19 @@ pub trait Render: 'static {
   /// widget size, and child nodes' size not affect its size.
   fn only_sized_by_parent(&self) -> bool { false }
 
+  /// Return the intrinsic size of the `intrinsic` kind without performing
+  /// layout. The `extent` is the size of the other axis, for example, the
+  /// height to compute the intrinsic width, it's infinite if not limited.
+  ///
+  /// The result is cached until the widget or its descendants changed. The
+  /// default implementation returns the max intrinsic size of its children.
+  ///
+  /// In implementing this function, query the intrinsic size of the children
+  /// across `LayoutCtx::child_intrinsic_size`, but not perform their layout.
+  fn intrinsic_size(&self, intrinsic: Intrinsic, extent: f32, ctx: &mut LayoutCtx) -> f32 {
+    ctx.children_max_intrinsic_size(intrinsic, extent)
+  }
+
   /// Determines the set of render widgets located at the given position.
   fn hit_test(&self, ctx: &HitTestCtx, pos: Point) -> HitTest {
     let is_hit = hit_test_impl(ctx, pos);
@@ -153,6 +153,12 @@ impl WidgetTree {
       if let Some(info) = self.store.get_mut(id) {
         info.size.take();
       }
+      // The intrinsic size of all the ancestors may depend on this widget.
+      for p in id.0.ancestors(&self.arena).map(WidgetId) {
+        if let Some(info) = self.store.get_mut(&p) {
+          info.intrinsic_sizes = <_>::default();
+        }
+      }
 
       // All ancestors of this render widget should relayout until the one which only
       // sized by parent.
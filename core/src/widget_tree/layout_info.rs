@@ -69,6 +69,35 @@ impl BoxClamp {
   }
 }
 
+/// The kinds of the intrinsic size of a widget, the size it prefers without
+/// the layout constraints from its parent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Intrinsic {
+  /// The smallest width the widget can be painted correctly in, such as the
+  /// width of the longest word of a text.
+  MinWidth,
+  /// The smallest width that the widget not benefit from a larger one, such as
+  /// the width of a text without any wrapping.
+  MaxWidth,
+  /// The smallest height the widget can be painted correctly in.
+  MinHeight,
+  /// The smallest height that the widget not benefit from a larger one.
+  MaxHeight,
+}
+
+impl Intrinsic {
+  /// Whether it's the intrinsic size in the x-axis.
+  pub fn is_width(self) -> bool { matches!(self, Intrinsic::MinWidth | Intrinsic::MaxWidth) }
+
+  /// Return the size in the axis of this intrinsic size.
+  pub fn axis_of(self, size: Size) -> f32 { if self.is_width() { size.width } else { size.height } }
+
+  /// Return the size in the other axis, the axis of the extent.
+  pub fn extent_of(self, size: Size) -> f32 {
+    if self.is_width() { size.height } else { size.width }
+  }
+}
+
 /// render object's layout box, the information about layout, including box
 /// size, box position, and the clamp of render object layout.
 #[derive(Debug, Default, Clone)]
@@ -81,6 +110,13 @@ pub struct LayoutInfo {
   pub size: Option<Size>,
   /// The position render object to place, default is zero
   pub pos: Point,
+  /// The distance from the top of the widget to the baseline of its first
+  /// line of text, only the widgets that display text have it.
+  pub baseline: Option<f32>,
+  /// The cache of the intrinsic sizes, one slot for each kind of
+  /// `Intrinsic`, keeps the extent of the other axis and the size of the last
+  /// query.
+  pub(crate) intrinsic_sizes: [Option<(f32, f32)>; 4],
 }
 
 /// Store the render object's place relative to parent coordinate and the
@@ -128,6 +164,33 @@ impl LayoutStore {
 }
 
 impl WidgetTree {
+  /// Return the intrinsic size of the widget `id`, query its render object if
+  /// it's not cached.
+  pub(crate) fn intrinsic_size(&mut self, id: WidgetId, intrinsic: Intrinsic, extent: f32) -> f32 {
+    let slot = intrinsic as usize;
+    let cached = self
+      .store
+      .layout_info(id)
+      .and_then(|info| info.intrinsic_sizes[slot])
+      .filter(|(e, _)| *e == extent);
+    if let Some((_, size)) = cached {
+      return size;
+    }
+
+    // Safety: the `tree` just use to get the widget of `id`, and `tree2` not drop
+    // or modify it during the query.
+    let tree2 = unsafe { &mut *(self as *mut WidgetTree) };
+    let mut ctx = LayoutCtx { id, tree: tree2 };
+    let size = id
+      .assert_get(self)
+      .intrinsic_size(intrinsic, extent, &mut ctx);
+    self
+      .store
+      .layout_info_or_default(id)
+      .intrinsic_sizes[slot] = Some((extent, size));
+    size
+  }
+
   pub(crate) fn map_to_parent(&self, id: WidgetId, pos: Point) -> Point {
     self
       .store
@@ -202,6 +265,11 @@ impl<'a> Layouter<'a> {
         // or modify it during perform layout.
         let tree2 = unsafe { &mut *(self.tree as *mut WidgetTree) };
 
+        // The baseline is set by the widget during its layout.
+        if let Some(info) = tree2.store.get_mut(&self.id) {
+          info.baseline = None;
+        }
+
         let Self { id, ref tree, .. } = *self;
         let mut ctx = LayoutCtx { id, tree: tree2 };
         let size = id
@@ -300,7 +368,7 @@ impl std::ops::DerefMut for LayoutStore {
 
 #[cfg(test)]
 mod tests {
-  use std::cell::Cell;
+  use std::{cell::Cell, rc::Rc};
 
   use super::*;
   use crate::{prelude::*, reset_test_env, test_helper::*};
@@ -326,6 +394,114 @@ mod tests {
     fn paint(&self, _: &mut PaintingCtx) {}
   }
 
+  /// A box as wide as the max intrinsic width of its child.
+  #[derive(Declare, SingleChild)]
+  struct IntrinsicBox {}
+
+  impl Render for IntrinsicBox {
+    fn perform_layout(&self, _: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+      let child = ctx.assert_single_child();
+      let width = ctx.child_intrinsic_size(child, Intrinsic::MaxWidth, f32::INFINITY);
+      // The second query hits the cache.
+      let width2 = ctx.child_intrinsic_size(child, Intrinsic::MaxWidth, f32::INFINITY);
+      assert_eq!(width, width2);
+      ctx.perform_single_child_layout(BoxClamp::fixed_width(width));
+      Size::new(width, 10.)
+    }
+
+    #[inline]
+    fn paint(&self, _: &mut PaintingCtx) {}
+  }
+
+  struct CountIntrinsic(Rc<Cell<usize>>);
+
+  impl Render for CountIntrinsic {
+    fn perform_layout(&self, clamp: BoxClamp, _: &mut LayoutCtx) -> Size { clamp.min }
+
+    fn intrinsic_size(&self, _: Intrinsic, _: f32, _: &mut LayoutCtx) -> f32 {
+      self.0.set(self.0.get() + 1);
+      5.
+    }
+
+    #[inline]
+    fn paint(&self, _: &mut PaintingCtx) {}
+  }
+
+  #[test]
+  fn intrinsic_size_cache() {
+    reset_test_env!();
+
+    let cnt = Rc::new(Cell::new(0));
+    let c_cnt = cnt.clone();
+    let (size, w_size) = split_value(Size::new(20., 20.));
+    let w = fn_widget! {
+      @MockMulti {
+        @IntrinsicBox { @MockBox { size: pipe!(*$size) } }
+        @IntrinsicBox { @ { CountIntrinsic(c_cnt.clone()) } }
+      }
+    };
+
+    let mut wnd = TestWindow::new(w);
+    wnd.draw_frame();
+    assert_eq!(cnt.get(), 1);
+    assert_eq!(wnd.layout_info_by_path(&[0, 0]).unwrap().size, Some(Size::new(20., 10.)));
+    assert_eq!(wnd.layout_info_by_path(&[0, 1]).unwrap().size, Some(Size::new(5., 10.)));
+
+    // The cache is cleared when the descendant changed.
+    *w_size.write() = Size::new(30., 30.);
+    wnd.draw_frame();
+    assert_eq!(wnd.layout_info_by_path(&[0, 0]).unwrap().size, Some(Size::new(30., 10.)));
+    assert_eq!(cnt.get(), 1);
+  }
+
+  /// A box queries the intrinsic sizes of its child in order.
+  #[derive(Declare, SingleChild)]
+  struct QueryBox {
+    queries: Vec<(Intrinsic, f32)>,
+  }
+
+  impl Render for QueryBox {
+    fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+      let child = ctx.assert_single_child();
+      for (intrinsic, extent) in self.queries.iter() {
+        ctx.child_intrinsic_size(child, *intrinsic, *extent);
+      }
+      ctx.perform_single_child_layout(clamp);
+      clamp.min
+    }
+
+    #[inline]
+    fn paint(&self, _: &mut PaintingCtx) {}
+  }
+
+  #[test]
+  fn intrinsic_size_slot_per_kind() {
+    reset_test_env!();
+
+    let cnt = Rc::new(Cell::new(0));
+    let c_cnt = cnt.clone();
+    let w = fn_widget! {
+      @QueryBox {
+        queries: vec![
+          (Intrinsic::MaxWidth, 10.),
+          (Intrinsic::MinWidth, 10.),
+          (Intrinsic::MaxWidth, 10.),
+          (Intrinsic::MaxWidth, 20.),
+          (Intrinsic::MaxWidth, 10.),
+        ],
+        @ { CountIntrinsic(c_cnt.clone()) }
+      }
+    };
+
+    let mut wnd = TestWindow::new(w);
+    wnd.draw_frame();
+    // The kinds have their own slot, and a query of another extent replaces the
+    // cached one of its kind.
+    assert_eq!(cnt.get(), 4);
+    let info = wnd.layout_info_by_path(&[0, 0]).unwrap();
+    assert_eq!(info.intrinsic_sizes.iter().flatten().count(), 2);
+  }
+
   #[test]
   fn fix_incorrect_relayout_root() {
     reset_test_env!();
@@ -2,6 +2,7 @@ use std::{
   cell::{Cell, RefCell},
   collections::VecDeque,
   convert::Infallible,
+  path::PathBuf,
   ptr::NonNull,
   rc::Rc,
 };
@@ -39,6 +40,8 @@ pub struct Window {
   /// event immediately. So we store the event emitter in this vector,
   /// and emit them after all borrow finished.
   pub(crate) delay_emitter: RefCell<VecDeque<DelayEvent>>,
+  pub(crate) gesture_arena: GestureArena,
+  pub(crate) drag_mgr: DragManager,
   /// A task pool use to process `Future` or `rxRust` task, and will block until
   /// all task finished before current frame end.
   frame_pool: RefCell<FuturesLocalSchedulerPool>,
@@ -140,6 +143,32 @@ impl Window {
       .dispatch_ime_pre_edit(ime)
   }
 
+  /// processes a file dragged from the operating system hovering the window
+  /// at `pos`.
+  pub fn processes_hovered_file(&self, path: PathBuf, pos: Point) {
+    self
+      .dispatcher
+      .borrow_mut()
+      .dispatch_hovered_file(path, pos)
+  }
+
+  /// processes the files hovering the window leave it without dropping.
+  pub fn processes_hovered_file_cancelled(&self) {
+    self
+      .dispatcher
+      .borrow_mut()
+      .dispatch_hovered_file_cancelled()
+  }
+
+  /// processes a file dragged from the operating system dropped on the window
+  /// at `pos`.
+  pub fn processes_dropped_file(&self, path: PathBuf, pos: Point) {
+    self
+      .dispatcher
+      .borrow_mut()
+      .dispatch_dropped_file(path, pos)
+  }
+
   pub fn process_mouse_input(&self, device_id: DeviceId, state: ElementState, button: MouseButton) {
     self
       .dispatcher
@@ -285,6 +314,8 @@ impl Window {
       painter: RefCell::new(painter),
       focus_mgr,
       delay_emitter: <_>::default(),
+      gesture_arena: <_>::default(),
+      drag_mgr: <_>::default(),
       frame_ticker: FrameTicker::default(),
       running_animates: <_>::default(),
       frame_pool: <_>::default(),
@@ -356,6 +387,13 @@ impl Window {
     });
   }
 
+  fn pointer_event(&self, target: WidgetId, touch: &Option<TouchPoint>) -> PointerEvent {
+    match touch {
+      Some(point) => PointerEvent::from_touch(target, point, self),
+      None => PointerEvent::from_mouse(target, self),
+    }
+  }
+
   fn run_priority_tasks(&self) {
     while let Some((task, _)) = self.priority_task_queue.pop() {
       // `pipe` used priority task queue to update the subtree, we need to force
@@ -481,30 +519,50 @@ impl Window {
           let mut e = Event::Wheel(WheelEvent::new(delta_x, delta_y, id, self));
           self.bottom_up_emit(&mut e, id, None);
         }
-        DelayEvent::PointerDown(id) => {
-          let mut e = Event::PointerDownCapture(PointerEvent::from_mouse(id, self));
+        DelayEvent::PointerDown(id, touch) => {
+          let e = self.pointer_event(id, &touch);
+          self
+            .gesture_arena
+            .pointer_down(e.id, e.global_pos());
+          let mut e = Event::PointerDownCapture(e);
           self.top_down_emit(&mut e, id, None);
-          let mut e = Event::PointerDown(PointerEvent::from_mouse(id, self));
+          let mut e = Event::PointerDown(self.pointer_event(id, &touch));
           self.bottom_up_emit(&mut e, id, None);
           self
             .focus_mgr
             .borrow_mut()
             .refresh_focus(self.tree());
         }
-        DelayEvent::PointerMove(id) => {
-          let mut e = Event::PointerMoveCapture(PointerEvent::from_mouse(id, self));
+        DelayEvent::PointerMove(id, touch) => {
+          let mut e = Event::PointerMoveCapture(self.pointer_event(id, &touch));
           self.top_down_emit(&mut e, id, None);
-          let mut e = Event::PointerMove(PointerEvent::from_mouse(id, self));
-          self.bottom_up_emit(&mut e, id, None);
+          let e = self.pointer_event(id, &touch);
+          let (pointer, pos) = (e.id, e.global_pos());
+          self.bottom_up_emit(&mut Event::PointerMove(e), id, None);
+          self
+            .gesture_arena
+            .pointer_move(pointer, pos, self);
         }
-        DelayEvent::PointerUp(id) => {
-          let mut e = Event::PointerUpCapture(PointerEvent::from_mouse(id, self));
+        DelayEvent::PointerUp(id, touch) => {
+          let mut e = Event::PointerUpCapture(self.pointer_event(id, &touch));
           self.top_down_emit(&mut e, id, None);
-          let mut e = Event::PointerUp(PointerEvent::from_mouse(id, self));
+          let e = self.pointer_event(id, &touch);
+          let (pointer, pos) = (e.id, e.global_pos());
+          self.bottom_up_emit(&mut Event::PointerUp(e), id, None);
+          self.gesture_arena.pointer_up(pointer, pos, self);
+        }
+        DelayEvent::PointerCancel(id, touch) => {
+          let e = self.pointer_event(id, &touch);
+          let pointer = e.id;
+          self.bottom_up_emit(&mut Event::PointerCancel(e), id, None);
+          self.gesture_arena.pointer_cancel(pointer, self);
+        }
+        DelayEvent::GotPointerCapture(id, touch) => {
+          let mut e = Event::GotPointerCapture(self.pointer_event(id, &touch));
           self.bottom_up_emit(&mut e, id, None);
         }
-        DelayEvent::_PointerCancel(id) => {
-          let mut e = Event::PointerCancel(PointerEvent::from_mouse(id, self));
+        DelayEvent::LostPointerCapture(id, touch) => {
+          let mut e = Event::LostPointerCapture(self.pointer_event(id, &touch));
           self.bottom_up_emit(&mut e, id, None);
         }
         DelayEvent::PointerEnter { bottom, up } => {
@@ -515,12 +573,43 @@ impl Window {
           let mut e = Event::PointerLeave(PointerEvent::from_mouse(bottom, self));
           self.bottom_up_emit(&mut e, bottom, up);
         }
-        DelayEvent::Tap(wid) => {
-          let mut e = Event::TapCapture(PointerEvent::from_mouse(wid, self));
+        // The tap is a part of the gesture recognized by the arena.
+        DelayEvent::Tap(..) if self.gesture_arena.is_claimed() => {}
+        DelayEvent::Tap(wid, touch) => {
+          let mut e = Event::TapCapture(self.pointer_event(wid, &touch));
           self.top_down_emit(&mut e, wid, None);
-          let mut e = Event::Tap(PointerEvent::from_mouse(wid, self));
+          let mut e = Event::Tap(self.pointer_event(wid, &touch));
           self.bottom_up_emit(&mut e, wid, None);
         }
+        DelayEvent::DragEnter { event, up } => {
+          let bottom = event.target();
+          self.top_down_emit(&mut Event::DragEnter(event), bottom, up);
+        }
+        DelayEvent::DragOver(e) => {
+          let target = e.target();
+          self.bottom_up_emit(&mut Event::DragOver(e), target, None);
+        }
+        DelayEvent::DragLeave { event, up } => {
+          let bottom = event.target();
+          self.bottom_up_emit(&mut Event::DragLeave(event), bottom, up);
+        }
+        DelayEvent::Drop(e) => {
+          let target = e.target();
+          self.bottom_up_emit(&mut Event::Drop(e), target, None);
+        }
+        DelayEvent::DragCancel => self.drag_mgr.cancel(None, self),
+        DelayEvent::FileHover { id, paths } => {
+          let mut e = Event::FileHover(FileDropEvent::new(paths, id, self));
+          self.bottom_up_emit(&mut e, id, None);
+        }
+        DelayEvent::FileHoverEnd { id, paths } => {
+          let mut e = Event::FileHoverEnd(FileDropEvent::new(paths, id, self));
+          self.bottom_up_emit(&mut e, id, None);
+        }
+        DelayEvent::FileDrop { id, paths } => {
+          let mut e = Event::FileDrop(FileDropEvent::new(paths, id, self));
+          self.bottom_up_emit(&mut e, id, None);
+        }
         DelayEvent::ImePreEdit { wid, pre_edit } => {
           let mut e = Event::ImePreEditCapture(ImePreEditEvent::new(pre_edit, wid, self));
           self.top_down_emit(&mut e, wid, None);
@@ -693,6 +782,8 @@ impl Drop for Window {
 
 /// Event that delay to emit, emit it when the window is not busy(nobody borrow
 /// parts of the window).
+///
+/// The pointer events carry the finger if they are fired by a touch.
 #[derive(Debug)]
 pub(crate) enum DelayEvent {
   Mounted(WidgetId),
@@ -708,13 +799,23 @@ pub(crate) enum DelayEvent {
   TabFocusMove,
   Chars { id: WidgetId, chars: String },
   Wheel { id: WidgetId, delta_x: f32, delta_y: f32 },
-  PointerDown(WidgetId),
-  PointerMove(WidgetId),
-  PointerUp(WidgetId),
-  _PointerCancel(WidgetId),
+  PointerDown(WidgetId, Option<TouchPoint>),
+  PointerMove(WidgetId, Option<TouchPoint>),
+  PointerUp(WidgetId, Option<TouchPoint>),
+  PointerCancel(WidgetId, Option<TouchPoint>),
+  GotPointerCapture(WidgetId, Option<TouchPoint>),
+  LostPointerCapture(WidgetId, Option<TouchPoint>),
   PointerEnter { bottom: WidgetId, up: Option<WidgetId> },
   PointerLeave { bottom: WidgetId, up: Option<WidgetId> },
-  Tap(WidgetId),
+  Tap(WidgetId, Option<TouchPoint>),
+  DragEnter { event: DragEvent, up: Option<WidgetId> },
+  DragOver(DragEvent),
+  DragLeave { event: DragEvent, up: Option<WidgetId> },
+  Drop(DragEvent),
+  DragCancel,
+  FileHover { id: WidgetId, paths: Vec<PathBuf> },
+  FileHoverEnd { id: WidgetId, paths: Vec<PathBuf> },
+  FileDrop { id: WidgetId, paths: Vec<PathBuf> },
   ImePreEdit { wid: WidgetId, pre_edit: ImePreEdit },
 }
 
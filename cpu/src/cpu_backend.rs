@@ -0,0 +1,317 @@
+use ribir_geom::{transform_to_device_rect, DeviceRect, Transform};
+use ribir_painter::{Color, PaintCommand, PaintPathAction, PainterBackend, PathCommand};
+
+use crate::{
+  raster::{color_to_f32, Coverage, Pixels, Shader},
+  CpuTexture,
+};
+
+/// A painter backend that rasterizes the paint commands by CPU.
+#[derive(Default)]
+pub struct CpuBackend {
+  viewport: DeviceRect,
+  clip_layer_stack: Vec<ClipLayer>,
+  skip_clip_cnt: usize,
+  surface_color: Option<Color>,
+}
+
+/// A clip layer is a coverage that limits where the commands can paint, the
+/// nested clip layers are already intersected with their parent.
+struct ClipLayer {
+  viewport: DeviceRect,
+  coverage: Coverage,
+}
+
+impl PainterBackend for CpuBackend {
+  type Texture = CpuTexture;
+
+  fn begin_frame(&mut self, surface: Color) { self.surface_color = Some(surface); }
+
+  fn draw_commands(
+    &mut self, viewport: DeviceRect, commands: &[PaintCommand], global_matrix: &Transform,
+    output: &mut Self::Texture,
+  ) {
+    if let Some(color) = self.surface_color.take() {
+      output.clear(color);
+    }
+    let clips = self.clip_layer_stack.len();
+    self.viewport = viewport;
+    let mut pixels = output.pixels();
+    for cmd in commands {
+      self.draw_command(cmd, global_matrix, &mut pixels);
+    }
+    assert_eq!(self.clip_layer_stack.len(), clips);
+  }
+
+  fn end_frame(&mut self) { self.surface_color = None; }
+}
+
+impl CpuBackend {
+  pub fn new() -> Self { Self::default() }
+
+  fn draw_command(&mut self, cmd: &PaintCommand, global_matrix: &Transform, pixels: &mut Pixels) {
+    match cmd {
+      PaintCommand::Path(PathCommand { path, paint_bounds, transform, action }) => {
+        if self.skip_clip_cnt > 0 {
+          if matches!(action, PaintPathAction::Clip) {
+            self.skip_clip_cnt += 1;
+          }
+          // Skip the commands if the clip layer is not visible.
+          return;
+        }
+
+        // The image is placed in an empty rect, nothing to draw.
+        if matches!(action, PaintPathAction::Image { rect, .. } if rect.is_empty()) {
+          return;
+        }
+
+        let bounds = transform_to_device_rect(paint_bounds, global_matrix);
+        let matrix = transform.then(global_matrix);
+        let coverage = self
+          .viewport()
+          .intersection(&bounds)
+          .and_then(|area| area.intersection(pixels.rect()))
+          .and_then(|area| Coverage::from_path(path, &matrix, &area));
+        let (Some(coverage), Some(to_path)) = (coverage, matrix.inverse()) else {
+          if matches!(action, PaintPathAction::Clip) {
+            self.skip_clip_cnt += 1;
+          }
+          // Skip the command if it is not visible.
+          return;
+        };
+
+        let blurred;
+        let shader = match action {
+          PaintPathAction::Color(color) => Shader::Color(color_to_f32(color)),
+          PaintPathAction::Image { img, opacity, rect, repeat, filter } => {
+            let to_img = to_path
+              .then_translate(-rect.origin.to_vector())
+              .then_scale(img.width() as f32 / rect.width(), img.height() as f32 / rect.height());
+            Shader::Image { img, to_img, opacity: *opacity, repeat: *repeat, filter: *filter }
+          }
+          PaintPathAction::Radial(gradient) => Shader::Radial { gradient, to_path },
+          PaintPathAction::Linear(gradient) => Shader::Linear { gradient, to_path },
+          PaintPathAction::Conic(gradient) => Shader::Conic { gradient, to_path },
+          PaintPathAction::Shadow { rect, radius, sigma, color, inset } => Shader::Shadow {
+            rect,
+            radius,
+            sigma: *sigma,
+            color: color_to_f32(color),
+            inset: *inset,
+            to_path,
+          },
+          PaintPathAction::Backdrop { sigma, tint, opacity } => {
+            blurred = pixels.blur(&coverage.rect, *sigma);
+            Shader::Backdrop {
+              rect: coverage.rect,
+              blurred: &blurred,
+              tint: color_to_f32(tint),
+              opacity: *opacity,
+            }
+          }
+          PaintPathAction::Clip => {
+            let coverage = match self.clip_layer_stack.last() {
+              Some(parent) => parent.coverage.intersect(&coverage),
+              None => Some(coverage),
+            };
+            match coverage {
+              Some(coverage) => {
+                let viewport = coverage.rect;
+                self
+                  .clip_layer_stack
+                  .push(ClipLayer { viewport, coverage })
+              }
+              None => self.skip_clip_cnt += 1,
+            }
+            return;
+          }
+        };
+        pixels.fill(&coverage, self.current_clip(), &shader);
+      }
+      PaintCommand::PopClip => {
+        if self.skip_clip_cnt > 0 {
+          self.skip_clip_cnt -= 1;
+        } else {
+          self.clip_layer_stack.pop();
+        }
+      }
+      PaintCommand::Bundle { transform, opacity, bounds, cmds } => {
+        if self.skip_clip_cnt > 0 {
+          return;
+        }
+        let matrix = transform.then(global_matrix);
+        let device_bounds = transform_to_device_rect(bounds, &matrix);
+        let Some(area) = self
+          .viewport()
+          .intersection(&device_bounds)
+          .and_then(|area| area.intersection(pixels.rect()))
+        else {
+          return;
+        };
+
+        // Draw the bundle in an isolated layer, and then composite the layer, so
+        // the opacity applies to the bundle as a whole.
+        let mut data = vec![0; area.area() as usize * 4];
+        let mut layer = Pixels::new(area, &mut data);
+        let viewport = std::mem::replace(&mut self.viewport, area);
+        let clip_layers = std::mem::take(&mut self.clip_layer_stack);
+        for cmd in cmds.iter() {
+          self.draw_command(cmd, &matrix, &mut layer);
+        }
+        self.viewport = viewport;
+        self.clip_layer_stack = clip_layers;
+
+        pixels.composite(&layer, *opacity, self.current_clip());
+      }
+      PaintCommand::Layer { opacity, blend_mode, bounds, cmds } => {
+        if self.skip_clip_cnt > 0 {
+          return;
+        }
+        let device_bounds = transform_to_device_rect(bounds, global_matrix);
+        let Some(area) = self
+          .viewport()
+          .intersection(&device_bounds)
+          .and_then(|area| area.intersection(pixels.rect()))
+        else {
+          return;
+        };
+
+        let mut data = vec![0; area.area() as usize * 4];
+        let mut layer = Pixels::new(area, &mut data);
+        let viewport = std::mem::replace(&mut self.viewport, area);
+        let clip_layers = std::mem::take(&mut self.clip_layer_stack);
+        for cmd in cmds.iter() {
+          self.draw_command(cmd, global_matrix, &mut layer);
+        }
+        self.viewport = viewport;
+        self.clip_layer_stack = clip_layers;
+
+        pixels.composite_layer(&layer, *opacity, *blend_mode, self.current_clip());
+      }
+    }
+  }
+
+  fn current_clip(&self) -> Option<&Coverage> { self.clip_layer_stack.last().map(|l| &l.coverage) }
+
+  fn viewport(&self) -> &DeviceRect {
+    self
+      .clip_layer_stack
+      .last()
+      .map_or(&self.viewport, |l| &l.viewport)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_algo::Resource;
+  use ribir_geom::{rect, DeviceSize};
+  use ribir_painter::{BackdropFilter, BlendMode, Painter, Path};
+
+  use super::*;
+
+  fn render(painter: &mut Painter, size: DeviceSize) -> CpuTexture {
+    let mut texture = CpuTexture::new(size);
+    let mut backend = CpuBackend::new();
+    backend.begin_frame(Color::TRANSPARENT);
+    let viewport = DeviceRect::from_size(size);
+    backend.draw_commands(viewport, &painter.finish(), &Transform::identity(), &mut texture);
+    backend.end_frame();
+    texture
+  }
+
+  fn pixel(texture: &CpuTexture, x: i32, y: i32) -> &[u8] {
+    let idx = ((y * texture.size().width + x) * 4) as usize;
+    &texture.pixel_bytes()[idx..idx + 4]
+  }
+
+  #[test]
+  fn clip_path() {
+    let mut painter = Painter::new(rect(0., 0., 20., 10.));
+    painter
+      .clip(Path::rect(&rect(0., 0., 10., 10.)))
+      .set_brush(Color::RED)
+      .rect(&rect(0., 0., 20., 10.))
+      .fill();
+
+    let texture = render(&mut painter, DeviceSize::new(20, 10));
+    assert_eq!(pixel(&texture, 5, 5), &[255, 0, 0, 255]);
+    assert_eq!(pixel(&texture, 15, 5), &[0, 0, 0, 0]);
+  }
+
+  #[test]
+  fn bundle_opacity_apply_to_group() {
+    let commands = {
+      let mut painter = Painter::new(rect(0., 0., 10., 10.));
+      painter
+        .set_brush(Color::RED)
+        .rect(&rect(0., 0., 10., 10.))
+        .fill()
+        .set_brush(Color::BLUE)
+        .rect(&rect(0., 0., 10., 10.))
+        .fill();
+      let commands: Box<[PaintCommand]> = painter.finish().to_vec().into();
+      commands
+    };
+
+    let mut painter = Painter::new(rect(0., 0., 10., 10.));
+    painter
+      .apply_alpha(0.5)
+      .draw_bundle_commands(rect(0., 0., 10., 10.), Resource::new(commands));
+
+    // The red rectangle is covered by the blue one, so the opacity should not
+    // let it through.
+    let texture = render(&mut painter, DeviceSize::new(10, 10));
+    assert_eq!(pixel(&texture, 5, 5), &[0, 0, 128, 128]);
+  }
+
+  #[test]
+  fn blend_layer() {
+    let mut painter = Painter::new(rect(0., 0., 20., 10.));
+    painter
+      .set_brush(Color::YELLOW)
+      .rect(&rect(0., 0., 20., 10.))
+      .fill()
+      .save_layer(1., BlendMode::Multiply)
+      .set_brush(Color::from_rgb(0, 255, 255))
+      .rect(&rect(0., 0., 10., 10.))
+      .fill()
+      .restore();
+
+    let texture = render(&mut painter, DeviceSize::new(20, 10));
+    assert_eq!(pixel(&texture, 5, 5), &[0, 255, 0, 255]);
+    assert_eq!(pixel(&texture, 15, 5), &[255, 255, 0, 255]);
+  }
+
+  #[test]
+  fn backdrop_filter() {
+    let mut painter = Painter::new(rect(0., 0., 40., 10.));
+    painter
+      .set_brush(Color::RED)
+      .rect(&rect(0., 0., 20., 10.))
+      .fill()
+      .set_brush(Color::BLUE)
+      .rect(&rect(20., 0., 20., 10.))
+      .fill()
+      .draw_backdrop_filter(Path::rect(&rect(10., 0., 20., 10.)), &BackdropFilter::blur(8.));
+
+    let texture = render(&mut painter, DeviceSize::new(40, 10));
+    // The edge of the colors is blurred symmetrically, and the outside is not
+    // touched.
+    let (left, right) = (pixel(&texture, 19, 5), pixel(&texture, 20, 5));
+    assert!(left[0] > left[2] && left[2] > 0);
+    assert_eq!(left, &[right[2], 0, right[0], 255]);
+    assert_eq!(pixel(&texture, 5, 5), &[255, 0, 0, 255]);
+
+    let mut painter = Painter::new(rect(0., 0., 10., 10.));
+    painter
+      .set_brush(Color::RED)
+      .rect(&rect(0., 0., 10., 10.))
+      .fill()
+      .draw_backdrop_filter(
+        Path::rect(&rect(0., 0., 10., 10.)),
+        &BackdropFilter::new(4., Color::BLUE.with_alpha(0.5)),
+      );
+    let texture = render(&mut painter, DeviceSize::new(10, 10));
+    assert_eq!(pixel(&texture, 5, 5), &[127, 0, 128, 255]);
+  }
+}
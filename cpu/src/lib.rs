@@ -0,0 +1,68 @@
+//! A pure CPU implementation of the `PainterBackend`.
+//!
+//! It rasterizes the paint commands in memory and doesn't need any GPU
+//! adapter, so it works on headless servers and CI machines, and can be used as
+//! a fallback when the GPU backend fails to initialize.
+mod cpu_backend;
+mod raster;
+pub use cpu_backend::*;
+use ribir_geom::{DeviceRect, DeviceSize};
+use ribir_painter::{image::ColorFormat, Color, PixelImage};
+
+/// A RGBA8 pixel buffer that the `CpuBackend` draws to. The color is stored
+/// without premultiplied alpha, the same as the texture of the GPU backend.
+#[derive(Clone)]
+pub struct CpuTexture {
+  size: DeviceSize,
+  data: Vec<u8>,
+}
+
+impl CpuTexture {
+  /// Create a texture with all pixels transparent.
+  pub fn new(size: DeviceSize) -> Self {
+    let len = size.width.max(0) as usize * size.height.max(0) as usize * 4;
+    Self { size, data: vec![0; len] }
+  }
+
+  #[inline]
+  pub fn size(&self) -> DeviceSize { self.size }
+
+  #[inline]
+  pub fn color_format(&self) -> ColorFormat { ColorFormat::Rgba8 }
+
+  /// The pixel data of the whole texture, row by row.
+  #[inline]
+  pub fn pixel_bytes(&self) -> &[u8] { &self.data }
+
+  /// Fill the whole texture with the `color`.
+  pub fn clear(&mut self, color: Color) {
+    let components = color.into_components();
+    self
+      .data
+      .chunks_exact_mut(4)
+      .for_each(|p| p.copy_from_slice(&components));
+  }
+
+  /// Return an image of the texture area, the part of `rect` out of the
+  /// texture is transparent.
+  pub fn copy_as_image(&self, rect: &DeviceRect) -> PixelImage {
+    let width = rect.width().max(0) as usize;
+    let height = rect.height().max(0) as usize;
+    let mut data = vec![0; width * height * 4];
+    let tex_rect = DeviceRect::from_size(self.size);
+    if let Some(area) = tex_rect.intersection(rect) {
+      let row_bytes = area.width() as usize * 4;
+      for y in area.min_y()..area.max_y() {
+        let src = ((y * self.size.width + area.min_x()) * 4) as usize;
+        let dst =
+          ((y - rect.min_y()) as usize * width + (area.min_x() - rect.min_x()) as usize) * 4;
+        data[dst..dst + row_bytes].copy_from_slice(&self.data[src..src + row_bytes]);
+      }
+    }
+    PixelImage::new(data.into(), width as u32, height as u32, ColorFormat::Rgba8)
+  }
+
+  pub(crate) fn pixels(&mut self) -> raster::Pixels<'_> {
+    raster::Pixels::new(DeviceRect::from_size(self.size), &mut self.data)
+  }
+}
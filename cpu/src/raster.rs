@@ -0,0 +1,566 @@
+use ribir_geom::{DeviceRect, Point, Rect, Transform};
+use ribir_painter::{
+  color::{ConicGradient, LinearGradient, RadialGradient},
+  image::ColorFormat,
+  BlendMode, Color, GradientStop, ImageFilter, ImageRepeat, Path, PathSegment, PixelImage, Radius,
+  SpreadMethod,
+};
+
+/// A mutable view of RGBA8 pixels, the `rect` is the area of the pixels in
+/// the device coordinate.
+pub(crate) struct Pixels<'a> {
+  rect: DeviceRect,
+  data: &'a mut [u8],
+}
+
+/// The alpha coverage of an area, every value is in the range of [0, 255].
+pub(crate) struct Coverage {
+  pub(crate) rect: DeviceRect,
+  pub(crate) alpha: Vec<u8>,
+}
+
+/// Describe how to compute the color of a pixel.
+pub(crate) enum Shader<'a> {
+  Color([f32; 4]),
+  /// The `to_img` transform a device position to the image position.
+  Image {
+    img: &'a PixelImage,
+    to_img: Transform,
+    opacity: f32,
+    repeat: ImageRepeat,
+    filter: ImageFilter,
+  },
+  /// The `to_path` transform a device position to the path position.
+  Linear {
+    gradient: &'a LinearGradient,
+    to_path: Transform,
+  },
+  Radial {
+    gradient: &'a RadialGradient,
+    to_path: Transform,
+  },
+  Conic {
+    gradient: &'a ConicGradient,
+    to_path: Transform,
+  },
+  Shadow {
+    rect: &'a Rect,
+    radius: &'a Radius,
+    sigma: f32,
+    color: [f32; 4],
+    inset: bool,
+    to_path: Transform,
+  },
+  /// The `blurred` colors are the premultiplied colors of the `rect`, they
+  /// are covered by the `tint`.
+  Backdrop {
+    rect: DeviceRect,
+    blurred: &'a [[f32; 4]],
+    tint: [f32; 4],
+    opacity: f32,
+  },
+}
+
+/// The max distance in pixels that the blur samples from, the same as the GPU
+/// backend.
+const MAX_BLUR_RADIUS: i32 = 96;
+
+impl<'a> Pixels<'a> {
+  pub(crate) fn new(rect: DeviceRect, data: &'a mut [u8]) -> Self {
+    debug_assert_eq!(data.len(), rect.area() as usize * 4);
+    Self { rect, data }
+  }
+
+  #[inline]
+  pub(crate) fn rect(&self) -> &DeviceRect { &self.rect }
+
+  /// Blend the color computed by the `shader` to the pixels that the
+  /// `coverage` covers, and the `clip` limits.
+  pub(crate) fn fill(&mut self, coverage: &Coverage, clip: Option<&Coverage>, shader: &Shader) {
+    let Some(area) = self.rect.intersection(&coverage.rect) else { return };
+    for y in area.min_y()..area.max_y() {
+      for x in area.min_x()..area.max_x() {
+        let mut alpha = coverage.value(x, y);
+        if let Some(clip) = clip {
+          alpha *= clip.value(x, y);
+        }
+        if alpha <= 0. {
+          continue;
+        }
+        if let Some(mut color) = shader.sample(x as f32 + 0.5, y as f32 + 0.5) {
+          color[3] *= alpha;
+          self.blend(x, y, color);
+        }
+      }
+    }
+  }
+
+  /// Blend the `src` pixels to these pixels with an extra `opacity`.
+  pub(crate) fn composite(&mut self, src: &Pixels, opacity: f32, clip: Option<&Coverage>) {
+    let Some(area) = self.rect.intersection(&src.rect) else { return };
+    for y in area.min_y()..area.max_y() {
+      for x in area.min_x()..area.max_x() {
+        let mut alpha = opacity;
+        if let Some(clip) = clip {
+          alpha *= clip.value(x, y);
+        }
+        let mut color = unpack(src.pixel(x, y));
+        color[3] *= alpha;
+        if color[3] > 0. {
+          self.blend(x, y, color);
+        }
+      }
+    }
+  }
+
+  /// Composite the `src` layer to these pixels with an extra `opacity`, the
+  /// color of the layer is mixed with these pixels by the `mode` first.
+  ///
+  /// The layer is drawn from transparent, so its color is premultiplied.
+  pub(crate) fn composite_layer(
+    &mut self, src: &Pixels, opacity: f32, mode: BlendMode, clip: Option<&Coverage>,
+  ) {
+    let Some(area) = self.rect.intersection(&src.rect) else { return };
+    for y in area.min_y()..area.max_y() {
+      for x in area.min_x()..area.max_x() {
+        let mut alpha = opacity;
+        if let Some(clip) = clip {
+          alpha *= clip.value(x, y);
+        }
+        let mut color = unpremultiply(unpack(src.pixel(x, y)));
+        if color[3] <= 0. || alpha <= 0. {
+          continue;
+        }
+        if mode != BlendMode::Normal {
+          let dst = unpremultiply(unpack(self.pixel(x, y)));
+          for i in 0..3 {
+            let mixed = blend_channel(mode, dst[i], color[i]);
+            color[i] = (1. - dst[3]) * color[i] + dst[3] * mixed;
+          }
+        }
+        color[3] *= alpha;
+        self.blend(x, y, color);
+      }
+    }
+  }
+
+  /// Blur the pixels of the `area` by the Gaussian function of `sigma`, the
+  /// pixels around the area are sampled too. Return the premultiplied colors
+  /// of the area.
+  pub(crate) fn blur(&self, area: &DeviceRect, sigma: f32) -> Vec<[f32; 4]> {
+    let kernel = blur_kernel(sigma);
+    let radius = (kernel.len() / 2) as i32;
+    let src = area
+      .inflate(radius, radius)
+      .intersection(&self.rect)
+      .unwrap_or(*area);
+    let convolve = |sample: &dyn Fn(i32) -> [f32; 4]| {
+      let mut sum = [0.; 4];
+      for (i, w) in kernel.iter().enumerate() {
+        let color = sample(i as i32 - radius);
+        (0..4).for_each(|c| sum[c] += color[c] * w);
+      }
+      sum
+    };
+
+    let width = area.width() as usize;
+    let mut rows = Vec::with_capacity(width * src.height() as usize);
+    for y in src.min_y()..src.max_y() {
+      for x in area.min_x()..area.max_x() {
+        rows.push(convolve(&|i| {
+          let x = (x + i).clamp(src.min_x(), src.max_x() - 1);
+          unpack(self.pixel(x, y))
+        }));
+      }
+    }
+    let mut blurred = Vec::with_capacity(area.area() as usize);
+    for y in area.min_y()..area.max_y() {
+      for col in 0..width {
+        blurred.push(convolve(&|i| {
+          let row = (y + i).clamp(src.min_y(), src.max_y() - 1) - src.min_y();
+          rows[row as usize * width + col]
+        }));
+      }
+    }
+    blurred
+  }
+
+  fn pixel(&self, x: i32, y: i32) -> &[u8] {
+    let idx = self.index(x, y);
+    &self.data[idx..idx + 4]
+  }
+
+  fn index(&self, x: i32, y: i32) -> usize {
+    let row = (y - self.rect.min_y()) as usize;
+    let col = (x - self.rect.min_x()) as usize;
+    (row * self.rect.width() as usize + col) * 4
+  }
+
+  /// Blend a straight alpha color with the pixel, the same way as the source
+  /// over alpha blending of the GPU backend.
+  fn blend(&mut self, x: i32, y: i32, src: [f32; 4]) {
+    let idx = self.index(x, y);
+    let dst = &mut self.data[idx..idx + 4];
+    let sa = src[3].clamp(0., 1.);
+    for i in 0..3 {
+      let d = dst[i] as f32 / 255.;
+      dst[i] = to_u8(src[i] * sa + d * (1. - sa));
+    }
+    let da = dst[3] as f32 / 255.;
+    dst[3] = to_u8(sa + da * (1. - sa));
+  }
+}
+
+impl Coverage {
+  /// Rasterize the `path` applied the `matrix` in the `area`. Return `None` if
+  /// nothing is covered.
+  pub(crate) fn from_path(path: &Path, matrix: &Transform, area: &DeviceRect) -> Option<Self> {
+    let path = to_skia_path(path)?;
+    let mut mask = tiny_skia::Mask::new(area.width() as u32, area.height() as u32)?;
+    let ts = tiny_skia::Transform::from_row(
+      matrix.m11,
+      matrix.m12,
+      matrix.m21,
+      matrix.m22,
+      matrix.m31 - area.min_x() as f32,
+      matrix.m32 - area.min_y() as f32,
+    );
+    mask.fill_path(&path, tiny_skia::FillRule::Winding, true, ts);
+    Some(Coverage { rect: *area, alpha: mask.data().to_vec() })
+  }
+
+  /// Multiply this coverage with another one, only the intersect area is kept.
+  pub(crate) fn intersect(&self, other: &Coverage) -> Option<Coverage> {
+    let rect = self.rect.intersection(&other.rect)?;
+    let mut alpha = Vec::with_capacity(rect.area() as usize);
+    for y in rect.min_y()..rect.max_y() {
+      for x in rect.min_x()..rect.max_x() {
+        alpha.push(to_u8(self.value(x, y) * other.value(x, y)));
+      }
+    }
+    Some(Coverage { rect, alpha })
+  }
+
+  fn value(&self, x: i32, y: i32) -> f32 {
+    if !self
+      .rect
+      .contains(ribir_geom::DevicePoint::new(x, y))
+    {
+      return 0.;
+    }
+    let row = (y - self.rect.min_y()) as usize;
+    let col = (x - self.rect.min_x()) as usize;
+    self.alpha[row * self.rect.width() as usize + col] as f32 / 255.
+  }
+}
+
+impl<'a> Shader<'a> {
+  /// Return the straight alpha color at the device position, or `None` if
+  /// nothing needs to paint.
+  fn sample(&self, x: f32, y: f32) -> Option<[f32; 4]> {
+    match self {
+      Shader::Color(c) => Some(*c),
+      Shader::Image { img, to_img, opacity, repeat, filter } => {
+        let pos = to_img.transform_point(Point::new(x, y));
+        let mut color = sample_image(img, pos, *repeat, *filter)?;
+        color[3] *= opacity;
+        Some(color)
+      }
+      Shader::Linear { gradient, to_path } => {
+        let LinearGradient { start, end, stops, spread_method } = gradient;
+        if start == end {
+          return None;
+        }
+        let pos = to_path.transform_point(Point::new(x, y));
+        let v = *end - *start;
+        let offset = (pos - *start).dot(v) / v.square_length();
+        Some(stops_color(stops, spread(offset, *spread_method)))
+      }
+      Shader::Radial { gradient, to_path } => {
+        let pos = to_path.transform_point(Point::new(x, y));
+        let offset = radial_offset(gradient, pos)?;
+        Some(stops_color(&gradient.stops, spread(offset, gradient.spread_method)))
+      }
+      Shader::Conic { gradient, to_path } => {
+        let pos = to_path.transform_point(Point::new(x, y));
+        Some(stops_color(&gradient.stops, gradient.offset(pos)))
+      }
+      Shader::Shadow { rect, radius, sigma, color, inset, to_path } => {
+        let pos = to_path.transform_point(Point::new(x, y));
+        let mut coverage = shadow_coverage(rect, radius, *sigma, pos);
+        if *inset {
+          coverage = 1. - coverage;
+        }
+        let mut color = *color;
+        color[3] *= coverage;
+        Some(color)
+      }
+      Shader::Backdrop { rect, blurred, tint, opacity } => {
+        let (col, row) = (x as i32 - rect.min_x(), y as i32 - rect.min_y());
+        let blurred = blurred[(row * rect.width() + col) as usize];
+        let ta = tint[3];
+        let alpha = ta + blurred[3] * (1. - ta);
+        if alpha <= 0. {
+          return None;
+        }
+        let mut color = [0., 0., 0., alpha * opacity];
+        (0..3).for_each(|i| color[i] = (tint[i] * ta + blurred[i] * (1. - ta)) / alpha);
+        Some(color)
+      }
+    }
+  }
+}
+
+pub(crate) fn color_to_f32(color: &Color) -> [f32; 4] { unpack(&color.into_components()) }
+
+fn unpack(c: &[u8]) -> [f32; 4] {
+  [c[0] as f32 / 255., c[1] as f32 / 255., c[2] as f32 / 255., c[3] as f32 / 255.]
+}
+
+fn unpremultiply(mut c: [f32; 4]) -> [f32; 4] {
+  if c[3] > 0. {
+    (0..3).for_each(|i| c[i] = (c[i] / c[3]).min(1.));
+  }
+  c
+}
+
+/// The separable blend function `B(cb, cs)` of the W3C compositing spec.
+fn blend_channel(mode: BlendMode, cb: f32, cs: f32) -> f32 {
+  let multiply = |cb: f32, cs: f32| cb * cs;
+  let screen = |cb: f32, cs: f32| cb + cs - cb * cs;
+  let hard_light = |cb: f32, cs: f32| {
+    if cs <= 0.5 { multiply(cb, 2. * cs) } else { screen(cb, 2. * cs - 1.) }
+  };
+  match mode {
+    BlendMode::Normal => cs,
+    BlendMode::Multiply => multiply(cb, cs),
+    BlendMode::Screen => screen(cb, cs),
+    BlendMode::Overlay => hard_light(cs, cb),
+    BlendMode::Darken => cb.min(cs),
+    BlendMode::Lighten => cb.max(cs),
+    BlendMode::ColorDodge => {
+      if cb <= 0. {
+        0.
+      } else if cs >= 1. {
+        1.
+      } else {
+        (cb / (1. - cs)).min(1.)
+      }
+    }
+    BlendMode::ColorBurn => {
+      if cb >= 1. {
+        1.
+      } else if cs <= 0. {
+        0.
+      } else {
+        1. - ((1. - cb) / cs).min(1.)
+      }
+    }
+    BlendMode::HardLight => hard_light(cb, cs),
+    BlendMode::SoftLight => {
+      if cs <= 0.5 {
+        cb - (1. - 2. * cs) * cb * (1. - cb)
+      } else {
+        let d = if cb <= 0.25 { ((16. * cb - 12.) * cb + 4.) * cb } else { cb.sqrt() };
+        cb + (2. * cs - 1.) * (d - cb)
+      }
+    }
+    BlendMode::Difference => (cb - cs).abs(),
+    BlendMode::Exclusion => cb + cs - 2. * cb * cs,
+  }
+}
+
+fn to_u8(v: f32) -> u8 { (v.clamp(0., 1.) * 255.).round() as u8 }
+
+fn to_skia_path(path: &Path) -> Option<tiny_skia::Path> {
+  let mut builder = tiny_skia::PathBuilder::new();
+  path.segments().for_each(|seg| match seg {
+    PathSegment::MoveTo(p) => builder.move_to(p.x, p.y),
+    PathSegment::LineTo(p) => builder.line_to(p.x, p.y),
+    PathSegment::QuadTo { ctrl, to } => builder.quad_to(ctrl.x, ctrl.y, to.x, to.y),
+    PathSegment::CubicTo { to, ctrl1, ctrl2 } => {
+      builder.cubic_to(ctrl1.x, ctrl1.y, ctrl2.x, ctrl2.y, to.x, to.y)
+    }
+    PathSegment::Close(true) => builder.close(),
+    PathSegment::Close(false) => {}
+  });
+  builder.finish()
+}
+
+/// Sample the image at the image position, return `None` if the position is
+/// out of the image in the direction that doesn't repeat.
+fn sample_image(
+  img: &PixelImage, pos: Point, repeat: ImageRepeat, filter: ImageFilter,
+) -> Option<[f32; 4]> {
+  let w = img.width() as i64;
+  let h = img.height() as i64;
+  if w == 0 || h == 0 {
+    return None;
+  }
+  let (repeat_x, repeat_y) = (repeat.repeat_x(), repeat.repeat_y());
+  if (!repeat_x && (pos.x < 0. || pos.x >= w as f32))
+    || (!repeat_y && (pos.y < 0. || pos.y >= h as f32))
+  {
+    return None;
+  }
+  // Wrap the texel that repeats, and clamp the others to the edge.
+  let wrap = |t: i64, size: i64, repeat: bool| {
+    if repeat { t.rem_euclid(size) } else { t.clamp(0, size - 1) }
+  };
+  let texel = |tx: i64, ty: i64| -> [f32; 4] {
+    let tx = wrap(tx, w, repeat_x) as usize;
+    let ty = wrap(ty, h, repeat_y) as usize;
+    let bytes = img.pixel_bytes();
+    match img.color_format() {
+      ColorFormat::Rgba8 => unpack(&bytes[(ty * w as usize + tx) * 4..]),
+      ColorFormat::Alpha8 => [0., 0., 0., bytes[ty * w as usize + tx] as f32 / 255.],
+    }
+  };
+  if filter == ImageFilter::Nearest {
+    return Some(texel(pos.x.floor() as i64, pos.y.floor() as i64));
+  }
+
+  // Bilinear interpolate the four nearest texels.
+  let x = pos.x - 0.5;
+  let y = pos.y - 0.5;
+  let (x0, y0) = (x.floor(), y.floor());
+  let (fx, fy) = (x - x0, y - y0);
+  let (x0, y0) = (x0 as i64, y0 as i64);
+  let c00 = texel(x0, y0);
+  let c10 = texel(x0 + 1, y0);
+  let c01 = texel(x0, y0 + 1);
+  let c11 = texel(x0 + 1, y0 + 1);
+  let mut color = [0.; 4];
+  for i in 0..4 {
+    let top = c00[i] * (1. - fx) + c10[i] * fx;
+    let bottom = c01[i] * (1. - fx) + c11[i] * fx;
+    color[i] = top * (1. - fy) + bottom * fy;
+  }
+  Some(color)
+}
+
+fn spread(offset: f32, method: SpreadMethod) -> f32 {
+  match method {
+    SpreadMethod::Pad => offset.clamp(0., 1.),
+    SpreadMethod::Reflect => 1. - ((offset / 2.).rem_euclid(1.) - 0.5).abs() * 2.,
+    SpreadMethod::Repeat => offset.rem_euclid(1.),
+  }
+}
+
+fn stops_color(stops: &[GradientStop], offset: f32) -> [f32; 4] {
+  match stops {
+    [] => [0.; 4],
+    [stop] => color_to_f32(&stop.color),
+    [first, second, rest @ ..] => {
+      let (mut prev, mut next) = (first, second);
+      for s in rest {
+        if next.offset >= offset {
+          break;
+        }
+        prev = next;
+        next = s;
+      }
+      let offset = offset.clamp(prev.offset, next.offset);
+      let range = next.offset - prev.offset;
+      let weight = if range > 0. { (offset - prev.offset) / range } else { 1. };
+      let (c1, c2) = (color_to_f32(&prev.color), color_to_f32(&next.color));
+      [0, 1, 2, 3].map(|i| c1[i] * (1. - weight) + c2[i] * weight)
+    }
+  }
+}
+
+/// Compute the offset of the two circles radial gradient, see
+/// https://html.spec.whatwg.org/multipage/canvas.html#dom-context-2d-createradialgradient
+fn radial_offset(gradient: &RadialGradient, pos: Point) -> Option<f32> {
+  let RadialGradient { start_center: c0, start_radius: r0, end_center: c1, end_radius: r1, .. } =
+    gradient;
+  let d0 = pos - *c0;
+  let d10 = *c1 - *c0;
+  let dr = r1 - r0;
+  let a = d10.square_length() - dr * dr;
+  let b = -2. * (d10.dot(d0) + dr * r0);
+  let c = d0.square_length() - r0 * r0;
+
+  let offset = if a.abs() < 0.1 {
+    if b.abs() < 0.1 {
+      return None;
+    }
+    -c / b
+  } else {
+    let delta = b * b - 4. * a * c;
+    if delta < 0. {
+      return None;
+    }
+    let sqrt_delta = delta.sqrt();
+    ((-b + sqrt_delta) / (2. * a)).max((-b - sqrt_delta) / (2. * a))
+  };
+
+  // The radius of the circle at the offset must be positive.
+  if r0 != r1 && offset < r0 / (r0 - r1) {
+    return None;
+  }
+  Some(offset)
+}
+
+/// The coverage of a rounded rectangle blurred by the Gaussian function, the
+/// same as the box shadow shader of the GPU backend, see
+/// https://madebyevan.com/shaders/fast-rounded-rectangle-shadows/
+fn shadow_coverage(rect: &Rect, radius: &Radius, sigma: f32, pos: Point) -> f32 {
+  let sigma = sigma.max(0.01);
+  let half = rect.size.to_vector() / 2.;
+  let p = pos - rect.center();
+  let corner = match (p.x < 0., p.y < 0.) {
+    (true, true) => radius.top_left,
+    (false, true) => radius.top_right,
+    (true, false) => radius.bottom_left,
+    (false, false) => radius.bottom_right,
+  };
+  let corner = corner.min(half.x).min(half.y);
+
+  // The shadow along the x-axis has a closed-form solution, and the y-axis is
+  // integrated by a few samples.
+  let shadow_x = |y: f32| {
+    let delta = (half.y - corner - y.abs()).min(0.);
+    let curved = half.x - corner + (corner * corner - delta * delta).max(0.).sqrt();
+    let k = std::f32::consts::FRAC_1_SQRT_2 / sigma;
+    0.5 * (erf((p.x + curved) * k) - erf((p.x - curved) * k))
+  };
+  let start = (-3. * sigma).clamp(p.y - half.y, p.y + half.y);
+  let end = (3. * sigma).clamp(p.y - half.y, p.y + half.y);
+  let step = (end - start) / 4.;
+  (0..4)
+    .map(|i| {
+      let y = start + step * (i as f32 + 0.5);
+      shadow_x(p.y - y) * gaussian(y, sigma) * step
+    })
+    .sum::<f32>()
+    .clamp(0., 1.)
+}
+
+/// The normalized weights of the Gaussian function that sample from `-r` to
+/// `r` pixels, the `r` is three times of `sigma`.
+fn blur_kernel(sigma: f32) -> Vec<f32> {
+  if sigma <= 0. {
+    return vec![1.];
+  }
+  let radius = ((3. * sigma).ceil() as i32).min(MAX_BLUR_RADIUS);
+  let mut kernel: Vec<f32> = (-radius..=radius)
+    .map(|i| (-((i * i) as f32) / (2. * sigma * sigma)).exp())
+    .collect();
+  let sum: f32 = kernel.iter().sum();
+  kernel.iter_mut().for_each(|w| *w /= sum);
+  kernel
+}
+
+fn gaussian(x: f32, sigma: f32) -> f32 {
+  let pi = std::f32::consts::PI;
+  (-(x * x) / (2. * sigma * sigma)).exp() / ((2. * pi).sqrt() * sigma)
+}
+
+/// An approximation of the error function.
+fn erf(x: f32) -> f32 {
+  let a = x.abs();
+  let t = 1. + (0.278393 + (0.230389 + 0.078108 * (a * a)) * a) * a;
+  let t = t * t;
+  (1. - 1. / (t * t)).copysign(x)
+}
@@ -34,6 +34,18 @@ macro_rules! painter_backend_eq_image_test {
           $(.with_comparison($comparison))?
           .test();
       }
+
+      #[test]
+      fn [<cpu_ $painter_fn>]() {
+        let mut painter = $painter_fn();
+        let viewport = painter.viewport().to_i32().cast_unit();
+        let img = cpu_render_commands(&painter.finish(), viewport, Color::TRANSPARENT);
+        let name = format!("{}_cpu", std::stringify!($painter_fn));
+        let file_path = test_case_name!(name, "png");
+        ImageTest::new(img, &file_path)
+          $(.with_comparison($comparison))?
+          .test();
+      }
     }
   };
 }
@@ -195,3 +207,22 @@ pub fn wgpu_render_commands(
   backend.end_frame();
   block_on(img).unwrap()
 }
+
+/// Render painter by cpu backend, and return the image.
+pub fn cpu_render_commands(
+  commands: &[ribir_painter::PaintCommand], viewport: ribir_geom::DeviceRect,
+  surface: ribir_painter::Color,
+) -> PixelImage {
+  use ribir_cpu::{CpuBackend, CpuTexture};
+  use ribir_geom::{DeviceRect, DeviceSize};
+  use ribir_painter::PainterBackend;
+
+  let rect = DeviceRect::from_size(DeviceSize::new(viewport.max_x() + 2, viewport.max_y() + 2));
+  let mut texture = CpuTexture::new(rect.size);
+  let mut backend = CpuBackend::new();
+
+  backend.begin_frame(surface);
+  backend.draw_commands(rect, commands, &Transform::identity(), &mut texture);
+  backend.end_frame();
+  texture.copy_as_image(&rect)
+}
@@ -50,16 +50,39 @@ macro_rules! widget_layout_test {
 #[macro_export]
 macro_rules! widget_image_tests {
   ($name:ident, $widget_tester:expr) => {
-    widget_image_tests!(gen_test: $name, with_default_by_wgpu, Theme::default(), $widget_tester);
+    widget_image_tests!(gen_test:
+      $name,
+      with_default_by_wgpu,
+      Theme::default(),
+      $widget_tester,
+      wgpu_render_commands
+    );
     widget_image_tests!(gen_test:
       $name,
       with_material_by_wgpu,
       ribir_material::purple::light(),
-      $widget_tester
+      $widget_tester,
+      wgpu_render_commands
+    );
+    widget_image_tests!(gen_test:
+      $name,
+      with_default_by_cpu,
+      Theme::default(),
+      $widget_tester,
+      cpu_render_commands
+    );
+    widget_image_tests!(gen_test:
+      $name,
+      with_material_by_cpu,
+      ribir_material::purple::light(),
+      $widget_tester,
+      cpu_render_commands
     );
   };
 
-  (gen_test: $name:ident, $suffix:ident, $theme:expr, $widget_tester:expr) => {
+  (gen_test:
+    $name:ident, $suffix:ident, $theme:expr, $widget_tester:expr, $render:ident
+  ) => {
     paste::paste! {
       #[test]
       fn [<$name _$suffix>]() {
@@ -73,7 +96,7 @@ macro_rules! widget_image_tests {
 
         let Frame { commands, viewport, surface } = wnd.take_last_frame().unwrap();
         let viewport = viewport.to_i32().cast_unit();
-        let img = $crate::wgpu_render_commands(&commands, viewport, surface);
+        let img = $crate::$render(&commands, viewport, surface);
 
         let mut img_test = $crate::ImageTest::new(img, &img_path);
         if let Some(c) = $widget_tester.comparison {
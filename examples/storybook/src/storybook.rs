@@ -43,10 +43,10 @@ fn content() -> Widget<'static> {
               padding: EdgeInsets::new(20., 40., 20., 40.),
               background: Palette::of(ctx!()).surface_container_low(),
               border_radius: Radius::all(4.),
-              border: Border::all(BorderSide {
-                color: Palette::of(ctx!()).primary().into(),
-                width: 1.,
-              }),
+              border: Border::all(BorderSide::new(
+                1.,
+                Palette::of(ctx!()).primary().into(),
+              )),
               @Row {
                 item_gap: 20.,
                 @SizedBox {
@@ -115,10 +115,10 @@ fn content() -> Widget<'static> {
               padding: EdgeInsets::new(20., 40., 20., 40.),
               background: Palette::of(ctx!()).surface_container_lowest(),
               border_radius: Radius::all(4.),
-              border: Border::all(BorderSide {
-                color: Palette::of(ctx!()).primary().into(),
-                width: 1.,
-              }),
+              border: Border::all(BorderSide::new(
+                1.,
+                Palette::of(ctx!()).primary().into(),
+              )),
               @Row {
                 item_gap: 20.,
                 @FabButton {
@@ -149,10 +149,10 @@ fn content() -> Widget<'static> {
               padding: EdgeInsets::new(20., 40., 20., 40.),
               background: Palette::of(ctx!()).surface_container_lowest(),
               border_radius: Radius::all(4.),
-              border: Border::all(BorderSide {
-                color: Palette::of(ctx!()).primary().into(),
-                width: 1.,
-              }),
+              border: Border::all(BorderSide::new(
+                1.,
+                Palette::of(ctx!()).primary().into(),
+              )),
               @Row {
                 item_gap: 20.,
                 @Button {
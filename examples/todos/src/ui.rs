@@ -109,7 +109,7 @@ fn input(
       h_align: HAlign::Stretch,
       border: {
         let color = Palette::of(ctx!()).surface_variant().into();
-        Border::only_bottom(BorderSide { width: 2., color })
+        Border::only_bottom(BorderSide::new(2., color))
       },
       on_key_down: move |e| {
         if e.key_code() == &PhysicalKey::Code(KeyCode::Enter) {
@@ -5,14 +5,15 @@ use ribir_geom::{
   rect_corners, transform_to_device_rect, DeviceRect, DeviceSize, Point, Transform,
 };
 use ribir_painter::{
-  image::ColorFormat, Color, PaintCommand, PaintPath, PaintPathAction, PainterBackend, PathCommand,
-  PixelImage, Vertex, VertexBuffers,
+  image::ColorFormat, BlendMode, Color, ImageFilter, PaintCommand, PaintPath, PaintPathAction,
+  PainterBackend, PathCommand, PixelImage, Vertex, VertexBuffers,
 };
 
 use crate::{
-  ColorAttr, GPUBackendImpl, GradientStopPrimitive, ImagePrimIndex, ImgPrimitive,
-  LinearGradientPrimIndex, LinearGradientPrimitive, MaskLayer, RadialGradientPrimIndex,
-  RadialGradientPrimitive,
+  BackdropFilterPrimitive, BlendLayerPrimitive, BoxShadowPrimIndex, BoxShadowPrimitive, ColorAttr,
+  ConicGradientPrimIndex, ConicGradientPrimitive, GPUBackendImpl, GradientStopPrimitive,
+  ImagePrimIndex, ImgPrimitive, LinearGradientPrimIndex, LinearGradientPrimitive, MaskLayer,
+  RadialGradientPrimIndex, RadialGradientPrimitive,
 };
 
 mod atlas;
@@ -32,6 +33,15 @@ pub struct GPUBackend<Impl: GPUBackendImpl> {
   linear_gradient_prims: Vec<LinearGradientPrimitive>,
   linear_gradient_stops: Vec<GradientStopPrimitive>,
   linear_gradient_vertices_buffer: VertexBuffers<LinearGradientPrimIndex>,
+  conic_gradient_prims: Vec<ConicGradientPrimitive>,
+  conic_gradient_stops: Vec<GradientStopPrimitive>,
+  conic_gradient_vertices_buffer: VertexBuffers<ConicGradientPrimIndex>,
+  box_shadow_prims: Vec<BoxShadowPrimitive>,
+  box_shadow_vertices_buffer: VertexBuffers<BoxShadowPrimIndex>,
+  blend_layer: Option<(BlendLayerPrimitive, Option<DeviceRect>)>,
+  blend_layer_vertices_buffer: VertexBuffers<()>,
+  backdrop_filter: Option<(BackdropFilterPrimitive, DeviceRect)>,
+  backdrop_filter_vertices_buffer: VertexBuffers<()>,
   current_phase: CurrentPhase,
   tex_ids_map: TextureIdxMap,
   viewport: DeviceRect,
@@ -48,8 +58,15 @@ enum CurrentPhase {
   Img,
   RadialGradient,
   LinearGradient,
+  ConicGradient,
+  BoxShadow,
+  BlendLayer,
+  BackdropFilter,
 }
 
+/// The max distance in pixels that the backdrop blur samples from.
+const MAX_BLUR_RADIUS: i32 = 96;
+
 struct ClipLayer {
   viewport: DeviceRect,
   mask_head: i32,
@@ -140,6 +157,15 @@ where
       linear_gradient_vertices_buffer: VertexBuffers::with_capacity(256, 512),
       linear_gradient_stops: vec![],
       linear_gradient_prims: vec![],
+      conic_gradient_vertices_buffer: VertexBuffers::with_capacity(256, 512),
+      conic_gradient_stops: vec![],
+      conic_gradient_prims: vec![],
+      box_shadow_vertices_buffer: VertexBuffers::with_capacity(256, 512),
+      box_shadow_prims: vec![],
+      blend_layer: None,
+      blend_layer_vertices_buffer: VertexBuffers::with_capacity(4, 6),
+      backdrop_filter: None,
+      backdrop_filter_vertices_buffer: VertexBuffers::with_capacity(4, 6),
       img_prims: vec![],
       current_phase: CurrentPhase::None,
       viewport: DeviceRect::zero(),
@@ -176,6 +202,11 @@ where
           return;
         };
 
+        // The image is placed in an empty rect, nothing to draw.
+        if matches!(action, PaintPathAction::Image { rect, .. } if rect.is_empty()) {
+          return;
+        }
+
         if !self.can_batch_path_command(cmd) {
           self.new_draw_phase(output);
         }
@@ -191,10 +222,27 @@ where
             add_rect_vertices(rect, output_tex_size, color_attr, buffer);
             self.current_phase = CurrentPhase::Color;
           }
-          PaintPathAction::Image { img, opacity } => {
+          PaintPathAction::Image { img, opacity, rect: img_rect, repeat, filter } => {
             let slice = self.tex_mgr.store_image(img, &mut self.gpu_impl);
-            let ts = matrix.inverse().unwrap();
-            self.draw_img_slice(slice, &ts, mask_head, *opacity, output_tex_size, rect);
+            let ts = matrix
+              .inverse()
+              .unwrap()
+              .then_translate(-img_rect.origin.to_vector())
+              .then_scale(
+                img.width() as f32 / img_rect.width(),
+                img.height() as f32 / img_rect.height(),
+              );
+            let mut flags = 0;
+            if !repeat.repeat_x() {
+              flags |= ImgPrimitive::NO_REPEAT_X;
+            }
+            if !repeat.repeat_y() {
+              flags |= ImgPrimitive::NO_REPEAT_Y;
+            }
+            if *filter == ImageFilter::Nearest {
+              flags |= ImgPrimitive::NEAREST;
+            }
+            self.draw_img_slice(slice, &ts, mask_head, *opacity, flags, output_tex_size, rect);
           }
           PaintPathAction::Radial(radial) => {
             let prim: RadialGradientPrimitive = RadialGradientPrimitive {
@@ -241,6 +289,67 @@ where
             add_rect_vertices(rect, output_tex_size, LinearGradientPrimIndex(prim_idx), buffer);
             self.current_phase = CurrentPhase::LinearGradient;
           }
+          PaintPathAction::Conic(conic) => {
+            let stop = (self.conic_gradient_stops.len() << 16 | conic.stops.len()) as u32;
+            let transform = matrix
+              .inverse()
+              .unwrap()
+              .then_translate(-conic.center.to_vector())
+              .then_rotate(-conic.start_angle);
+            let prim = ConicGradientPrimitive { transform: transform.to_array(), stop, mask_head };
+            let stops = conic.stops.iter().map(GradientStopPrimitive::new);
+            self.conic_gradient_stops.extend(stops);
+            let prim_idx = self.conic_gradient_prims.len() as u32;
+            self.conic_gradient_prims.push(prim);
+            let buffer = &mut self.conic_gradient_vertices_buffer;
+            add_rect_vertices(rect, output_tex_size, ConicGradientPrimIndex(prim_idx), buffer);
+            self.current_phase = CurrentPhase::ConicGradient;
+          }
+          PaintPathAction::Shadow { rect: shadow_rect, radius, sigma, color, inset } => {
+            let center = shadow_rect.center();
+            let to_center = matrix
+              .inverse()
+              .unwrap()
+              .then_translate(Vector2D::new(-center.x, -center.y));
+            let prim = BoxShadowPrimitive {
+              transform: to_center.to_array(),
+              half_size: [shadow_rect.width() / 2., shadow_rect.height() / 2.],
+              radius: [radius.top_left, radius.top_right, radius.bottom_right, radius.bottom_left],
+              sigma: *sigma,
+              color: color.into_u32(),
+              mask_head,
+              inset: *inset as u32,
+            };
+            let prim_idx = self.box_shadow_prims.len() as u32;
+            self.box_shadow_prims.push(prim);
+            let buffer = &mut self.box_shadow_vertices_buffer;
+            add_rect_vertices(rect, output_tex_size, BoxShadowPrimIndex(prim_idx), buffer);
+            self.current_phase = CurrentPhase::BoxShadow;
+          }
+          PaintPathAction::Backdrop { sigma, tint, opacity } => {
+            let radius = ((3. * sigma).ceil() as i32).min(MAX_BLUR_RADIUS);
+            let Some(backdrop) = viewport
+              .inflate(radius, radius)
+              .intersection(&DeviceRect::from_size(output_tex_size))
+            else {
+              return;
+            };
+            let prim = BackdropFilterPrimitive {
+              backdrop_origin: backdrop.origin.to_f32().to_array(),
+              backdrop_size: backdrop.size.to_f32().to_array(),
+              sigma: *sigma,
+              tint: tint.into_u32(),
+              mask_head,
+              opacity: *opacity,
+            };
+            self.backdrop_filter = Some((prim, backdrop));
+            let buffer = &mut self.backdrop_filter_vertices_buffer;
+            add_rect_vertices(rect, output_tex_size, (), buffer);
+            self.current_phase = CurrentPhase::BackdropFilter;
+            // The filter reads the content below it, so it can't batch with
+            // others.
+            self.new_draw_phase(output);
+          }
           PaintPathAction::Clip => self
             .clip_layer_stack
             .push(ClipLayer { viewport, mask_head }),
@@ -315,7 +424,61 @@ where
           .clip_layer_stack
           .last()
           .map_or(-1, |l| l.mask_head);
-        self.draw_img_slice(slice, &view_to_slice, mask_head, *opacity, output_tex_size, points);
+        self.draw_img_slice(slice, &view_to_slice, mask_head, *opacity, 0, output_tex_size, points);
+      }
+      PaintCommand::Layer { opacity, blend_mode, bounds, cmds } => {
+        if self.skip_clip_cnt > 0 {
+          return;
+        }
+        let bounds = transform_to_device_rect(bounds, global_matrix);
+        let Some(view) = self
+          .viewport()
+          .intersection(&bounds)
+          .and_then(|view| view.intersection(&DeviceRect::from_size(output_tex_size)))
+        else {
+          return;
+        };
+
+        // The layer is composited over the commands before it, so draw them first.
+        self.new_draw_phase(output);
+
+        let this = self as *mut Self;
+        let slice = self
+          .tex_mgr
+          .store_layer(view.size, &mut self.gpu_impl, |slice, tex, _| {
+            // SAFETY: The same as the bundle, the texture is held by the texture
+            // manager and only the allocated slice is drawn.
+            let this = unsafe { &mut *this };
+            let viewport = this.viewport;
+            let slice = DeviceRect::new(slice.origin, view.size);
+            this
+              .clip_layer_stack
+              .push(ClipLayer { viewport: slice, mask_head: -1 });
+            let offset = (slice.origin - view.origin).to_f32();
+            let matrix = global_matrix.then_translate(offset.cast_unit());
+            this.draw_commands(slice, cmds, &matrix, tex);
+            this.clip_layer_stack.pop();
+            this.viewport = viewport;
+            this.begin_draw_phase();
+          });
+
+        let layer_offset = (slice.rect.origin - view.origin).to_f32();
+        let prim = BlendLayerPrimitive {
+          layer_offset: layer_offset.to_array(),
+          backdrop_origin: view.origin.to_f32().to_array(),
+          mask_head: self.current_clip_mask_index(),
+          tex_idx: self.tex_ids_map.tex_idx(slice.tex_id),
+          opacity: *opacity,
+          blend_mode: *blend_mode as u32,
+        };
+        let backdrop = (*blend_mode != BlendMode::Normal).then_some(view);
+        self.blend_layer = Some((prim, backdrop));
+        let rect = rect_corners(&view.to_f32().cast_unit());
+        add_rect_vertices(rect, output_tex_size, (), &mut self.blend_layer_vertices_buffer);
+        self.current_phase = CurrentPhase::BlendLayer;
+        // The layer can't batch with others, because it may read the content
+        // below it.
+        self.new_draw_phase(output);
       }
     }
   }
@@ -394,15 +557,42 @@ where
       .indices
       .clear();
     self.linear_gradient_stops.clear();
+    self.conic_gradient_prims.clear();
+    self
+      .conic_gradient_vertices_buffer
+      .indices
+      .clear();
+    self
+      .conic_gradient_vertices_buffer
+      .vertices
+      .clear();
+    self.conic_gradient_stops.clear();
+    self.box_shadow_prims.clear();
+    self.box_shadow_vertices_buffer.indices.clear();
+    self.box_shadow_vertices_buffer.vertices.clear();
+    self.blend_layer = None;
+    self.blend_layer_vertices_buffer.indices.clear();
+    self.blend_layer_vertices_buffer.vertices.clear();
+    self.backdrop_filter = None;
+    self
+      .backdrop_filter_vertices_buffer
+      .indices
+      .clear();
+    self
+      .backdrop_filter_vertices_buffer
+      .vertices
+      .clear();
   }
 
+  #[allow(clippy::too_many_arguments)]
   fn draw_img_slice(
     &mut self, img_slice: TextureSlice, transform: &Transform, mask_head: i32, opacity: f32,
-    output_tex_size: DeviceSize, rect: [Point; 4],
+    flags: i32, output_tex_size: DeviceSize, rect: [Point; 4],
   ) {
     let img_start = img_slice.rect.origin.to_f32().to_array();
     let img_size = img_slice.rect.size.to_f32().to_array();
-    let mask_head_and_tex_idx = mask_head << 16 | self.tex_ids_map.tex_idx(img_slice.tex_id) as i32;
+    let tex_idx = self.tex_ids_map.tex_idx(img_slice.tex_id) as i32;
+    let mask_head_and_tex_idx = mask_head << 16 | flags | tex_idx;
     let prim_idx = self.img_prims.len() as u32;
     let prim = ImgPrimitive {
       transform: transform.to_array(),
@@ -421,6 +611,8 @@ where
     let limits = self.gpu_impl.limits();
     let tex_used = self.tex_ids_map.len();
     match (self.current_phase, &cmd.action) {
+      // Draw the commands before the filter first, even the surface clear.
+      (_, PaintPathAction::Backdrop { .. }) => false,
       (CurrentPhase::None, _) => true,
       (_, PaintPathAction::Clip) | (CurrentPhase::Color, PaintPathAction::Color(_)) => {
         tex_used < limits.max_tex_load
@@ -438,6 +630,15 @@ where
           && self.linear_gradient_prims.len() < limits.max_linear_gradient_primitives
           && self.linear_gradient_stops.len() < limits.max_gradient_stop_primitives
       }
+      (CurrentPhase::ConicGradient, PaintPathAction::Conic(_)) => {
+        tex_used < limits.max_tex_load
+          && self.conic_gradient_prims.len() < limits.max_conic_gradient_primitives
+          && self.conic_gradient_stops.len() < limits.max_gradient_stop_primitives
+      }
+      (CurrentPhase::BoxShadow, PaintPathAction::Shadow { .. }) => {
+        tex_used < limits.max_tex_load
+          && self.box_shadow_prims.len() < limits.max_box_shadow_primitives
+      }
       _ => false,
     }
   }
@@ -544,6 +745,40 @@ where
         let rg = 0..self.linear_gradient_vertices_buffer.indices.len() as u32;
         gpu_impl.draw_linear_gradient_triangles(output, rg, color.take())
       }
+      CurrentPhase::ConicGradient
+        if !self
+          .conic_gradient_vertices_buffer
+          .indices
+          .is_empty() =>
+      {
+        gpu_impl.load_conic_gradient_primitives(&self.conic_gradient_prims);
+        gpu_impl.load_conic_gradient_stops(&self.conic_gradient_stops);
+        gpu_impl.load_conic_gradient_vertices(&self.conic_gradient_vertices_buffer);
+        let rg = 0..self.conic_gradient_vertices_buffer.indices.len() as u32;
+        gpu_impl.draw_conic_gradient_triangles(output, rg, color.take())
+      }
+      CurrentPhase::BoxShadow if !self.box_shadow_vertices_buffer.indices.is_empty() => {
+        gpu_impl.load_box_shadow_primitives(&self.box_shadow_prims);
+        gpu_impl.load_box_shadow_vertices(&self.box_shadow_vertices_buffer);
+        let rg = 0..self.box_shadow_vertices_buffer.indices.len() as u32;
+        gpu_impl.draw_box_shadow_triangles(output, rg, color.take())
+      }
+      CurrentPhase::BlendLayer => {
+        if let Some((prim, backdrop)) = self.blend_layer.as_ref() {
+          gpu_impl.load_blend_layer_primitive(prim);
+          gpu_impl.load_blend_layer_vertices(&self.blend_layer_vertices_buffer);
+          let rg = 0..self.blend_layer_vertices_buffer.indices.len() as u32;
+          gpu_impl.draw_blend_layer_triangles(output, rg, *backdrop)
+        }
+      }
+      CurrentPhase::BackdropFilter => {
+        if let Some((prim, backdrop)) = self.backdrop_filter.as_ref() {
+          gpu_impl.load_backdrop_filter_primitive(prim);
+          gpu_impl.load_backdrop_filter_vertices(&self.backdrop_filter_vertices_buffer);
+          let rg = 0..self.backdrop_filter_vertices_buffer.indices.len() as u32;
+          gpu_impl.draw_backdrop_filter_triangles(output, rg, *backdrop)
+        }
+      }
       _ => {}
     }
   }
@@ -597,7 +832,7 @@ mod tests {
   use ribir_algo::Resource;
   use ribir_dev_helper::*;
   use ribir_geom::*;
-  use ribir_painter::{Brush, Painter, Path, Svg};
+  use ribir_painter::{BoxShadow, Brush, Painter, Path, Svg};
 
   use super::*;
 
@@ -622,7 +857,7 @@ mod tests {
     let img = PixelImage::from_png(include_bytes!("../imgs/leaves.png"));
     let share_img = Resource::new(img);
 
-    let img_brush = Brush::Image(share_img);
+    let img_brush: Brush = share_img.into();
 
     draw_arrow_path(&mut painter);
     painter.set_brush(Color::RED).fill();
@@ -742,6 +977,51 @@ mod tests {
     painter
   }
 
+  painter_backend_eq_image_test!(draw_image_brush, comparison = 0.001);
+  fn draw_image_brush() -> Painter {
+    use ribir_painter::{image::ColorFormat, ImageBrush, ImageFilter, ImageFit, ImageRepeat};
+
+    let mut painter = painter(Size::new(240., 120.));
+    // A 4x4 pixel art of the checkerboard.
+    let data = (0..16)
+      .flat_map(
+        |i| if (i / 4 + i % 4) % 2 == 0 { [240, 180, 40, 255] } else { [40, 120, 200, 255] },
+      )
+      .collect::<Vec<u8>>();
+    let pixel_art = Resource::new(PixelImage::new(data.into(), 4, 4, ColorFormat::Rgba8));
+    let brush = ImageBrush::new(pixel_art).with_fit(ImageFit::Fill);
+    painter
+      .draw_img_brush(brush.clone().with_filter(ImageFilter::Nearest), &rect(0., 0., 60., 60.))
+      .draw_img_brush(brush, &rect(60., 0., 60., 60.));
+
+    let leaves = Resource::new(PixelImage::from_png(include_bytes!("../imgs/leaves.png")));
+    let brush = ImageBrush::new(leaves).with_align(0.5, 0.5);
+    painter
+      .set_brush(Color::GRAY)
+      .rect(&rect(120., 0., 120., 60.))
+      .fill()
+      .draw_img_brush(
+        brush
+          .clone()
+          .with_fit(ImageFit::Contain)
+          .with_repeat(ImageRepeat::NoRepeat),
+        &rect(120., 0., 120., 60.),
+      )
+      .draw_img_brush(brush.clone().with_fit(ImageFit::Cover), &rect(0., 60., 120., 60.));
+
+    // Repeat a small image in the horizontal direction only.
+    let small = brush
+      .with_bounds(rect(120., 70., 40., 26.))
+      .with_fit(ImageFit::Fill)
+      .with_repeat(ImageRepeat::RepeatX);
+    painter
+      .set_brush(Color::GRAY)
+      .rect(&rect(120., 60., 120., 60.))
+      .fill()
+      .draw_img_brush(small, &rect(120., 60., 120., 60.));
+    painter
+  }
+
   painter_backend_eq_image_test!(draw_svg_gradient, comparison = 0.0025);
   fn draw_svg_gradient() -> Painter {
     let mut painter = painter(Size::new(64., 64.));
@@ -752,6 +1032,178 @@ mod tests {
     painter
   }
 
+  painter_backend_eq_image_test!(draw_conic_gradient, comparison = 0.001);
+  fn draw_conic_gradient() -> Painter {
+    use ribir_painter::{color::ConicGradient, GradientStop};
+
+    let mut painter = painter(Size::new(240., 120.));
+    let hues = [Color::RED, Color::YELLOW, Color::GREEN, Color::BLUE, Color::RED];
+    let stops = hues
+      .iter()
+      .enumerate()
+      .map(|(i, c)| GradientStop::new(*c, i as f32 / 4.))
+      .collect();
+    let wheel = ConicGradient { center: Point::new(60., 60.), start_angle: Angle::zero(), stops };
+    painter
+      .set_brush(Brush::ConicGradient(wheel))
+      .circle(Point::new(60., 60.), 50.)
+      .fill();
+
+    // A progress ring start from the top, with a rotated transform.
+    let stops = vec![
+      GradientStop::new(Color::from_rgb(40, 120, 200).with_alpha(0.), 0.),
+      GradientStop::new(Color::from_rgb(40, 120, 200), 0.75),
+      GradientStop::new(Color::TRANSPARENT, 0.75),
+    ];
+    let ring = ConicGradient { center: Point::zero(), start_angle: Angle::degrees(-90.), stops };
+    painter
+      .translate(180., 60.)
+      .apply_transform(&Transform::rotation(Angle::degrees(45.)))
+      .set_brush(Brush::ConicGradient(ring))
+      .set_line_width(14.)
+      .circle(Point::zero(), 43.)
+      .stroke();
+    painter
+  }
+
+  painter_backend_eq_image_test!(draw_svg_conic_gradient, comparison = 0.001);
+  fn draw_svg_conic_gradient() -> Painter {
+    use ribir_painter::{color::ConicGradient, export_svg, GradientStop};
+
+    // SVG has no conic gradient, it's exported as wedges of solid colors, and
+    // imported back as they are.
+    let size = Size::new(120., 120.);
+    let mut wheel = Painter::new(Rect::from_size(size));
+    let hues = [Color::RED, Color::YELLOW, Color::GREEN, Color::BLUE, Color::RED];
+    let stops = hues
+      .iter()
+      .enumerate()
+      .map(|(i, c)| GradientStop::new(*c, i as f32 / 4.))
+      .collect();
+    let gradient =
+      ConicGradient { center: Point::new(60., 60.), start_angle: Angle::zero(), stops };
+    wheel
+      .set_brush(Brush::ConicGradient(gradient))
+      .circle(Point::new(60., 60.), 50.)
+      .fill();
+    let svg = export_svg(size, &wheel.finish());
+    let svg = Svg::parse_from_bytes(svg.as_bytes()).unwrap();
+
+    let mut painter = painter(size);
+    painter.draw_svg(&svg);
+    painter
+  }
+
+  painter_backend_eq_image_test!(draw_box_shadow, comparison = 0.001);
+  fn draw_box_shadow() -> Painter {
+    let mut painter = painter(Size::new(240., 120.));
+    let rect = rect(20., 20., 80., 80.);
+    let radius = ribir_painter::Radius::all(10.);
+    let shadow = BoxShadow::new(Vector::new(4., 6.), 12., 2., Color::BLACK.with_alpha(0.6));
+    painter
+      .draw_box_shadow(&rect, &radius, &shadow)
+      .set_brush(Color::WHITE)
+      .rect_round(&rect, &radius)
+      .fill();
+
+    let shadow = BoxShadow::new(Vector::new(4., 4.), 10., 0., Color::BLUE).inset();
+    painter
+      .translate(120., 0.)
+      .set_brush(Color::WHITE)
+      .rect_round(&rect, &radius)
+      .fill()
+      .draw_box_shadow(&rect, &radius, &shadow);
+    painter
+  }
+
+  painter_backend_eq_image_test!(draw_blend_layers, comparison = 0.001);
+  fn draw_blend_layers() -> Painter {
+    use ribir_painter::BlendMode::*;
+
+    let mut painter = painter(Size::new(240., 120.));
+    let modes = [
+      Normal, Multiply, Screen, Overlay, Darken, Lighten, ColorDodge, ColorBurn, HardLight,
+      SoftLight, Difference, Exclusion,
+    ];
+    for (i, mode) in modes.into_iter().enumerate() {
+      let x = (i % 6) as f32 * 40.;
+      let y = (i / 6) as f32 * 40.;
+      painter
+        .set_brush(Color::from_rgb(240, 180, 40))
+        .rect(&rect(x, y, 40., 20.))
+        .fill()
+        .set_brush(Color::from_rgb(40, 120, 200))
+        .rect(&rect(x, y + 20., 40., 20.))
+        .fill()
+        .save_layer(0.9, mode)
+        .set_brush(Color::from_rgb(200, 60, 120))
+        .circle(Point::new(x + 20., y + 20.), 16.)
+        .fill()
+        .restore();
+    }
+
+    // The opacity applies to the layer as a whole.
+    painter
+      .save_layer(0.5, Normal)
+      .set_brush(Color::RED)
+      .circle(Point::new(30., 100.), 16.)
+      .fill()
+      .set_brush(Color::BLUE)
+      .circle(Point::new(50., 100.), 16.)
+      .fill()
+      .restore();
+
+    // A clipped layer in a clipped layer.
+    painter
+      .set_brush(Color::from_rgb(40, 120, 200))
+      .rect(&rect(100., 80., 100., 40.))
+      .fill()
+      .save();
+    painter
+      .clip(Path::circle(Point::new(150., 100.), 18.))
+      .save_layer(1., Difference)
+      .clip(Path::rect(&rect(120., 80., 40., 40.)))
+      .set_brush(Color::from_rgb(240, 180, 40))
+      .rect(&rect(100., 80., 100., 40.))
+      .fill()
+      .restore();
+    painter.restore();
+    painter
+  }
+
+  painter_backend_eq_image_test!(draw_backdrop_filter, comparison = 0.001);
+  fn draw_backdrop_filter() -> Painter {
+    use ribir_painter::BackdropFilter;
+
+    let mut painter = painter(Size::new(240., 120.));
+    for i in 0..12 {
+      let color = if i % 2 == 0 { Color::from_rgb(240, 180, 40) } else { Color::BLUE };
+      painter
+        .set_brush(color)
+        .rect(&rect(i as f32 * 20., 0., 20., 120.))
+        .fill();
+    }
+    painter
+      .set_brush(Color::RED)
+      .circle(Point::new(120., 60.), 30.)
+      .fill();
+
+    let radius = ribir_painter::Radius::all(12.);
+    let glass = Path::rect_round(&rect(20., 20., 90., 80.), &radius);
+    painter.draw_backdrop_filter(glass, &BackdropFilter::blur(12.));
+    let glass = Path::rect_round(&rect(130., 20., 90., 80.), &radius);
+    let filter = BackdropFilter::new(6., Color::WHITE.with_alpha(0.4));
+    painter.draw_backdrop_filter(glass, &filter);
+
+    // Only blur the clipped area.
+    painter
+      .save()
+      .clip(Path::circle(Point::new(120., 110.), 10.))
+      .draw_backdrop_filter(Path::rect(&rect(0., 90., 240., 30.)), &BackdropFilter::blur(8.))
+      .restore();
+    painter
+  }
+
   // This test is disabled on Windows as it fails in the CI environment (exit code
   // 2173), although it passes on a physical Windows machine.
   #[cfg(not(target_os = "windows"))]
@@ -197,6 +197,20 @@ where
     )
   }
 
+  /// Allocate a cleared area in the target texture to draw a layer, the area
+  /// only lives in the current frame.
+  pub(super) fn store_layer(
+    &mut self, size: DeviceSize, gpu: &mut T::Host,
+    init: impl FnOnce(&DeviceRect, &mut T, &mut T::Host),
+  ) -> TextureSlice {
+    let dist = self.target_atlas.allocate(size, gpu);
+    let rect = dist.tex_rect(&self.target_atlas);
+    let texture = self.target_atlas.get_texture_mut(dist.tex_id());
+    texture.clear_areas(&[rect], gpu);
+    init(&rect, texture, gpu);
+    TextureSlice { tex_id: TextureID::Bundle(dist.tex_id()), rect }
+  }
+
   pub(super) fn texture(&self, tex_id: TextureID) -> &T { id_to_texture!(self, tex_id) }
 
   fn alpha_allocate(
@@ -58,8 +58,33 @@ pub use wgpu_impl::*;
 ///   |     |  +------------------------------------+    |
 ///   |     |  | load_linear_gradient_primitives()  |    |
 ///   |     +->| load_linear_gradient_stops()       |    |
-///   |        | load_linear_gradient_vertices()    |    |
-///   |        | draw_linear_gradient_triangles()   |    |
+///   |     |  | load_linear_gradient_vertices()    |    |
+///   |     |  | draw_linear_gradient_triangles()   |    |
+///   |     |  +------------------------------------+    |
+///   |     |                                            |
+///   |     |  +------------------------------------+    |
+///   |     |  | load_conic_gradient_primitives()   |    |
+///   |     +->| load_conic_gradient_stops()        |    |
+///   |     |  | load_conic_gradient_vertices()     |    |
+///   |     |  | draw_conic_gradient_triangles()    |    |
+///   |     |  +------------------------------------+    |
+///   |     |                                            |
+///   |     |  +------------------------------------+    |
+///   |     |  | load_box_shadow_primitives()       |    |
+///   |     +->| load_box_shadow_vertices()         |    |
+///   |     |  | draw_box_shadow_triangles()        |    |
+///   |     |  +------------------------------------+    |
+///   |     |                                            |
+///   |     |  +------------------------------------+    |
+///   |     |  | load_blend_layer_primitive()       |    |
+///   |     +->| load_blend_layer_vertices()        |    |
+///   |     |  | draw_blend_layer_triangles()       |    |
+///   |     |  +------------------------------------+    |
+///   |     |                                            |
+///   |     |  +------------------------------------+    |
+///   |     |  | load_backdrop_filter_primitive()   |    |
+///   |     +->| load_backdrop_filter_vertices()    |    |
+///   |        | draw_backdrop_filter_triangles()   |    |
 ///   |        +------------------------------------+    |
 ///   +---<----------------------------------------------+
 ///
@@ -131,6 +156,33 @@ pub trait GPUBackendImpl {
   /// Load the vertices and indices buffer that `draw_linear_gradient_triangles`
   /// will use.
   fn load_linear_gradient_vertices(&mut self, buffers: &VertexBuffers<LinearGradientPrimIndex>);
+
+  /// Load the primitives that `draw_conic_gradient_triangles` will use.
+  fn load_conic_gradient_primitives(&mut self, primitives: &[ConicGradientPrimitive]);
+  /// Load the gradient color stops that `draw_conic_gradient_triangles` will
+  /// use.
+  fn load_conic_gradient_stops(&mut self, stops: &[GradientStopPrimitive]);
+  /// Load the vertices and indices buffer that `draw_conic_gradient_triangles`
+  /// will use.
+  fn load_conic_gradient_vertices(&mut self, buffers: &VertexBuffers<ConicGradientPrimIndex>);
+
+  /// Load the primitives that `draw_box_shadow_triangles` will use.
+  fn load_box_shadow_primitives(&mut self, primitives: &[BoxShadowPrimitive]);
+  /// Load the vertices and indices buffer that `draw_box_shadow_triangles`
+  /// will use.
+  fn load_box_shadow_vertices(&mut self, buffers: &VertexBuffers<BoxShadowPrimIndex>);
+
+  /// Load the primitive that `draw_blend_layer_triangles` will use.
+  fn load_blend_layer_primitive(&mut self, primitive: &BlendLayerPrimitive);
+  /// Load the vertices and indices buffer that `draw_blend_layer_triangles`
+  /// will use.
+  fn load_blend_layer_vertices(&mut self, buffers: &VertexBuffers<()>);
+
+  /// Load the primitive that `draw_backdrop_filter_triangles` will use.
+  fn load_backdrop_filter_primitive(&mut self, primitive: &BackdropFilterPrimitive);
+  /// Load the vertices and indices buffer that
+  /// `draw_backdrop_filter_triangles` will use.
+  fn load_backdrop_filter_vertices(&mut self, buffers: &VertexBuffers<()>);
   /// Draw pure color triangles in the texture. And use the clear color clear
   /// the texture first if it's a Some-Value
   fn draw_color_triangles(
@@ -153,6 +205,37 @@ pub trait GPUBackendImpl {
     &mut self, texture: &mut Self::Texture, indices: Range<u32>, clear: Option<Color>,
   );
 
+  /// Draw triangles fill with color conic gradient. And use the clear color
+  /// clear the texture first if it's a Some-Value
+  fn draw_conic_gradient_triangles(
+    &mut self, texture: &mut Self::Texture, indices: Range<u32>, clear: Option<Color>,
+  );
+
+  /// Draw triangles fill with the Gaussian-blurred rounded rectangle. And use
+  /// the clear color clear the texture first if it's a Some-Value
+  fn draw_box_shadow_triangles(
+    &mut self, texture: &mut Self::Texture, indices: Range<u32>, clear: Option<Color>,
+  );
+
+  /// Draw triangles fill with a layer texture, the layer is mixed with the
+  /// content of the `texture` by its blend mode.
+  ///
+  /// The `backdrop` is the area of the `texture` that the blend mode reads,
+  /// the implementation should copy it before drawing, so the triangles can
+  /// read the content below them. It's `None` if the blend mode is normal.
+  fn draw_blend_layer_triangles(
+    &mut self, texture: &mut Self::Texture, indices: Range<u32>, backdrop: Option<DeviceRect>,
+  );
+
+  /// Draw triangles fill with the content of the `texture` below them, the
+  /// content is blurred and covered by the tint color.
+  ///
+  /// The `backdrop` is the area of the `texture` that the blur reads, the
+  /// implementation should copy it before drawing.
+  fn draw_backdrop_filter_triangles(
+    &mut self, texture: &mut Self::Texture, indices: Range<u32>, backdrop: DeviceRect,
+  );
+
   fn copy_texture_from_texture(
     &mut self, dist_tex: &mut Self::Texture, copy_to: DevicePoint, from_tex: &Self::Texture,
     from_rect: &DeviceRect,
@@ -176,6 +259,12 @@ pub struct DrawPhaseLimits {
   /// The maximum number of linear gradient primitives that the backend can load
   /// in a single draw
   pub max_linear_gradient_primitives: usize,
+  /// The maximum number of conic gradient primitives that the backend can load
+  /// in a single draw
+  pub max_conic_gradient_primitives: usize,
+  /// The maximum number of box shadow primitives that the backend can load in
+  /// a single draw
+  pub max_box_shadow_primitives: usize,
   /// The maximum number of gradient stops that the backend can load in a single
   /// draw phase
   pub max_gradient_stop_primitives: usize,
@@ -204,6 +293,14 @@ pub struct RadialGradientPrimIndex(u32);
 #[derive(AsBytes, PartialEq, Clone, Copy, Debug)]
 pub struct LinearGradientPrimIndex(u32);
 
+#[repr(packed)]
+#[derive(AsBytes, PartialEq, Clone, Copy, Debug)]
+pub struct ConicGradientPrimIndex(u32);
+
+#[repr(packed)]
+#[derive(AsBytes, PartialEq, Clone, Copy, Debug)]
+pub struct BoxShadowPrimIndex(u32);
+
 #[repr(packed)]
 #[derive(AsBytes, PartialEq, Clone, Copy, Debug)]
 pub struct GradientStopPrimitive {
@@ -263,6 +360,78 @@ pub struct LinearGradientPrimitive {
   pub mask_head_and_spread: i32,
 }
 
+#[repr(packed)]
+#[derive(AsBytes, PartialEq, Clone, Copy, Debug)]
+pub struct ConicGradientPrimitive {
+  /// A 2x3 column-major matrix, transform a vertex position to the position
+  /// relative to the center of the gradient, and rotated so that the start
+  /// angle is at the positive x-axis.
+  pub transform: [f32; 6],
+  /// The color stop information, there are two parts:
+  /// - The high 16-bit index represents the start index of the color stop.
+  /// - The low 16-bit index represents the size of the color stop.
+  pub stop: u32,
+  /// The index of the head mask layer.
+  pub mask_head: i32,
+}
+
+#[repr(packed)]
+#[derive(AsBytes, PartialEq, Clone, Copy, Debug)]
+pub struct BoxShadowPrimitive {
+  /// A 2x3 column-major matrix, transform a vertex position to the position
+  /// relative to the center of the shadow rectangle.
+  pub transform: [f32; 6],
+  /// The half size of the shadow rectangle.
+  pub half_size: [f32; 2],
+  /// The corner radius of the shadow rectangle, in the order of top-left,
+  /// top-right, bottom-right and bottom-left.
+  pub radius: [f32; 4],
+  /// The standard deviation of the Gaussian blur.
+  pub sigma: f32,
+  /// The color of the shadow.
+  pub color: u32,
+  /// The index of the head mask layer.
+  pub mask_head: i32,
+  /// 1 if the shadow is painted outside the rectangle, otherwise 0.
+  pub inset: u32,
+}
+
+#[repr(packed)]
+#[derive(AsBytes, PartialEq, Clone, Copy, Debug)]
+pub struct BlendLayerPrimitive {
+  /// The offset to add to a device position to get its layer texture position.
+  pub layer_offset: [f32; 2],
+  /// The origin of the backdrop area in the device, the backdrop area is
+  /// copied to a texture start from zero.
+  pub backdrop_origin: [f32; 2],
+  /// The index of the head mask layer.
+  pub mask_head: i32,
+  /// The index of the layer texture.
+  pub tex_idx: u32,
+  /// The opacity of the whole layer.
+  pub opacity: f32,
+  /// The blend mode, the value of `BlendMode`.
+  pub blend_mode: u32,
+}
+
+#[repr(packed)]
+#[derive(AsBytes, PartialEq, Clone, Copy, Debug)]
+pub struct BackdropFilterPrimitive {
+  /// The origin of the backdrop area in the device, the backdrop area is
+  /// copied to a texture start from zero.
+  pub backdrop_origin: [f32; 2],
+  /// The size of the backdrop area.
+  pub backdrop_size: [f32; 2],
+  /// The standard deviation of the Gaussian blur.
+  pub sigma: f32,
+  /// The color covers the blurred backdrop.
+  pub tint: u32,
+  /// The index of the head mask layer.
+  pub mask_head: i32,
+  /// The opacity of the filtered backdrop.
+  pub opacity: f32,
+}
+
 #[repr(packed)]
 #[derive(AsBytes, PartialEq, Clone, Copy)]
 pub struct ImgPrimitive {
@@ -273,14 +442,25 @@ pub struct ImgPrimitive {
   pub img_start: [f32; 2],
   /// The size of the image image.
   pub img_size: [f32; 2],
-  /// This represents a mix of two 16-bit indices:
+  /// This represents a mix of two 16-bit values:
   /// - The high 16-bit index represents the head mask layer. It is an i16.
-  /// - The low 16-bit index represents the texture. It is a u16.
+  /// - The low 16-bit, the low 8-bit is the index of the texture, and the high
+  ///   8-bit is the sampling flags, see [`ImgPrimitive::NO_REPEAT_X`],
+  ///   [`ImgPrimitive::NO_REPEAT_Y`] and [`ImgPrimitive::NEAREST`].
   pub mask_head_and_tex_idx: i32,
   /// extra alpha apply to current vertex
   pub opacity: f32,
 }
 
+impl ImgPrimitive {
+  /// The image doesn't repeat in the horizontal direction.
+  pub const NO_REPEAT_X: i32 = 1 << 8;
+  /// The image doesn't repeat in the vertical direction.
+  pub const NO_REPEAT_Y: i32 = 1 << 9;
+  /// Sample the nearest pixel of the image instead of interpolating.
+  pub const NEAREST: i32 = 1 << 10;
+}
+
 /// The mask layer describes an alpha channel layer that is used in the fragment
 /// shader to sample the alpha channel and apply it to the color.
 #[derive(AsBytes, Clone)]
@@ -10,7 +10,11 @@ use ribir_painter::{image::ColorFormat, Color, PixelImage, VertexBuffers};
 
 use self::{
   draw_alpha_triangles_pass::DrawAlphaTrianglesPass,
+  draw_backdrop_filter_pass::DrawBackdropFilterPass,
+  draw_blend_layer_pass::DrawBlendLayerPass,
+  draw_box_shadow_pass::DrawBoxShadowTrianglesPass,
   draw_color_triangles_pass::DrawColorTrianglesPass,
+  draw_conic_gradient_pass::DrawConicGradientTrianglesPass,
   draw_img_triangles_pass::DrawImgTrianglesPass,
   draw_linear_gradient_pass::DrawLinearGradientTrianglesPass,
   draw_radial_gradient_pass::DrawRadialGradientTrianglesPass,
@@ -18,16 +22,22 @@ use self::{
   uniform::Uniform,
 };
 use crate::{
-  gpu_backend::Texture, ColorAttr, DrawPhaseLimits, GPUBackendImpl, GradientStopPrimitive,
-  ImagePrimIndex, ImgPrimitive, LinearGradientPrimIndex, LinearGradientPrimitive, MaskLayer,
-  RadialGradientPrimIndex, RadialGradientPrimitive,
+  gpu_backend::Texture, BackdropFilterPrimitive, BlendLayerPrimitive, BoxShadowPrimIndex,
+  BoxShadowPrimitive, ColorAttr, ConicGradientPrimIndex, ConicGradientPrimitive, DrawPhaseLimits,
+  GPUBackendImpl, GradientStopPrimitive, ImagePrimIndex, ImgPrimitive, LinearGradientPrimIndex,
+  LinearGradientPrimitive, MaskLayer, RadialGradientPrimIndex, RadialGradientPrimitive,
 };
+mod readback_texture;
 mod shaders;
 mod uniform;
 mod vertex_buffer;
 
 mod draw_alpha_triangles_pass;
+mod draw_backdrop_filter_pass;
+mod draw_blend_layer_pass;
+mod draw_box_shadow_pass;
 mod draw_color_triangles_pass;
+mod draw_conic_gradient_pass;
 mod draw_img_triangles_pass;
 mod draw_linear_gradient_pass;
 mod draw_radial_gradient_pass;
@@ -50,6 +60,10 @@ pub struct WgpuImpl {
   img_triangles_pass: Option<DrawImgTrianglesPass>,
   radial_gradient_pass: Option<DrawRadialGradientTrianglesPass>,
   linear_gradient_pass: Option<DrawLinearGradientTrianglesPass>,
+  conic_gradient_pass: Option<DrawConicGradientTrianglesPass>,
+  box_shadow_pass: Option<DrawBoxShadowTrianglesPass>,
+  blend_layer_pass: Option<DrawBlendLayerPass>,
+  backdrop_filter_pass: Option<DrawBackdropFilterPass>,
   texs_layout: wgpu::BindGroupLayout,
   textures_bind: Option<wgpu::BindGroup>,
   mask_layers_uniform: Uniform<MaskLayer>,
@@ -125,6 +139,62 @@ macro_rules! linear_gradient_pass {
   };
 }
 
+macro_rules! conic_gradient_pass {
+  ($backend:ident) => {
+    $backend
+      .conic_gradient_pass
+      .get_or_insert_with(|| {
+        DrawConicGradientTrianglesPass::new(
+          &$backend.device,
+          $backend.mask_layers_uniform.layout(),
+          &$backend.texs_layout,
+          &$backend.limits,
+        )
+      })
+  };
+}
+
+macro_rules! box_shadow_pass {
+  ($backend:ident) => {
+    $backend.box_shadow_pass.get_or_insert_with(|| {
+      DrawBoxShadowTrianglesPass::new(
+        &$backend.device,
+        $backend.mask_layers_uniform.layout(),
+        &$backend.texs_layout,
+        &$backend.limits,
+      )
+    })
+  };
+}
+
+macro_rules! backdrop_filter_pass {
+  ($backend:ident) => {
+    $backend
+      .backdrop_filter_pass
+      .get_or_insert_with(|| {
+        DrawBackdropFilterPass::new(
+          &$backend.device,
+          $backend.mask_layers_uniform.layout(),
+          &$backend.texs_layout,
+          &$backend.limits,
+        )
+      })
+  };
+}
+
+macro_rules! blend_layer_pass {
+  ($backend:ident) => {
+    $backend.blend_layer_pass.get_or_insert_with(|| {
+      DrawBlendLayerPass::new(
+        &$backend.device,
+        $backend.mask_layers_uniform.layout(),
+        &$backend.texs_layout,
+        &$backend.limits,
+      )
+    })
+  };
+}
+
 pub(crate) use command_encoder;
 
 pub struct Surface<'a> {
@@ -216,6 +286,42 @@ impl GPUBackendImpl for WgpuImpl {
     linear_gradient_pass!(self).load_triangles_vertices(buffers, &self.device, &self.queue);
   }
 
+  fn load_conic_gradient_primitives(&mut self, primitives: &[ConicGradientPrimitive]) {
+    conic_gradient_pass!(self).load_conic_gradient_primitives(&self.queue, primitives);
+  }
+
+  fn load_conic_gradient_stops(&mut self, stops: &[GradientStopPrimitive]) {
+    conic_gradient_pass!(self).load_gradient_stops(&self.queue, stops);
+  }
+
+  fn load_conic_gradient_vertices(&mut self, buffers: &VertexBuffers<ConicGradientPrimIndex>) {
+    conic_gradient_pass!(self).load_triangles_vertices(buffers, &self.device, &self.queue);
+  }
+
+  fn load_box_shadow_primitives(&mut self, primitives: &[BoxShadowPrimitive]) {
+    box_shadow_pass!(self).load_box_shadow_primitives(&self.queue, primitives);
+  }
+
+  fn load_box_shadow_vertices(&mut self, buffers: &VertexBuffers<BoxShadowPrimIndex>) {
+    box_shadow_pass!(self).load_triangles_vertices(buffers, &self.device, &self.queue);
+  }
+
+  fn load_blend_layer_primitive(&mut self, primitive: &BlendLayerPrimitive) {
+    blend_layer_pass!(self).load_blend_layer_primitive(primitive);
+  }
+
+  fn load_blend_layer_vertices(&mut self, buffers: &VertexBuffers<()>) {
+    blend_layer_pass!(self).load_triangles_vertices(buffers, &self.device, &self.queue);
+  }
+
+  fn load_backdrop_filter_primitive(&mut self, primitive: &BackdropFilterPrimitive) {
+    backdrop_filter_pass!(self).load_backdrop_filter_primitive(primitive);
+  }
+
+  fn load_backdrop_filter_vertices(&mut self, buffers: &VertexBuffers<()>) {
+    backdrop_filter_pass!(self).load_triangles_vertices(buffers, &self.device, &self.queue);
+  }
+
   fn load_mask_layers(&mut self, layers: &[crate::MaskLayer]) {
     self
       .mask_layers_uniform
@@ -265,6 +371,80 @@ impl GPUBackendImpl for WgpuImpl {
     self.submit()
   }
 
+  fn draw_conic_gradient_triangles(
+    &mut self, texture: &mut Self::Texture, indices: Range<u32>, clear: Option<Color>,
+  ) {
+    let encoder = command_encoder!(self);
+
+    conic_gradient_pass!(self).draw_triangles(
+      texture,
+      indices,
+      clear,
+      &self.device,
+      encoder,
+      self.textures_bind.as_ref().unwrap(),
+      &self.mask_layers_uniform,
+    );
+
+    self.submit()
+  }
+
+  fn draw_box_shadow_triangles(
+    &mut self, texture: &mut Self::Texture, indices: Range<u32>, clear: Option<Color>,
+  ) {
+    let encoder = command_encoder!(self);
+
+    box_shadow_pass!(self).draw_triangles(
+      texture,
+      indices,
+      clear,
+      &self.device,
+      encoder,
+      self.textures_bind.as_ref().unwrap(),
+      &self.mask_layers_uniform,
+    );
+
+    self.submit()
+  }
+
+  fn draw_blend_layer_triangles(
+    &mut self, texture: &mut Self::Texture, indices: Range<u32>, backdrop: Option<DeviceRect>,
+  ) {
+    let encoder = command_encoder!(self);
+
+    blend_layer_pass!(self).draw_triangles(
+      texture,
+      indices,
+      backdrop,
+      &self.device,
+      &self.queue,
+      encoder,
+      self.textures_bind.as_ref().unwrap(),
+      &self.mask_layers_uniform,
+    );
+
+    self.submit()
+  }
+
+  fn draw_backdrop_filter_triangles(
+    &mut self, texture: &mut Self::Texture, indices: Range<u32>, backdrop: DeviceRect,
+  ) {
+    let encoder = command_encoder!(self);
+
+    backdrop_filter_pass!(self).draw_triangles(
+      texture,
+      indices,
+      backdrop,
+      &self.device,
+      &self.queue,
+      encoder,
+      self.textures_bind.as_ref().unwrap(),
+      &self.mask_layers_uniform,
+    );
+
+    self.submit()
+  }
+
   fn draw_alpha_triangles_with_scissor(
     &mut self, indices: &Range<u32>, texture: &mut Self::Texture, scissor: DeviceRect,
   ) {
@@ -534,17 +714,34 @@ impl Texture for WgpuTexture {
 
 impl WgpuImpl {
   /// Create a new instance of `WgpuImpl` with a headless surface.
-  pub async fn headless() -> Self { Self::create(None).await.0 }
+  pub async fn headless() -> Self {
+    Self::create(None)
+      .await
+      .expect("No suitable GPU adapters found on the system!")
+      .0
+  }
 
   /// Create a new instance of `WgpuImpl` with a surface target and also return
   /// the surface.
   pub async fn new<'a>(target: impl Into<wgpu::SurfaceTarget<'a>>) -> (Self, Surface<'a>) {
-    let (gpu_impl, surface) = Self::create(Some(target.into())).await;
-    (gpu_impl, surface.unwrap())
+    Self::try_new(target)
+      .await
+      .expect("No suitable GPU adapters found on the system!")
+  }
+
+  /// Same as `new`, but return `None` if the surface, the adapter or the device
+  /// can't be created, so the caller can fall back to another backend.
+  pub async fn try_new<'a>(
+    target: impl Into<wgpu::SurfaceTarget<'a>>,
+  ) -> Option<(Self, Surface<'a>)> {
+    let (gpu_impl, surface) = Self::create(Some(target.into())).await?;
+    Some((gpu_impl, surface?))
   }
 
   #[allow(clippy::needless_lifetimes)]
-  async fn create<'a>(target: Option<wgpu::SurfaceTarget<'a>>) -> (WgpuImpl, Option<Surface<'a>>) {
+  async fn create<'a>(
+    target: Option<wgpu::SurfaceTarget<'a>>,
+  ) -> Option<(WgpuImpl, Option<Surface<'a>>)> {
     let mut instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
       backends: wgpu::Backends::PRIMARY,
       ..<_>::default()
@@ -565,15 +762,17 @@ impl WgpuImpl {
       });
     }
 
-    let surface = target.map(|t| instance.create_surface(t).unwrap());
+    let surface = match target {
+      Some(t) => Some(instance.create_surface(t).ok()?),
+      None => None,
+    };
     let adapter = instance
       .request_adapter(&wgpu::RequestAdapterOptions {
         power_preference: wgpu::PowerPreference::default(),
         compatible_surface: surface.as_ref(),
         force_fallback_adapter: false,
       })
-      .await
-      .expect("No suitable GPU adapters found on the system!");
+      .await?;
 
     let (device, queue) = adapter
       .request_device(
@@ -585,7 +784,7 @@ impl WgpuImpl {
         None,
       )
       .await
-      .expect("Unable to find a suitable GPU adapter!");
+      .ok()?;
 
     let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
       address_mode_u: wgpu::AddressMode::ClampToEdge,
@@ -617,6 +816,8 @@ impl WgpuImpl {
       max_image_primitives: uniform_bytes / size_of::<ImgPrimitive>(),
       max_radial_gradient_primitives: uniform_bytes / size_of::<RadialGradientPrimitive>(),
       max_linear_gradient_primitives: uniform_bytes / size_of::<LinearGradientPrimitive>(),
+      max_conic_gradient_primitives: uniform_bytes / size_of::<ConicGradientPrimitive>(),
+      max_box_shadow_primitives: uniform_bytes / size_of::<BoxShadowPrimitive>(),
       max_gradient_stop_primitives: uniform_bytes / size_of::<GradientStopPrimitive>(),
       max_mask_layers: uniform_bytes / size_of::<MaskLayer>(),
     };
@@ -638,6 +839,10 @@ impl WgpuImpl {
       img_triangles_pass: None,
       radial_gradient_pass: None,
       linear_gradient_pass: None,
+      conic_gradient_pass: None,
+      box_shadow_pass: None,
+      blend_layer_pass: None,
+      backdrop_filter_pass: None,
       texs_layout,
       textures_bind: None,
       mask_layers_uniform,
@@ -646,15 +851,17 @@ impl WgpuImpl {
 
     let surface = surface.map(|surface| {
       use wgpu::TextureFormat::*;
-      let format = surface
-        .get_capabilities(&adapter)
+      let capabilities = surface.get_capabilities(&adapter);
+      let format = capabilities
         .formats
         .into_iter()
         .find(|&f| f == Rgba8Unorm || f == Bgra8Unorm)
         .expect("No suitable format found for the surface!");
+      // The blend layers need to copy the content of the surface.
+      let copy_src = capabilities.usages & wgpu::TextureUsages::COPY_SRC;
 
       let config = wgpu::SurfaceConfiguration {
-        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | copy_src,
         format,
         width: 0,
         height: 0,
@@ -667,7 +874,7 @@ impl WgpuImpl {
       Surface { surface, config, current_texture: None }
     });
 
-    (gpu_impl, surface)
+    Some((gpu_impl, surface))
   }
 
   pub fn start_capture(&self) { self.device.start_capture(); }
@@ -0,0 +1,244 @@
+use std::{mem::size_of, ops::Range};
+
+use ribir_geom::{DeviceRect, DeviceSize};
+use ribir_painter::{Vertex, VertexBuffers};
+
+use super::{
+  readback_texture::{readback_layout, ReadbackTexture},
+  shaders::backdrop_filter_shader,
+  uniform::Uniform,
+  vertex_buffer::VerticesBuffer,
+};
+use crate::{BackdropFilterPrimitive, DrawPhaseLimits, MaskLayer, WgpuTexture};
+
+/// Draw the backdrop filter by two passes, the first pass blurs the backdrop
+/// horizontally to a texture, and the second pass blurs it vertically and
+/// draws the triangles.
+pub struct DrawBackdropFilterPass {
+  vertices_buffer: VerticesBuffer<()>,
+  blur_pipeline: Option<wgpu::RenderPipeline>,
+  pipeline: Option<wgpu::RenderPipeline>,
+  shader: wgpu::ShaderModule,
+  format: Option<wgpu::TextureFormat>,
+  prim: Option<BackdropFilterPrimitive>,
+  prim_uniform: Uniform<BackdropFilterPrimitive>,
+  backdrop_layout: wgpu::BindGroupLayout,
+  /// A copy of the content below the triangles.
+  backdrop: ReadbackTexture,
+  /// The backdrop blurred horizontally.
+  blurred: ReadbackTexture,
+  layout: wgpu::PipelineLayout,
+}
+
+impl DrawBackdropFilterPass {
+  pub fn new(
+    device: &wgpu::Device, mask_layout: &wgpu::BindGroupLayout,
+    texs_layout: &wgpu::BindGroupLayout, limits: &DrawPhaseLimits,
+  ) -> Self {
+    let vertices_buffer = VerticesBuffer::new(4, 6, device);
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+      label: Some("Backdrop filter shader"),
+      source: wgpu::ShaderSource::Wgsl(backdrop_filter_shader(limits).into()),
+    });
+
+    let prim_uniform = Uniform::new(device, wgpu::ShaderStages::FRAGMENT, 1);
+    let backdrop_layout = readback_layout(device);
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+      label: Some("Backdrop filter pipeline layout"),
+      bind_group_layouts: &[mask_layout, texs_layout, prim_uniform.layout(), &backdrop_layout],
+      push_constant_ranges: &[],
+    });
+    Self {
+      vertices_buffer,
+      blur_pipeline: None,
+      pipeline: None,
+      shader,
+      format: None,
+      prim: None,
+      prim_uniform,
+      backdrop_layout,
+      backdrop: ReadbackTexture::new("Backdrop filter backdrop", wgpu::TextureUsages::empty()),
+      blurred: ReadbackTexture::new(
+        "Backdrop filter blurred",
+        wgpu::TextureUsages::RENDER_ATTACHMENT,
+      ),
+      layout,
+    }
+  }
+
+  pub fn load_triangles_vertices(
+    &mut self, buffers: &VertexBuffers<()>, device: &wgpu::Device, queue: &wgpu::Queue,
+  ) {
+    self
+      .vertices_buffer
+      .write_buffer(buffers, device, queue);
+  }
+
+  pub fn load_backdrop_filter_primitive(&mut self, primitive: &BackdropFilterPrimitive) {
+    self.prim = Some(*primitive);
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  pub fn draw_triangles(
+    &mut self, texture: &WgpuTexture, indices: Range<u32>, backdrop: DeviceRect,
+    device: &wgpu::Device, queue: &wgpu::Queue, encoder: &mut wgpu::CommandEncoder,
+    textures_bind: &wgpu::BindGroup, mask_layer_uniform: &Uniform<MaskLayer>,
+  ) {
+    let format = texture.format();
+    self.update(format, device);
+
+    let mut prim = self
+      .prim
+      .expect("The primitive of the backdrop filter is not loaded.");
+    let layout = &self.backdrop_layout;
+    // The content of the texture can't be read if it's not a copy source, only
+    // the tint is drawn.
+    let readable = ReadbackTexture::readable(texture);
+    let size = if readable {
+      self
+        .backdrop
+        .copy_from(texture, &backdrop, layout, device, encoder);
+      backdrop.size
+    } else {
+      prim.backdrop_size = [1., 1.];
+      DeviceSize::new(1, 1)
+    };
+    self.blurred.prepare(size, format, layout, device);
+    self
+      .prim_uniform
+      .write_buffer(queue, std::slice::from_ref(&prim));
+
+    {
+      let load =
+        if readable { wgpu::LoadOp::Load } else { wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT) };
+      let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some("Backdrop blur render pass"),
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+          view: self.blurred.view(),
+          resolve_target: None,
+          ops: wgpu::Operations { load, store: wgpu::StoreOp::Store },
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+      });
+      if readable {
+        rpass.set_scissor_rect(0, 0, size.width as u32, size.height as u32);
+        rpass.set_bind_group(0, mask_layer_uniform.bind_group(), &[]);
+        rpass.set_bind_group(1, textures_bind, &[]);
+        rpass.set_bind_group(2, self.prim_uniform.bind_group(), &[]);
+        rpass.set_bind_group(3, self.backdrop.bind_group(), &[]);
+        rpass.set_pipeline(self.blur_pipeline.as_ref().unwrap());
+        rpass.draw(0..3, 0..1);
+      }
+    }
+
+    let color_attachments = texture.color_attachments(None);
+    let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+      label: Some("Backdrop filter render pass"),
+      color_attachments: &[Some(color_attachments)],
+      depth_stencil_attachment: None,
+      timestamp_writes: None,
+      occlusion_query_set: None,
+    });
+
+    rpass.set_vertex_buffer(0, self.vertices_buffer.vertices().slice(..));
+    rpass.set_index_buffer(self.vertices_buffer.indices().slice(..), wgpu::IndexFormat::Uint32);
+    rpass.set_bind_group(0, mask_layer_uniform.bind_group(), &[]);
+    rpass.set_bind_group(1, textures_bind, &[]);
+    rpass.set_bind_group(2, self.prim_uniform.bind_group(), &[]);
+    rpass.set_bind_group(3, self.blurred.bind_group(), &[]);
+
+    rpass.set_pipeline(self.pipeline.as_ref().unwrap());
+    rpass.draw_indexed(indices, 0, 0..1);
+  }
+
+  fn update(&mut self, format: wgpu::TextureFormat, device: &wgpu::Device) {
+    if self.format != Some(format) {
+      self.pipeline.take();
+      self.blur_pipeline.take();
+      self.format = Some(format);
+    }
+
+    if self.blur_pipeline.is_none() {
+      let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("Backdrop blur pipeline"),
+        layout: Some(&self.layout),
+        vertex: wgpu::VertexState {
+          module: &self.shader,
+          entry_point: "vs_full",
+          buffers: &[],
+          compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+          module: &self.shader,
+          entry_point: "fs_blur_x",
+          targets: &[Some(wgpu::ColorTargetState {
+            format,
+            blend: None,
+            write_mask: wgpu::ColorWrites::all(),
+          })],
+          compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        multiview: None,
+      });
+      self.blur_pipeline = Some(pipeline);
+    }
+
+    if self.pipeline.is_none() {
+      let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("Backdrop filter pipeline"),
+        layout: Some(&self.layout),
+        vertex: wgpu::VertexState {
+          module: &self.shader,
+          entry_point: "vs_main",
+          buffers: &[wgpu::VertexBufferLayout {
+            array_stride: size_of::<Vertex<()>>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &[
+              // position
+              wgpu::VertexAttribute {
+                offset: 0,
+                shader_location: 0,
+                format: wgpu::VertexFormat::Float32x2,
+              },
+            ],
+          }],
+          compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+          module: &self.shader,
+          entry_point: "fs_main",
+          targets: &[Some(wgpu::ColorTargetState {
+            format,
+            blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+            write_mask: wgpu::ColorWrites::all(),
+          })],
+          compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+          topology: wgpu::PrimitiveTopology::TriangleList,
+          strip_index_format: None,
+          front_face: wgpu::FrontFace::Ccw,
+          // Always draw rect with transform, there is no distinction between front and back,
+          // everything needs to be drawn.
+          cull_mode: None,
+          unclipped_depth: false,
+          polygon_mode: wgpu::PolygonMode::Fill,
+          conservative: false,
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState {
+          count: 1,
+          mask: !0,
+          alpha_to_coverage_enabled: false,
+        },
+        multiview: None,
+      });
+      self.pipeline = Some(pipeline);
+    }
+  }
+}
@@ -0,0 +1,182 @@
+use std::{mem::size_of, ops::Range};
+
+use ribir_geom::{DeviceRect, DeviceSize};
+use ribir_painter::{Vertex, VertexBuffers};
+
+use super::{
+  readback_texture::{readback_layout, ReadbackTexture},
+  shaders::blend_layer_shader,
+  uniform::Uniform,
+  vertex_buffer::VerticesBuffer,
+};
+use crate::{BlendLayerPrimitive, DrawPhaseLimits, MaskLayer, WgpuTexture};
+
+pub struct DrawBlendLayerPass {
+  vertices_buffer: VerticesBuffer<()>,
+  pipeline: Option<wgpu::RenderPipeline>,
+  shader: wgpu::ShaderModule,
+  format: Option<wgpu::TextureFormat>,
+  prim: Option<BlendLayerPrimitive>,
+  prim_uniform: Uniform<BlendLayerPrimitive>,
+  backdrop_layout: wgpu::BindGroupLayout,
+  /// A copy of the content below the layer, it has the same format as the
+  /// target texture.
+  backdrop: ReadbackTexture,
+  layout: wgpu::PipelineLayout,
+}
+
+impl DrawBlendLayerPass {
+  pub fn new(
+    device: &wgpu::Device, mask_layout: &wgpu::BindGroupLayout,
+    texs_layout: &wgpu::BindGroupLayout, limits: &DrawPhaseLimits,
+  ) -> Self {
+    let vertices_buffer = VerticesBuffer::new(4, 6, device);
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+      label: Some("Blend layer shader"),
+      source: wgpu::ShaderSource::Wgsl(blend_layer_shader(limits).into()),
+    });
+
+    let prim_uniform = Uniform::new(device, wgpu::ShaderStages::FRAGMENT, 1);
+    let backdrop_layout = readback_layout(device);
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+      label: Some("Blend layer pipeline layout"),
+      bind_group_layouts: &[mask_layout, texs_layout, prim_uniform.layout(), &backdrop_layout],
+      push_constant_ranges: &[],
+    });
+    Self {
+      vertices_buffer,
+      pipeline: None,
+      shader,
+      format: None,
+      prim: None,
+      prim_uniform,
+      backdrop_layout,
+      backdrop: ReadbackTexture::new("Blend layer backdrop", wgpu::TextureUsages::empty()),
+      layout,
+    }
+  }
+
+  pub fn load_triangles_vertices(
+    &mut self, buffers: &VertexBuffers<()>, device: &wgpu::Device, queue: &wgpu::Queue,
+  ) {
+    self
+      .vertices_buffer
+      .write_buffer(buffers, device, queue);
+  }
+
+  pub fn load_blend_layer_primitive(&mut self, primitive: &BlendLayerPrimitive) {
+    self.prim = Some(*primitive);
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  pub fn draw_triangles(
+    &mut self, texture: &WgpuTexture, indices: Range<u32>, backdrop: Option<DeviceRect>,
+    device: &wgpu::Device, queue: &wgpu::Queue, encoder: &mut wgpu::CommandEncoder,
+    textures_bind: &wgpu::BindGroup, mask_layer_uniform: &Uniform<MaskLayer>,
+  ) {
+    self.update(texture.format(), device);
+
+    let mut prim = self
+      .prim
+      .expect("The primitive of the blend layer is not loaded.");
+    // The content of the texture can't be read if it's not a copy source, the
+    // layer is drawn as normal.
+    let backdrop = backdrop.filter(|_| ReadbackTexture::readable(texture));
+    let layout = &self.backdrop_layout;
+    match backdrop {
+      Some(rect) => self
+        .backdrop
+        .copy_from(texture, &rect, layout, device, encoder),
+      None => {
+        prim.blend_mode = 0;
+        let size = DeviceSize::new(1, 1);
+        self
+          .backdrop
+          .prepare(size, texture.format(), layout, device);
+      }
+    }
+    self
+      .prim_uniform
+      .write_buffer(queue, std::slice::from_ref(&prim));
+
+    let pipeline = self.pipeline.as_ref().unwrap();
+    let color_attachments = texture.color_attachments(None);
+    let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+      label: Some("Blend layer render pass"),
+      color_attachments: &[Some(color_attachments)],
+      depth_stencil_attachment: None,
+      timestamp_writes: None,
+      occlusion_query_set: None,
+    });
+
+    rpass.set_vertex_buffer(0, self.vertices_buffer.vertices().slice(..));
+    rpass.set_index_buffer(self.vertices_buffer.indices().slice(..), wgpu::IndexFormat::Uint32);
+    rpass.set_bind_group(0, mask_layer_uniform.bind_group(), &[]);
+    rpass.set_bind_group(1, textures_bind, &[]);
+    rpass.set_bind_group(2, self.prim_uniform.bind_group(), &[]);
+    rpass.set_bind_group(3, self.backdrop.bind_group(), &[]);
+
+    rpass.set_pipeline(pipeline);
+    rpass.draw_indexed(indices, 0, 0..1);
+  }
+
+  fn update(&mut self, format: wgpu::TextureFormat, device: &wgpu::Device) {
+    if self.format != Some(format) {
+      self.pipeline.take();
+      self.format = Some(format);
+    }
+
+    if self.pipeline.is_none() {
+      let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("Blend layer pipeline"),
+        layout: Some(&self.layout),
+        vertex: wgpu::VertexState {
+          module: &self.shader,
+          entry_point: "vs_main",
+          buffers: &[wgpu::VertexBufferLayout {
+            array_stride: size_of::<Vertex<()>>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &[
+              // position
+              wgpu::VertexAttribute {
+                offset: 0,
+                shader_location: 0,
+                format: wgpu::VertexFormat::Float32x2,
+              },
+            ],
+          }],
+          compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+          module: &self.shader,
+          entry_point: "fs_main",
+          targets: &[Some(wgpu::ColorTargetState {
+            format,
+            blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+            write_mask: wgpu::ColorWrites::all(),
+          })],
+          compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+          topology: wgpu::PrimitiveTopology::TriangleList,
+          strip_index_format: None,
+          front_face: wgpu::FrontFace::Ccw,
+          // Always draw rect with transform, there is no distinction between front and back,
+          // everything needs to be drawn.
+          cull_mode: None,
+          unclipped_depth: false,
+          polygon_mode: wgpu::PolygonMode::Fill,
+          conservative: false,
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState {
+          count: 1,
+          mask: !0,
+          alpha_to_coverage_enabled: false,
+        },
+        multiview: None,
+      });
+      self.pipeline = Some(pipeline);
+    }
+  }
+}
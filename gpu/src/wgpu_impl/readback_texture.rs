@@ -0,0 +1,122 @@
+use ribir_geom::{DeviceRect, DeviceSize};
+
+use crate::WgpuTexture;
+
+/// A texture to hold the content read back from a target texture, so the
+/// draw can read the content below it. It's reused and only grows.
+pub struct ReadbackTexture {
+  label: &'static str,
+  usage: wgpu::TextureUsages,
+  texture: Option<(wgpu::Texture, wgpu::TextureView, wgpu::BindGroup)>,
+}
+
+impl ReadbackTexture {
+  pub fn new(label: &'static str, usage: wgpu::TextureUsages) -> Self {
+    let usage = usage | wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::TEXTURE_BINDING;
+    Self { label, usage, texture: None }
+  }
+
+  /// Return if the content of `texture` can be read back.
+  pub fn readable(texture: &WgpuTexture) -> bool {
+    texture
+      .inner_tex
+      .texture()
+      .usage()
+      .contains(wgpu::TextureUsages::COPY_SRC)
+  }
+
+  /// Copy the `rect` of the `from` texture to the origin of this texture.
+  pub fn copy_from(
+    &mut self, from: &WgpuTexture, rect: &DeviceRect, layout: &wgpu::BindGroupLayout,
+    device: &wgpu::Device, encoder: &mut wgpu::CommandEncoder,
+  ) {
+    self.prepare(rect.size, from.format(), layout, device);
+    encoder.copy_texture_to_texture(
+      wgpu::ImageCopyTexture {
+        texture: from.inner_tex.texture(),
+        mip_level: 0,
+        origin: wgpu::Origin3d { x: rect.min_x() as u32, y: rect.min_y() as u32, z: 0 },
+        aspect: wgpu::TextureAspect::All,
+      },
+      wgpu::ImageCopyTexture {
+        texture: self.texture(),
+        mip_level: 0,
+        origin: wgpu::Origin3d::ZERO,
+        aspect: wgpu::TextureAspect::All,
+      },
+      wgpu::Extent3d {
+        width: rect.width() as u32,
+        height: rect.height() as u32,
+        depth_or_array_layers: 1,
+      },
+    );
+  }
+
+  /// Ensure the texture is large enough to hold `size` and has the `format`.
+  pub fn prepare(
+    &mut self, size: DeviceSize, format: wgpu::TextureFormat, layout: &wgpu::BindGroupLayout,
+    device: &wgpu::Device,
+  ) {
+    let reuse = self.texture.as_ref().is_some_and(|(tex, ..)| {
+      tex.format() == format
+        && tex.width() >= size.width as u32
+        && tex.height() >= size.height as u32
+    });
+    if reuse {
+      return;
+    }
+
+    let (width, height) = self
+      .texture
+      .as_ref()
+      .filter(|(tex, ..)| tex.format() == format)
+      .map_or((1, 1), |(tex, ..)| (tex.width(), tex.height()));
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+      label: Some(self.label),
+      size: wgpu::Extent3d {
+        width: width.max(size.width as u32),
+        height: height.max(size.height as u32),
+        depth_or_array_layers: 1,
+      },
+      mip_level_count: 1,
+      sample_count: 1,
+      dimension: wgpu::TextureDimension::D2,
+      format,
+      usage: self.usage,
+      view_formats: &[],
+    });
+    let view = texture.create_view(&<_>::default());
+    let bind = device.create_bind_group(&wgpu::BindGroupDescriptor {
+      label: Some(self.label),
+      layout,
+      entries: &[wgpu::BindGroupEntry {
+        binding: 0,
+        resource: wgpu::BindingResource::TextureView(&view),
+      }],
+    });
+    self.texture = Some((texture, view, bind));
+  }
+
+  pub fn texture(&self) -> &wgpu::Texture { &self.texture.as_ref().unwrap().0 }
+
+  pub fn view(&self) -> &wgpu::TextureView { &self.texture.as_ref().unwrap().1 }
+
+  pub fn bind_group(&self) -> &wgpu::BindGroup { &self.texture.as_ref().unwrap().2 }
+}
+
+/// The layout of a bind group that only has the read back texture.
+pub fn readback_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+  device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+    label: Some("Readback texture layout"),
+    entries: &[wgpu::BindGroupLayoutEntry {
+      binding: 0,
+      visibility: wgpu::ShaderStages::FRAGMENT,
+      ty: wgpu::BindingType::Texture {
+        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+        view_dimension: wgpu::TextureViewDimension::D2,
+        multisampled: false,
+      },
+      count: None,
+    }],
+  })
+}
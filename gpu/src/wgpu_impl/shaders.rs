@@ -326,6 +326,487 @@ fn fs_main(input: FragInput) -> @location(0) vec4<f32> {
 "#
 }
 
+pub fn conic_gradient_shader(limits: &DrawPhaseLimits) -> String {
+  basic_template(limits.max_mask_layers)
+    + &format!(
+      r#"
+@group(2) @binding(0)
+var<uniform> prims: array<Primitive, {}>;
+
+@group(3) @binding(0)
+var<uniform> stops: array<StopPair, {}>;"#,
+      limits.max_conic_gradient_primitives,
+      limits.max_gradient_stop_primitives / 2,
+    )
+    + r#"
+struct Vertex {
+  @location(0) pos: vec2<f32>,
+  @location(1) @interpolate(flat) prim_idx: u32,
+};
+
+struct FragInput {
+  @builtin(position) pos: vec4<f32>,
+  @location(0) @interpolate(flat) prim_idx: u32,
+}
+
+@vertex
+fn vs_main(v: Vertex) -> FragInput {
+    var input: FragInput;
+    // convert from gpu-backend coords(0..1) to wgpu corrds(-1..1)
+    let pos = v.pos * vec2(2., -2.) + vec2(-1., 1.);
+    input.pos = vec4<f32>(pos, 0.0, 1.0);
+    input.prim_idx = v.prim_idx;
+    return input;
+}
+
+// A pair of stops. This arrangement aligns the stops with 16 bytes, minimizing excessive padding.
+struct StopPair {
+    color1: u32,
+    offset1: f32,
+    color2: u32,
+    offset2: f32,
+}
+
+struct Stop {
+    color: vec4<f32>,
+    offset: f32,
+}
+
+// Since a the different alignment between WebGPU and WebGL, we not use 
+// mat3x2<f32> in the struct, but use vec2<f32> instead. Then, we compose it.
+struct Primitive {
+  t0: vec2<f32>,
+  t1: vec2<f32>,
+  t2: vec2<f32>,
+  // A value mixed stop_start(u16) and stop_cnt(u16)
+  stop: u32,
+  mask_head: i32
+}
+
+fn unpackUnorm4x8(color: u32) -> vec4<f32> {
+    return vec4<f32>(
+        f32((color & 0xff000000) >> 24) / 255.0,
+        f32((color & 0x00ff0000) >> 16) / 255.0,
+        f32((color & 0x0000ff00) >> 8) / 255.0,
+        f32((color & 0x000000ff) >> 0) / 255.0
+    );
+}
+
+fn get_stop(idx: u32) -> Stop {
+    let pair = stops[idx / 2];
+    if idx % 2 == 0 {
+        return Stop(unpackUnorm4x8(pair.color1), pair.offset1);
+    } else {
+        return Stop(unpackUnorm4x8(pair.color2), pair.offset2);
+    }
+}
+
+@fragment
+fn fs_main(input: FragInput) -> @location(0) vec4<f32> {
+    let prim = prims[input.prim_idx];
+    // The position relative to the center, and the start angle is rotated to
+    // the positive x-axis.
+    let pos = mat3x2(prim.t0, prim.t1, prim.t2) * vec3(input.pos.xy, 1.);
+
+    var alpha = 1.;
+    var mask_idx = prim.mask_head;
+    loop {
+        if mask_idx < 0 { break; }
+
+        let mask = mask_layers[u32(mask_idx)];
+        alpha *= mask_sample(mask, input.pos.xy);
+        mask_idx = mask.prev_mask_idx;
+    }
+
+    // A full turn maps to the offset from 0 to 1, clockwise in the y-down axis.
+    var offset = fract(atan2(pos.y, pos.x) / 6.283185307179586);
+
+    let stop_start = prim.stop >> 16;
+    let stop_cnt = prim.stop & 0x0000ffff;
+    var prev = get_stop(stop_start);
+    if stop_cnt < 2 {
+        return prev.color * vec4<f32>(1., 1., 1., alpha);
+    }
+    var next = get_stop(stop_start + 1);
+    for (var i = 2u; i < stop_cnt && next.offset < offset; i++) {
+        prev = next;
+        next = get_stop(stop_start + i);
+    }
+
+    offset = max(prev.offset, min(next.offset, offset));
+    var weight2 = 1.;
+    if next.offset > prev.offset {
+        weight2 = (offset - prev.offset) / (next.offset - prev.offset);
+    }
+    return mix(prev.color, next.color, weight2) * vec4<f32>(1., 1., 1., alpha);
+}
+"#
+}
+
+pub fn box_shadow_shader(limits: &DrawPhaseLimits) -> String {
+  basic_template(limits.max_mask_layers)
+    + &format!(
+      r#"
+@group(2) @binding(0)
+var<uniform> prims: array<Primitive, {}>;"#,
+      limits.max_box_shadow_primitives,
+    )
+    + r#"
+struct Vertex {
+  @location(0) pos: vec2<f32>,
+  @location(1) @interpolate(flat) prim_idx: u32,
+};
+
+struct FragInput {
+  @builtin(position) pos: vec4<f32>,
+  @location(0) @interpolate(flat) prim_idx: u32,
+}
+
+@vertex
+fn vs_main(v: Vertex) -> FragInput {
+    var input: FragInput;
+    // convert from gpu-backend coords(0..1) to wgpu corrds(-1..1)
+    let pos = v.pos * vec2(2., -2.) + vec2(-1., 1.);
+    input.pos = vec4<f32>(pos, 0.0, 1.0);
+    input.prim_idx = v.prim_idx;
+    return input;
+}
+
+// Since a the different alignment between WebGPU and WebGL, we not use 
+// mat3x2<f32> in the struct, but use vec2<f32> instead. Then, we compose it.
+struct Primitive {
+  t0: vec2<f32>,
+  t1: vec2<f32>,
+  t2: vec2<f32>,
+  half_size: vec2<f32>,
+  radius_top_left: f32,
+  radius_top_right: f32,
+  radius_bottom_right: f32,
+  radius_bottom_left: f32,
+  sigma: f32,
+  color: u32,
+  mask_head: i32,
+  inset: u32,
+}
+
+fn unpackUnorm4x8(color: u32) -> vec4<f32> {
+    return vec4<f32>(
+        f32((color & 0xff000000) >> 24) / 255.0,
+        f32((color & 0x00ff0000) >> 16) / 255.0,
+        f32((color & 0x0000ff00) >> 8) / 255.0,
+        f32((color & 0x000000ff) >> 0) / 255.0
+    );
+}
+
+fn gaussian(x: f32, sigma: f32) -> f32 {
+    let pi = 3.141592653589793;
+    return exp(-(x * x) / (2. * sigma * sigma)) / (sqrt(2. * pi) * sigma);
+}
+
+// An approximation of the error function.
+fn erf(x: vec2<f32>) -> vec2<f32> {
+    let s = sign(x);
+    let a = abs(x);
+    var t = 1. + (0.278393 + (0.230389 + 0.078108 * (a * a)) * a) * a;
+    t *= t;
+    return s - s / (t * t);
+}
+
+// The blurred shadow along the x-axis has a closed-form solution.
+fn shadow_x(x: f32, y: f32, sigma: f32, corner: f32, half_size: vec2<f32>) -> f32 {
+    let delta = min(half_size.y - corner - abs(y), 0.);
+    let curved = half_size.x - corner + sqrt(max(0., corner * corner - delta * delta));
+    let integral = 0.5 + 0.5 * erf((x + vec2(-curved, curved)) * (sqrt(0.5) / sigma));
+    return integral.y - integral.x;
+}
+
+// The coverage of a rounded rectangle blurred by the Gaussian function, the
+// y-axis is integrated by a few samples, see
+// https://madebyevan.com/shaders/fast-rounded-rectangle-shadows/
+fn shadow_coverage(prim: Primitive, pos: vec2<f32>) -> f32 {
+    let sigma = max(prim.sigma, 0.01);
+    let half_size = prim.half_size;
+    var corner = prim.radius_bottom_right;
+    if pos.x < 0. && pos.y < 0. {
+        corner = prim.radius_top_left;
+    } else if pos.y < 0. {
+        corner = prim.radius_top_right;
+    } else if pos.x < 0. {
+        corner = prim.radius_bottom_left;
+    }
+    corner = min(corner, min(half_size.x, half_size.y));
+
+    let low = pos.y - half_size.y;
+    let high = pos.y + half_size.y;
+    let start = clamp(-3. * sigma, low, high);
+    let end = clamp(3. * sigma, low, high);
+    let step = (end - start) / 4.;
+    var y = start + step * 0.5;
+    var value = 0.;
+    for (var i = 0; i < 4; i++) {
+        value += shadow_x(pos.x, pos.y - y, sigma, corner, half_size) * gaussian(y, sigma) * step;
+        y += step;
+    }
+    return clamp(value, 0., 1.);
+}
+
+@fragment
+fn fs_main(input: FragInput) -> @location(0) vec4<f32> {
+    let prim = prims[input.prim_idx];
+    let pos = mat3x2(prim.t0, prim.t1, prim.t2) * vec3(input.pos.xy, 1.);
+
+    var alpha = 1.;
+    var mask_idx = prim.mask_head;
+    loop {
+        if mask_idx < 0 { break; }
+
+        let mask = mask_layers[u32(mask_idx)];
+        alpha *= mask_sample(mask, input.pos.xy);
+        mask_idx = mask.prev_mask_idx;
+    }
+
+    var coverage = shadow_coverage(prim, pos);
+    if prim.inset != 0u {
+        coverage = 1. - coverage;
+    }
+    let color = unpackUnorm4x8(prim.color);
+    return vec4<f32>(color.rgb, color.a * coverage * alpha);
+}
+"#
+}
+
+pub fn blend_layer_shader(limits: &DrawPhaseLimits) -> String {
+  basic_template(limits.max_mask_layers)
+    + r#"
+@group(2) @binding(0)
+var<uniform> prim: Primitive;
+
+@group(3) @binding(0)
+var backdrop: texture_2d<f32>;
+
+struct Vertex {
+  @location(0) pos: vec2<f32>,
+};
+
+struct FragInput {
+  @builtin(position) pos: vec4<f32>,
+}
+
+@vertex
+fn vs_main(v: Vertex) -> FragInput {
+    var input: FragInput;
+    // convert from gpu-backend coords(0..1) to wgpu corrds(-1..1)
+    let pos = v.pos * vec2(2., -2.) + vec2(-1., 1.);
+    input.pos = vec4<f32>(pos, 0.0, 1.0);
+    return input;
+}
+
+struct Primitive {
+  layer_offset: vec2<f32>,
+  backdrop_origin: vec2<f32>,
+  mask_head: i32,
+  tex_idx: u32,
+  opacity: f32,
+  // The same order as the `BlendMode` of the painter.
+  blend_mode: u32,
+}
+
+fn layer_sample(pos: vec2<i32>) -> vec4<f32> {
+    switch prim.tex_idx {
+      case 0u: { return textureLoad(tex_0, pos, 0); }
+      case 1u: { return textureLoad(tex_1, pos, 0); }
+      case 2u: { return textureLoad(tex_2, pos, 0); }
+      case 3u: { return textureLoad(tex_3, pos, 0); }
+      case 4u: { return textureLoad(tex_4, pos, 0); }
+      case 5u: { return textureLoad(tex_5, pos, 0); }
+      case 6u: { return textureLoad(tex_6, pos, 0); }
+      case 7u: { return textureLoad(tex_7, pos, 0); }
+      // should not happen, use a red color to indicate error
+      default: { return vec4<f32>(1., 0., 0., 1.); }
+    }
+}
+
+// The layer and the backdrop are drawn from transparent, their colors are
+// premultiplied.
+fn unpremultiply(color: vec4<f32>) -> vec4<f32> {
+    if color.a <= 0. {
+        return color;
+    }
+    return vec4<f32>(min(color.rgb / color.a, vec3<f32>(1.)), color.a);
+}
+
+fn screen(cb: vec3<f32>, cs: vec3<f32>) -> vec3<f32> {
+    return cb + cs - cb * cs;
+}
+
+fn hard_light(cb: vec3<f32>, cs: vec3<f32>) -> vec3<f32> {
+    return select(screen(cb, 2. * cs - 1.), cb * 2. * cs, cs <= vec3<f32>(0.5));
+}
+
+// The separable blend functions of the W3C compositing spec.
+fn blend_fn(cb: vec3<f32>, cs: vec3<f32>) -> vec3<f32> {
+    let zero = vec3<f32>(0.);
+    let one = vec3<f32>(1.);
+    switch prim.blend_mode {
+      case 1u: { return cb * cs; }
+      case 2u: { return screen(cb, cs); }
+      case 3u: { return hard_light(cs, cb); }
+      case 4u: { return min(cb, cs); }
+      case 5u: { return max(cb, cs); }
+      case 6u: {
+        let dodge = min(one, cb / max(one - cs, vec3<f32>(1e-6)));
+        return select(select(dodge, one, cs >= one), zero, cb <= zero);
+      }
+      case 7u: {
+        let burn = one - min(one, (one - cb) / max(cs, vec3<f32>(1e-6)));
+        return select(select(burn, zero, cs <= zero), one, cb >= one);
+      }
+      case 8u: { return hard_light(cb, cs); }
+      case 9u: {
+        let d = select(sqrt(cb), ((16. * cb - 12.) * cb + 4.) * cb, cb <= vec3<f32>(0.25));
+        let light = cb + (2. * cs - 1.) * (d - cb);
+        let dark = cb - (1. - 2. * cs) * cb * (1. - cb);
+        return select(light, dark, cs <= vec3<f32>(0.5));
+      }
+      case 10u: { return abs(cb - cs); }
+      case 11u: { return cb + cs - 2. * cb * cs; }
+      default: { return cs; }
+    }
+}
+
+@fragment
+fn fs_main(input: FragInput) -> @location(0) vec4<f32> {
+    let layer_pos = vec2<i32>(floor(input.pos.xy + prim.layer_offset));
+    var color = unpremultiply(layer_sample(layer_pos));
+    if prim.blend_mode != 0u {
+        let backdrop_pos = vec2<i32>(floor(input.pos.xy - prim.backdrop_origin));
+        let dst = unpremultiply(textureLoad(backdrop, backdrop_pos, 0));
+        let mixed = (1. - dst.a) * color.rgb + dst.a * blend_fn(dst.rgb, color.rgb);
+        color = vec4<f32>(mixed, color.a);
+    }
+
+    var alpha = prim.opacity;
+    var mask_idx = prim.mask_head;
+    loop {
+        if mask_idx < 0 { break; }
+
+        let mask = mask_layers[u32(mask_idx)];
+        alpha *= mask_sample(mask, input.pos.xy);
+        mask_idx = mask.prev_mask_idx;
+    }
+
+    return vec4<f32>(color.rgb, color.a * alpha);
+}
+"#
+}
+
+pub fn backdrop_filter_shader(limits: &DrawPhaseLimits) -> String {
+  basic_template(limits.max_mask_layers)
+    + r#"
+@group(2) @binding(0)
+var<uniform> prim: Primitive;
+
+@group(3) @binding(0)
+var backdrop: texture_2d<f32>;
+
+struct Vertex {
+  @location(0) pos: vec2<f32>,
+};
+
+struct FragInput {
+  @builtin(position) pos: vec4<f32>,
+}
+
+@vertex
+fn vs_main(v: Vertex) -> FragInput {
+    var input: FragInput;
+    // convert from gpu-backend coords(0..1) to wgpu corrds(-1..1)
+    let pos = v.pos * vec2(2., -2.) + vec2(-1., 1.);
+    input.pos = vec4<f32>(pos, 0.0, 1.0);
+    return input;
+}
+
+// A triangle covers the whole target, the horizontal blur draws to a texture
+// that the backdrop area starts from zero.
+@vertex
+fn vs_full(@builtin(vertex_index) idx: u32) -> FragInput {
+    var input: FragInput;
+    let uv = vec2<f32>(f32((idx << 1u) & 2u), f32(idx & 2u));
+    input.pos = vec4<f32>(uv * vec2(2., -2.) + vec2(-1., 1.), 0.0, 1.0);
+    return input;
+}
+
+struct Primitive {
+  backdrop_origin: vec2<f32>,
+  backdrop_size: vec2<f32>,
+  sigma: f32,
+  tint: u32,
+  mask_head: i32,
+  opacity: f32,
+}
+
+fn unpackUnorm4x8(color: u32) -> vec4<f32> {
+    return vec4<f32>(
+        f32((color & 0xff000000) >> 24) / 255.0,
+        f32((color & 0x00ff0000) >> 16) / 255.0,
+        f32((color & 0x0000ff00) >> 8) / 255.0,
+        f32((color & 0x000000ff) >> 0) / 255.0
+    );
+}
+
+// Blur the backdrop along the `dir`, the samples out of the backdrop area use
+// the color of the edge.
+fn blur(pos: vec2<i32>, dir: vec2<i32>) -> vec4<f32> {
+    let max_pos = vec2<i32>(prim.backdrop_size) - 1;
+    if prim.sigma <= 0. {
+        return textureLoad(backdrop, clamp(pos, vec2<i32>(0), max_pos), 0);
+    }
+    // The Gaussian blur is almost invisible beyond three times of sigma, and
+    // the samples are limited, the same as the CPU backend.
+    let radius = min(i32(ceil(3. * prim.sigma)), 96);
+    var sum = vec4<f32>(0.);
+    var weights = 0.;
+    for (var i = -radius; i <= radius; i++) {
+        let p = clamp(pos + dir * i, vec2<i32>(0), max_pos);
+        let w = exp(-f32(i * i) / (2. * prim.sigma * prim.sigma));
+        sum += textureLoad(backdrop, p, 0) * w;
+        weights += w;
+    }
+    return sum / weights;
+}
+
+@fragment
+fn fs_blur_x(input: FragInput) -> @location(0) vec4<f32> {
+    return blur(vec2<i32>(floor(input.pos.xy)), vec2<i32>(1, 0));
+}
+
+@fragment
+fn fs_main(input: FragInput) -> @location(0) vec4<f32> {
+    let pos = vec2<i32>(floor(input.pos.xy - prim.backdrop_origin));
+    // The backdrop is premultiplied, the tint covers it.
+    let blurred = blur(pos, vec2<i32>(0, 1));
+    let tint = unpackUnorm4x8(prim.tint);
+    let a = tint.a + blurred.a * (1. - tint.a);
+    if a <= 0. {
+        discard;
+    }
+    let rgb = (tint.rgb * tint.a + blurred.rgb * (1. - tint.a)) / a;
+
+    var alpha = prim.opacity;
+    var mask_idx = prim.mask_head;
+    loop {
+        if mask_idx < 0 { break; }
+
+        let mask = mask_layers[u32(mask_idx)];
+        alpha *= mask_sample(mask, input.pos.xy);
+        mask_idx = mask.prev_mask_idx;
+    }
+
+    return vec4<f32>(rgb, a * alpha);
+}
+"#
+}
+
 pub fn color_triangles_shader(max_mask_layers: usize) -> String {
   basic_template(max_mask_layers)
     + r#"
@@ -399,7 +880,8 @@ pub fn img_triangles_shader(limits: &DrawPhaseLimits) -> String {
     img_size: vec2<f32>,
     /// This is a mix field,
     /// - the high 16 bits is the index of head mask layer, as a i16 type.
-    /// - the low 16 bits is the index of texture, as a u16 type.
+    /// - the low 8 bits is the index of texture, and the 8 bits above it are
+    ///   the sampling flags.
     mask_head_and_tex_idx: i32,
     /// extra alpha apply to current vertex
     opacity: f32,
@@ -426,8 +908,19 @@ pub fn img_triangles_shader(limits: &DrawPhaseLimits) -> String {
   fn fs_main(f: VertexOutput) -> @location(0) vec4<f32> {
       let prim = primtives[f.prim_idx];
       let pos = mat3x2(prim.t0, prim.t1, prim.t2) * f.pos.xyz;
-      var img_pos = pos.xy % prim.img_size + prim.img_start;
-      var color = img_sample(prim, img_pos);
+      let flags = prim.mask_head_and_tex_idx & 0x0000FF00;
+      // Wrap the position in the direction that repeats, and discard the
+      // position out of the image in the other directions.
+      var img_pos = pos.xy - floor(pos.xy / prim.img_size) * prim.img_size;
+      if (flags & 0x100) != 0 {
+          if pos.x < 0. || pos.x >= prim.img_size.x { discard; }
+          img_pos.x = pos.x;
+      }
+      if (flags & 0x200) != 0 {
+          if pos.y < 0. || pos.y >= prim.img_size.y { discard; }
+          img_pos.y = pos.y;
+      }
+      var color = img_sample(prim, img_pos + prim.img_start, (flags & 0x400) != 0);
   
       var mask_idx = prim.mask_head_and_tex_idx >> 16 ;
       var alpha = 1.0;
@@ -443,22 +936,25 @@ pub fn img_triangles_shader(limits: &DrawPhaseLimits) -> String {
       return color;
   }
   
-  fn img_sample(prim: ImgPrimitive, pos: vec2<f32>) -> vec4<f32> {
-      switch abs(prim.mask_head_and_tex_idx & 0x0000FFFF) {
-        case 0: { return img_tex_smaple(tex_0, prim, pos); }
-        case 1: { return img_tex_smaple(tex_1, prim, pos); }
-        case 2: { return img_tex_smaple(tex_2, prim, pos); }
-        case 3: { return img_tex_smaple(tex_3, prim, pos); }
-        case 4: { return img_tex_smaple(tex_4, prim, pos); }
-        case 5: { return img_tex_smaple(tex_5, prim, pos); }
-        case 6: { return img_tex_smaple(tex_6, prim, pos); }
-        case 7: { return img_tex_smaple(tex_7, prim, pos); }
+  fn img_sample(prim: ImgPrimitive, pos: vec2<f32>, nearest: bool) -> vec4<f32> {
+      switch prim.mask_head_and_tex_idx & 0x000000FF {
+        case 0: { return img_tex_smaple(tex_0, prim, pos, nearest); }
+        case 1: { return img_tex_smaple(tex_1, prim, pos, nearest); }
+        case 2: { return img_tex_smaple(tex_2, prim, pos, nearest); }
+        case 3: { return img_tex_smaple(tex_3, prim, pos, nearest); }
+        case 4: { return img_tex_smaple(tex_4, prim, pos, nearest); }
+        case 5: { return img_tex_smaple(tex_5, prim, pos, nearest); }
+        case 6: { return img_tex_smaple(tex_6, prim, pos, nearest); }
+        case 7: { return img_tex_smaple(tex_7, prim, pos, nearest); }
         // should not happen, use a red color to indicate error
         default: { return vec4<f32>(1., 0., 0., 1.); }
     };
   }
   
-  fn img_tex_smaple(tex: texture_2d<f32>, prim: ImgPrimitive, pos: vec2<f32>) -> vec4<f32> {
+  fn img_tex_smaple(tex: texture_2d<f32>, prim: ImgPrimitive, pos: vec2<f32>, nearest: bool) -> vec4<f32> {
+      if nearest {
+          return textureLoad(tex, vec2<i32>(floor(pos)), 0);
+      }
       let img_tex_size = textureDimensions(tex);
       let sample_pos = pos / vec2<f32>(f32(img_tex_size.x), f32(img_tex_size.y));
       return textureSampleLevel(tex, s_sampler, sample_pos, 0.);
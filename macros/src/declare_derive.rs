@@ -196,6 +196,25 @@ pub(crate) fn declare_derive(input: &mut syn::DeriveInput) -> syn::Result<TokenS
           self
         }
 
+        #[doc="Attaches a handler to the widget that is triggered when it captures a pointer."]
+        #vis fn on_got_pointer_capture(
+          mut self,
+          f: impl FnMut(&mut PointerEvent) + 'static
+        ) -> Self {
+          self.fat_obj = self.fat_obj.on_got_pointer_capture(f);
+          self
+        }
+
+        #[doc="Attaches a handler to the widget that is triggered when the pointer it \
+          captured is released."]
+        #vis fn on_lost_pointer_capture(
+          mut self,
+          f: impl FnMut(&mut PointerEvent) + 'static
+        ) -> Self {
+          self.fat_obj = self.fat_obj.on_lost_pointer_capture(f);
+          self
+        }
+
         #[doc="Attaches a handler to the widget that is triggered when a pointer device \
           is moved into the hit test boundaries of an widget or one of its descendants"]
         #vis fn on_pointer_enter(mut self, f: impl FnMut(&mut PointerEvent) + 'static) -> Self {
@@ -285,6 +304,90 @@ pub(crate) fn declare_derive(input: &mut syn::DeriveInput) -> syn::Result<TokenS
           self
         }
 
+        #[doc="Attaches a handler to the widget that is triggered when the pointers start to drag over the widget."]
+        #vis fn on_pan_start(mut self, f: impl FnMut(&mut PanEvent) + 'static) -> Self {
+          self.fat_obj = self.fat_obj.on_pan_start(f);
+          self
+        }
+
+        #[doc="Attaches a handler to the widget that is triggered when the dragging pointers move."]
+        #vis fn on_pan_update(mut self, f: impl FnMut(&mut PanEvent) + 'static) -> Self {
+          self.fat_obj = self.fat_obj.on_pan_update(f);
+          self
+        }
+
+        #[doc="Attaches a handler to the widget that is triggered when the dragging pointers lift up, the event carries the velocity of the pointers."]
+        #vis fn on_pan_end(mut self, f: impl FnMut(&mut PanEvent) + 'static) -> Self {
+          self.fat_obj = self.fat_obj.on_pan_end(f);
+          self
+        }
+
+        #[doc="Attaches a handler to the widget that is triggered when two or more pointers pinch on the widget."]
+        #vis fn on_scale(mut self, f: impl FnMut(&mut ScaleEvent) + 'static) -> Self {
+          self.fat_obj = self.fat_obj.on_scale(f);
+          self
+        }
+
+        #[doc="Attaches a handler to the widget that is triggered when two or more pointers rotate on the widget."]
+        #vis fn on_rotate(mut self, f: impl FnMut(&mut RotateEvent) + 'static) -> Self {
+          self.fat_obj = self.fat_obj.on_rotate(f);
+          self
+        }
+
+        #[doc="Attaches a handler to the widget that is triggered when a pointer presses on the widget for a while without moving."]
+        #vis fn on_long_press(mut self, f: impl FnMut(&mut LongPressEvent) + 'static) -> Self {
+          self.fat_obj = self.fat_obj.on_long_press(f);
+          self
+        }
+
+        #[doc="Attaches a handler to the widget that is triggered when a pointer drags fast over the widget and lifts up."]
+        #vis fn on_swipe(mut self, f: impl FnMut(&mut SwipeEvent) + 'static) -> Self {
+          self.fat_obj = self.fat_obj.on_swipe(f);
+          self
+        }
+
+        #[doc="Attaches a handler to the widget that is triggered when a dragged widget enters it."]
+        #vis fn on_drag_enter(mut self, f: impl FnMut(&mut DragEvent) + 'static) -> Self {
+          self.fat_obj = self.fat_obj.on_drag_enter(f);
+          self
+        }
+
+        #[doc="Attaches a handler to the widget that is triggered when a dragged widget moves over it."]
+        #vis fn on_drag_over(mut self, f: impl FnMut(&mut DragEvent) + 'static) -> Self {
+          self.fat_obj = self.fat_obj.on_drag_over(f);
+          self
+        }
+
+        #[doc="Attaches a handler to the widget that is triggered when a dragged widget leaves it, or the drag over it is dropped or cancelled."]
+        #vis fn on_drag_leave(mut self, f: impl FnMut(&mut DragEvent) + 'static) -> Self {
+          self.fat_obj = self.fat_obj.on_drag_leave(f);
+          self
+        }
+
+        #[doc="Attaches a handler to the widget that is triggered when a dragged widget is dropped on it."]
+        #vis fn on_drop(mut self, f: impl FnMut(&mut DragEvent) + 'static) -> Self {
+          self.fat_obj = self.fat_obj.on_drop(f);
+          self
+        }
+
+        #[doc="Attaches a handler to the widget that is triggered when files dragged from the operating system hover it."]
+        #vis fn on_file_hover(mut self, f: impl FnMut(&mut FileDropEvent) + 'static) -> Self {
+          self.fat_obj = self.fat_obj.on_file_hover(f);
+          self
+        }
+
+        #[doc="Attaches a handler to the widget that is triggered when the files hovering it leave, are dropped or cancelled."]
+        #vis fn on_file_hover_end(mut self, f: impl FnMut(&mut FileDropEvent) + 'static) -> Self {
+          self.fat_obj = self.fat_obj.on_file_hover_end(f);
+          self
+        }
+
+        #[doc="Attaches a handler to the widget that is triggered when files dragged from the operating system are dropped on it."]
+        #vis fn on_file_drop(mut self, f: impl FnMut(&mut FileDropEvent) + 'static) -> Self {
+          self.fat_obj = self.fat_obj.on_file_drop(f);
+          self
+        }
+
         #[doc="Attaches a handler to the widget that is triggered when the user rotates a
           wheel button on a pointing device (typically a mouse)."]
         #vis fn on_wheel(mut self, f: impl FnMut(&mut WheelEvent) + 'static) -> Self {
@@ -434,6 +537,22 @@ pub(crate) fn declare_derive(input: &mut syn::DeriveInput) -> syn::Result<TokenS
           self
         }
 
+        #[doc="Initializes the shadows of the widget."]
+        #vis fn box_shadow<const _M: u8>(
+          mut self, v: impl DeclareInto<Vec<BoxShadow>, _M>
+        ) -> Self {
+          self.fat_obj = self.fat_obj.box_shadow(v);
+          self
+        }
+
+        #[doc="Initializes the filter applied to the content behind the widget."]
+        #vis fn backdrop_filter<const _M: u8>(
+          mut self, v: impl DeclareInto<Option<BackdropFilter>, _M>
+        ) -> Self {
+          self.fat_obj = self.fat_obj.backdrop_filter(v);
+          self
+        }
+
         #[doc="Initializes the extra space within the widget."]
         #vis fn padding<const _M: u8>(mut self, v: impl DeclareInto<EdgeInsets, _M>) -> Self
         {
@@ -441,6 +560,20 @@ pub(crate) fn declare_derive(input: &mut syn::DeriveInput) -> syn::Result<TokenS
           self
         }
 
+        #[doc="Initializes the payload of the widget to make it draggable."]
+        #vis fn draggable<const _M: u8>(
+          mut self, v: impl DeclareInto<Option<DragData>, _M>
+        ) -> Self {
+          self.fat_obj = self.fat_obj.draggable(v);
+          self
+        }
+
+        #[doc="Initializes the widget follows the pointer while dragging the widget."]
+        #vis fn drag_preview<const _M: u8>(mut self, v: impl DeclareInto<GenWidget, _M>) -> Self {
+          self.fat_obj = self.fat_obj.drag_preview(v);
+          self
+        }
+
         #[doc="Initializes the cursor of the widget."]
         #vis fn cursor<const _M: u8>(mut self, v: impl DeclareInto<CursorIcon, _M>) -> Self {
           self.fat_obj = self.fat_obj.cursor(v);
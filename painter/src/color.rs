@@ -1,8 +1,8 @@
 use material_color_utilities_rs::htc;
-use ribir_geom::Point;
+use ribir_geom::{Angle, Point, Rect, Vector};
 use serde::{Deserialize, Serialize};
 
-use crate::SpreadMethod;
+use crate::{Path, SpreadMethod};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct Color {
@@ -41,6 +41,85 @@ pub struct LinearGradient {
   pub spread_method: SpreadMethod,
 }
 
+/// A gradient that sweeps the colors around the `center`, it works like the
+/// `conic-gradient` of CSS. The offsets of the stops from 0 to 1 map to a full
+/// turn start from the `start_angle`, and the angle is clockwise from the
+/// positive x-axis.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ConicGradient {
+  pub center: Point,
+  pub start_angle: Angle,
+  pub stops: Vec<GradientStop>,
+}
+
+impl ConicGradient {
+  /// Return the offset of the gradient at the `pos`.
+  pub fn offset(&self, pos: Point) -> f32 {
+    let v = pos - self.center;
+    let turn = (v.y.atan2(v.x) - self.start_angle.radians) / std::f32::consts::TAU;
+    turn.rem_euclid(1.)
+  }
+
+  /// Split the gradient into wedges that cover the `bounds`, every wedge is
+  /// filled by the color at its middle. It's used by the formats that don't
+  /// support the conic gradient.
+  pub(crate) fn wedges(&self, bounds: &Rect) -> impl Iterator<Item = (Path, Color)> + '_ {
+    const COUNT: usize = 180;
+    let step = std::f32::consts::TAU / COUNT as f32;
+    let corners = [
+      bounds.min(),
+      bounds.max(),
+      Point::new(bounds.max_x(), bounds.min_y()),
+      Point::new(bounds.min_x(), bounds.max_y()),
+    ];
+    let far = corners
+      .iter()
+      .map(|p| (*p - self.center).length())
+      .fold(0., f32::max);
+    // Every wedge but the last overlaps half of the next one, so no seam is
+    // left between them by the anti-aliasing.
+    let span = move |i: usize| if i + 1 < COUNT { step * 1.5 } else { step };
+    // The chord of the wedge should be out of the bounds.
+    let radius = far / (step * 0.75).cos() + 1.;
+    let point =
+      move |angle: f32| self.center + Vector::from_angle_and_length(Angle::radians(angle), radius);
+    (0..COUNT).map(move |i| {
+      let start = self.start_angle.radians + step * i as f32;
+      let mut builder = Path::builder();
+      builder
+        .begin_path(self.center)
+        .line_to(point(start))
+        .line_to(point(start + span(i)))
+        .end_path(true);
+      let color = stops_color(&self.stops, (i as f32 + 0.5) / COUNT as f32);
+      (builder.build(), color)
+    })
+  }
+}
+
+/// Return the color at the `offset` of the `stops` that are sorted by offset.
+fn stops_color(stops: &[GradientStop], offset: f32) -> Color {
+  let Some(first) = stops.first() else { return Color::TRANSPARENT };
+  if offset <= first.offset {
+    return first.color;
+  }
+  for w in stops.windows(2) {
+    let (prev, next) = (&w[0], &w[1]);
+    if offset <= next.offset {
+      let t = if next.offset > prev.offset {
+        (offset - prev.offset) / (next.offset - prev.offset)
+      } else {
+        1.
+      };
+      let [r0, g0, b0, a0] = prev.color.into_f32_components();
+      let [r1, g1, b1, a1] = next.color.into_f32_components();
+      let mix = |a: f32, b: f32| a + (b - a) * t;
+      return Color::from_f32_rgba(mix(r0, r1), mix(g0, g1), mix(b0, b1), mix(a0, a1));
+    }
+  }
+  stops.last().unwrap().color
+}
+
 /// Describe the light tone of a color, should between [0, 1.0], 0.0 gives
 /// absolute black and 1.0 give the brightest white.
 #[derive(Clone, Debug, Copy)]
@@ -1,5 +1,6 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, time::Duration};
 
+use ribir_algo::Resource;
 use ribir_geom::DeviceSize;
 use serde::{Deserialize, Serialize};
 
@@ -34,13 +35,37 @@ impl PixelImage {
   }
 
   #[cfg(feature = "png")]
-  pub fn from_png(bytes: &[u8]) -> Self {
-    let img = ::image::load(std::io::Cursor::new(bytes), image::ImageFormat::Png)
+  pub fn from_png(bytes: &[u8]) -> Self { Self::load(bytes, image::ImageFormat::Png) }
+
+  #[cfg(feature = "jpeg")]
+  pub fn from_jpeg(bytes: &[u8]) -> Self { Self::load(bytes, image::ImageFormat::Jpeg) }
+
+  #[cfg(feature = "webp")]
+  pub fn from_webp(bytes: &[u8]) -> Self { Self::load(bytes, image::ImageFormat::WebP) }
+
+  #[cfg(feature = "bmp")]
+  pub fn from_bmp(bytes: &[u8]) -> Self { Self::load(bytes, image::ImageFormat::Bmp) }
+
+  /// Decode the first frame of a GIF image, use [`AnimatedImage::from_gif`] to
+  /// decode all its frames.
+  #[cfg(feature = "gif")]
+  pub fn from_gif(bytes: &[u8]) -> Self { Self::load(bytes, image::ImageFormat::Gif) }
+
+  /// Decode an image, the format is guessed from the content of the bytes.
+  /// Only the formats enabled by the features (`png`, `jpeg`, `webp`, `bmp`
+  /// and `gif`) can be decoded.
+  #[cfg(feature = "image")]
+  pub fn from_bytes(bytes: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
+    let img = ::image::load_from_memory(bytes)?;
+    Ok(img.into_rgba8().into())
+  }
+
+  #[cfg(feature = "image")]
+  fn load(bytes: &[u8], format: image::ImageFormat) -> Self {
+    ::image::load(std::io::Cursor::new(bytes), format)
       .unwrap()
-      .to_rgba8();
-    let width = img.width();
-    let height = img.height();
-    PixelImage::new(img.into_raw().into(), width, height, ColorFormat::Rgba8)
+      .into_rgba8()
+      .into()
   }
 
   #[cfg(feature = "png")]
@@ -76,3 +101,215 @@ impl std::fmt::Debug for PixelImage {
       .finish()
   }
 }
+
+#[cfg(feature = "image")]
+impl From<::image::RgbaImage> for PixelImage {
+  fn from(img: ::image::RgbaImage) -> Self {
+    let width = img.width();
+    let height = img.height();
+    PixelImage::new(img.into_raw().into(), width, height, ColorFormat::Rgba8)
+  }
+}
+
+/// The browsers treat a frame delay shorter than this as "as fast as
+/// possible" and play it with [`DEFAULT_FRAME_DELAY`], we do the same.
+const MIN_FRAME_DELAY: Duration = Duration::from_millis(10);
+const DEFAULT_FRAME_DELAY: Duration = Duration::from_millis(100);
+
+/// One frame of an [`AnimatedImage`].
+#[derive(Debug, Clone)]
+pub struct ImageFrame {
+  /// The frame composited with the previous frames, so it can be drawn
+  /// directly.
+  pub image: Resource<PixelImage>,
+  /// How long the frame is shown.
+  pub delay: Duration,
+}
+
+/// An image with a sequence of frames, such as an animated GIF, APNG or WebP.
+///
+/// A still image decoded by the same decoders is an animated image with only
+/// one frame.
+#[derive(Debug, Clone)]
+pub struct AnimatedImage {
+  frames: Vec<ImageFrame>,
+}
+
+impl AnimatedImage {
+  /// Create an animated image from its frames.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `frames` is empty.
+  pub fn new(mut frames: Vec<ImageFrame>) -> Self {
+    assert!(!frames.is_empty(), "An animated image needs at least one frame.");
+    frames.iter_mut().for_each(|f| {
+      if f.delay < MIN_FRAME_DELAY {
+        f.delay = DEFAULT_FRAME_DELAY;
+      }
+    });
+    Self { frames }
+  }
+
+  #[cfg(feature = "gif")]
+  pub fn from_gif(bytes: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
+    let decoder = ::image::codecs::gif::GifDecoder::new(std::io::Cursor::new(bytes))?;
+    Self::decode(decoder)
+  }
+
+  /// Decode an APNG image, a PNG image without animation is decoded as one
+  /// frame.
+  #[cfg(feature = "png")]
+  pub fn from_png(bytes: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
+    let decoder = ::image::codecs::png::PngDecoder::new(std::io::Cursor::new(bytes))?;
+    if decoder.is_apng() {
+      Self::decode(decoder.apng())
+    } else {
+      Ok(Self::still(::image::DynamicImage::from_decoder(decoder)?.into_rgba8()))
+    }
+  }
+
+  /// Decode a WebP image, a WebP image without animation is decoded as one
+  /// frame.
+  #[cfg(feature = "webp")]
+  pub fn from_webp(bytes: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
+    let decoder = ::image::codecs::webp::WebPDecoder::new(std::io::Cursor::new(bytes))?;
+    if decoder.has_animation() {
+      Self::decode(decoder)
+    } else {
+      Ok(Self::still(::image::DynamicImage::from_decoder(decoder)?.into_rgba8()))
+    }
+  }
+
+  /// Decode an image, the format is guessed from the content of the bytes.
+  /// GIF, APNG and WebP images are decoded with all their frames, the other
+  /// formats are decoded as one frame.
+  #[cfg(feature = "image")]
+  pub fn from_bytes(bytes: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
+    match ::image::guess_format(bytes)? {
+      #[cfg(feature = "gif")]
+      ::image::ImageFormat::Gif => Self::from_gif(bytes),
+      #[cfg(feature = "png")]
+      ::image::ImageFormat::Png => Self::from_png(bytes),
+      #[cfg(feature = "webp")]
+      ::image::ImageFormat::WebP => Self::from_webp(bytes),
+      _ => Ok(Self::still(::image::load_from_memory(bytes)?.into_rgba8())),
+    }
+  }
+
+  #[inline]
+  pub fn frames(&self) -> &[ImageFrame] { &self.frames }
+
+  /// Return if the image has more than one frame.
+  #[inline]
+  pub fn is_animated(&self) -> bool { self.frames.len() > 1 }
+
+  /// The size of the image, all the frames have the same size.
+  pub fn size(&self) -> DeviceSize { self.frames[0].image.size() }
+
+  /// The time to play all the frames once.
+  pub fn duration(&self) -> Duration { self.frames.iter().map(|f| f.delay).sum() }
+
+  /// Return the index of the frame that should be shown after the animation
+  /// has been played for `elapsed`, the animation repeats forever.
+  pub fn frame_index_at(&self, elapsed: Duration) -> usize {
+    let total = self.duration().as_nanos();
+    let mut remain = if total == 0 { 0 } else { elapsed.as_nanos() % total };
+    self
+      .frames
+      .iter()
+      .position(|f| {
+        let delay = f.delay.as_nanos();
+        if remain < delay {
+          true
+        } else {
+          remain -= delay;
+          false
+        }
+      })
+      .unwrap_or(0)
+  }
+
+  #[cfg(feature = "image")]
+  fn decode<'a>(
+    decoder: impl ::image::AnimationDecoder<'a>,
+  ) -> Result<Self, Box<dyn std::error::Error>> {
+    let frames = decoder
+      .into_frames()
+      .map(|frame| {
+        let frame = frame?;
+        let delay = Duration::from(frame.delay());
+        Ok(ImageFrame { image: Resource::new(frame.into_buffer().into()), delay })
+      })
+      .collect::<Result<Vec<_>, ::image::ImageError>>()?;
+    if frames.is_empty() {
+      return Err("The image has no frame.".into());
+    }
+    Ok(Self::new(frames))
+  }
+
+  #[cfg(feature = "image")]
+  fn still(img: ::image::RgbaImage) -> Self {
+    Self::new(vec![ImageFrame { image: Resource::new(img.into()), delay: Duration::ZERO }])
+  }
+}
+
+impl From<Resource<PixelImage>> for AnimatedImage {
+  fn from(image: Resource<PixelImage>) -> Self {
+    Self::new(vec![ImageFrame { image, delay: Duration::ZERO }])
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn frame(color: u8, delay: Duration) -> ImageFrame {
+    let img = PixelImage::new(vec![color; 4].into(), 1, 1, ColorFormat::Rgba8);
+    ImageFrame { image: Resource::new(img), delay }
+  }
+
+  #[test]
+  fn frame_index_repeat() {
+    let img = AnimatedImage::new(vec![
+      frame(0, Duration::from_millis(100)),
+      frame(1, Duration::from_millis(200)),
+      // Too short delay is played as the default delay.
+      frame(2, Duration::ZERO),
+    ]);
+
+    assert_eq!(img.duration(), Duration::from_millis(400));
+    assert_eq!(img.frame_index_at(Duration::ZERO), 0);
+    assert_eq!(img.frame_index_at(Duration::from_millis(150)), 1);
+    assert_eq!(img.frame_index_at(Duration::from_millis(350)), 2);
+    assert_eq!(img.frame_index_at(Duration::from_millis(450)), 0);
+  }
+
+  #[cfg(feature = "gif")]
+  #[test]
+  fn decode_gif_frames() {
+    use ::image::{codecs::gif::GifEncoder, Delay, Frame, RgbaImage};
+
+    let mut bytes = vec![];
+    {
+      let mut encoder = GifEncoder::new(&mut bytes);
+      for color in [[255, 0, 0, 255], [0, 0, 255, 255]] {
+        let buffer = RgbaImage::from_pixel(2, 2, color.into());
+        let delay = Delay::from_numer_denom_ms(50, 1);
+        encoder
+          .encode_frame(Frame::from_parts(buffer, 0, 0, delay))
+          .unwrap();
+      }
+    }
+
+    let img = AnimatedImage::from_bytes(&bytes).unwrap();
+    assert!(img.is_animated());
+    assert_eq!(img.size(), DeviceSize::new(2, 2));
+    assert_eq!(img.frames()[0].delay, Duration::from_millis(50));
+    assert_eq!(&img.frames()[0].image.pixel_bytes()[..4], &[255, 0, 0, 255]);
+    assert_eq!(&img.frames()[1].image.pixel_bytes()[..4], &[0, 0, 255, 255]);
+
+    let still = PixelImage::from_bytes(&bytes).unwrap();
+    assert_eq!(still.pixel_bytes()[..4], [255, 0, 0, 255]);
+  }
+}
@@ -13,6 +13,12 @@ pub mod image;
 mod style;
 pub use style::*;
 
-pub use crate::image::PixelImage;
+pub use crate::image::{AnimatedImage, ImageFrame, PixelImage};
 mod svg;
 pub use svg::Svg;
+#[cfg(feature = "pdf")]
+mod pdf_export;
+#[cfg(feature = "pdf")]
+pub use pdf_export::export_pdf;
+mod svg_export;
+pub use svg_export::export_svg;
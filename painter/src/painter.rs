@@ -5,10 +5,10 @@ use ribir_geom::{Angle, DeviceRect, Point, Rect, Size, Transform, Vector};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-  color::{LinearGradient, RadialGradient},
+  color::{ConicGradient, LinearGradient, RadialGradient},
   path::*,
   path_builder::PathBuilder,
-  Brush, Color, PixelImage, Svg,
+  BackdropFilter, BoxShadow, Brush, Color, ImageBrush, ImageFilter, ImageRepeat, PixelImage, Svg,
 };
 /// The painter is a two-dimensional grid. The coordinate (0, 0) is at the
 /// upper-left corner of the canvas. Along the X-axis, values increase towards
@@ -71,9 +71,35 @@ pub enum PaintPath {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PaintPathAction {
   Color(Color),
-  Image { img: Resource<PixelImage>, opacity: f32 },
+  /// Paint the image that placed in the `rect` of the path coordinate, and
+  /// repeat it by the `repeat`.
+  Image {
+    img: Resource<PixelImage>,
+    opacity: f32,
+    rect: Rect,
+    repeat: ImageRepeat,
+    filter: ImageFilter,
+  },
   Radial(RadialGradient),
   Linear(LinearGradient),
+  Conic(ConicGradient),
+  /// Paint a rounded rectangle blurred by the Gaussian function, the geometry
+  /// is in the same coordinate as the path. If it's `inset`, paint the color
+  /// outside the blurred rectangle instead.
+  Shadow {
+    rect: Rect,
+    radius: Radius,
+    sigma: f32,
+    color: Color,
+    inset: bool,
+  },
+  /// Paint the content behind the path that blurred by the Gaussian function
+  /// and covered by the `tint` color.
+  Backdrop {
+    sigma: f32,
+    tint: Color,
+    opacity: f32,
+  },
   Clip,
 }
 
@@ -86,6 +112,27 @@ pub enum SpreadMethod {
   Repeat,
 }
 
+/// The blend mode use to composite a layer with the content below it. The
+/// formulas follow the [W3C Compositing and Blending](https://www.w3.org/TR/compositing-1/#blending) specification.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq, Hash)]
+pub enum BlendMode {
+  /// Draw the source over the destination, without blending.
+  #[default]
+  Normal,
+  Multiply,
+  Screen,
+  Overlay,
+  Darken,
+  Lighten,
+  ColorDodge,
+  ColorBurn,
+  HardLight,
+  SoftLight,
+  Difference,
+  Exclusion,
+}
+
 impl From<usvg::SpreadMethod> for SpreadMethod {
   fn from(value: usvg::SpreadMethod) -> Self {
     match value {
@@ -123,6 +170,17 @@ pub enum PaintCommand {
     bounds: Rect,
     cmds: Resource<Box<[PaintCommand]>>,
   },
+  /// An isolated group of paint commands. The commands are composited together
+  /// in an offscreen layer first, then the layer is composited with the content
+  /// below it by the `blend_mode` and `opacity`.
+  Layer {
+    opacity: f32,
+    blend_mode: BlendMode,
+    /// The bounds of the layer, it's the union of all the commands' bounds and
+    /// in the same axis as the commands.
+    bounds: Rect,
+    cmds: Box<[PaintCommand]>,
+  },
 }
 
 #[derive(Clone)]
@@ -136,6 +194,17 @@ struct PainterState {
   /// The visible boundary of the painter in visual axis, not care about the
   /// transform.
   bounds: Rect,
+  /// The layer started by this state, it will be composited when the state
+  /// restore.
+  layer: Option<LayerState>,
+}
+
+#[derive(Clone, Copy)]
+struct LayerState {
+  /// The index of the first command of the layer.
+  cmd_start: usize,
+  opacity: f32,
+  blend_mode: BlendMode,
 }
 
 impl PainterState {
@@ -147,6 +216,7 @@ impl PainterState {
       transform: Transform::identity(),
       clip_cnt: 0,
       opacity: 1.,
+      layer: None,
     }
   }
 }
@@ -200,19 +270,42 @@ impl Painter {
   /// Saves the entire state of the canvas by pushing the current drawing state
   /// onto a stack.
   pub fn save(&mut self) -> &mut Self {
-    let new_state = self.current_state().clone();
+    let mut new_state = self.current_state().clone();
+    new_state.layer = None;
     self.state_stack.push(new_state);
     self
   }
 
+  /// Saves the entire state like [`Painter::save`], and starts an isolated
+  /// layer. All the commands drawn before the state restored are composited
+  /// together first, then the result is composited with the content below it
+  /// by `blend_mode` and `opacity`.
+  ///
+  /// Unlike [`Painter::apply_alpha`], the `opacity` is applied to the layer as
+  /// a whole, so the overlapping children do not show through each other.
+  pub fn save_layer(&mut self, opacity: f32, blend_mode: BlendMode) -> &mut Self {
+    let opacity = self.alpha() * opacity;
+    let cmd_start = self.commands.len();
+    self.save();
+    let state = self.current_state_mut();
+    // The opacity is applied when the layer composited.
+    state.opacity = if opacity > 0. { 1. } else { 0. };
+    state.layer = Some(LayerState { cmd_start, opacity, blend_mode });
+    self
+  }
+
   /// Restores the most recently saved canvas state by popping the top entry in
   /// the drawing state stack. If there is no saved state, this method does
   /// nothing.
   #[inline]
   pub fn restore(&mut self) {
     let clip_cnt = self.current_state().clip_cnt;
-    self.state_stack.pop();
+    let layer = self.state_stack.pop().and_then(|s| s.layer);
     self.push_n_pop_cmd(clip_cnt - self.current_state().clip_cnt);
+    if let Some(layer) = layer {
+      let bounds = self.current_state().bounds;
+      self.compose_layer(layer, bounds);
+    }
   }
 
   pub fn reset(&mut self) {
@@ -290,6 +383,17 @@ impl Painter {
     self
   }
 
+  /// Return the dash pattern of the stroke pen.
+  #[inline]
+  pub fn get_dash(&self) -> Option<&DashPattern> { self.stroke_options().dash.as_ref() }
+
+  /// Set the dash pattern of the stroke pen, `None` for a solid line.
+  #[inline]
+  pub fn set_dash(&mut self, dash: Option<DashPattern>) -> &mut Self {
+    self.current_state_mut().stroke_options.dash = dash;
+    self
+  }
+
   /// Return the current transformation matrix being applied to the layer.
   #[inline]
   pub fn get_transform(&self) -> &Transform { &self.current_state().transform }
@@ -337,9 +441,23 @@ impl Painter {
     {
       let mut action = match self.current_state().brush.clone() {
         Brush::Color(color) => PaintPathAction::Color(color),
-        Brush::Image(img) => PaintPathAction::Image { img, opacity: 1. },
+        Brush::Image(brush) => {
+          let rect = brush.image_rect(&path.bounds);
+          // The image is placed in an empty rect, nothing to draw.
+          if rect.is_empty() {
+            return self;
+          }
+          PaintPathAction::Image {
+            rect,
+            img: brush.img,
+            opacity: 1.,
+            repeat: brush.repeat,
+            filter: brush.filter,
+          }
+        }
         Brush::RadialGradient(radial_gradient) => PaintPathAction::Radial(radial_gradient),
         Brush::LinearGradient(linear_gradient) => PaintPathAction::Linear(linear_gradient),
+        Brush::ConicGradient(conic_gradient) => PaintPathAction::Conic(conic_gradient),
       };
       action.apply_alpha(self.alpha());
       let ts = *self.get_transform();
@@ -496,6 +614,74 @@ impl Painter {
     self
   }
 
+  /// Draws the `shadow` of a box that has the `rect` and the corner `radius`.
+  ///
+  /// The outer shadow isn't clipped by the box, so it should be drawn before
+  /// the box itself.
+  pub fn draw_box_shadow(&mut self, rect: &Rect, radius: &Radius, shadow: &BoxShadow) -> &mut Self {
+    invisible_return!(self);
+    let color = shadow.color.apply_alpha(self.alpha());
+    if color.alpha == 0 {
+      return self;
+    }
+
+    let sigma = shadow.blur_radius.max(0.) / 2.;
+    let spread = if shadow.inset { -shadow.spread_radius } else { shadow.spread_radius };
+    let shadow_rect = rect
+      .translate(shadow.offset)
+      .inflate(spread, spread);
+    // A negative spread may make the shadow rectangle empty, keep its center.
+    let size = shadow_rect.size.max(Size::zero());
+    let shadow_rect = Rect::new(shadow_rect.center() - size.to_vector() / 2., size);
+    let spread_corner = |r: f32| if r > 0. { (r + spread).max(0.) } else { 0. };
+    let shadow_radius = Radius::new(
+      spread_corner(radius.top_left),
+      spread_corner(radius.top_right),
+      spread_corner(radius.bottom_left),
+      spread_corner(radius.bottom_right),
+    );
+
+    let path = if shadow.inset {
+      Path::rect_round(rect, radius)
+    } else {
+      // The Gaussian blur is almost invisible beyond three times of sigma.
+      let extent = 3. * sigma;
+      Path::rect(&shadow_rect.inflate(extent, extent))
+    };
+    if locatable_bounds(path.bounds()) && self.intersect_paint_bounds(path.bounds()) {
+      let action = PaintPathAction::Shadow {
+        rect: shadow_rect,
+        radius: shadow_radius,
+        sigma,
+        color,
+        inset: shadow.inset,
+      };
+      let cmd = PathCommand::new(path.into(), action, *self.get_transform());
+      self.commands.push(PaintCommand::Path(cmd));
+    }
+    self
+  }
+
+  /// Draws the content that has been painted behind the `path` with the
+  /// `filter`, the content is blurred and then covered by the tint color.
+  pub fn draw_backdrop_filter(
+    &mut self, path: impl Into<PaintPath>, filter: &BackdropFilter,
+  ) -> &mut Self {
+    invisible_return!(self);
+    let sigma = filter.blur_radius.max(0.) / 2.;
+    if sigma == 0. && filter.tint.alpha == 0 {
+      return self;
+    }
+
+    let path = path.into();
+    if locatable_bounds(path.bounds()) && self.intersect_paint_bounds(path.bounds()) {
+      let action = PaintPathAction::Backdrop { sigma, tint: filter.tint, opacity: self.alpha() };
+      let cmd = PathCommand::new(path, action, *self.get_transform());
+      self.commands.push(PaintCommand::Path(cmd));
+    }
+    self
+  }
+
   /// Draws a bundle of paint commands that can be treated as a single command.
   /// This allows the backend to cache it.
   ///
@@ -542,6 +728,13 @@ impl Painter {
             bounds,
             cmds,
           },
+          mut layer @ PaintCommand::Layer { .. } => {
+            layer.transform(&transform);
+            if let PaintCommand::Layer { opacity, .. } = &mut layer {
+              *opacity *= alpha;
+            }
+            layer
+          }
         };
         self.commands.push(cmd);
       }
@@ -586,6 +779,20 @@ impl Painter {
 
     self
   }
+
+  /// Fill the `dst_rect` with the image brush, the image is fitted and aligned
+  /// in the `dst_rect` if the brush has no bounds.
+  ///
+  /// Use it to control how the image is scaled, repeated and sampled, for
+  /// example, use `ImageFilter::Nearest` to keep the pixel art sharp.
+  pub fn draw_img_brush(&mut self, brush: impl Into<ImageBrush>, dst_rect: &Rect) -> &mut Self {
+    self
+      .save_guard()
+      .set_brush(brush.into())
+      .rect(dst_rect)
+      .fill();
+    self
+  }
 }
 
 impl Painter {
@@ -618,13 +825,50 @@ impl Painter {
     }
   }
 
+  /// Pop all the clips and composite all the layers not restored, from the top
+  /// state to the bottom state.
   fn fill_all_pop_clips(&mut self) {
-    let clip_cnt = self.current_state().clip_cnt;
+    for idx in (0..self.state_stack.len()).rev() {
+      let below_clip_cnt = idx
+        .checked_sub(1)
+        .map_or(0, |i| self.state_stack[i].clip_cnt);
+      let state = &mut self.state_stack[idx];
+      let clip_cnt = state.clip_cnt - below_clip_cnt;
+      let layer = state.layer.take();
+      self.push_n_pop_cmd(clip_cnt);
+      if let Some(layer) = layer {
+        let bounds = self.state_stack[idx - 1].bounds;
+        self.compose_layer(layer, bounds);
+      }
+    }
     self
       .state_stack
       .iter_mut()
       .for_each(|s| s.clip_cnt = 0);
-    self.push_n_pop_cmd(clip_cnt);
+  }
+
+  /// Collect the commands of the `layer` into a layer command. The layer is
+  /// dropped if nothing is visible in `bounds`.
+  fn compose_layer(&mut self, layer: LayerState, bounds: Rect) {
+    let LayerState { cmd_start, opacity, blend_mode } = layer;
+    if opacity >= 1. && blend_mode == BlendMode::Normal {
+      // Nothing to composite, keep the commands as they are.
+      return;
+    }
+
+    let cmds: Box<[PaintCommand]> = self.commands.drain(cmd_start..).collect();
+    let layer_bounds = cmds
+      .iter()
+      .filter_map(PaintCommand::bounds)
+      .reduce(|a, b| a.union(&b))
+      .and_then(|rect| rect.intersection(&bounds));
+    if let Some(bounds) = layer_bounds {
+      if opacity > 0. {
+        self
+          .commands
+          .push(PaintCommand::Layer { opacity, blend_mode, bounds, cmds });
+      }
+    }
   }
 
   fn is_visible_canvas(&self) -> bool {
@@ -642,9 +886,10 @@ impl Painter {
   fn is_visible_brush(&self) -> bool {
     match self.current_state().brush {
       Brush::Color(c) => c.alpha > 0,
-      Brush::Image(_) => true,
+      Brush::Image(ref brush) => brush.img.width() > 0 && brush.img.height() > 0,
       Brush::RadialGradient(RadialGradient { ref stops, .. })
-      | Brush::LinearGradient(LinearGradient { ref stops, .. }) => {
+      | Brush::LinearGradient(LinearGradient { ref stops, .. })
+      | Brush::ConicGradient(ConicGradient { ref stops, .. }) => {
         stops.iter().any(|s| s.color.alpha > 0)
       }
     }
@@ -733,15 +978,49 @@ impl PathCommand {
   }
 }
 
+impl PaintCommand {
+  /// The bounds of the visual content of this command, `None` if the command
+  /// paints nothing.
+  pub fn bounds(&self) -> Option<Rect> {
+    match self {
+      PaintCommand::Path(PathCommand { paint_bounds, action, .. }) => {
+        (!matches!(action, PaintPathAction::Clip)).then_some(*paint_bounds)
+      }
+      PaintCommand::PopClip => None,
+      PaintCommand::Bundle { transform, bounds, .. } => {
+        Some(transform.outer_transformed_rect(bounds))
+      }
+      PaintCommand::Layer { bounds, .. } => Some(*bounds),
+    }
+  }
+
+  /// Apply the `transform` to the command, the command will be painted in the
+  /// axis after the `transform`.
+  pub fn transform(&mut self, ts: &Transform) {
+    match self {
+      PaintCommand::Path(path) => path.transform(ts),
+      PaintCommand::PopClip => {}
+      PaintCommand::Bundle { transform, .. } => *transform = transform.then(ts),
+      PaintCommand::Layer { bounds, cmds, .. } => {
+        *bounds = ts.outer_transformed_rect(bounds);
+        cmds.iter_mut().for_each(|c| c.transform(ts));
+      }
+    }
+  }
+}
+
 impl PaintPathAction {
   pub fn apply_alpha(&mut self, alpha: f32) -> &mut Self {
     match self {
       PaintPathAction::Color(color) => *color = color.apply_alpha(alpha),
       PaintPathAction::Image { opacity, .. } => *opacity *= alpha,
       PaintPathAction::Radial(RadialGradient { stops, .. })
-      | PaintPathAction::Linear(LinearGradient { stops, .. }) => stops
+      | PaintPathAction::Linear(LinearGradient { stops, .. })
+      | PaintPathAction::Conic(ConicGradient { stops, .. }) => stops
         .iter_mut()
         .for_each(|s| s.color = s.color.apply_alpha(alpha)),
+      PaintPathAction::Shadow { color, .. } => *color = color.apply_alpha(alpha),
+      PaintPathAction::Backdrop { opacity, .. } => *opacity *= alpha,
       PaintPathAction::Clip => {}
     }
     self
@@ -766,9 +1045,33 @@ mod test {
   use ribir_geom::rect;
 
   use super::*;
+  use crate::{image::ColorFormat, ImageFit};
 
   fn painter() -> Painter { Painter::new(Rect::from_size(Size::new(512., 512.))) }
 
+  #[test]
+  fn dash_stroke() {
+    let dashes = |dash: Option<DashPattern>| {
+      let mut painter = painter();
+      painter.set_dash(dash).begin_path(Point::zero());
+      painter.line_to(Point::new(20., 0.));
+      painter.end_path(false).stroke();
+      let cmds = painter.finish();
+      let PaintCommand::Path(PathCommand { path, .. }) = &cmds[0] else { unreachable!() };
+      path
+        .segments()
+        .filter(|s| matches!(s, PathSegment::MoveTo(_)))
+        .count()
+    };
+
+    assert_eq!(dashes(None), 1);
+    assert_eq!(dashes(Some(DashPattern::new([4., 4.], 0.))), 3);
+    // The odd values repeat to `[4., 2., 4., 2.]`, and start from the gap.
+    assert_eq!(dashes(Some(DashPattern::new([4., 2., 4.], 4.))), 3);
+    // An invalid pattern draws a solid line.
+    assert_eq!(dashes(Some(DashPattern::new([0., 0.], 0.))), 1);
+  }
+
   #[test]
   fn save_guard() {
     let mut painter = painter();
@@ -788,6 +1091,71 @@ mod test {
     assert_eq!(&Transform::new(1., 0., 0., 1., 0., 0.), painter.get_transform());
   }
 
+  #[test]
+  fn layer_group_opacity() {
+    let mut painter = painter();
+    painter
+      .apply_alpha(0.5)
+      .save_layer(0.5, BlendMode::Multiply)
+      .set_brush(Color::RED)
+      .rect(&rect(0., 0., 10., 10.))
+      .fill()
+      .rect(&rect(5., 5., 10., 10.))
+      .fill()
+      .restore();
+    let commands = painter.finish();
+
+    assert_eq!(commands.len(), 1);
+    let PaintCommand::Layer { opacity, blend_mode, bounds, cmds } = &commands[0] else {
+      panic!("should be a layer");
+    };
+    assert_eq!(*opacity, 0.25);
+    assert_eq!(*blend_mode, BlendMode::Multiply);
+    assert_eq!(*bounds, rect(0., 0., 15., 15.));
+    assert_eq!(cmds.len(), 2);
+    // The opacity is not applied to the children.
+    assert!(cmds.iter().all(|c| matches!(
+      c,
+      PaintCommand::Path(PathCommand { action: PaintPathAction::Color(Color::RED), .. })
+    )));
+  }
+
+  #[test]
+  fn normal_opaque_layer_is_flatten() {
+    let mut painter = painter();
+    painter
+      .save_layer(1., BlendMode::Normal)
+      .rect(&rect(0., 0., 10., 10.))
+      .fill()
+      .restore();
+    // empty layer is dropped.
+    painter
+      .save_layer(0.5, BlendMode::Screen)
+      .restore();
+
+    let commands = painter.finish();
+    assert_eq!(commands.len(), 1);
+    assert!(matches!(commands[0], PaintCommand::Path(_)));
+  }
+
+  #[test]
+  fn finish_unrestored_layer() {
+    let mut painter = painter();
+    let commands = painter
+      .save_layer(0.5, BlendMode::Screen)
+      .clip(Path::rect(&rect(0., 0., 5., 5.)))
+      .rect(&rect(0., 0., 10., 10.))
+      .fill()
+      .finish();
+
+    assert_eq!(commands.len(), 1);
+    let PaintCommand::Layer { bounds, cmds, .. } = &commands[0] else {
+      panic!("should be a layer");
+    };
+    assert_eq!(*bounds, rect(0., 0., 10., 10.));
+    assert!(matches!(cmds.last(), Some(PaintCommand::PopClip)));
+  }
+
   #[test]
   fn fix_clip_pop_without_restore() {
     let mut painter = painter();
@@ -821,6 +1189,20 @@ mod test {
     assert_eq!(painter.commands.len(), 0);
   }
 
+  #[test]
+  fn skip_img_in_empty_rect() {
+    let mut painter = painter();
+    let img = PixelImage::new(vec![255; 16].into(), 2, 2, ColorFormat::Rgba8);
+    let brush = ImageBrush::new(Resource::new(img)).with_fit(ImageFit::Fill);
+    painter
+      .draw_img_brush(brush.clone(), &rect(10., 10., 0., 20.))
+      .draw_img_brush(brush.clone().with_bounds(rect(0., 0., 20., 0.)), &rect(10., 10., 20., 20.));
+    assert_eq!(painter.commands.len(), 0);
+
+    painter.draw_img_brush(brush, &rect(10., 10., 20., 20.));
+    assert_eq!(painter.commands.len(), 1);
+  }
+
   #[test]
   fn filter_invalid_commands() {
     let mut painter = painter();
@@ -53,6 +53,30 @@ pub struct StrokeOptions {
   ///
   /// Default: Miter
   pub line_join: LineJoin,
+
+  /// The dash pattern of the stroke, a solid line if it's `None`.
+  ///
+  /// Default: None
+  pub dash: Option<DashPattern>,
+}
+
+/// The dash pattern of a stroke, it works like the `stroke-dasharray` and
+/// `stroke-dashoffset` of SVG.
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct DashPattern {
+  /// The lengths of the dashes and the gaps alternately. If the number of
+  /// values is odd, the values are repeated to yield an even number. A
+  /// pattern with negative values or a zero sum draws a solid line.
+  pub array: Vec<f32>,
+  /// The distance into the pattern to start the stroke.
+  pub offset: f32,
+}
+
+impl DashPattern {
+  #[inline]
+  pub fn new(array: impl Into<Vec<f32>>, offset: f32) -> Self {
+    Self { array: array.into(), offset }
+  }
 }
 
 /// Draws at the beginning and end of an open path contour.
@@ -91,7 +115,7 @@ pub enum PathSegment {
 }
 
 /// The radius of each corner of a rounded rectangle.
-#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default)]
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Serialize, Deserialize)]
 pub struct Radius {
   pub top_left: f32,
   pub top_right: f32,
@@ -311,6 +335,7 @@ impl Default for StrokeOptions {
       miter_limit: 4.0,
       line_cap: LineCap::default(),
       line_join: LineJoin::default(),
+      dash: None,
     }
   }
 }
@@ -323,6 +348,7 @@ impl std::hash::Hash for StrokeOptions {
     OrderedFloat(self.miter_limit).hash(state);
     self.line_cap.hash(state);
     self.line_join.hash(state);
+    self.dash.hash(state);
   }
 }
 
@@ -332,11 +358,36 @@ impl PartialEq for StrokeOptions {
       && OrderedFloat(self.miter_limit).eq(&OrderedFloat(other.miter_limit))
       && self.line_cap.eq(&other.line_cap)
       && self.line_join.eq(&other.line_join)
+      && self.dash.eq(&other.dash)
   }
 }
 
 impl Eq for StrokeOptions {}
 
+impl std::hash::Hash for DashPattern {
+  fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+    self
+      .array
+      .iter()
+      .for_each(|v| OrderedFloat(*v).hash(state));
+    OrderedFloat(self.offset).hash(state);
+  }
+}
+
+impl PartialEq for DashPattern {
+  fn eq(&self, other: &Self) -> bool {
+    self.array.len() == other.array.len()
+      && self
+        .array
+        .iter()
+        .zip(other.array.iter())
+        .all(|(a, b)| OrderedFloat(*a).eq(&OrderedFloat(*b)))
+      && OrderedFloat(self.offset).eq(&OrderedFloat(other.offset))
+  }
+}
+
+impl Eq for DashPattern {}
+
 #[cfg(feature = "tessellation")]
 impl<Attr> Vertex<Attr> {
   #[inline]
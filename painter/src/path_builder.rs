@@ -6,7 +6,7 @@ use lyon_algorithms::path::{
 };
 use ribir_geom::{Angle, Point, Rect, Transform, Vector};
 
-use crate::{LineCap, LineJoin, Path, Radius, StrokeOptions};
+use crate::{DashPattern, LineCap, LineJoin, Path, Radius, StrokeOptions};
 
 #[derive(Default)]
 pub struct PathBuilder {
@@ -201,10 +201,18 @@ pub(crate) fn stroke_path(
     }
   });
 
-  let path = builder
-    .finish()
-    .unwrap()
-    .stroke(&options.clone().into(), resolution)?;
+  let mut path = builder.finish().unwrap();
+  // The stroker doesn't dash the path, split the path into dashes first. Stroke
+  // the solid path if it can't be dashed.
+  if let Some(dashed) = options
+    .dash
+    .as_ref()
+    .and_then(tiny_dash)
+    .and_then(|dash| path.dash(&dash, resolution))
+  {
+    path = dashed;
+  }
+  let path = path.stroke(&options.clone().into(), resolution)?;
 
   let mut builder = LyonPath::svg_builder();
   path.segments().for_each(|seg| match seg {
@@ -225,6 +233,14 @@ pub(crate) fn stroke_path(
   Some(builder.build())
 }
 
+fn tiny_dash(dash: &DashPattern) -> Option<tiny_skia_path::StrokeDash> {
+  let mut array = dash.array.clone();
+  if array.len() % 2 == 1 {
+    array.extend_from_within(..);
+  }
+  tiny_skia_path::StrokeDash::new(array, dash.offset)
+}
+
 fn into_tiny_transform(ts: Transform) -> tiny_skia_path::Transform {
   let Transform { m11, m12, m21, m22, m31, m32, .. } = ts;
   tiny_skia_path::Transform { sx: m11, kx: m21, ky: m12, sy: m22, tx: m31, ty: m32 }
@@ -232,7 +248,7 @@ fn into_tiny_transform(ts: Transform) -> tiny_skia_path::Transform {
 
 impl From<StrokeOptions> for tiny_skia_path::Stroke {
   fn from(value: StrokeOptions) -> Self {
-    let StrokeOptions { width, miter_limit, line_cap, line_join } = value;
+    let StrokeOptions { width, miter_limit, line_cap, line_join, .. } = value;
     tiny_skia_path::Stroke {
       width,
       miter_limit,
@@ -0,0 +1,982 @@
+use std::collections::HashMap;
+
+use pdf_writer::{
+  types::{
+    BlendMode as PdfBlendMode, ColorSpaceOperand, FunctionShadingType, MaskType, PaintType,
+    TilingType,
+  },
+  writers::Resources,
+  Chunk, Content, Finish, Name, Pdf, Rect as PdfRect, Ref,
+};
+use ribir_algo::Resource;
+use ribir_geom::{Point, Rect, Size, Transform};
+
+use crate::{
+  color::{LinearGradient, RadialGradient},
+  image::ColorFormat,
+  BlendMode, Color, GradientStop, ImageFilter, ImageRepeat, PaintCommand, PaintPath,
+  PaintPathAction, Path, PathCommand, PathSegment, PixelImage, Radius, SpreadMethod,
+};
+
+/// The max periods to repeat a gradient to cover the bounds of its path.
+const MAX_GRADIENT_PERIODS: usize = 64;
+/// PDF has no blur, a blurred shadow is approximated by stacking the layers of
+/// its rectangle spreading from `-2σ` to `2σ`.
+const SHADOW_LAYERS: usize = 8;
+
+/// Export the paint commands to a PDF document.
+///
+/// The content of `content_size` is split into pages of `page_size`, from left
+/// to right and then from top to bottom. Everything keeps as vector graphics,
+/// includes the glyphs of the text, that are drawn as the paths of
+/// `Face::outline_glyph` by `ribir_text`. The shared paths, images and bundles
+/// are only embedded once in the document, so the glyphs are reused by all the
+/// text.
+///
+/// Requires the `pdf` feature.
+///
+/// Since PDF doesn't support blur, the blurred shadows are approximated, and
+/// the backdrop filters only keep their tint color. The conic gradients are
+/// approximated by the small wedges filled with solid colors.
+///
+/// # Panics
+///
+/// Panics if the `page_size` is empty.
+pub fn export_pdf(content_size: Size, page_size: Size, commands: &[PaintCommand]) -> Vec<u8> {
+  assert!(!page_size.is_empty(), "The page size of pdf must not be empty.");
+
+  let mut writer = PdfWriter::default();
+  let catalog = writer.alloc();
+  let page_tree = writer.alloc();
+  let content = writer.write_form("Content", commands, &Rect::from_size(content_size), false);
+
+  let cols = (content_size.width / page_size.width)
+    .ceil()
+    .max(1.) as usize;
+  let rows = (content_size.height / page_size.height)
+    .ceil()
+    .max(1.) as usize;
+  let mut pages = Vec::with_capacity(cols * rows);
+  for row in 0..rows {
+    for col in 0..cols {
+      let mut page_content = Content::new();
+      // Flip the y axis, Ribir's origin is at the top-left corner but PDF's is
+      // at the bottom-left corner.
+      page_content
+        .transform([1., 0., 0., -1., 0., page_size.height])
+        .transform([
+          1.,
+          0.,
+          0.,
+          1.,
+          -(col as f32) * page_size.width,
+          -(row as f32) * page_size.height,
+        ])
+        .x_object(Name(content.as_bytes()));
+      let data = page_content.finish();
+
+      let (page, contents) = (writer.alloc(), writer.alloc());
+      writer.chunk.stream(contents, &data);
+      writer
+        .chunk
+        .page(page)
+        .parent(page_tree)
+        .media_box(PdfRect::new(0., 0., page_size.width, page_size.height))
+        .contents(contents)
+        .pair(Name(b"Resources"), writer.resources);
+      pages.push(page);
+    }
+  }
+  writer.write_resources();
+
+  let mut pdf = Pdf::new();
+  pdf.catalog(catalog).pages(page_tree);
+  pdf
+    .pages(page_tree)
+    .count(pages.len() as i32)
+    .kids(pages);
+  pdf.extend(&writer.chunk);
+  pdf.finish()
+}
+
+/// All the forms, images, shadings and patterns share the same resources
+/// dictionary.
+struct PdfWriter {
+  chunk: Chunk,
+  next_ref: Ref,
+  resources: Ref,
+  x_objects: Vec<(String, Ref)>,
+  shadings: Vec<(String, Ref)>,
+  patterns: Vec<(String, Ref)>,
+  ext_states: Vec<(String, Ref)>,
+  alpha_states: HashMap<u32, String>,
+  blend_states: HashMap<BlendMode, String>,
+  paths: HashMap<Resource<Path>, String>,
+  /// The embedded images, keyed by the image and whether it's interpolated.
+  images: HashMap<(Resource<PixelImage>, bool), String>,
+  bundles: HashMap<Resource<Box<[PaintCommand]>>, String>,
+}
+
+impl Default for PdfWriter {
+  fn default() -> Self {
+    let mut next_ref = Ref::new(1);
+    let resources = next_ref.bump();
+    Self {
+      chunk: Chunk::new(),
+      next_ref,
+      resources,
+      x_objects: vec![],
+      shadings: vec![],
+      patterns: vec![],
+      ext_states: vec![],
+      alpha_states: HashMap::default(),
+      blend_states: HashMap::default(),
+      paths: HashMap::default(),
+      images: HashMap::default(),
+      bundles: HashMap::default(),
+    }
+  }
+}
+
+impl PdfWriter {
+  fn alloc(&mut self) -> Ref { self.next_ref.bump() }
+
+  fn write_resources(&mut self) {
+    let mut resources = self
+      .chunk
+      .indirect(self.resources)
+      .start::<Resources>();
+    let mut x_objects = resources.x_objects();
+    self.x_objects.iter().for_each(|(name, id)| {
+      x_objects.pair(Name(name.as_bytes()), *id);
+    });
+    x_objects.finish();
+    let mut shadings = resources.shadings();
+    self.shadings.iter().for_each(|(name, id)| {
+      shadings.pair(Name(name.as_bytes()), *id);
+    });
+    shadings.finish();
+    let mut patterns = resources.patterns();
+    self.patterns.iter().for_each(|(name, id)| {
+      patterns.pair(Name(name.as_bytes()), *id);
+    });
+    patterns.finish();
+    let mut ext_states = resources.ext_g_states();
+    self.ext_states.iter().for_each(|(name, id)| {
+      ext_states.pair(Name(name.as_bytes()), *id);
+    });
+  }
+
+  fn new_x_object(&mut self, prefix: &str) -> (String, Ref) {
+    let name = format!("{prefix}{}", self.x_objects.len());
+    let id = self.alloc();
+    self.x_objects.push((name.clone(), id));
+    (name, id)
+  }
+
+  fn write_form(
+    &mut self, prefix: &str, commands: &[PaintCommand], bbox: &Rect, group: bool,
+  ) -> String {
+    let mut content = Content::new();
+    self.write_commands(&mut content, commands);
+    let data = content.finish();
+
+    let (name, id) = self.new_x_object(prefix);
+    let mut form = self.chunk.form_xobject(id, &data);
+    form
+      .bbox(pdf_rect(bbox))
+      .pair(Name(b"Resources"), self.resources);
+    if group {
+      form.group().transparency();
+    }
+    name
+  }
+
+  fn write_commands(&mut self, content: &mut Content, commands: &[PaintCommand]) {
+    // The clips only work for the commands in the same level, the unpopped
+    // clips of the level will be restored at the end.
+    let mut clips = 0;
+    for cmd in commands {
+      match cmd {
+        PaintCommand::Path(PathCommand { path, transform, action, .. }) => {
+          content.save_state();
+          match action {
+            PaintPathAction::Clip => {
+              write_path(content, path, Some(transform));
+              content.clip_nonzero().end_path();
+              clips += 1;
+              // Keep the state until the clip is popped.
+              continue;
+            }
+            PaintPathAction::Color(color) => {
+              self.set_alpha(content, color.alpha as f32 / 255.);
+              let [r, g, b, _] = color.into_f32_components();
+              content.set_fill_rgb(r, g, b);
+              if let PaintPath::Share(path) = path {
+                apply_transform(content, transform);
+                let name = self.shared_path(path);
+                content.x_object(Name(name.as_bytes()));
+              } else {
+                write_path(content, path, Some(transform));
+                content.fill_nonzero();
+              }
+            }
+            PaintPathAction::Image { img, opacity, rect, repeat, filter } => {
+              apply_transform(content, transform);
+              write_path(content, path, None);
+              content.clip_nonzero().end_path();
+              self.set_alpha(content, *opacity);
+              let smooth = *filter == ImageFilter::Linear;
+              self.fill_image(content, img, rect, *repeat, smooth, path.bounds());
+            }
+            PaintPathAction::Radial(gradient) => {
+              apply_transform(content, transform);
+              write_path(content, path, None);
+              content.clip_nonzero().end_path();
+              self.radial_gradient(content, gradient, path.bounds());
+            }
+            PaintPathAction::Linear(gradient) => {
+              apply_transform(content, transform);
+              write_path(content, path, None);
+              content.clip_nonzero().end_path();
+              self.linear_gradient(content, gradient, path.bounds());
+            }
+            PaintPathAction::Conic(gradient) => {
+              apply_transform(content, transform);
+              write_path(content, path, None);
+              content.clip_nonzero().end_path();
+              // PDF has no conic shading, fill the wedges with solid colors.
+              for (wedge, color) in gradient.wedges(path.bounds()) {
+                if color.alpha == 0 {
+                  continue;
+                }
+                content.save_state();
+                self.set_alpha(content, color.alpha as f32 / 255.);
+                let [r, g, b, _] = color.into_f32_components();
+                content.set_fill_rgb(r, g, b);
+                write_path(content, &wedge, None);
+                content.fill_nonzero();
+                content.restore_state();
+              }
+            }
+            PaintPathAction::Backdrop { tint, opacity, .. } => {
+              self.set_alpha(content, tint.alpha as f32 / 255. * opacity);
+              let [r, g, b, _] = tint.into_f32_components();
+              content.set_fill_rgb(r, g, b);
+              write_path(content, path, Some(transform));
+              content.fill_nonzero();
+            }
+            PaintPathAction::Shadow { rect, radius, sigma, color, inset } => {
+              apply_transform(content, transform);
+              write_path(content, path, None);
+              content.clip_nonzero().end_path();
+              let layers = if *sigma > 0. { SHADOW_LAYERS } else { 1 };
+              // Every layer is composited over the others, so the overlapped
+              // area reaches the alpha of the color.
+              let alpha = 1. - (1. - color.alpha as f32 / 255.).powf(1. / layers as f32);
+              self.set_alpha(content, alpha);
+              let [r, g, b, _] = color.into_f32_components();
+              content.set_fill_rgb(r, g, b);
+              for i in 0..layers {
+                let d = if layers > 1 {
+                  sigma * (4. * (i as f32 + 0.5) / layers as f32 - 2.)
+                } else {
+                  0.
+                };
+                // The inner shadow paints outside of its rectangle, so the
+                // layer spreads in the opposite direction.
+                let d = if *inset { -d } else { d };
+                let layer = rect.inflate(d, d);
+                if layer.is_empty() && !inset {
+                  continue;
+                }
+                let corner = |r: f32| if r > 0. { (r + d).max(0.) } else { 0. };
+                let radius = Radius::new(
+                  corner(radius.top_left),
+                  corner(radius.top_right),
+                  corner(radius.bottom_left),
+                  corner(radius.bottom_right),
+                );
+                if *inset {
+                  write_path(content, &Path::rect(path.bounds()), None);
+                }
+                if !layer.is_empty() {
+                  write_path(content, &Path::rect_round(&layer, &radius), None);
+                }
+                content.fill_even_odd();
+              }
+            }
+          }
+          content.restore_state();
+        }
+        PaintCommand::PopClip => {
+          if clips > 0 {
+            clips -= 1;
+            content.restore_state();
+          }
+        }
+        PaintCommand::Bundle { transform, opacity, bounds, cmds } => {
+          content.save_state();
+          apply_transform(content, transform);
+          self.set_alpha(content, *opacity);
+          let name = match self.bundles.get(cmds) {
+            Some(name) => name.clone(),
+            None => {
+              let name = self.write_form("B", cmds, bounds, true);
+              self.bundles.insert(cmds.clone(), name.clone());
+              name
+            }
+          };
+          content
+            .x_object(Name(name.as_bytes()))
+            .restore_state();
+        }
+        PaintCommand::Layer { opacity, blend_mode, bounds, cmds } => {
+          content.save_state();
+          self.set_alpha(content, *opacity);
+          self.set_blend_mode(content, *blend_mode);
+          let name = self.write_form("L", cmds, bounds, true);
+          content
+            .x_object(Name(name.as_bytes()))
+            .restore_state();
+        }
+      }
+    }
+    (0..clips).for_each(|_| {
+      content.restore_state();
+    });
+  }
+
+  fn set_alpha(&mut self, content: &mut Content, alpha: f32) {
+    if alpha >= 1. {
+      return;
+    }
+    let name = match self.alpha_states.get(&alpha.to_bits()) {
+      Some(name) => name.clone(),
+      None => {
+        let name = format!("Gs{}", self.ext_states.len());
+        let id = self.alloc();
+        self
+          .chunk
+          .ext_graphics(id)
+          .non_stroking_alpha(alpha);
+        self.ext_states.push((name.clone(), id));
+        self
+          .alpha_states
+          .insert(alpha.to_bits(), name.clone());
+        name
+      }
+    };
+    content.set_parameters(Name(name.as_bytes()));
+  }
+
+  fn set_blend_mode(&mut self, content: &mut Content, mode: BlendMode) {
+    if mode == BlendMode::Normal {
+      return;
+    }
+    let name = match self.blend_states.get(&mode) {
+      Some(name) => name.clone(),
+      None => {
+        let name = format!("Gs{}", self.ext_states.len());
+        let id = self.alloc();
+        self
+          .chunk
+          .ext_graphics(id)
+          .blend_mode(pdf_blend_mode(mode));
+        self.ext_states.push((name.clone(), id));
+        self.blend_states.insert(mode, name.clone());
+        name
+      }
+    };
+    content.set_parameters(Name(name.as_bytes()));
+  }
+
+  /// Write the shared path as a form, its fill color is decided by the
+  /// content that uses it.
+  fn shared_path(&mut self, path: &Resource<Path>) -> String {
+    if let Some(name) = self.paths.get(path) {
+      return name.clone();
+    }
+    let mut content = Content::new();
+    write_path(&mut content, path, None);
+    content.fill_nonzero();
+    let data = content.finish();
+
+    let (name, id) = self.new_x_object("P");
+    self
+      .chunk
+      .form_xobject(id, &data)
+      .bbox(pdf_rect(path.bounds()));
+    self.paths.insert(path.clone(), name.clone());
+    name
+  }
+
+  /// Fill the image to cover the `bounds`, the repeated image is filled by a
+  /// tiling pattern.
+  fn fill_image(
+    &mut self, content: &mut Content, img: &Resource<PixelImage>, rect: &Rect, repeat: ImageRepeat,
+    smooth: bool, bounds: &Rect,
+  ) {
+    let (w, h) = (rect.width(), rect.height());
+    if w <= 0. || h <= 0. {
+      return;
+    }
+    let name = self.image(img, smooth);
+    if !repeat.repeat_x() && !repeat.repeat_y() {
+      // The image space is y-up, but the current space is y-down.
+      content
+        .transform([w, 0., 0., -h, rect.min_x(), rect.max_y()])
+        .x_object(Name(name.as_bytes()));
+      return;
+    }
+
+    // The tiles only spread along the repeated axes.
+    let (x, x_end) = if repeat.repeat_x() {
+      (bounds.min_x(), bounds.max_x())
+    } else {
+      (rect.min_x(), rect.max_x())
+    };
+    let (y, y_end) = if repeat.repeat_y() {
+      (bounds.min_y(), bounds.max_y())
+    } else {
+      (rect.min_y(), rect.max_y())
+    };
+    let area = Rect::new(Point::new(x, y), Size::new(x_end - x, y_end - y));
+    let pattern = self.image_pattern(&name, rect);
+
+    // The matrix of a pattern maps to the space of its parent content stream,
+    // so the pattern is filled in a form to follow the current transform.
+    let mut tiles = Content::new();
+    tiles
+      .set_fill_color_space(ColorSpaceOperand::Pattern)
+      .set_fill_pattern(None, Name(pattern.as_bytes()))
+      .rect(area.min_x(), area.min_y(), area.width(), area.height())
+      .fill_nonzero();
+    let data = tiles.finish();
+    let (form, id) = self.new_x_object("T");
+    self
+      .chunk
+      .form_xobject(id, &data)
+      .bbox(pdf_rect(&area))
+      .pair(Name(b"Resources"), self.resources);
+    content.x_object(Name(form.as_bytes()));
+  }
+
+  /// A tiling pattern repeats the image placed in the `rect`.
+  fn image_pattern(&mut self, image: &str, rect: &Rect) -> String {
+    let mut cell = Content::new();
+    // The cell is a unit square in y-down space, but the image space is y-up.
+    cell
+      .transform([1., 0., 0., -1., 0., 1.])
+      .x_object(Name(image.as_bytes()));
+    let data = cell.finish();
+
+    let name = format!("Pt{}", self.patterns.len());
+    let id = self.alloc();
+    self.patterns.push((name.clone(), id));
+    let mut pattern = self.chunk.tiling_pattern(id, &data);
+    pattern
+      .paint_type(PaintType::Colored)
+      .tiling_type(TilingType::ConstantSpacing)
+      .bbox(PdfRect::new(0., 0., 1., 1.))
+      .x_step(1.)
+      .y_step(1.)
+      .matrix([rect.width(), 0., 0., rect.height(), rect.min_x(), rect.min_y()]);
+    pattern.pair(Name(b"Resources"), self.resources);
+    name
+  }
+
+  fn image(&mut self, img: &Resource<PixelImage>, smooth: bool) -> String {
+    let key = (img.clone(), smooth);
+    if let Some(name) = self.images.get(&key) {
+      return name.clone();
+    }
+    let bytes = img.pixel_bytes();
+    let (rgb, alpha): (Vec<u8>, Vec<u8>) = match img.color_format() {
+      ColorFormat::Rgba8 => (
+        bytes
+          .chunks_exact(4)
+          .flat_map(|p| [p[0], p[1], p[2]])
+          .collect(),
+        bytes.chunks_exact(4).map(|p| p[3]).collect(),
+      ),
+      // The alpha image is used as a mask of black color.
+      ColorFormat::Alpha8 => (vec![0; bytes.len() * 3], bytes.to_vec()),
+    };
+    let (width, height) = (img.width() as i32, img.height() as i32);
+
+    let mask = if alpha.iter().any(|a| *a < 255) {
+      let id = self.alloc();
+      let mut mask = self.chunk.image_xobject(id, &alpha);
+      mask.width(width).height(height);
+      mask.color_space().device_gray();
+      mask.bits_per_component(8).interpolate(smooth);
+      mask.finish();
+      Some(id)
+    } else {
+      None
+    };
+
+    let (name, id) = self.new_x_object("Im");
+    let mut image = self.chunk.image_xobject(id, &rgb);
+    image.width(width).height(height);
+    image.color_space().device_rgb();
+    image.bits_per_component(8).interpolate(smooth);
+    if let Some(mask) = mask {
+      image.s_mask(mask);
+    }
+    image.finish();
+    self.images.insert(key, name.clone());
+    name
+  }
+
+  fn linear_gradient(&mut self, content: &mut Content, gradient: &LinearGradient, bounds: &Rect) {
+    let LinearGradient { start, end, stops, spread_method } = gradient;
+    let v = *end - *start;
+    let mut domain = [0., 1.];
+    if *spread_method != SpreadMethod::Pad && v.square_length() > 0. {
+      // Repeat the gradient to cover all the corners of the bounds.
+      let offsets = corners(bounds).map(|p| (p - *start).dot(v) / v.square_length());
+      let min = offsets
+        .iter()
+        .fold(f32::MAX, |a, b| a.min(*b))
+        .floor();
+      let max = offsets
+        .iter()
+        .fold(f32::MIN, |a, b| a.max(*b))
+        .ceil();
+      let periods = MAX_GRADIENT_PERIODS as f32;
+      domain = [min.max(-periods), max.min(periods).max(min + 1.)];
+    }
+    let p0 = *start + v * domain[0];
+    let p1 = *start + v * domain[1];
+    let shading = Shading {
+      kind: FunctionShadingType::Axial,
+      coords: vec![p0.x, p0.y, p1.x, p1.y],
+      domain,
+      stops,
+      spread: *spread_method,
+    };
+    self.paint_shading(content, &shading, bounds);
+  }
+
+  fn radial_gradient(&mut self, content: &mut Content, gradient: &RadialGradient, bounds: &Rect) {
+    let RadialGradient {
+      start_center: c0,
+      start_radius: r0,
+      end_center: c1,
+      end_radius: r1,
+      stops,
+      spread_method,
+    } = gradient;
+    let circle = |t: f32| (c0.lerp(*c1, t), r0 + (r1 - r0) * t);
+    let mut domain = [0., 1.];
+    if *spread_method != SpreadMethod::Pad {
+      let covered = |t: f32| {
+        let (center, radius) = circle(t);
+        corners(bounds)
+          .iter()
+          .all(|p| (*p - center).length() <= radius)
+      };
+      // Repeat the circles in the direction they grow, until they cover the
+      // bounds or shrink to zero.
+      let (grow, shrink) = if r1 >= r0 { (1, 0) } else { (0, 1) };
+      let step = if r1 >= r0 { 1. } else { -1. };
+      for _ in 0..MAX_GRADIENT_PERIODS {
+        if covered(domain[grow]) {
+          break;
+        }
+        domain[grow] += step;
+      }
+      for _ in 0..MAX_GRADIENT_PERIODS {
+        if circle(domain[shrink] - step).1 < 0. {
+          break;
+        }
+        domain[shrink] -= step;
+      }
+    }
+    let (p0, radius0) = circle(domain[0]);
+    let (p1, radius1) = circle(domain[1]);
+    let shading = Shading {
+      kind: FunctionShadingType::Radial,
+      coords: vec![p0.x, p0.y, radius0.max(0.), p1.x, p1.y, radius1.max(0.)],
+      domain,
+      stops,
+      spread: *spread_method,
+    };
+    self.paint_shading(content, &shading, bounds);
+  }
+
+  /// Paint the shading in the current clip, the alpha of the stops is applied
+  /// by a soft mask.
+  fn paint_shading(&mut self, content: &mut Content, shading: &Shading, bounds: &Rect) {
+    if shading.stops.iter().any(|s| s.color.alpha < 255) {
+      let alpha = self.write_shading(shading, |c| vec![c.alpha as f32 / 255.]);
+      let mut mask_content = Content::new();
+      mask_content.shading(Name(alpha.as_bytes()));
+      let data = mask_content.finish();
+
+      let form = self.alloc();
+      let mut mask = self.chunk.form_xobject(form, &data);
+      mask
+        .bbox(pdf_rect(bounds))
+        .pair(Name(b"Resources"), self.resources);
+      mask
+        .group()
+        .transparency()
+        .color_space()
+        .device_gray();
+      mask.finish();
+
+      let name = format!("Gs{}", self.ext_states.len());
+      let id = self.alloc();
+      self
+        .chunk
+        .ext_graphics(id)
+        .soft_mask()
+        .subtype(MaskType::Luminosity)
+        .group(form);
+      self.ext_states.push((name.clone(), id));
+      content.set_parameters(Name(name.as_bytes()));
+    }
+
+    let name = self.write_shading(shading, |c| {
+      let [r, g, b, _] = c.into_f32_components();
+      vec![r, g, b]
+    });
+    content.shading(Name(name.as_bytes()));
+  }
+
+  fn write_shading(
+    &mut self, shading: &Shading, components: impl Fn(&Color) -> Vec<f32>,
+  ) -> String {
+    let Shading { kind, coords, domain, stops, spread } = shading;
+    let gray = components(&Color::BLACK).len() == 1;
+    let function = self.gradient_function(stops, *domain, *spread, &components);
+
+    let name = format!("Sh{}", self.shadings.len());
+    let id = self.alloc();
+    let mut shading = self.chunk.function_shading(id);
+    shading.shading_type(*kind);
+    if gray {
+      shading.color_space().device_gray();
+    } else {
+      shading.color_space().device_rgb();
+    }
+    let extend = *spread == SpreadMethod::Pad;
+    shading
+      .function(function)
+      .coords(coords.iter().copied())
+      .extend([extend, extend]);
+    shading
+      .insert(Name(b"Domain"))
+      .array()
+      .items(domain.iter().copied());
+    shading.finish();
+    self.shadings.push((name.clone(), id));
+    name
+  }
+
+  /// Write a function that maps the offset in the `domain` to the color of
+  /// the stops, the stops are repeated in every period of the domain if the
+  /// spread method is not `Pad`.
+  fn gradient_function(
+    &mut self, stops: &[GradientStop], domain: [f32; 2], spread: SpreadMethod,
+    components: &impl Fn(&Color) -> Vec<f32>,
+  ) -> Ref {
+    let period = self.stops_function(stops, components);
+    if spread == SpreadMethod::Pad {
+      return period;
+    }
+
+    let (start, end) = (domain[0] as i32, domain[1] as i32);
+    let id = self.alloc();
+    self
+      .chunk
+      .stitching_function(id)
+      .domain(domain)
+      .functions((start..end).map(|_| period))
+      .bounds((start + 1..end).map(|k| k as f32))
+      .encode((start..end).flat_map(|k| {
+        if spread == SpreadMethod::Reflect && k.rem_euclid(2) == 1 { [1., 0.] } else { [0., 1.] }
+      }));
+    id
+  }
+
+  fn stops_function(
+    &mut self, stops: &[GradientStop], components: &impl Fn(&Color) -> Vec<f32>,
+  ) -> Ref {
+    // Make sure the stops cover the whole range of [0, 1].
+    let mut stops: Vec<GradientStop> = stops
+      .iter()
+      .map(|s| GradientStop::new(s.color, s.offset.clamp(0., 1.)))
+      .collect();
+    let first = stops
+      .first()
+      .map_or(Color::TRANSPARENT, |s| s.color);
+    if !stops.first().is_some_and(|s| s.offset <= 0.) {
+      stops.insert(0, GradientStop::new(first, 0.));
+    }
+    let last = stops.last().unwrap().color;
+    if stops.last().unwrap().offset < 1. {
+      stops.push(GradientStop::new(last, 1.));
+    }
+
+    let functions: Vec<Ref> = stops
+      .windows(2)
+      .map(|pair| {
+        let id = self.alloc();
+        self
+          .chunk
+          .exponential_function(id)
+          .domain([0., 1.])
+          .c0(components(&pair[0].color))
+          .c1(components(&pair[1].color))
+          .n(1.);
+        id
+      })
+      .collect();
+    if let [function] = functions[..] {
+      return function;
+    }
+
+    let id = self.alloc();
+    let mut bounds = Vec::with_capacity(functions.len() - 1);
+    let mut prev = 0.;
+    for s in &stops[1..stops.len() - 1] {
+      prev = s.offset.max(prev);
+      bounds.push(prev);
+    }
+    self
+      .chunk
+      .stitching_function(id)
+      .domain([0., 1.])
+      .encode(functions.iter().flat_map(|_| [0., 1.]))
+      .functions(functions)
+      .bounds(bounds);
+    id
+  }
+}
+
+struct Shading<'a> {
+  kind: FunctionShadingType,
+  coords: Vec<f32>,
+  domain: [f32; 2],
+  stops: &'a [GradientStop],
+  spread: SpreadMethod,
+}
+
+fn write_path(content: &mut Content, path: &Path, transform: Option<&Transform>) {
+  let map = |p: Point| transform.map_or(p, |t| t.transform_point(p));
+  let mut current = Point::zero();
+  path.segments().for_each(|seg| match seg {
+    PathSegment::MoveTo(to) => {
+      current = map(to);
+      content.move_to(current.x, current.y);
+    }
+    PathSegment::LineTo(to) => {
+      current = map(to);
+      content.line_to(current.x, current.y);
+    }
+    PathSegment::QuadTo { ctrl, to } => {
+      // PDF only supports cubic bezier curve, so elevate the quadratic one.
+      let (ctrl, to) = (map(ctrl), map(to));
+      let ctrl1 = current + (ctrl - current) * (2. / 3.);
+      let ctrl2 = to + (ctrl - to) * (2. / 3.);
+      content.cubic_to(ctrl1.x, ctrl1.y, ctrl2.x, ctrl2.y, to.x, to.y);
+      current = to;
+    }
+    PathSegment::CubicTo { to, ctrl1, ctrl2 } => {
+      let (ctrl1, ctrl2, to) = (map(ctrl1), map(ctrl2), map(to));
+      content.cubic_to(ctrl1.x, ctrl1.y, ctrl2.x, ctrl2.y, to.x, to.y);
+      current = to;
+    }
+    PathSegment::Close(true) => {
+      content.close_path();
+    }
+    PathSegment::Close(false) => {}
+  });
+}
+
+fn apply_transform(content: &mut Content, t: &Transform) {
+  if *t != Transform::identity() {
+    content.transform([t.m11, t.m12, t.m21, t.m22, t.m31, t.m32]);
+  }
+}
+
+fn pdf_blend_mode(mode: BlendMode) -> PdfBlendMode {
+  match mode {
+    BlendMode::Normal => PdfBlendMode::Normal,
+    BlendMode::Multiply => PdfBlendMode::Multiply,
+    BlendMode::Screen => PdfBlendMode::Screen,
+    BlendMode::Overlay => PdfBlendMode::Overlay,
+    BlendMode::Darken => PdfBlendMode::Darken,
+    BlendMode::Lighten => PdfBlendMode::Lighten,
+    BlendMode::ColorDodge => PdfBlendMode::ColorDodge,
+    BlendMode::ColorBurn => PdfBlendMode::ColorBurn,
+    BlendMode::HardLight => PdfBlendMode::HardLight,
+    BlendMode::SoftLight => PdfBlendMode::SoftLight,
+    BlendMode::Difference => PdfBlendMode::Difference,
+    BlendMode::Exclusion => PdfBlendMode::Exclusion,
+  }
+}
+
+fn pdf_rect(rect: &Rect) -> PdfRect {
+  PdfRect::new(rect.min_x(), rect.min_y(), rect.max_x(), rect.max_y())
+}
+
+fn corners(rect: &Rect) -> [Point; 4] {
+  [
+    rect.min(),
+    Point::new(rect.max_x(), rect.min_y()),
+    rect.max(),
+    Point::new(rect.min_x(), rect.max_y()),
+  ]
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_geom::rect;
+
+  use super::*;
+  use crate::{Brush, ImageBrush, Painter};
+
+  fn commands(painter: &mut Painter) -> Box<[PaintCommand]> { painter.finish().to_vec().into() }
+
+  fn contains(pdf: &[u8], s: &str) -> bool { pdf.windows(s.len()).any(|w| w == s.as_bytes()) }
+
+  #[test]
+  fn paginate() {
+    let mut painter = Painter::new(rect(0., 0., 100., 250.));
+    painter
+      .set_brush(Color::RED)
+      .rect(&rect(0., 0., 100., 250.))
+      .fill();
+    let pdf = export_pdf(Size::new(100., 250.), Size::new(100., 100.), &commands(&mut painter));
+
+    assert!(pdf.starts_with(b"%PDF-"));
+    assert!(contains(&pdf, "/Count 3"));
+    assert!(contains(&pdf, "1 0 0 -1 0 100 cm\n1 0 0 1 0 -200 cm\n/Content0 Do"));
+    assert!(contains(&pdf, "1 0 0 rg\n0 0 m\n100 0 l\n100 250 l\n0 250 l\nh\nf"));
+  }
+
+  #[test]
+  fn share_path_embed_once() {
+    let path = Resource::new(Path::rect(&rect(0., 0., 10., 10.)));
+    let mut painter = Painter::new(rect(0., 0., 100., 100.));
+    painter
+      .set_brush(Color::BLUE)
+      .fill_path(path.clone())
+      .translate(20., 0.)
+      .fill_path(path);
+    let pdf = export_pdf(Size::new(100., 100.), Size::new(100., 100.), &commands(&mut painter));
+
+    assert!(contains(&pdf, "/P0 Do"));
+    assert!(contains(&pdf, "0 0 1 rg\n1 0 0 1 20 0 cm\n/P0 Do"));
+    assert_eq!(
+      pdf
+        .windows(b"/Subtype /Form".len())
+        .filter(|w| w == b"/Subtype /Form")
+        .count(),
+      2
+    );
+  }
+
+  #[test]
+  fn clip_and_bundle_opacity() {
+    let inner = {
+      let mut painter = Painter::new(rect(0., 0., 10., 10.));
+      painter
+        .set_brush(Color::BLUE)
+        .rect(&rect(0., 0., 10., 10.))
+        .fill();
+      commands(&mut painter)
+    };
+
+    let mut painter = Painter::new(rect(0., 0., 100., 100.));
+    painter
+      .clip(Path::rect(&rect(0., 0., 50., 50.)))
+      .apply_alpha(0.5)
+      .draw_bundle_commands(rect(0., 0., 10., 10.), Resource::new(inner));
+    let pdf = export_pdf(Size::new(100., 100.), Size::new(100., 100.), &commands(&mut painter));
+
+    assert!(contains(&pdf, "W\nn\nq\n/Gs0 gs\n/B0 Do\nQ\nQ"));
+    assert!(contains(&pdf, "/ca 0.5"));
+    assert!(contains(&pdf, "/S /Transparency"));
+  }
+
+  #[test]
+  fn blend_layer() {
+    let mut painter = Painter::new(rect(0., 0., 100., 100.));
+    painter
+      .save_layer(0.5, BlendMode::Screen)
+      .set_brush(Color::RED)
+      .rect(&rect(0., 0., 10., 10.))
+      .fill()
+      .restore();
+    let pdf = export_pdf(Size::new(100., 100.), Size::new(100., 100.), &commands(&mut painter));
+
+    assert!(contains(&pdf, "q\n/Gs0 gs\n/Gs1 gs\n/L0 Do\nQ"));
+    assert!(contains(&pdf, "/BM /Screen"));
+  }
+
+  #[test]
+  fn gradient_and_image() {
+    let stops =
+      vec![GradientStop::new(Color::RED, 0.), GradientStop::new(Color::BLUE.with_alpha(0.), 1.)];
+    let img = PixelImage::new(vec![255; 16].into(), 2, 2, ColorFormat::Rgba8);
+    let mut painter = Painter::new(rect(0., 0., 100., 100.));
+    painter
+      .set_brush(Brush::LinearGradient(LinearGradient {
+        start: Point::new(0., 0.),
+        end: Point::new(10., 0.),
+        stops,
+        spread_method: SpreadMethod::Repeat,
+      }))
+      .rect(&rect(0., 0., 30., 10.))
+      .fill()
+      .set_brush(img)
+      .rect(&rect(0., 0., 4., 2.))
+      .fill();
+    let pdf = export_pdf(Size::new(100., 100.), Size::new(100., 100.), &commands(&mut painter));
+
+    // The gradient repeats three times to cover the path.
+    assert!(contains(&pdf, "/Domain [0 3]"));
+    assert!(contains(&pdf, "/Coords [0 0 30 0]"));
+    // The alpha of the stops is applied by a soft mask.
+    assert!(contains(&pdf, "/S /Luminosity"));
+    assert!(contains(&pdf, "/Gs0 gs\n/Sh1 sh"));
+    // The image is repeated by a tiling pattern in its own form.
+    assert!(contains(&pdf, "/Pattern cs\n/Pt0 scn\n0 0 4 2 re\nf"));
+    assert!(contains(&pdf, "/Matrix [2 0 0 2 0 0]"));
+    assert!(contains(&pdf, "1 0 0 -1 0 1 cm\n/Im0 Do"));
+  }
+
+  #[test]
+  fn image_tiles() {
+    let img = PixelImage::new(vec![255; 4].into(), 1, 1, ColorFormat::Rgba8);
+    let mut painter = Painter::new(rect(0., 0., 1000., 1000.));
+    let mut brush = ImageBrush::new(Resource::new(img));
+    brush.bounds = Some(rect(10., 20., 1., 1.));
+    painter.draw_img_brush(brush.clone(), &rect(0., 0., 1000., 1000.));
+    let pdf = export_pdf(Size::new(1000., 1000.), Size::new(1000., 1000.), &commands(&mut painter));
+
+    // A tiny tile over a large page only writes one pattern fill.
+    assert!(pdf.len() < 4096);
+    assert!(contains(&pdf, "/Matrix [1 0 0 1 10 20]"));
+    assert!(contains(&pdf, "0 0 1000 1000 re\nf"));
+
+    // Only repeat in the horizontal direction, the tiles keep in the row.
+    brush.repeat = ImageRepeat::RepeatX;
+    painter.draw_img_brush(brush.clone(), &rect(0., 0., 1000., 1000.));
+    let pdf = export_pdf(Size::new(1000., 1000.), Size::new(1000., 1000.), &commands(&mut painter));
+    assert!(contains(&pdf, "0 20 1000 1 re\nf"));
+
+    // No repeat draws the image directly.
+    brush.repeat = ImageRepeat::NoRepeat;
+    painter.draw_img_brush(brush, &rect(0., 0., 1000., 1000.));
+    let pdf = export_pdf(Size::new(1000., 1000.), Size::new(1000., 1000.), &commands(&mut painter));
+    assert!(contains(&pdf, "1 0 0 -1 10 21 cm\n/Im0 Do"));
+    assert!(!contains(&pdf, "/Pattern cs"));
+  }
+}
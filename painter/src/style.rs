@@ -1,18 +1,21 @@
 use ribir_algo::Resource;
+use ribir_geom::{Rect, Size, Vector};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-  color::{LinearGradient, RadialGradient},
+  color::{ConicGradient, LinearGradient, RadialGradient},
   Color, PixelImage,
 };
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Brush {
   Color(Color),
-  /// Image brush always use a repeat mode to brush the path.
-  Image(Resource<PixelImage>),
+  /// Brush the path with an image, see [`ImageBrush`] for how the image is
+  /// placed.
+  Image(ImageBrush),
   RadialGradient(RadialGradient),
   LinearGradient(LinearGradient),
+  ConicGradient(ConicGradient),
 }
 
 impl Brush {
@@ -35,8 +38,17 @@ impl From<Color> for Option<Brush> {
 }
 
 impl From<Resource<PixelImage>> for Brush {
+  /// The image keeps at the origin of the path coordinate and repeats, like a
+  /// pattern of the path.
+  fn from(img: Resource<PixelImage>) -> Self {
+    let bounds = Rect::from_size(Size::new(img.width() as f32, img.height() as f32));
+    Brush::Image(ImageBrush::new(img).with_bounds(bounds))
+  }
+}
+
+impl From<ImageBrush> for Brush {
   #[inline]
-  fn from(img: Resource<PixelImage>) -> Self { Brush::Image(img) }
+  fn from(img: ImageBrush) -> Self { Brush::Image(img) }
 }
 
 impl From<PixelImage> for Brush {
@@ -48,3 +60,202 @@ impl Default for Brush {
   #[inline]
   fn default() -> Self { Color::BLACK.into() }
 }
+
+/// How an image is sized in the box it paints, it works like the `object-fit`
+/// of CSS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ImageFit {
+  /// Keep the original size of the image.
+  #[default]
+  None,
+  /// Stretch the image to the size of the box, the aspect ratio is not kept.
+  Fill,
+  /// Scale the image to fit inside the box, and keep its aspect ratio.
+  Contain,
+  /// Scale the image to cover the whole box, and keep its aspect ratio.
+  Cover,
+}
+
+/// How an image repeats to fill the area out of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ImageRepeat {
+  /// Repeat in both directions.
+  #[default]
+  Repeat,
+  /// Only repeat in the horizontal direction.
+  RepeatX,
+  /// Only repeat in the vertical direction.
+  RepeatY,
+  /// Paint the image only once, the area out of it is transparent.
+  NoRepeat,
+}
+
+impl ImageRepeat {
+  #[inline]
+  pub fn repeat_x(self) -> bool { matches!(self, ImageRepeat::Repeat | ImageRepeat::RepeatX) }
+
+  #[inline]
+  pub fn repeat_y(self) -> bool { matches!(self, ImageRepeat::Repeat | ImageRepeat::RepeatY) }
+}
+
+/// The filter to sample the image when it's scaled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ImageFilter {
+  /// Interpolate the nearest pixels, the image looks smooth.
+  #[default]
+  Linear,
+  /// Use the nearest pixel, keep the hard edges of the pixel art.
+  Nearest,
+}
+
+/// The image brush describes how to place an image in a box and fill a path
+/// with it, like the background image of CSS.
+///
+/// The image is sized by the `fit`, aligned in the box by the `align`, and then
+/// repeated by the `repeat`. By default, the image keeps its original size and
+/// repeats from the top-left of the bounds of the path.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImageBrush {
+  pub img: Resource<PixelImage>,
+  /// The box to place the image in, in the coordinate of the path. Use the
+  /// bounds of the painted path if it's `None`.
+  pub bounds: Option<Rect>,
+  pub fit: ImageFit,
+  pub repeat: ImageRepeat,
+  /// The alignment of the image in the box, as a fraction of the free space.
+  /// `(0, 0)` aligns to the top-left, `(0.5, 0.5)` centers the image and `(1,
+  /// 1)` aligns to the bottom-right.
+  pub align: Vector,
+  pub filter: ImageFilter,
+}
+
+impl ImageBrush {
+  pub fn new(img: Resource<PixelImage>) -> Self {
+    Self {
+      img,
+      bounds: None,
+      fit: ImageFit::None,
+      repeat: ImageRepeat::Repeat,
+      align: Vector::zero(),
+      filter: ImageFilter::Linear,
+    }
+  }
+
+  #[inline]
+  pub fn with_bounds(mut self, bounds: Rect) -> Self {
+    self.bounds = Some(bounds);
+    self
+  }
+
+  #[inline]
+  pub fn with_fit(mut self, fit: ImageFit) -> Self {
+    self.fit = fit;
+    self
+  }
+
+  #[inline]
+  pub fn with_repeat(mut self, repeat: ImageRepeat) -> Self {
+    self.repeat = repeat;
+    self
+  }
+
+  #[inline]
+  pub fn with_align(mut self, x: f32, y: f32) -> Self {
+    self.align = Vector::new(x, y);
+    self
+  }
+
+  #[inline]
+  pub fn with_filter(mut self, filter: ImageFilter) -> Self {
+    self.filter = filter;
+    self
+  }
+
+  /// Return the rect of the image placed in the `path_bounds`, the image
+  /// repeats from it.
+  pub fn image_rect(&self, path_bounds: &Rect) -> Rect {
+    let bounds = self.bounds.unwrap_or(*path_bounds);
+    let img_size = Size::new(self.img.width() as f32, self.img.height() as f32);
+    let size = match self.fit {
+      ImageFit::None => img_size,
+      ImageFit::Fill => bounds.size,
+      ImageFit::Contain | ImageFit::Cover => {
+        let sx = bounds.width() / img_size.width;
+        let sy = bounds.height() / img_size.height;
+        let scale = if self.fit == ImageFit::Contain { sx.min(sy) } else { sx.max(sy) };
+        img_size * scale
+      }
+    };
+    let free = bounds.size - size;
+    let offset = Vector::new(free.width * self.align.x, free.height * self.align.y);
+    Rect::new(bounds.origin + offset, size)
+  }
+}
+
+impl From<Resource<PixelImage>> for ImageBrush {
+  #[inline]
+  fn from(img: Resource<PixelImage>) -> Self { Self::new(img) }
+}
+
+impl From<PixelImage> for ImageBrush {
+  #[inline]
+  fn from(img: PixelImage) -> Self { Self::new(Resource::new(img)) }
+}
+
+/// The shadow cast by a box, it works like the `box-shadow` of CSS.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct BoxShadow {
+  /// The offset of the shadow relative to the box.
+  pub offset: Vector,
+  /// The larger this value, the bigger the blur, the standard deviation of the
+  /// Gaussian blur is half of it.
+  pub blur_radius: f32,
+  /// Positive values cause the shadow to expand and grow bigger, negative
+  /// values cause the shadow to shrink.
+  pub spread_radius: f32,
+  /// The color of the shadow.
+  pub color: Color,
+  /// If true, the shadow is drawn inside the box, as if the content was
+  /// depressed inside the box.
+  pub inset: bool,
+}
+
+impl BoxShadow {
+  #[inline]
+  pub fn new(offset: Vector, blur_radius: f32, spread_radius: f32, color: Color) -> Self {
+    Self { offset, blur_radius, spread_radius, color, inset: false }
+  }
+
+  /// Convert the shadow to an inner shadow.
+  #[inline]
+  pub fn inset(mut self) -> Self {
+    self.inset = true;
+    self
+  }
+}
+
+impl From<BoxShadow> for Vec<BoxShadow> {
+  #[inline]
+  fn from(shadow: BoxShadow) -> Self { vec![shadow] }
+}
+
+/// The filter applied to the content painted behind a box, it works like the
+/// `backdrop-filter` of CSS with a blur and a color tint, such as the frosted
+/// glass.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct BackdropFilter {
+  /// The larger this value, the bigger the blur, the standard deviation of the
+  /// Gaussian blur is half of it.
+  pub blur_radius: f32,
+  /// The color painted over the blurred content.
+  pub tint: Color,
+}
+
+impl BackdropFilter {
+  #[inline]
+  pub fn new(blur_radius: f32, tint: Color) -> Self { Self { blur_radius, tint } }
+
+  /// A filter that only blurs the content behind the box.
+  #[inline]
+  pub fn blur(blur_radius: f32) -> Self { Self::new(blur_radius, Color::TRANSPARENT) }
+}
@@ -7,7 +7,7 @@ use usvg::{Options, Stop, Tree, TreeParsing};
 
 use crate::{
   color::{LinearGradient, RadialGradient},
-  Brush, Color, GradientStop, LineCap, LineJoin, PaintCommand, Path, StrokeOptions,
+  Brush, Color, DashPattern, GradientStop, LineCap, LineJoin, PaintCommand, Path, StrokeOptions,
 };
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -82,6 +82,10 @@ impl Svg {
                 line_cap: cap,
                 line_join: join,
                 miter_limit: stroke.miterlimit.get(),
+                dash: stroke
+                  .dasharray
+                  .as_ref()
+                  .map(|array| DashPattern::new(array.clone(), stroke.dashoffset)),
               };
 
               let (brush, transform) = brush_from_usvg_paint(&stroke.paint, stroke.opacity, &size);
@@ -229,6 +233,9 @@ fn brush_from_usvg_paint(
 
       (Brush::RadialGradient(gradient), matrix_convert(radial_gradient.transform))
     }
+    // SVG defines no conic gradient, and `usvg` has no sweep paint server, so
+    // nothing maps to `Brush::ConicGradient`. The exported conic gradients are
+    // wedges of solid colors, they are imported as colors.
     paint => {
       log::warn!("[painter]: not support `{paint:?}` in svg, use black instead!");
       (Color::BLACK.into(), Transform::identity())
@@ -264,3 +271,42 @@ fn convert_to_gradient_stops(stops: &[Stop]) -> Vec<GradientStop> {
   }
   stops
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn stroke_bounds(dash: &str) -> Rect {
+    let svg = format!(
+      r#"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="20">
+        <path d="M 0 10 L 100 10" fill="none" stroke="black" stroke-width="2" {dash}/>
+      </svg>"#
+    );
+    let svg = Svg::parse_from_bytes(svg.as_bytes()).unwrap();
+    match &svg.commands[0] {
+      PaintCommand::Path(p) => p.paint_bounds,
+      _ => unreachable!(),
+    }
+  }
+
+  #[test]
+  fn stroke_dasharray() {
+    let solid = stroke_bounds("");
+    assert_eq!((solid.min_x(), solid.max_x()), (0., 100.));
+
+    // The dashes are 0..20 and 50..70.
+    let dashed = stroke_bounds(r#"stroke-dasharray="20 30""#);
+    assert_eq!((dashed.min_x(), dashed.max_x()), (0., 70.));
+
+    // The pattern starts at 40, so the dashes are 10..30 and 60..80.
+    let offset = stroke_bounds(r#"stroke-dasharray="20 30" stroke-dashoffset="40""#);
+    assert_eq!((offset.min_x(), offset.max_x()), (10., 80.));
+  }
+
+  #[test]
+  fn undashable_stroke_is_solid() {
+    // The whole path is in the gap of the pattern.
+    let bounds = stroke_bounds(r#"stroke-dasharray="1 200" stroke-dashoffset="50""#);
+    assert_eq!((bounds.min_x(), bounds.max_x()), (0., 100.));
+  }
+}
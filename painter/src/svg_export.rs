@@ -0,0 +1,648 @@
+use std::fmt::Write;
+
+use ribir_geom::{Point, Rect, Size, Transform};
+
+use crate::{
+  color::{LinearGradient, RadialGradient},
+  BlendMode, Color, GradientStop, ImageFilter, ImageRepeat, PaintCommand, PaintPathAction, Path,
+  PathCommand, PathSegment, PixelImage, SpreadMethod,
+};
+
+/// Export the paint commands to a standalone SVG document of `size`.
+///
+/// Every path keeps its transform, the clips become `<clipPath>` groups, and
+/// the bundles become `<g>` elements with their transform and opacity, the
+/// layers become isolated `<g>` elements with their `mix-blend-mode`. The
+/// output is stable for the same commands, so it can be compared textually.
+///
+/// SVG can't read the content behind a path, so the backdrop filters only
+/// keep their tint color. And the conic gradients are approximated by the
+/// small wedges filled with solid colors.
+///
+/// The images are embedded as PNG data urls, that requires the `png` feature,
+/// otherwise the paths painted by image are ignored with a warning.
+pub fn export_svg(size: Size, commands: &[PaintCommand]) -> String {
+  let mut writer = SvgWriter::default();
+  writer.write_commands(commands);
+
+  let SvgWriter { defs, body, clip_groups, .. } = writer;
+  let mut svg = String::new();
+  let _ = write!(
+    svg,
+    r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+    w = size.width,
+    h = size.height
+  );
+  svg.push('\n');
+  if !defs.is_empty() {
+    svg.push_str("<defs>\n");
+    svg.push_str(&defs);
+    svg.push_str("</defs>\n");
+  }
+  svg.push_str(&body);
+  // Close the clip groups that have not been popped.
+  (0..clip_groups).for_each(|_| svg.push_str("</g>\n"));
+  svg.push_str("</svg>\n");
+  svg
+}
+
+#[derive(Default)]
+struct SvgWriter {
+  defs: String,
+  body: String,
+  next_id: usize,
+  clip_groups: usize,
+}
+
+impl SvgWriter {
+  fn write_commands(&mut self, commands: &[PaintCommand]) {
+    // The clips only work for the commands in the same level, the unpopped
+    // clips of the level will be closed at the end.
+    let clip_groups = std::mem::take(&mut self.clip_groups);
+    for cmd in commands {
+      self.write_command(cmd);
+    }
+    (0..self.clip_groups).for_each(|_| self.body.push_str("</g>\n"));
+    self.clip_groups = clip_groups;
+  }
+
+  fn write_command(&mut self, cmd: &PaintCommand) {
+    match cmd {
+      PaintCommand::Path(PathCommand { path, transform, action, .. }) => {
+        let d = path_data(path);
+        if d.is_empty() {
+          if matches!(action, PaintPathAction::Clip) {
+            // Keep the clip pair balanced, an empty clip hides everything.
+            self.body.push_str("<g visibility=\"hidden\">\n");
+            self.clip_groups += 1;
+          }
+          return;
+        }
+        let ts = transform_attr(transform);
+        let fill = match action {
+          PaintPathAction::Color(color) => color_attrs("fill", color),
+          PaintPathAction::Image { img, opacity, rect, repeat, filter } => {
+            let Some(id) = self.write_image_pattern(img, rect, *repeat, *filter, path.bounds())
+            else {
+              return;
+            };
+            let mut fill = format!(r#"fill="url(#{id})""#);
+            if *opacity < 1. {
+              let _ = write!(fill, r#" fill-opacity="{opacity}""#);
+            }
+            fill
+          }
+          PaintPathAction::Radial(gradient) => {
+            format!(r#"fill="url(#{})""#, self.write_radial_gradient(gradient))
+          }
+          PaintPathAction::Linear(gradient) => {
+            format!(r#"fill="url(#{})""#, self.write_linear_gradient(gradient))
+          }
+          PaintPathAction::Conic(gradient) => {
+            // SVG has no conic gradient, fill the wedges clipped by the path.
+            let id = self.new_id("clip");
+            let _ = writeln!(
+              self.defs,
+              r#"<clipPath id="{id}" clipPathUnits="userSpaceOnUse"><path d="{d}"/></clipPath>"#
+            );
+            let _ = writeln!(self.body, r#"<g clip-path="url(#{id})"{ts}>"#);
+            for (wedge, color) in gradient.wedges(path.bounds()) {
+              if color.alpha > 0 {
+                let fill = color_attrs("fill", &color);
+                let _ = writeln!(self.body, r#"<path d="{}" {fill}/>"#, path_data(&wedge));
+              }
+            }
+            self.body.push_str("</g>\n");
+            return;
+          }
+          PaintPathAction::Shadow { rect, radius, sigma, color, inset } => {
+            let shadow = Path::rect_round(rect, radius);
+            let extent = 3. * sigma;
+            let blur = if *sigma > 0. {
+              let area = shadow
+                .bounds()
+                .union(path.bounds())
+                .inflate(extent, extent);
+              let id = self.new_id("blur");
+              let _ = writeln!(
+                self.defs,
+                r#"<filter id="{id}" filterUnits="userSpaceOnUse" x="{}" y="{}" width="{}" height="{}"><feGaussianBlur stdDeviation="{sigma}"/></filter>"#,
+                area.min_x(),
+                area.min_y(),
+                area.width(),
+                area.height()
+              );
+              format!(r#" filter="url(#{id})""#)
+            } else {
+              String::new()
+            };
+            let fill = color_attrs("fill", color);
+            let shadow_d = path_data(&shadow);
+            if *inset {
+              // Blur a hole of the shadow rectangle, and only keep the part
+              // inside the box.
+              let id = self.new_id("clip");
+              let _ = writeln!(
+                self.defs,
+                r#"<clipPath id="{id}" clipPathUnits="userSpaceOnUse"><path d="{d}"/></clipPath>"#
+              );
+              let outer = path_data(&Path::rect(
+                &path
+                  .bounds()
+                  .union(&shadow.bounds().inflate(extent, extent))
+                  .inflate(extent + 1., extent + 1.),
+              ));
+              let _ = writeln!(
+                self.body,
+                r#"<g clip-path="url(#{id})"{ts}><path d="{outer} {shadow_d}" fill-rule="evenodd" {fill}{blur}/></g>"#
+              );
+            } else {
+              let _ = writeln!(self.body, r#"<path d="{shadow_d}"{ts} {fill}{blur}/>"#);
+            }
+            return;
+          }
+          PaintPathAction::Backdrop { tint, opacity, .. } => {
+            let tint = tint.apply_alpha(*opacity);
+            if tint.alpha == 0 {
+              return;
+            }
+            color_attrs("fill", &tint)
+          }
+          PaintPathAction::Clip => {
+            let id = self.new_id("clip");
+            let _ = writeln!(
+              self.defs,
+              r#"<clipPath id="{id}" clipPathUnits="userSpaceOnUse"><path d="{d}"{ts}/></clipPath>"#
+            );
+            let _ = writeln!(self.body, r#"<g clip-path="url(#{id})">"#);
+            self.clip_groups += 1;
+            return;
+          }
+        };
+        let _ = writeln!(self.body, r#"<path d="{d}"{ts} {fill}/>"#);
+      }
+      PaintCommand::PopClip => {
+        if self.clip_groups > 0 {
+          self.clip_groups -= 1;
+          self.body.push_str("</g>\n");
+        }
+      }
+      PaintCommand::Bundle { transform, opacity, cmds, .. } => {
+        let _ = write!(self.body, "<g{}", transform_attr(transform));
+        if *opacity < 1. {
+          let _ = write!(self.body, r#" opacity="{opacity}""#);
+        }
+        self.body.push_str(">\n");
+        self.write_commands(cmds);
+        self.body.push_str("</g>\n");
+      }
+      PaintCommand::Layer { opacity, blend_mode, cmds, .. } => {
+        self
+          .body
+          .push_str(r#"<g style="isolation:isolate"#);
+        if *blend_mode != BlendMode::Normal {
+          let _ = write!(self.body, ";mix-blend-mode:{}", blend_mode_name(*blend_mode));
+        }
+        self.body.push('"');
+        if *opacity < 1. {
+          let _ = write!(self.body, r#" opacity="{opacity}""#);
+        }
+        self.body.push_str(">\n");
+        self.write_commands(cmds);
+        self.body.push_str("</g>\n");
+      }
+    }
+  }
+
+  fn new_id(&mut self, prefix: &str) -> String {
+    let id = format!("{prefix}{}", self.next_id);
+    self.next_id += 1;
+    id
+  }
+
+  fn write_linear_gradient(&mut self, gradient: &LinearGradient) -> String {
+    let LinearGradient { start, end, stops, spread_method } = gradient;
+    let id = self.new_id("linear");
+    let _ = writeln!(
+      self.defs,
+      r#"<linearGradient id="{id}" gradientUnits="userSpaceOnUse" x1="{}" y1="{}" x2="{}" y2="{}" spreadMethod="{}">"#,
+      start.x,
+      start.y,
+      end.x,
+      end.y,
+      spread_name(*spread_method)
+    );
+    write_stops(&mut self.defs, stops);
+    self.defs.push_str("</linearGradient>\n");
+    id
+  }
+
+  fn write_radial_gradient(&mut self, gradient: &RadialGradient) -> String {
+    let RadialGradient { start_center, start_radius, end_center, end_radius, stops, spread_method } =
+      gradient;
+    let id = self.new_id("radial");
+    let _ = writeln!(
+      self.defs,
+      r#"<radialGradient id="{id}" gradientUnits="userSpaceOnUse" cx="{}" cy="{}" r="{end_radius}" fx="{}" fy="{}" fr="{start_radius}" spreadMethod="{}">"#,
+      end_center.x,
+      end_center.y,
+      start_center.x,
+      start_center.y,
+      spread_name(*spread_method)
+    );
+    write_stops(&mut self.defs, stops);
+    self.defs.push_str("</radialGradient>\n");
+    id
+  }
+
+  /// The image is repeated in the path space, so we use a pattern to fill it.
+  /// The pattern tile is enlarged in the direction that doesn't repeat, so
+  /// only one image covers the path.
+  fn write_image_pattern(
+    &mut self, img: &PixelImage, rect: &Rect, repeat: ImageRepeat, filter: ImageFilter,
+    path_bounds: &Rect,
+  ) -> Option<String> {
+    let url = png_data_url(img)?;
+    let id = self.new_id("image");
+    let (w, h) = (rect.width(), rect.height());
+    let tile_w = if repeat.repeat_x() {
+      w
+    } else {
+      w.max(path_bounds.max_x() - rect.min_x())
+        .max(rect.max_x() - path_bounds.min_x())
+    };
+    let tile_h = if repeat.repeat_y() {
+      h
+    } else {
+      h.max(path_bounds.max_y() - rect.min_y())
+        .max(rect.max_y() - path_bounds.min_y())
+    };
+    let _ = write!(self.defs, r#"<pattern id="{id}" patternUnits="userSpaceOnUse""#);
+    if rect.origin != Point::zero() {
+      let _ = write!(self.defs, r#" x="{}" y="{}""#, rect.min_x(), rect.min_y());
+    }
+    let _ =
+      write!(self.defs, r#" width="{tile_w}" height="{tile_h}"><image width="{w}" height="{h}""#);
+    if Size::new(w, h) != Size::new(img.width() as f32, img.height() as f32) {
+      self
+        .defs
+        .push_str(r#" preserveAspectRatio="none""#);
+    }
+    if filter == ImageFilter::Nearest {
+      self
+        .defs
+        .push_str(r#" style="image-rendering:pixelated""#);
+    }
+    let _ = writeln!(self.defs, r#" xlink:href="{url}"/></pattern>"#);
+    Some(id)
+  }
+}
+
+fn path_data(path: &Path) -> String {
+  let mut d = String::new();
+  path.segments().for_each(|seg| {
+    if !d.is_empty() && !matches!(seg, PathSegment::Close(false)) {
+      d.push(' ');
+    }
+    let _ = match seg {
+      PathSegment::MoveTo(p) => write!(d, "M{} {}", p.x, p.y),
+      PathSegment::LineTo(p) => write!(d, "L{} {}", p.x, p.y),
+      PathSegment::QuadTo { ctrl, to } => write!(d, "Q{} {} {} {}", ctrl.x, ctrl.y, to.x, to.y),
+      PathSegment::CubicTo { to, ctrl1, ctrl2 } => {
+        write!(d, "C{} {} {} {} {} {}", ctrl1.x, ctrl1.y, ctrl2.x, ctrl2.y, to.x, to.y)
+      }
+      PathSegment::Close(true) => write!(d, "Z"),
+      PathSegment::Close(false) => Ok(()),
+    };
+  });
+  d
+}
+
+fn transform_attr(ts: &Transform) -> String {
+  if *ts == Transform::identity() {
+    String::new()
+  } else {
+    format!(
+      r#" transform="matrix({} {} {} {} {} {})""#,
+      ts.m11, ts.m12, ts.m21, ts.m22, ts.m31, ts.m32
+    )
+  }
+}
+
+fn color_attrs(name: &str, color: &Color) -> String {
+  let mut attrs = format!(r#"{name}="{}""#, hex_color(color));
+  if color.alpha < 255 {
+    let _ = write!(attrs, r#" {name}-opacity="{}""#, color.alpha as f32 / 255.);
+  }
+  attrs
+}
+
+fn hex_color(color: &Color) -> String {
+  format!("#{:02x}{:02x}{:02x}", color.red, color.green, color.blue)
+}
+
+fn write_stops(svg: &mut String, stops: &[GradientStop]) {
+  for GradientStop { color, offset } in stops {
+    let _ = writeln!(svg, r#"<stop offset="{offset}" {}/>"#, color_attrs("stop-color", color));
+  }
+}
+
+fn spread_name(spread: SpreadMethod) -> &'static str {
+  match spread {
+    SpreadMethod::Pad => "pad",
+    SpreadMethod::Reflect => "reflect",
+    SpreadMethod::Repeat => "repeat",
+  }
+}
+
+fn blend_mode_name(mode: BlendMode) -> &'static str {
+  match mode {
+    BlendMode::Normal => "normal",
+    BlendMode::Multiply => "multiply",
+    BlendMode::Screen => "screen",
+    BlendMode::Overlay => "overlay",
+    BlendMode::Darken => "darken",
+    BlendMode::Lighten => "lighten",
+    BlendMode::ColorDodge => "color-dodge",
+    BlendMode::ColorBurn => "color-burn",
+    BlendMode::HardLight => "hard-light",
+    BlendMode::SoftLight => "soft-light",
+    BlendMode::Difference => "difference",
+    BlendMode::Exclusion => "exclusion",
+  }
+}
+
+#[cfg(feature = "png")]
+fn png_data_url(img: &PixelImage) -> Option<String> {
+  use crate::image::ColorFormat;
+
+  // The alpha image is used as a mask of black color.
+  let rgba;
+  let img = match img.color_format() {
+    ColorFormat::Rgba8 => img,
+    ColorFormat::Alpha8 => {
+      let data: Vec<u8> = img
+        .pixel_bytes()
+        .iter()
+        .flat_map(|a| [0, 0, 0, *a])
+        .collect();
+      rgba = PixelImage::new(data.into(), img.width(), img.height(), ColorFormat::Rgba8);
+      &rgba
+    }
+  };
+  let mut png = vec![];
+  if let Err(err) = img.write_as_png(&mut png) {
+    log::warn!("[painter]: encode image to png failed when export svg: {err}");
+    return None;
+  }
+  Some(format!("data:image/png;base64,{}", base64(&png)))
+}
+
+#[cfg(not(feature = "png"))]
+fn png_data_url(_: &PixelImage) -> Option<String> {
+  log::warn!("[painter]: export svg with image requires the `png` feature, ignored!");
+  None
+}
+
+#[cfg(feature = "png")]
+fn base64(bytes: &[u8]) -> String {
+  const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+  let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+  for chunk in bytes.chunks(3) {
+    let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+    let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+    for i in 0..4 {
+      if i <= chunk.len() {
+        out.push(TABLE[(n >> (18 - i * 6)) as usize & 0x3f] as char);
+      } else {
+        out.push('=');
+      }
+    }
+  }
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_algo::Resource;
+  use ribir_geom::{rect, Angle, Point, Vector};
+
+  use super::*;
+  use crate::{color::ConicGradient, BackdropFilter, BoxShadow, Brush, Painter, Radius};
+
+  fn commands(painter: &mut Painter) -> Box<[PaintCommand]> { painter.finish().to_vec().into() }
+
+  #[test]
+  fn fill_and_clip() {
+    let mut painter = Painter::new(rect(0., 0., 100., 100.));
+    painter
+      .clip(Path::rect(&rect(0., 0., 50., 50.)))
+      .set_brush(Color::RED.with_alpha(0.5))
+      .translate(10., 10.)
+      .rect(&rect(0., 0., 20., 20.))
+      .fill();
+
+    let svg = export_svg(Size::new(100., 100.), &commands(&mut painter));
+    assert_eq!(
+      svg,
+      r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="100" height="100" viewBox="0 0 100 100">
+<defs>
+<clipPath id="clip0" clipPathUnits="userSpaceOnUse"><path d="M0 0 L50 0 L50 50 L0 50 Z"/></clipPath>
+</defs>
+<g clip-path="url(#clip0)">
+<path d="M0 0 L20 0 L20 20 L0 20 Z" transform="matrix(1 0 0 1 10 10)" fill="#ff0000" fill-opacity="0.5019608"/>
+</g>
+</svg>
+"##
+    );
+  }
+
+  #[test]
+  fn gradients() {
+    let stops = vec![GradientStop::new(Color::RED, 0.), GradientStop::new(Color::BLUE, 1.)];
+    let mut painter = Painter::new(rect(0., 0., 100., 100.));
+    painter
+      .set_brush(Brush::LinearGradient(LinearGradient {
+        start: Point::new(0., 0.),
+        end: Point::new(10., 0.),
+        stops: stops.clone(),
+        spread_method: SpreadMethod::Repeat,
+      }))
+      .rect(&rect(0., 0., 10., 10.))
+      .fill()
+      .set_brush(Brush::RadialGradient(RadialGradient {
+        start_center: Point::new(5., 5.),
+        start_radius: 0.,
+        end_center: Point::new(5., 5.),
+        end_radius: 5.,
+        stops,
+        spread_method: SpreadMethod::Pad,
+      }))
+      .rect(&rect(0., 0., 10., 10.))
+      .fill();
+
+    let svg = export_svg(Size::new(10., 10.), &commands(&mut painter));
+    assert!(svg.contains(
+      r##"<linearGradient id="linear0" gradientUnits="userSpaceOnUse" x1="0" y1="0" x2="10" y2="0" spreadMethod="repeat">
+<stop offset="0" stop-color="#ff0000"/>
+<stop offset="1" stop-color="#0000ff"/>
+</linearGradient>"##
+    ));
+    assert!(svg.contains(
+      r##"<radialGradient id="radial1" gradientUnits="userSpaceOnUse" cx="5" cy="5" r="5" fx="5" fy="5" fr="0" spreadMethod="pad">"##
+    ));
+    assert!(svg.contains(r##"fill="url(#linear0)""##));
+    assert!(svg.contains(r##"fill="url(#radial1)""##));
+  }
+
+  #[test]
+  fn nested_bundle() {
+    let inner = {
+      let mut painter = Painter::new(rect(0., 0., 10., 10.));
+      painter
+        .set_brush(Color::BLUE)
+        .clip(Path::rect(&rect(0., 0., 5., 5.)))
+        .rect(&rect(0., 0., 10., 10.))
+        .fill();
+      commands(&mut painter)
+    };
+
+    let mut painter = Painter::new(rect(0., 0., 100., 100.));
+    painter
+      .translate(20., 0.)
+      .apply_alpha(0.5)
+      .draw_bundle_commands(rect(0., 0., 10., 10.), Resource::new(inner));
+
+    let svg = export_svg(Size::new(100., 100.), &commands(&mut painter));
+    assert!(svg.contains(
+      r##"<g transform="matrix(1 0 0 1 20 0)" opacity="0.5">
+<g clip-path="url(#clip0)">
+<path d="M0 0 L10 0 L10 10 L0 10 Z" fill="#0000ff"/>
+</g>
+</g>
+"##
+    ));
+  }
+
+  #[test]
+  fn box_shadow() {
+    let mut painter = Painter::new(rect(0., 0., 100., 100.));
+    let shadow = BoxShadow::new(Vector::new(0., 2.), 4., 0., Color::BLACK);
+    painter
+      .draw_box_shadow(&rect(10., 10., 20., 20.), &Radius::all(2.), &shadow)
+      .draw_box_shadow(&rect(50., 50., 20., 20.), &Radius::all(2.), &shadow.inset());
+    let svg = export_svg(Size::new(100., 100.), &commands(&mut painter));
+
+    assert_eq!(
+      svg
+        .matches("<feGaussianBlur stdDeviation=\"2\"/>")
+        .count(),
+      2
+    );
+    assert_eq!(svg.matches("fill-rule=\"evenodd\"").count(), 1);
+    assert!(svg.contains(r#"<g clip-path="url(#clip2)">"#));
+  }
+
+  #[test]
+  fn blend_layer() {
+    let mut painter = Painter::new(rect(0., 0., 100., 100.));
+    painter
+      .save_layer(0.5, BlendMode::Multiply)
+      .set_brush(Color::RED)
+      .rect(&rect(10., 10., 20., 20.))
+      .fill()
+      .restore();
+    let svg = export_svg(Size::new(100., 100.), &commands(&mut painter));
+    assert!(svg.contains(
+      r##"<g style="isolation:isolate;mix-blend-mode:multiply" opacity="0.5">
+<path d="M10 10 L30 10 L30 30 L10 30 Z" fill="#ff0000"/>
+</g>
+"##
+    ));
+  }
+
+  #[test]
+  fn backdrop_filter_keep_tint() {
+    let mut painter = Painter::new(rect(0., 0., 100., 100.));
+    let path = Path::rect(&rect(10., 10., 20., 20.));
+    painter
+      .draw_backdrop_filter(path.clone(), &BackdropFilter::blur(4.))
+      .draw_backdrop_filter(path, &BackdropFilter::new(4., Color::RED));
+    let svg = export_svg(Size::new(100., 100.), &commands(&mut painter));
+    assert_eq!(svg.matches("<path").count(), 1);
+    assert!(svg.contains(r##"<path d="M10 10 L30 10 L30 30 L10 30 Z" fill="#ff0000"/>"##));
+  }
+
+  #[test]
+  fn conic_gradient_wedges() {
+    let mut painter = Painter::new(rect(0., 0., 100., 100.));
+    let gradient = ConicGradient {
+      center: Point::new(20., 20.),
+      start_angle: Angle::zero(),
+      stops: vec![GradientStop::new(Color::RED, 0.), GradientStop::new(Color::BLUE, 1.)],
+    };
+    painter
+      .set_brush(Brush::ConicGradient(gradient))
+      .rect(&rect(10., 10., 20., 20.))
+      .fill();
+    let svg = export_svg(Size::new(100., 100.), &commands(&mut painter));
+    assert!(svg.contains(r#"<clipPath id="clip0" clipPathUnits="userSpaceOnUse"><path d="M10 10 L30 10 L30 30 L10 30 Z"/></clipPath>"#));
+    assert!(svg.contains(r#"<g clip-path="url(#clip0)">"#));
+    // Every wedge is filled with a solid color, from red to blue.
+    assert_eq!(svg.matches("<path").count(), 181);
+    assert!(svg.contains(r##"fill="#fe0001"/>"##));
+    assert!(svg.contains(r##"fill="#0100fe"/>"##));
+  }
+
+  #[test]
+  fn parse_exported() {
+    let mut painter = Painter::new(rect(0., 0., 100., 100.));
+    painter
+      .set_brush(Color::RED)
+      .rect(&rect(10., 10., 20., 20.))
+      .fill();
+    let svg = export_svg(Size::new(100., 100.), &commands(&mut painter));
+    let svg = crate::Svg::parse_from_bytes(svg.as_bytes()).unwrap();
+    assert_eq!(svg.size, Size::new(100., 100.));
+    assert_eq!(svg.commands.len(), 1);
+  }
+
+  #[cfg(feature = "png")]
+  #[test]
+  fn embed_image() {
+    assert_eq!(base64(b"Man"), "TWFu");
+    assert_eq!(base64(b"Ma"), "TWE=");
+    assert_eq!(base64(b"M"), "TQ==");
+
+    let img = PixelImage::new(vec![255; 4].into(), 1, 1, crate::image::ColorFormat::Rgba8);
+    let mut painter = Painter::new(rect(0., 0., 10., 10.));
+    painter
+      .set_brush(img)
+      .rect(&rect(0., 0., 10., 10.))
+      .fill();
+    let svg = export_svg(Size::new(10., 10.), &commands(&mut painter));
+    assert!(svg.contains(
+      r#"<pattern id="image0" patternUnits="userSpaceOnUse" width="1" height="1"><image width="1" height="1" xlink:href="data:image/png;base64,"#
+    ));
+    assert!(svg.contains(r#"fill="url(#image0)""#));
+  }
+
+  #[cfg(feature = "png")]
+  #[test]
+  fn image_brush_pattern() {
+    use crate::{ImageBrush, ImageFilter, ImageFit, ImageRepeat};
+
+    let img = PixelImage::new(vec![255; 16].into(), 2, 2, crate::image::ColorFormat::Rgba8);
+    let brush = ImageBrush::new(Resource::new(img))
+      .with_fit(ImageFit::Contain)
+      .with_align(0.5, 0.5)
+      .with_repeat(ImageRepeat::RepeatX)
+      .with_filter(ImageFilter::Nearest);
+    let mut painter = Painter::new(rect(0., 0., 100., 100.));
+    painter.draw_img_brush(brush, &rect(10., 10., 40., 20.));
+    let svg = export_svg(Size::new(100., 100.), &commands(&mut painter));
+    assert!(svg.contains(
+      r#"<pattern id="image0" patternUnits="userSpaceOnUse" x="20" y="10" width="20" height="20"><image width="20" height="20" preserveAspectRatio="none" style="image-rendering:pixelated" xlink:href="data:image/png;base64,"#
+    ));
+  }
+}
@@ -8,7 +8,7 @@ use ribir_geom::{rect, Point, Rect};
 use ribir_painter::{path_builder::PathBuilder, Path, PathStyle, PixelImage, Svg};
 use rustybuzz::ttf_parser::{GlyphId, OutlineBuilder};
 
-use crate::{svg_glyph_cache::SvgGlyphCache, FontFace, FontFamily};
+use crate::{svg_glyph_cache::SvgGlyphCache, Em, FontFace, FontFamily};
 /// A wrapper of fontdb and cache font data.
 pub struct FontDB {
   default_fonts: Vec<ID>,
@@ -342,6 +342,12 @@ impl Face {
 
   #[inline]
   pub fn units_per_em(&self) -> u16 { self.rb_face.deref().units_per_em() }
+
+  /// The distance from the baseline to the top of the face.
+  #[inline]
+  pub fn ascender(&self) -> Em {
+    Em::absolute(self.rb_face.deref().ascender() as f32 / self.units_per_em() as f32)
+  }
 }
 
 fn to_db_family(f: &FontFamily) -> Family {
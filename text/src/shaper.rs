@@ -28,6 +28,8 @@ pub struct TextShaper {
 pub struct ShapeResult {
   pub text: Substr,
   pub glyphs: Vec<Glyph<Em>>,
+  /// The ascent of the face that shapes the first glyph.
+  pub ascent: Em,
 }
 
 #[derive(PartialEq, Eq, Hash, Clone)]
@@ -73,7 +75,19 @@ impl TextShaper {
           }
         }
 
-        let glyphs = Rc::new(ShapeResult { text: text.clone(), glyphs });
+        let ascent = glyphs
+          .first()
+          .map(|g| g.face_id)
+          .or_else(|| face_ids.first().copied())
+          .and_then(|id| {
+            self
+              .font_db
+              .borrow_mut()
+              .face_data_or_insert(id)
+              .map(|f| f.ascender())
+          })
+          .unwrap_or(Em::absolute(1.));
+        let glyphs = Rc::new(ShapeResult { text: text.clone(), glyphs, ascent });
         self.shape_cache.borrow_mut().put(
           ShapeKey { face_ids: face_ids.into(), text: text.clone(), direction },
           glyphs.clone(),
@@ -107,3 +107,50 @@ pub fn draw_glyphs(
     }
   });
 }
+
+#[cfg(test)]
+mod tests {
+  use ribir_geom::rect;
+  use ribir_painter::{export_pdf, Color};
+
+  use super::*;
+  use crate::{shaper::TextShaper, typography_store::TypographyStore, *};
+
+  #[test]
+  fn export_glyph_outlines_to_pdf() {
+    let font_db = Rc::new(RefCell::new(FontDB::default()));
+    let path = env!("CARGO_MANIFEST_DIR").to_owned() + "/../fonts/DejaVuSans.ttf";
+    let _ = font_db.borrow_mut().load_font_file(path);
+    let shaper = TextShaper::new(font_db.clone());
+    let store = TypographyStore::new(<_>::default(), font_db.clone(), shaper);
+    let face =
+      FontFace { families: Box::new([FontFamily::Name("DejaVu Sans".into())]), ..<_>::default() };
+    let cfg = TypographyCfg {
+      line_height: None,
+      letter_space: None,
+      text_align: TextAlign::Start,
+      bounds: (Em::MAX, Em::MAX).into(),
+      line_dir: PlaceLineDirection::TopToBottom,
+      overflow: Overflow::Clip,
+    };
+    let glyphs = store.typography("Hello".into(), FontSize::Pixel(14.0.into()), &face, cfg);
+
+    let bounds = rect(0., 0., 100., 20.);
+    let mut painter = Painter::new(bounds);
+    let style = PathStyle::Fill;
+    draw_glyphs_in_rect(&mut painter, glyphs, bounds, Color::BLACK.into(), 14., &style, font_db);
+    let commands = painter.finish().to_vec();
+    let pdf = export_pdf(bounds.size, bounds.size, &commands);
+
+    let count = |s: &str| {
+      pdf
+        .windows(s.len())
+        .filter(|w| *w == s.as_bytes())
+        .count()
+    };
+    // The glyphs are the outline paths, `H`, `e`, `l` and `o` are embedded once
+    // besides the content form.
+    assert_eq!(count("/Subtype /Form"), 5);
+    assert_eq!(count("/Subtype /Image"), 0);
+  }
+}
@@ -65,6 +65,8 @@ pub struct VisualLine {
   pub y: Em,
   pub height: Em,
   pub width: Em,
+  /// The distance from the line top to its baseline.
+  pub ascent: Em,
   /// The glyph position is relative the line x/y
   pub glyphs: Vec<Glyph<Em>>,
 }
@@ -246,6 +248,7 @@ where
     let text = run.text();
     let base = run.range().start as u32;
     let line_offset = (font_size - Em::absolute(1.)) / 2.;
+    let ascent = run.ascent() * font_size.value();
     let is_auto_wrap = self.cfg.overflow.is_auto_wrap();
 
     let verify_line_height = |this: &mut Self| {
@@ -255,6 +258,7 @@ where
       } else {
         line.height = line.height.max(font_size)
       }
+      line.ascent = line.ascent.max(line_offset + ascent);
     };
     let new_line = |this: &mut Self, cursor: &mut dyn InlineCursor| {
       this.end_line();
@@ -377,6 +381,8 @@ pub trait InputRun {
   fn text(&self) -> &str;
   fn glyphs(&self) -> &[Glyph<Em>];
   fn font_size(&self) -> FontSize;
+  /// The ascent of the run's font in the unit of its font size.
+  fn ascent(&self) -> Em;
   fn letter_space(&self) -> Option<Pixel>;
   fn range(&self) -> Range<usize>;
 }
@@ -212,6 +212,9 @@ impl InputRun for ShapeRun {
   #[inline]
   fn font_size(&self) -> FontSize { self.font_size }
 
+  #[inline]
+  fn ascent(&self) -> Em { self.shape_result.ascent }
+
   #[inline]
   fn letter_space(&self) -> Option<Pixel> { self.letter_space }
 
@@ -230,6 +233,16 @@ impl VisualGlyphs {
     )
   }
 
+  /// Return the distance from the top of the text to the baseline of its first
+  /// line in pixel, `None` if the lines are placed horizontally.
+  pub fn first_baseline(&self) -> Option<f32> {
+    if self.visual_info.line_dir.is_horizontal() {
+      return None;
+    }
+    let line = self.visual_info.visual_lines.first()?;
+    Some(self.to_pixel_value(self.y + line.y + line.ascent))
+  }
+
   pub fn nearest_glyph(&self, offset_x: f32, offset_y: f32) -> (usize, usize) {
     let x: Em = -self.x + Pixel(offset_x / self.scale).into();
     let y: Em = -self.y + Pixel(offset_y / self.scale).into();
@@ -92,6 +92,11 @@ const LIST_ITEM_SIZE: f32 = 40.;
 const AVATAR_SIZE: f32 = 40.;
 const AVATAR_RADIUS: f32 = 20.;
 const LIST_IMAGE_ITEM_SIZE: f32 = 56.;
+const SLIDER_HEIGHT: f32 = 44.;
+const SLIDER_THUMB_SIZE: f32 = 20.;
+const SLIDER_STATE_LAYER_SIZE: f32 = 40.;
+const STATE_LAYER_SIZE: f32 = 40.;
+const RADIO_SIZE: f32 = 20.;
 
 const ICON_TINY: Size = Size::new(18., 18.);
 const ICON_SMALL: Size = Size::new(24., 24.);
@@ -233,6 +238,61 @@ fn init_custom_style(theme: &mut Theme) {
       foreground: theme.palette.on_surface_variant().into(),
       text_style: theme.typography_theme.body_medium.text.clone(),
     });
+  let palette = &theme.palette;
+  theme.custom_styles.set_custom_style(SliderStyle {
+    height: SLIDER_HEIGHT,
+    track_height: 4.,
+    track_radius: 2.,
+    thumb_size: Size::splat(SLIDER_THUMB_SIZE),
+    tick_size: 2.,
+    active_track: palette.primary().into(),
+    inactive_track: palette.surface_container_highest().into(),
+    thumb: palette.primary(),
+    active_tick: palette.on_primary().with_alpha(0.38).into(),
+    inactive_tick: palette
+      .on_surface_variant()
+      .with_alpha(0.38)
+      .into(),
+    label_background: palette.primary().into(),
+    label_foreground: palette.on_primary().into(),
+    label_style: theme.typography_theme.label_medium.text.clone(),
+    label_padding: EdgeInsets::symmetrical(4., 8.),
+    label_radius: 14.,
+    label_gap: 4.,
+  });
+  theme.custom_styles.set_custom_style(SwitchStyle {
+    track_size: Size::new(52., 32.),
+    thumb_size: 16.,
+    checked_thumb_size: 24.,
+    border_width: 2.,
+    track: palette.surface_container_highest().into(),
+    border: palette.outline().into(),
+    thumb: palette.outline().into(),
+    checked_thumb: palette.on_primary().into(),
+  });
+  theme.custom_styles.set_custom_style(RadioStyle {
+    size: RADIO_SIZE,
+    dot_size: 10.,
+    border_width: 2.,
+    unselected: palette.on_surface_variant().into(),
+    label_style: theme.typography_theme.body_large.text.clone(),
+    label_color: palette.on_surface().into(),
+  });
+  theme
+    .custom_styles
+    .set_custom_style(SegmentedButtonStyle {
+      height: 40.,
+      padding: EdgeInsets::horizontal(12.),
+      radius: BUTTON_RADIUS,
+      border_width: 1.,
+      border: palette.outline().into(),
+      foreground: palette.on_surface().into(),
+      selected_background: palette.secondary_container().into(),
+      selected_foreground: palette.on_secondary_container().into(),
+      label_style: theme.typography_theme.label_large.text.clone(),
+      icon_size: ICON_TINY,
+      icon_gap: LABEL_GAP,
+    });
 }
 
 fn override_compose_decorator(theme: &mut Theme) {
@@ -285,6 +345,70 @@ fn override_compose_decorator(theme: &mut Theme) {
     }
     .into_widget()
   });
+  styles.override_compose_decorator::<SliderThumbDecorator>(move |style, host, _| {
+    fn_widget! {
+      // The state layer is a circle around the thumb.
+      let margin = (SLIDER_STATE_LAYER_SIZE - SLIDER_THUMB_SIZE) / 2.;
+      @InteractiveLayer {
+        color: pipe!($style.color),
+        border_radii: Radius::all(SLIDER_STATE_LAYER_SIZE / 2.),
+        @$host {
+          margin: EdgeInsets::all(margin)
+        }
+      }
+    }
+    .into_widget()
+  });
+  styles.override_compose_decorator::<SwitchThumbDecorator>(move |style, host, _| {
+    fn_widget! {
+      // The state layer is a circle around the thumb, whatever the thumb size.
+      @InteractiveLayer {
+        color: pipe!($style.color),
+        border_radii: Radius::all(STATE_LAYER_SIZE / 2.),
+        @ConstrainedBox {
+          clamp: BoxClamp::fixed_size(Size::splat(STATE_LAYER_SIZE)),
+          @$host {
+            h_align: HAlign::Center,
+            v_align: VAlign::Center,
+          }
+        }
+      }
+    }
+    .into_widget()
+  });
+  styles.override_compose_decorator::<RadioDecorator>(move |style, host, _| {
+    fn_widget! {
+      @Ripple {
+        center: true,
+        color: pipe!($style.color),
+        radius: STATE_LAYER_SIZE / 2.,
+        bounded: RippleBound::Unbounded,
+        @InteractiveLayer {
+          color: pipe!($style.color),
+          border_radii: Radius::all(STATE_LAYER_SIZE / 2.),
+          @$host {
+            margin: EdgeInsets::all((STATE_LAYER_SIZE - RADIO_SIZE) / 2.)
+          }
+        }
+      }
+    }
+    .into_widget()
+  });
+  styles.override_compose_decorator::<SegmentDecorator>(move |style, host, _| {
+    fn_widget! {
+      @Ripple {
+        center: false,
+        color: pipe!($style.color),
+        bounded: RippleBound::Radius($style.radius),
+        @InteractiveLayer {
+          color: pipe!($style.color),
+          border_radii: $style.radius,
+          @{ host }
+        }
+      }
+    }
+    .into_widget()
+  });
   styles.override_compose_decorator::<FilledButtonDecorator>(move |style, host, _| {
     fn_widget! {
       @Ripple {
@@ -331,6 +455,32 @@ fn override_compose_decorator(theme: &mut Theme) {
     }
     .into_widget()
   });
+  styles.override_compose_decorator::<FabButtonDecorator>(move |style, host, _| {
+    fn_widget! {
+      let radius = Radius::all(FabButtonStyle::of(ctx!()).radius);
+      let shadow = Palette::of(ctx!()).shadow();
+      @Ripple {
+        center: false,
+        color: {
+          let palette = Palette::of(ctx!()).clone();
+          pipe!(palette.on_of(&palette.base_of(&$style.color)))
+        },
+        bounded: RippleBound::Radius(radius),
+        @InteractiveLayer {
+          border_radii: radius,
+          color: {
+            let palette = Palette::of(ctx!()).clone();
+            pipe!(palette.on_of(&palette.base_of(&$style.color)))
+          },
+          @$host {
+            border_radius: radius,
+            box_shadow: md::elevation::shadows(md::elevation::LEVEL3, shadow),
+          }
+        }
+      }
+    }
+    .into_widget()
+  });
   styles.override_compose_decorator::<ButtonDecorator>(move |style, host, _| {
     fn_widget! {
       @Ripple {
@@ -59,3 +59,44 @@ pub mod easing {
     pub const EXR_LONG4: Duration = Duration::from_millis(1000);
   }
 }
+
+// The elevation levels, every level casts a key shadow and an ambient shadow.
+// See https://m3.material.io/styles/elevation/tokens
+pub mod elevation {
+  use ribir_core::prelude::*;
+
+  pub const LEVEL0: usize = 0;
+  pub const LEVEL1: usize = 1;
+  pub const LEVEL2: usize = 2;
+  pub const LEVEL3: usize = 3;
+  pub const LEVEL4: usize = 4;
+  pub const LEVEL5: usize = 5;
+
+  // The `[key_y, key_blur, ambient_y, ambient_blur, ambient_spread]` of the
+  // level 1 to 5.
+  const SHADOWS: [[f32; 5]; 5] = [
+    [1., 2., 1., 3., 1.],
+    [1., 2., 2., 6., 2.],
+    [1., 3., 4., 8., 3.],
+    [2., 3., 6., 10., 4.],
+    [4., 4., 8., 12., 6.],
+  ];
+
+  /// Returns the shadows of the elevation `level` in the `color`, the level
+  /// above `LEVEL5` is the same as `LEVEL5`.
+  pub fn shadows(level: usize, color: Color) -> Vec<BoxShadow> {
+    if level == LEVEL0 {
+      return vec![];
+    }
+    let [key_y, key_blur, ambient_y, ambient_blur, ambient_spread] = SHADOWS[level.min(LEVEL5) - 1];
+    vec![
+      BoxShadow::new(Vector::new(0., key_y), key_blur, 0., color.apply_alpha(0.3)),
+      BoxShadow::new(
+        Vector::new(0., ambient_y),
+        ambient_blur,
+        ambient_spread,
+        color.apply_alpha(0.15),
+      ),
+    ]
+  }
+}
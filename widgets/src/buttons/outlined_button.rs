@@ -117,10 +117,10 @@ impl ComposeChild<'static> for OutlinedButton {
             background_color: None,
             foreground_color: pipe!(Palette::of(ctx!()).base_of(&$this.color)),
             radius,
-            border_style: pipe!(Border::all(BorderSide {
-              width: border_width,
-              color: Palette::of(ctx!()).base_of(&$this.color).into()
-            })),
+            border_style: pipe!(Border::all(BorderSide::new(
+              border_width,
+              Palette::of(ctx!()).base_of(&$this.color).into()
+            ))),
             padding_style,
 
             @ { child }
@@ -0,0 +1,917 @@
+use std::{
+  cell::{Cell, RefCell},
+  cmp::Ordering,
+  rc::Rc,
+};
+
+use ribir_core::prelude::*;
+
+use crate::{
+  input::{CaretPosition, EditableText, Input},
+  layout::{Column, Expanded, Row},
+  lazy_list::LazyList,
+  prelude::Text,
+};
+
+/// The data source of a [`DataTable`], the table asks it for the text of the
+/// cells it displays, so only the visible cells are read.
+pub trait TableModel: 'static {
+  /// The number of the rows.
+  fn row_count(&self) -> usize;
+
+  /// The text of the cell at `row` and `column`.
+  fn cell_text(&self, row: usize, column: usize) -> CowArc<str>;
+
+  /// Compare two rows by the cells of `column`, it's used to sort the rows.
+  /// The default implementation compares the text of the cells.
+  fn compare(&self, column: usize, a: usize, b: usize) -> Ordering {
+    self
+      .cell_text(a, column)
+      .cmp(&self.cell_text(b, column))
+  }
+
+  /// Called when the user finishes editing a cell. The default implementation
+  /// ignores the edit.
+  fn set_cell_text(&mut self, _row: usize, _column: usize, _text: &str) {}
+}
+
+impl TableModel for Vec<Vec<String>> {
+  fn row_count(&self) -> usize { self.len() }
+
+  fn cell_text(&self, row: usize, column: usize) -> CowArc<str> {
+    self
+      .get(row)
+      .and_then(|r| r.get(column))
+      .map_or_else(CowArc::default, |c| c.clone().into())
+  }
+
+  fn set_cell_text(&mut self, row: usize, column: usize, text: &str) {
+    if let Some(cell) = self.get_mut(row).and_then(|r| r.get_mut(column)) {
+      *cell = text.to_string();
+    }
+  }
+}
+
+/// The definition of a column of the [`DataTable`]. The `n`th column displays
+/// the column `n` of the model.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableColumn {
+  pub title: CowArc<str>,
+  pub width: f32,
+  /// The width the user can't resize the column below.
+  pub min_width: f32,
+  /// Whether the rows can be sorted by tapping the header of the column.
+  pub sortable: bool,
+  /// Whether the cells of the column can be edited.
+  pub editable: bool,
+}
+
+impl TableColumn {
+  pub fn new(title: impl Into<CowArc<str>>, width: f32) -> Self {
+    Self { title: title.into(), width, min_width: 24., sortable: true, editable: false }
+  }
+
+  pub fn with_min_width(mut self, min_width: f32) -> Self {
+    self.min_width = min_width;
+    self
+  }
+
+  pub fn with_sortable(mut self, sortable: bool) -> Self {
+    self.sortable = sortable;
+    self
+  }
+
+  pub fn with_editable(mut self, editable: bool) -> Self {
+    self.editable = editable;
+    self
+  }
+}
+
+/// The order the rows of a [`DataTable`] are sorted in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortOrder {
+  Ascending,
+  Descending,
+}
+
+#[derive(Clone)]
+pub struct DataTableStyle {
+  pub header_text_style: CowArc<TextStyle>,
+  pub text_style: CowArc<TextStyle>,
+  pub foreground: Brush,
+  pub header_background: Brush,
+  pub background: Brush,
+  pub selected_background: Brush,
+  pub cell_padding: EdgeInsets,
+}
+
+impl CustomStyle for DataTableStyle {
+  fn default_style(ctx: &impl ProviderCtx) -> Self {
+    let palette = Palette::of(ctx);
+    let typography = TypographyTheme::of(ctx);
+    Self {
+      header_text_style: typography.title_small.text.clone(),
+      text_style: typography.body_medium.text.clone(),
+      foreground: palette.on_surface().into(),
+      header_background: palette.surface_container().into(),
+      background: palette.surface().into(),
+      selected_background: palette.secondary_container().into(),
+      cell_padding: EdgeInsets::horizontal(16.),
+    }
+  }
+}
+
+/// A table displays the rows of a [`TableModel`] in the columns it defines.
+///
+/// The header stays at the top and the first column stays at the left when
+/// the table scrolls. The rows are hosted by a [`LazyList`], so only the rows
+/// in the viewport are built, and the table keeps smooth with a large model.
+///
+/// - Tap the header of a sortable column to sort the rows by it, tap it again
+///   to reverse the order.
+/// - Drag the right edge of a header to resize the column, double tap it to fit
+///   the column to its content, drag a header to another header to move the
+///   column there.
+/// - Tap a row to select it, or move the selection with the arrow, `Home`,
+///   `End`, `PageUp` and `PageDown` keys.
+/// - Double tap an editable cell, or press `Enter` on the selected row, to edit
+///   the cell with an [`Input`]. `Enter` or leaving the input commits the edit
+///   and `Escape` cancels it.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let _table = fn_widget! {
+///   let rows: Vec<Vec<String>> = (0..10_000)
+///     .map(|i| vec![i.to_string(), format!("name {i}")])
+///     .collect();
+///   @DataTable {
+///     model: rows,
+///     columns: vec![
+///       TableColumn::new("Id", 80.),
+///       TableColumn::new("Name", 200.).with_editable(true),
+///     ],
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct DataTable<M: TableModel> {
+  #[declare(strict)]
+  model: M,
+  pub columns: Vec<TableColumn>,
+  /// The height of every row.
+  #[declare(default = 40.)]
+  pub row_height: f32,
+  #[declare(default = 48.)]
+  pub header_height: f32,
+  /// Whether the first displayed column stays at the left when the table
+  /// scrolls horizontally.
+  #[declare(default = true)]
+  pub fixed_first_column: bool,
+  #[declare(skip)]
+  sort: Option<(usize, SortOrder)>,
+  /// The model rows in the display order, empty if the rows are not sorted.
+  #[declare(skip)]
+  order: Vec<usize>,
+  #[declare(skip)]
+  column_order: Vec<usize>,
+  #[declare(skip)]
+  selected: Option<usize>,
+  #[declare(skip)]
+  editing: Option<(usize, usize)>,
+}
+
+impl<M: TableModel> DataTable<M> {
+  pub fn model(&self) -> &M { &self.model }
+
+  /// Update the model, and sort the rows again if they are sorted.
+  pub fn update_model(&mut self, f: impl FnOnce(&mut M)) {
+    f(&mut self.model);
+    let count = self.model.row_count();
+    if self.selected.is_some_and(|row| row >= count) {
+      self.selected = None;
+    }
+    self.editing = None;
+    self.resort();
+  }
+
+  /// The model row displayed at `index`.
+  pub fn row_at(&self, index: usize) -> usize { self.order.get(index).copied().unwrap_or(index) }
+
+  /// The display index of the model row.
+  pub fn index_of_row(&self, row: usize) -> Option<usize> {
+    if row >= self.model.row_count() {
+      None
+    } else if self.order.is_empty() {
+      Some(row)
+    } else {
+      self.order.iter().position(|r| *r == row)
+    }
+  }
+
+  pub fn sort(&self) -> Option<(usize, SortOrder)> { self.sort }
+
+  /// Sort the rows by the column, or cancel the sorting if `sort` is `None`.
+  pub fn set_sort(&mut self, sort: Option<(usize, SortOrder)>) {
+    self.sort = sort;
+    self.resort();
+  }
+
+  /// Sort the rows by the column in ascending order, or reverse the order if
+  /// the rows are already sorted by it.
+  pub fn sort_by(&mut self, column: usize) {
+    let order = match self.sort {
+      Some((c, SortOrder::Ascending)) if c == column => SortOrder::Descending,
+      _ => SortOrder::Ascending,
+    };
+    self.set_sort(Some((column, order)));
+  }
+
+  /// The columns in the order they are displayed.
+  pub fn column_order(&self) -> Vec<usize> {
+    if self.column_order.len() == self.columns.len() {
+      self.column_order.clone()
+    } else {
+      (0..self.columns.len()).collect()
+    }
+  }
+
+  /// Move the column displayed at `from` to display at `to`.
+  pub fn move_column(&mut self, from: usize, to: usize) {
+    let mut order = self.column_order();
+    if from < order.len() && to < order.len() {
+      let column = order.remove(from);
+      order.insert(to, column);
+      self.column_order = order;
+    }
+  }
+
+  /// Resize the column, the width is not less than its `min_width`.
+  pub fn resize_column(&mut self, column: usize, width: f32) {
+    if let Some(c) = self.columns.get_mut(column) {
+      c.width = width.max(c.min_width);
+    }
+  }
+
+  pub fn selected_row(&self) -> Option<usize> { self.selected }
+
+  pub fn select_row(&mut self, row: Option<usize>) {
+    self.selected = row.filter(|row| *row < self.model.row_count());
+  }
+
+  pub fn editing_cell(&self) -> Option<(usize, usize)> { self.editing }
+
+  /// Start to edit the cell if its column is editable.
+  pub fn edit_cell(&mut self, row: usize, column: usize) {
+    let editable = self
+      .columns
+      .get(column)
+      .is_some_and(|c| c.editable);
+    if editable && row < self.model.row_count() {
+      self.editing = Some((row, column));
+    }
+  }
+
+  /// Finish editing and pass the text to the model. The rows are not sorted
+  /// again, so the edited row stays in its place.
+  pub fn commit_edit(&mut self, text: &str) {
+    if let Some((row, column)) = self.editing.take() {
+      self.model.set_cell_text(row, column, text);
+    }
+  }
+
+  pub fn cancel_edit(&mut self) { self.editing = None; }
+
+  fn resort(&mut self) {
+    self.order.clear();
+    if let Some((column, sort)) = self.sort {
+      let model = &self.model;
+      self.order.extend(0..model.row_count());
+      self.order.sort_by(|a, b| {
+        let ord = model.compare(column, *a, *b);
+        if sort == SortOrder::Descending { ord.reverse() } else { ord }
+      });
+    }
+  }
+
+  fn display_widths(&self) -> Vec<f32> {
+    self
+      .column_order()
+      .into_iter()
+      .map(|c| self.columns[c].width)
+      .collect()
+  }
+
+  fn total_width(&self) -> f32 { self.columns.iter().map(|c| c.width).sum() }
+
+  /// The display index the selection moves to when pressing `key`.
+  fn navigate(&self, key: &VirtualKey, page: usize) -> Option<usize> {
+    let count = self.model.row_count();
+    let last = count.checked_sub(1)?;
+    let current = self
+      .selected
+      .and_then(|row| self.index_of_row(row));
+    let idx = match key {
+      VirtualKey::Named(NamedKey::ArrowDown) => current.map_or(0, |i| (i + 1).min(last)),
+      VirtualKey::Named(NamedKey::ArrowUp) => current.map_or(0, |i| i.saturating_sub(1)),
+      VirtualKey::Named(NamedKey::PageDown) => current.map_or(0, |i| (i + page).min(last)),
+      VirtualKey::Named(NamedKey::PageUp) => current.map_or(0, |i| i.saturating_sub(page)),
+      VirtualKey::Named(NamedKey::Home) => 0,
+      VirtualKey::Named(NamedKey::End) => last,
+      _ => return None,
+    };
+    Some(idx)
+  }
+}
+
+/// The max intrinsic width of the cells of every column, recorded when the
+/// rows are laid out, so only the built rows are counted.
+#[derive(Clone, Default)]
+struct ContentWidths(Rc<RefCell<Vec<f32>>>);
+
+impl ContentWidths {
+  fn record(&self, column: usize, width: f32) {
+    let mut widths = self.0.borrow_mut();
+    if widths.len() <= column {
+      widths.resize(column + 1, 0.);
+    }
+    widths[column] = widths[column].max(width);
+  }
+
+  fn get(&self, column: usize) -> Option<f32> { self.0.borrow().get(column).copied() }
+}
+
+#[derive(Clone, Copy)]
+enum ColumnDrag {
+  /// Resizing the column from the pointer position and the width it started.
+  Resize { column: usize, from_x: f32, from_width: f32 },
+  /// Moving the column displayed at the index.
+  Move { from: usize },
+}
+
+impl<M: TableModel> Compose for DataTable<M> {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    fn_widget! {
+      let style = DataTableStyle::of(ctx!());
+      let h_offset = Stateful::new(0f32);
+      let drag: Rc<Cell<Option<ColumnDrag>>> = <_>::default();
+      let content_widths = ContentWidths::default();
+
+      // Only rebuild the header cells when the columns are reordered, so the
+      // resize handle keeps capturing the pointer while the column resizes.
+      let header_order = Stateful::new(($this.column_order(), $this.fixed_first_column));
+      let order_u = watch!(($this.column_order(), $this.fixed_first_column))
+        .subscribe(move |order| if *$header_order != order {
+          *$header_order.write() = order;
+        });
+
+      let header = {
+        let style = style.clone();
+        let drag = drag.clone();
+        let this2 = this.clone_writer();
+        let content_widths = content_widths.clone();
+        pipe!($header_order.clone())
+          .map(move |(order, fixed)| {
+            let mut cells = order
+              .into_iter()
+              .enumerate()
+              .map(|(idx, column)| {
+                let widths = content_widths.clone();
+                header_cell(this2.clone_writer(), idx, column, &style, drag.clone(), widths)
+              })
+              .collect::<Vec<_>>();
+            if fixed {
+              cells.rotate_left(1);
+            }
+            cells
+          })
+      };
+
+      let list = @LazyList {
+        item_count: pipe!($this.model.row_count()),
+        item_extent: pipe!($this.row_height),
+        item_builder: {
+          let style = style.clone();
+          let this = this.clone_writer();
+          let h_offset = h_offset.clone_watcher();
+          let content_widths = content_widths.clone();
+          move |idx: usize| {
+            let h_offset = h_offset.clone_watcher();
+            table_row(this.clone_writer(), idx, &style, h_offset, content_widths.clone())
+          }
+        },
+      };
+      let scroll = $list.inner_scrollable_widget().clone_writer();
+
+      let mut table = @Column {
+        tab_index: 0i16,
+        on_wheel: move |e| {
+          let width = e.window().layout_size(e.current_target()).map_or(0., |s| s.width);
+          let max = ($this.total_width() - width).max(0.);
+          let offset = (*$h_offset - e.delta_x).clamp(0., max);
+          if *$h_offset != offset {
+            *$h_offset.write() = offset;
+          }
+        },
+        on_key_down: move |e| {
+          if $this.editing.is_some() {
+            return;
+          }
+          let rh = $this.row_height;
+          let page = (scroll.read().scroll_view_size().height / rh).max(1.) as usize;
+          let idx = $this.navigate(e.key(), page);
+          if let Some(idx) = idx {
+            let row = $this.row_at(idx);
+            $this.write().select_row(Some(row));
+            scroll_into_view(&mut scroll.write(), idx, rh);
+          } else if *e.key() == VirtualKey::Named(NamedKey::Enter) {
+            let table = $this;
+            let column = table.column_order().into_iter().find(|c| table.columns[*c].editable);
+            if let (Some(row), Some(column)) = (table.selected, column) {
+              drop(table);
+              $this.write().edit_cell(row, column);
+            }
+          }
+        },
+        on_pointer_up: move |_| drag.set(None),
+      };
+      // Back to the table after the editor is removed, to continue the keyboard
+      // navigation.
+      let u = watch!($this.editing.is_some())
+        .distinct_until_changed()
+        .filter(|editing| !editing)
+        .subscribe(move |_| $table.request_focus());
+
+      @ $table {
+        on_disposed: move |_| {
+          u.unsubscribe();
+          order_u.unsubscribe();
+        },
+        @TableRow {
+          widths: pipe!($this.display_widths()),
+          columns: pipe!($this.column_order()),
+          content_widths: content_widths.clone(),
+          offset: pipe!(*$h_offset),
+          fixed: pipe!($this.fixed_first_column),
+          background: style.header_background.clone(),
+          clamp: pipe!(BoxClamp::fixed_height($this.header_height)),
+          @ { header }
+        }
+        @Expanded { @ { list } }
+      }
+    }
+    .into_widget()
+  }
+}
+
+fn header_cell<M: TableModel>(
+  this: impl StateWriter<Value = DataTable<M>>, idx: usize, column: usize, style: &DataTableStyle,
+  drag: Rc<Cell<Option<ColumnDrag>>>, content_widths: ContentWidths,
+) -> Widget<'static> {
+  let style = style.clone();
+  fn_widget! {
+    let drag2 = drag.clone();
+    let drag3 = drag.clone();
+    let drag4 = drag.clone();
+    let drag5 = drag.clone();
+    let arrow = pipe!($this.sort).map(move |sort| match sort {
+      Some((c, SortOrder::Ascending)) if c == column => "↑",
+      Some((c, SortOrder::Descending)) if c == column => "↓",
+      _ => "",
+    });
+    @Row {
+      align_items: Align::Center,
+      padding: style.cell_padding,
+      background: style.header_background.clone(),
+      on_tap: move |_| {
+        if $this.columns[column].sortable {
+          $this.write().sort_by(column);
+        }
+      },
+      on_pointer_down: move |_| drag.set(Some(ColumnDrag::Move { from: idx })),
+      on_pointer_up: move |_| {
+        if let Some(ColumnDrag::Move { from }) = drag2.get() {
+          if from != idx {
+            $this.write().move_column(from, idx);
+          }
+        }
+      },
+      @Expanded {
+        @Text {
+          text: pipe!($this.columns[column].title.clone()),
+          text_style: style.header_text_style.clone(),
+          foreground: style.foreground.clone(),
+        }
+      }
+      @Text {
+        text: arrow,
+        text_style: style.header_text_style.clone(),
+        foreground: style.foreground.clone(),
+      }
+      @Container {
+        size: Size::new(4., f32::INFINITY),
+        cursor: CursorIcon::ColResize,
+        on_pointer_down: move |e| {
+          let from_width = $this.columns[column].width;
+          let from_x = e.global_pos().x;
+          drag3.set(Some(ColumnDrag::Resize { column, from_x, from_width }));
+          // Keep resizing when the pointer moves out of the handle.
+          e.set_pointer_capture(e.id);
+          e.stop_propagation();
+        },
+        on_pointer_move: move |e| {
+          if let Some(ColumnDrag::Resize { column, from_x, from_width }) = drag4.get() {
+            $this.write().resize_column(column, from_width + e.global_pos().x - from_x);
+          }
+        },
+        on_lost_pointer_capture: move |_| drag5.set(None),
+        // Resizing is not a tap on the header.
+        on_tap: move |e| e.stop_propagation(),
+        on_double_tap: move |e| {
+          if let Some(width) = content_widths.get(column) {
+            $this.write().resize_column(column, width);
+          }
+          e.stop_propagation();
+        },
+      }
+    }
+  }
+  .into_widget()
+}
+
+fn table_row<M: TableModel>(
+  this: impl StateWriter<Value = DataTable<M>>, idx: usize, style: &DataTableStyle,
+  h_offset: impl StateWatcher<Value = f32>, content_widths: ContentWidths,
+) -> Widget<'static> {
+  let style = style.clone();
+  fn_widget! {
+    let background = {
+      let style = style.clone();
+      distinct_pipe!($this.selected == Some($this.row_at(idx))).map(move |selected| {
+        if selected { style.selected_background.clone() } else { style.background.clone() }
+      })
+    };
+    let cells = {
+      let style = style.clone();
+      let this2 = this.clone_writer();
+      distinct_pipe!(($this.column_order(), $this.fixed_first_column))
+        .map(move |(order, fixed)| {
+          let mut cells = order
+            .into_iter()
+            .enumerate()
+            .map(|(i, column)| {
+              let fixed = fixed && i == 0;
+              body_cell(this2.clone_writer(), idx, column, fixed, &style)
+            })
+            .collect::<Vec<_>>();
+          if fixed {
+            cells.rotate_left(1);
+          }
+          cells
+        })
+    };
+    @TableRow {
+      widths: pipe!($this.display_widths()),
+      columns: pipe!($this.column_order()),
+      content_widths,
+      offset: pipe!(*$h_offset),
+      fixed: pipe!($this.fixed_first_column),
+      background,
+      on_tap: move |_| {
+        let row = $this.row_at(idx);
+        $this.write().select_row(Some(row));
+      },
+      @ { cells }
+    }
+  }
+  .into_widget()
+}
+
+fn body_cell<M: TableModel>(
+  this: impl StateWriter<Value = DataTable<M>>, idx: usize, column: usize, fixed: bool,
+  style: &DataTableStyle,
+) -> Widget<'static> {
+  let style = style.clone();
+  fn_widget! {
+    let cell = distinct_pipe! {
+      let table = $this;
+      let row = table.row_at(idx);
+      (row, table.editing == Some((row, column)), table.model.cell_text(row, column))
+    };
+    let this2 = this.clone_writer();
+    let style2 = style.clone();
+    let cell = cell.map(move |(row, editing, text)| {
+      let this = this2.clone_writer();
+      if editing {
+        cell_editor(this, row, column, text)
+      } else {
+        let style = style2.clone();
+        fn_widget! {
+          @Text {
+            text,
+            text_style: style.text_style.clone(),
+            foreground: style.foreground.clone(),
+            v_align: VAlign::Center,
+            padding: style.cell_padding,
+            on_double_tap: move |_| $this.write().edit_cell(row, column),
+          }
+        }
+        .into_widget()
+      }
+    });
+
+    // The row sizes the cell, so it reports the width of its content.
+    let mut cell = FatObj::new(cell);
+    if fixed {
+      // The fixed cell covers the cells scrolled under it.
+      let background = distinct_pipe!($this.selected == Some($this.row_at(idx)))
+        .map(move |selected| {
+          if selected { style.selected_background.clone() } else { style.background.clone() }
+        });
+      cell = cell.background(background);
+    }
+    cell
+  }
+  .into_widget()
+}
+
+fn cell_editor<M: TableModel>(
+  this: impl StateWriter<Value = DataTable<M>>, row: usize, column: usize, text: CowArc<str>,
+) -> Widget<'static> {
+  fn_widget! {
+    let input = @Input { auto_focus: true, size: None };
+    let caret = CaretPosition { cluster: text.len(), position: None };
+    $input.write().set_text_with_caret(&text, caret.into());
+    @ $input {
+      v_align: VAlign::Center,
+      on_key_down: move |e| match e.key() {
+        VirtualKey::Named(NamedKey::Enter) => {
+          let text = $input.text().clone();
+          $this.write().commit_edit(&text);
+          e.stop_propagation();
+        }
+        VirtualKey::Named(NamedKey::Escape) => {
+          $this.write().cancel_edit();
+          e.stop_propagation();
+        }
+        _ => {}
+      },
+      on_blur: move |_| {
+        if $this.editing == Some((row, column)) {
+          let text = $input.text().clone();
+          $this.write().commit_edit(&text);
+        }
+      },
+    }
+  }
+  .into_widget()
+}
+
+fn scroll_into_view(scroll: &mut ScrollableWidget, idx: usize, extent: f32) {
+  let pos = scroll.get_scroll_pos();
+  let view = scroll.scroll_view_size().height;
+  let top = idx as f32 * extent;
+  if top < pos.y {
+    scroll.jump_to(Point::new(pos.x, top));
+  } else if top + extent > pos.y + view {
+    scroll.jump_to(Point::new(pos.x, top + extent - view));
+  }
+}
+
+/// A row of the cells of a `DataTable`, the cells are placed one after
+/// another by the widths, and scrolled horizontally by the offset. If `fixed`
+/// is true, the last child is the first column and it's not scrolled, so it's
+/// painted above the scrolled cells.
+///
+/// The max intrinsic width of the cells is recorded to the `content_widths` of
+/// their `columns`.
+#[derive(Declare, MultiChild)]
+struct TableRow {
+  widths: Vec<f32>,
+  columns: Vec<usize>,
+  content_widths: ContentWidths,
+  offset: f32,
+  fixed: bool,
+}
+
+impl Render for TableRow {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let height = if clamp.max.height.is_finite() { clamp.max.height } else { clamp.min.height };
+    let total: f32 = self.widths.iter().sum();
+    let size = clamp.clamp(Size::new(total, height));
+    let offset = self.offset.min(total - size.width).max(0.);
+
+    let mut children = ctx.children().collect::<Vec<_>>();
+    if self.fixed && !children.is_empty() {
+      children.rotate_right(1);
+    }
+    let mut x = 0.;
+    for (i, (child, width)) in children.into_iter().zip(&self.widths).enumerate() {
+      if let Some(column) = self.columns.get(i) {
+        let content = ctx.child_intrinsic_size(child, Intrinsic::MaxWidth, height);
+        self.content_widths.record(*column, content);
+      }
+      let mut l = ctx.child_layouter(child);
+      l.perform_widget_layout(BoxClamp::fixed_size(Size::new(*width, height)));
+      let pos_x = if self.fixed && i == 0 { 0. } else { x - offset };
+      l.update_position(Point::new(pos_x, 0.));
+      x += width;
+    }
+    size
+  }
+
+  fn paint(&self, ctx: &mut PaintingCtx) {
+    let size = ctx
+      .box_rect()
+      .expect("impossible without size in painting stage")
+      .size;
+    ctx
+      .painter()
+      .clip(Path::rect(&Rect::from_size(size)));
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+  use winit::{
+    event::{DeviceId, ElementState, MouseButton, WindowEvent},
+    keyboard::{KeyCode, KeyLocation, PhysicalKey},
+  };
+
+  use super::*;
+
+  fn double_tap(wnd: &mut TestWindow, x: f32, y: f32) {
+    let device_id = unsafe { DeviceId::dummy() };
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::CursorMoved { device_id, position: (x, y).into() });
+    for _ in 0..2 {
+      wnd.process_mouse_input(device_id, ElementState::Pressed, MouseButton::Left);
+      wnd.process_mouse_input(device_id, ElementState::Released, MouseButton::Left);
+    }
+    wnd.draw_frame();
+  }
+
+  fn rows() -> Vec<Vec<String>> {
+    ["b", "c", "a"]
+      .iter()
+      .enumerate()
+      .map(|(i, name)| vec![i.to_string(), name.to_string()])
+      .collect()
+  }
+
+  fn columns() -> Vec<TableColumn> {
+    vec![
+      TableColumn::new("Id", 50.),
+      TableColumn::new("Name", 100.)
+        .with_min_width(40.)
+        .with_editable(true),
+    ]
+  }
+
+  fn table_wnd() -> (TestWindow, Stateful<DataTable<Vec<Vec<String>>>>) {
+    let (table, w_table) = split_value(None);
+    let wnd = TestWindow::new_with_size(
+      fn_widget! {
+        let table = @DataTable { model: rows(), columns: columns(), auto_focus: true };
+        *$w_table.write() = Some(table.clone_writer());
+        table
+      },
+      Size::new(200., 200.),
+    );
+    let table = table.read().as_ref().unwrap().clone_writer();
+    (wnd, table)
+  }
+
+  #[test]
+  fn sort_rows() {
+    reset_test_env!();
+
+    let (mut wnd, table) = table_wnd();
+    wnd.draw_frame();
+
+    table.write().sort_by(1);
+    wnd.draw_frame();
+    let order = |t: &DataTable<_>| (0..3).map(|i| t.row_at(i)).collect::<Vec<_>>();
+    assert_eq!(order(&table.read()), vec![2, 0, 1]);
+
+    table.write().sort_by(1);
+    assert_eq!(table.read().sort(), Some((1, SortOrder::Descending)));
+    assert_eq!(order(&table.read()), vec![1, 0, 2]);
+
+    table
+      .write()
+      .update_model(|m| m.push(vec!["3".into(), "d".into()]));
+    assert_eq!(table.read().row_at(0), 3);
+
+    table.write().set_sort(None);
+    assert_eq!(order(&table.read()), vec![0, 1, 2]);
+  }
+
+  #[test]
+  fn move_and_resize_column() {
+    reset_test_env!();
+
+    let (mut wnd, table) = table_wnd();
+    wnd.draw_frame();
+
+    table.write().move_column(1, 0);
+    assert_eq!(table.read().column_order(), vec![1, 0]);
+    assert_eq!(table.read().display_widths(), vec![100., 50.]);
+
+    table.write().resize_column(1, 10.);
+    assert_eq!(table.read().columns[1].width, 40.);
+    wnd.draw_frame();
+  }
+
+  #[test]
+  fn drag_to_resize_column() {
+    reset_test_env!();
+
+    let (mut wnd, table) = table_wnd();
+    wnd.draw_frame();
+
+    let device_id = unsafe { DeviceId::dummy() };
+    #[allow(deprecated)]
+    let move_to = |wnd: &mut TestWindow, x: f32| {
+      wnd.processes_native_event(WindowEvent::CursorMoved { device_id, position: (x, 24.).into() });
+      wnd.draw_frame();
+    };
+    // Press the right edge of the `Name` header, and drag out of the handle.
+    move_to(&mut wnd, 132.);
+    wnd.process_mouse_input(device_id, ElementState::Pressed, MouseButton::Left);
+    wnd.draw_frame();
+    move_to(&mut wnd, 160.);
+    assert_eq!(table.read().columns[1].width, 128.);
+    move_to(&mut wnd, 190.);
+    assert_eq!(table.read().columns[1].width, 158.);
+
+    // The resizing stops after the pointer is released.
+    wnd.process_mouse_input(device_id, ElementState::Released, MouseButton::Left);
+    wnd.draw_frame();
+    move_to(&mut wnd, 100.);
+    assert_eq!(table.read().columns[1].width, 158.);
+  }
+
+  #[test]
+  fn fit_column_to_content() {
+    reset_test_env!();
+
+    let (mut wnd, table) = table_wnd();
+    wnd.draw_frame();
+
+    // Double tap the right edge of the `Name` header, inside its padding, the
+    // column shrinks to the header.
+    double_tap(&mut wnd, 132., 24.);
+    let width = table.read().columns[1].width;
+    assert!(40. < width && width < 100.);
+
+    // A long name widens the column.
+    table
+      .write()
+      .update_model(|m| m[0][1] = "a name longer than the column".into());
+    wnd.draw_frame();
+    double_tap(&mut wnd, 50. + width - 18., 24.);
+    assert!(table.read().columns[1].width > 100.);
+  }
+
+  #[test]
+  fn keyboard_select_and_edit() {
+    reset_test_env!();
+
+    let (mut wnd, table) = table_wnd();
+    wnd.draw_frame();
+    table.write().sort_by(1);
+    wnd.draw_frame();
+
+    let press = |wnd: &mut TestWindow, key: NamedKey, code: KeyCode| {
+      wnd.processes_keyboard_event(
+        PhysicalKey::Code(code),
+        VirtualKey::Named(key),
+        false,
+        KeyLocation::Standard,
+        ElementState::Pressed,
+      );
+      wnd.draw_frame();
+    };
+
+    press(&mut wnd, NamedKey::ArrowDown, KeyCode::ArrowDown);
+    assert_eq!(table.read().selected_row(), Some(2));
+    press(&mut wnd, NamedKey::ArrowDown, KeyCode::ArrowDown);
+    assert_eq!(table.read().selected_row(), Some(0));
+    press(&mut wnd, NamedKey::End, KeyCode::End);
+    assert_eq!(table.read().selected_row(), Some(1));
+
+    press(&mut wnd, NamedKey::Enter, KeyCode::Enter);
+    assert_eq!(table.read().editing_cell(), Some((1, 1)));
+
+    wnd.processes_receive_chars("!".into());
+    wnd.draw_frame();
+    press(&mut wnd, NamedKey::Enter, KeyCode::Enter);
+    assert_eq!(table.read().editing_cell(), None);
+    assert_eq!(table.read().model()[1][1], "c!");
+  }
+}
@@ -0,0 +1,839 @@
+use std::{
+  cell::RefCell,
+  collections::{HashMap, VecDeque},
+  future::Future,
+  pin::Pin,
+  rc::Rc,
+  task::{Context, Poll},
+  time::Duration,
+};
+
+use futures::channel::oneshot;
+use ribir_core::{
+  overlay::{AutoClosePolicy, WeakOverlay},
+  prelude::*,
+  window::{WindowFlags, WindowId},
+};
+
+use crate::prelude::*;
+
+#[derive(Clone)]
+pub struct DialogStyle {
+  /// The mask over the window below a dialog or a bottom sheet.
+  pub mask: Brush,
+  pub background: Brush,
+  pub foreground: Brush,
+  pub title_style: CowArc<TextStyle>,
+  pub content_style: CowArc<TextStyle>,
+  pub radius: f32,
+  pub padding: EdgeInsets,
+  pub min_width: f32,
+  pub max_width: f32,
+  /// The max width of a bottom sheet, it's as wide as the window if the window
+  /// is narrower.
+  pub sheet_max_width: f32,
+}
+
+impl CustomStyle for DialogStyle {
+  fn default_style(ctx: &impl ProviderCtx) -> Self {
+    let palette = Palette::of(ctx);
+    let typography = TypographyTheme::of(ctx);
+    Self {
+      mask: palette.scrim().with_alpha(0.32).into(),
+      background: palette.surface_container_high().into(),
+      foreground: palette.on_surface().into(),
+      title_style: typography.headline_small.text.clone(),
+      content_style: typography.body_medium.text.clone(),
+      radius: 28.,
+      padding: EdgeInsets::all(24.),
+      min_width: 280.,
+      max_width: 560.,
+      sheet_max_width: 640.,
+    }
+  }
+}
+
+#[derive(Clone)]
+pub struct SnackbarStyle {
+  pub background: Brush,
+  pub foreground: Brush,
+  pub text_style: CowArc<TextStyle>,
+  pub radius: f32,
+  pub padding: EdgeInsets,
+  pub margin: EdgeInsets,
+  pub max_width: f32,
+}
+
+impl CustomStyle for SnackbarStyle {
+  fn default_style(ctx: &impl ProviderCtx) -> Self {
+    let palette = Palette::of(ctx);
+    Self {
+      background: palette.inverse_surface().into(),
+      foreground: palette.inverse_on_surface().into(),
+      text_style: TypographyTheme::of(ctx).body_medium.text.clone(),
+      radius: 4.,
+      padding: EdgeInsets::new(4., 8., 4., 16.),
+      margin: EdgeInsets::all(16.),
+      max_width: 560.,
+    }
+  }
+}
+
+/// The handle to close a showing dialog, bottom sheet or snackbar, and resolve
+/// its [`DialogResult`].
+pub struct DialogCloser<T>(Rc<RefCell<CloserInner<T>>>);
+
+struct CloserInner<T> {
+  sender: Option<oneshot::Sender<Option<T>>>,
+  /// The overlay holds its closer, so the closer holds it weakly.
+  overlay: Option<WeakOverlay>,
+  progress: Stateful<f32>,
+  leave_duration: Option<Duration>,
+  wnd_id: Option<WindowId>,
+  on_closed: Option<Box<dyn FnOnce()>>,
+}
+
+impl<T> Clone for DialogCloser<T> {
+  fn clone(&self) -> Self { Self(self.0.clone()) }
+}
+
+impl<T> DialogCloser<T> {
+  /// Close with the choice of the user.
+  pub fn close(&self, value: T) { self.finish(Some(value)); }
+
+  /// Close without a choice, the result resolves with `None`.
+  pub fn dismiss(&self) { self.finish(None); }
+
+  /// Return whether it's already closed.
+  pub fn is_closed(&self) -> bool { self.0.borrow().sender.is_none() }
+
+  pub(crate) fn new() -> Self {
+    Self(Rc::new(RefCell::new(CloserInner {
+      sender: None,
+      overlay: None,
+      progress: Stateful::new(0.),
+      leave_duration: None,
+      wnd_id: None,
+      on_closed: None,
+    })))
+  }
+
+  /// Attach the overlay to close, and return the result resolved by this
+  /// closer.
+  pub(crate) fn attach(&self, overlay: &Overlay, wnd: &Window) -> DialogResult<T> {
+    let (sender, receiver) = oneshot::channel();
+    let mut inner = self.0.borrow_mut();
+    inner.sender = Some(sender);
+    inner.overlay = Some(overlay.downgrade());
+    inner.wnd_id = Some(wnd.id());
+    DialogResult { closer: self.clone(), receiver }
+  }
+
+  fn finish(&self, value: Option<T>) {
+    let mut inner = self.0.borrow_mut();
+    let Some(sender) = inner.sender.take() else { return };
+    let _ = sender.send(value);
+
+    // Play the leave animation before removing the widgets.
+    *inner.progress.write() = 0.;
+    let animated = inner
+      .wnd_id
+      .and_then(AppCtx::get_window)
+      .is_some_and(|wnd| wnd.flags().contains(WindowFlags::ANIMATIONS));
+    let leave = inner.leave_duration.filter(|_| animated);
+    let overlay = inner.overlay.take().and_then(|o| o.upgrade());
+    let on_closed = inner.on_closed.take();
+    drop(inner);
+
+    let close = move || {
+      if let Some(overlay) = overlay {
+        overlay.close();
+      }
+      if let Some(on_closed) = on_closed {
+        on_closed();
+      }
+    };
+    match leave {
+      Some(duration) => {
+        let mut close = Some(close);
+        observable::timer((), duration, AppCtx::scheduler())
+          .subscribe(move |_| close.take().map_or((), |close| close()));
+      }
+      None => close(),
+    }
+  }
+}
+
+/// The future of a showing dialog, bottom sheet or snackbar, resolves with the
+/// choice of the user, or `None` if it's dismissed.
+pub struct DialogResult<T> {
+  closer: DialogCloser<T>,
+  receiver: oneshot::Receiver<Option<T>>,
+}
+
+impl<T> DialogResult<T> {
+  /// The handle to close it without the user.
+  pub fn closer(&self) -> DialogCloser<T> { self.closer.clone() }
+}
+
+impl<T> Future for DialogResult<T> {
+  type Output = Option<T>;
+
+  fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    Pin::new(&mut self.get_mut().receiver)
+      .poll(cx)
+      .map(|r| r.ok().flatten())
+  }
+}
+
+type ContentGen<T> = Box<dyn FnMut(&DialogCloser<T>) -> Widget<'static>>;
+
+/// A modal dialog shows the content in the center of the window, above a mask.
+///
+/// The focus is trapped inside the dialog, so the tab navigation cycles within
+/// its content. Pressing `Escape` or tapping the mask dismisses it if it's
+/// dismissible.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let _w = fn_widget! {
+///   @FilledButton {
+///     on_tap: move |e| {
+///       let result = Dialog::new(|closer: &DialogCloser<String>| {
+///         let closer = closer.clone();
+///         fn_widget! {
+///           @Text {
+///             text: "Tap me to close",
+///             on_tap: move |_| closer.close("tapped".to_string()),
+///           }
+///         }
+///         .into_widget()
+///       })
+///       .show(e.window());
+///       let _ = AppCtx::spawn_local(async move {
+///         println!("{:?}", result.await);
+///       });
+///     },
+///     @{ Label::new("Show dialog") }
+///   }
+/// };
+/// ```
+pub struct Dialog<T> {
+  content: ContentGen<T>,
+  dismissible: bool,
+}
+
+impl<T: 'static> Dialog<T> {
+  /// Create a dialog, the `content` may be called many times, it receives the
+  /// closer to close the dialog with the choice of the user.
+  pub fn new(content: impl FnMut(&DialogCloser<T>) -> Widget<'static> + 'static) -> Self {
+    Self { content: Box::new(content), dismissible: true }
+  }
+
+  /// Set whether `Escape` and tapping the mask dismiss the dialog, default is
+  /// true.
+  pub fn with_dismissible(mut self, dismissible: bool) -> Self {
+    self.dismissible = dismissible;
+    self
+  }
+
+  /// Show the dialog in the window.
+  pub fn show(self, wnd: Rc<Window>) -> DialogResult<T> {
+    show_surface(wnd, SurfaceKind::Dialog, self.dismissible, self.content)
+  }
+}
+
+/// A modal sheet slides up from the bottom of the window.
+///
+/// It behaves like a [`Dialog`], traps the focus and is dismissed by `Escape`
+/// or tapping the mask if it's dismissible.
+pub struct BottomSheet<T> {
+  content: ContentGen<T>,
+  dismissible: bool,
+}
+
+impl<T: 'static> BottomSheet<T> {
+  /// Create a bottom sheet, the `content` may be called many times, it receives
+  /// the closer to close the sheet with the choice of the user.
+  pub fn new(content: impl FnMut(&DialogCloser<T>) -> Widget<'static> + 'static) -> Self {
+    Self { content: Box::new(content), dismissible: true }
+  }
+
+  /// Set whether `Escape` and tapping the mask dismiss the sheet, default is
+  /// true.
+  pub fn with_dismissible(mut self, dismissible: bool) -> Self {
+    self.dismissible = dismissible;
+    self
+  }
+
+  /// Show the sheet in the window.
+  pub fn show(self, wnd: Rc<Window>) -> DialogResult<T> {
+    show_surface(wnd, SurfaceKind::BottomSheet, self.dismissible, self.content)
+  }
+}
+
+/// A dialog asks the user to choose one of the actions, its result is the
+/// index of the chosen action.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let _w = fn_widget! {
+///   @FilledButton {
+///     on_tap: move |e| {
+///       let result = AlertDialog::new("Discard draft?")
+///         .with_content("The draft will be lost.")
+///         .with_actions(["Cancel", "Discard"])
+///         .show(e.window());
+///       let _ = AppCtx::spawn_local(async move {
+///         if result.await == Some(1) {
+///           println!("discarded");
+///         }
+///       });
+///     },
+///     @{ Label::new("Discard") }
+///   }
+/// };
+/// ```
+pub struct AlertDialog {
+  title: CowArc<str>,
+  content: Option<CowArc<str>>,
+  actions: Vec<CowArc<str>>,
+  dismissible: bool,
+}
+
+impl AlertDialog {
+  pub fn new(title: impl Into<CowArc<str>>) -> Self {
+    Self { title: title.into(), content: None, actions: vec![], dismissible: true }
+  }
+
+  /// Set the supporting text below the title.
+  pub fn with_content(mut self, content: impl Into<CowArc<str>>) -> Self {
+    self.content = Some(content.into());
+    self
+  }
+
+  /// Set the labels of the actions, they are placed at the bottom end in
+  /// order.
+  pub fn with_actions<L: Into<CowArc<str>>>(
+    mut self, actions: impl IntoIterator<Item = L>,
+  ) -> Self {
+    self.actions = actions.into_iter().map(Into::into).collect();
+    self
+  }
+
+  /// Set whether `Escape` and tapping the mask dismiss the dialog, default is
+  /// true.
+  pub fn with_dismissible(mut self, dismissible: bool) -> Self {
+    self.dismissible = dismissible;
+    self
+  }
+
+  /// Show the dialog in the window, the result is the index of the action the
+  /// user chose.
+  pub fn show(self, wnd: Rc<Window>) -> DialogResult<usize> {
+    let Self { title, content, actions, dismissible } = self;
+    Dialog::new(move |closer: &DialogCloser<usize>| {
+      let title = title.clone();
+      let content = content.clone();
+      let actions = actions.clone();
+      let closer = closer.clone();
+      fn_widget! {
+        let style = DialogStyle::of(ctx!());
+        let content = content.map(|text| @Text {
+          text,
+          margin: EdgeInsets::only_top(16.),
+          foreground: style.foreground.clone(),
+          text_style: style.content_style.clone(),
+          overflow: Overflow::AutoWrap,
+        });
+        let actions = actions.into_iter().enumerate().map(|(idx, label)| {
+          let (closer, closer2) = (closer.clone(), closer.clone());
+          @Button {
+            tab_index: 0i16,
+            on_tap: move |_| closer.close(idx),
+            on_key_down: move |e| {
+              if matches!(e.key(), VirtualKey::Named(NamedKey::Enter | NamedKey::Space)) {
+                closer2.close(idx);
+              }
+            },
+            @{ Label::new(label) }
+          }
+        });
+        @Column {
+          @Text {
+            text: title,
+            foreground: style.foreground.clone(),
+            text_style: style.title_style.clone(),
+            overflow: Overflow::AutoWrap,
+          }
+          @{ content }
+          @Row {
+            margin: EdgeInsets::only_top(24.),
+            h_align: HAlign::Right,
+            item_gap: 8.,
+            @{ actions }
+          }
+        }
+      }
+      .into_widget()
+    })
+    .with_dismissible(dismissible)
+    .show(wnd)
+  }
+}
+
+/// A brief message shows at the bottom of the window with an optional action,
+/// it disappears after a while.
+///
+/// The snackbar is not modal, the user can still interact with the widgets
+/// below it. Its result resolves with `Some(())` if the user taps the action,
+/// or `None` if it disappears by itself.
+pub struct Snackbar {
+  message: CowArc<str>,
+  action: Option<CowArc<str>>,
+  duration: Duration,
+}
+
+impl Snackbar {
+  pub fn new(message: impl Into<CowArc<str>>) -> Self {
+    Self { message: message.into(), action: None, duration: Duration::from_secs(4) }
+  }
+
+  /// Set the label of the action.
+  pub fn with_action(mut self, action: impl Into<CowArc<str>>) -> Self {
+    self.action = Some(action.into());
+    self
+  }
+
+  /// Set how long the snackbar shows, default is 4 seconds.
+  pub fn with_duration(mut self, duration: Duration) -> Self {
+    self.duration = duration;
+    self
+  }
+
+  /// Show the snackbar in the window, it waits for the showing snackbar of
+  /// the window to close first.
+  pub fn show(self, wnd: Rc<Window>) -> DialogResult<()> {
+    let Self { message, action, duration } = self;
+    let (overlay, result) = surface_overlay(
+      &wnd,
+      SurfaceKind::Snackbar,
+      false,
+      Box::new(move |closer: &DialogCloser<()>| {
+        let message = message.clone();
+        let action = action.clone();
+        let closer = closer.clone();
+        fn_widget! {
+          let style = SnackbarStyle::of(ctx!());
+          let action = action.map(|label| @Button {
+            on_tap: move |_| closer.close(()),
+            @{ Label::new(label) }
+          });
+          @Row {
+            align_items: Align::Center,
+            padding: style.padding,
+            background: style.background.clone(),
+            border_radius: Radius::all(style.radius),
+            @Expanded {
+              @Text {
+                text: message,
+                margin: EdgeInsets::vertical(10.),
+                foreground: style.foreground.clone(),
+                text_style: style.text_style.clone(),
+                overflow: Overflow::AutoWrap,
+              }
+            }
+            @{ action }
+          }
+        }
+        .into_widget()
+      }),
+    );
+
+    let closer = result.closer();
+    let wnd_id = wnd.id();
+    let wnd = Rc::downgrade(&wnd);
+    queue_snackbar(
+      wnd_id,
+      Box::new(move || {
+        let Some(wnd) = wnd.upgrade().filter(|_| !closer.is_closed()) else { return false };
+        closer.0.borrow_mut().on_closed = Some(Box::new(move || next_snackbar(wnd_id)));
+        overlay.show(wnd);
+        observable::timer((), duration, AppCtx::scheduler()).subscribe(move |_| closer.dismiss());
+        true
+      }),
+    );
+    result
+  }
+}
+
+/// Show a snackbar, return false if it doesn't need to show anymore.
+type ShowSnackbar = Box<dyn FnOnce() -> bool>;
+
+thread_local! {
+  /// The snackbars waiting in every window that has a showing snackbar.
+  static SNACKBAR_QUEUES: RefCell<HashMap<WindowId, VecDeque<ShowSnackbar>>> =
+    RefCell::new(HashMap::new());
+}
+
+fn queue_snackbar(wnd_id: WindowId, show: ShowSnackbar) {
+  let show = SNACKBAR_QUEUES.with_borrow_mut(|queues| match queues.get_mut(&wnd_id) {
+    Some(queue) => {
+      queue.push_back(show);
+      None
+    }
+    None => {
+      queues.insert(wnd_id, VecDeque::new());
+      Some(show)
+    }
+  });
+  if show.is_some_and(|show| !show()) {
+    next_snackbar(wnd_id);
+  }
+}
+
+/// Show the next waiting snackbar of the window after the showing one closed.
+fn next_snackbar(wnd_id: WindowId) {
+  loop {
+    let show = SNACKBAR_QUEUES.with_borrow_mut(|queues| {
+      let show = queues.get_mut(&wnd_id)?.pop_front();
+      if show.is_none() {
+        queues.remove(&wnd_id);
+      }
+      show
+    });
+    match show {
+      Some(show) => {
+        if show() {
+          break;
+        }
+      }
+      None => break,
+    }
+  }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum SurfaceKind {
+  Dialog,
+  BottomSheet,
+  Snackbar,
+}
+
+fn show_surface<T: 'static>(
+  wnd: Rc<Window>, kind: SurfaceKind, dismissible: bool, content: ContentGen<T>,
+) -> DialogResult<T> {
+  let (overlay, result) = surface_overlay(&wnd, kind, dismissible, content);
+  overlay.show(wnd);
+  result
+}
+
+fn surface_overlay<T: 'static>(
+  wnd: &Window, kind: SurfaceKind, dismissible: bool, mut content: ContentGen<T>,
+) -> (Overlay, DialogResult<T>) {
+  let closer = DialogCloser::new();
+  let c_closer = closer.clone();
+  let overlay = Overlay::new(move |ctx: &mut BuildCtx| {
+    let closer = c_closer.clone();
+    let progress = closer.0.borrow().progress.clone_writer();
+    if closer.0.borrow().leave_duration.is_none() {
+      let transition = transitions::EASE_OUT.of(ctx);
+      closer.0.borrow_mut().leave_duration = Some(transition.duration());
+      progress
+        .clone_writer()
+        .transition(transition, ctx);
+    }
+    let content = content(&closer);
+    surface(kind, dismissible, progress, closer, content)
+  });
+  overlay.set_auto_close_policy(AutoClosePolicy::NONE);
+  overlay.set_modal(kind != SurfaceKind::Snackbar);
+
+  let result = closer.attach(&overlay, wnd);
+  (overlay, result)
+}
+
+fn surface<T: 'static>(
+  kind: SurfaceKind, dismissible: bool, progress: Stateful<f32>, closer: DialogCloser<T>,
+  content: Widget<'static>,
+) -> Widget<'static> {
+  fn_widget! {
+    if kind == SurfaceKind::Snackbar {
+      let style = SnackbarStyle::of(ctx!());
+      let w = @SlideUp {
+        factor: pipe!(*$progress),
+        opacity: pipe!(*$progress),
+        h_align: HAlign::Center,
+        v_align: VAlign::Bottom,
+        margin: style.margin,
+        @ConstrainedBox {
+          clamp: BoxClamp { min: Size::zero(), max: Size::new(style.max_width, f32::INFINITY) },
+          @{ content }
+        }
+      };
+      return w.into_widget();
+    }
+
+    let style = DialogStyle::of(ctx!());
+    let (closer, closer2) = (closer.clone(), closer);
+    let radius = if kind == SurfaceKind::Dialog {
+      Radius::all(style.radius)
+    } else {
+      Radius::top(style.radius)
+    };
+    // The dialog shrinks to its content, and the bottom sheet fills its width.
+    let content = if kind == SurfaceKind::Dialog {
+      @IntrinsicWidth { @{ content } }.into_widget()
+    } else {
+      content
+    };
+    let body = @ConstrainedBox {
+      clamp: if kind == SurfaceKind::Dialog {
+        BoxClamp {
+          min: Size::new(style.min_width, 0.),
+          max: Size::new(style.max_width, f32::INFINITY),
+        }
+      } else {
+        BoxClamp {
+          min: Size::new(style.sheet_max_width, 0.),
+          max: Size::new(style.sheet_max_width, f32::INFINITY),
+        }
+      },
+      padding: style.padding,
+      background: style.background.clone(),
+      border_radius: radius,
+      @{ content }
+    };
+    let body = if kind == SurfaceKind::Dialog {
+      @$body { opacity: pipe!(*$progress) }.into_widget()
+    } else {
+      @SlideUp { factor: pipe!(*$progress), @{ body } }.into_widget()
+    };
+
+    @Container {
+      size: Size::new(f32::INFINITY, f32::INFINITY),
+      background: style.mask.clone(),
+      on_tap: move |e| {
+        if dismissible && e.target() == e.current_target() {
+          closer.dismiss();
+        }
+      },
+      @FocusScope {
+        trap: true,
+        can_focus: true,
+        auto_focus: true,
+        h_align: HAlign::Center,
+        v_align: if kind == SurfaceKind::Dialog { VAlign::Center } else { VAlign::Bottom },
+        on_key_down: move |e| {
+          if dismissible && *e.key() == VirtualKey::Named(NamedKey::Escape) {
+            closer2.dismiss();
+          }
+        },
+        @{ body }
+      }
+    }
+    .into_widget()
+  }
+  .into_widget()
+}
+
+/// Slides the child up from below its box, `factor` is the rate of the height
+/// of the child that has slid in.
+#[derive(Declare, SingleChild)]
+struct SlideUp {
+  factor: f32,
+}
+
+impl Render for SlideUp {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let mut layouter = ctx.assert_single_child_layouter();
+    let size = layouter.perform_widget_layout(clamp);
+    layouter.update_position(Point::new(0., size.height * (1. - self.factor)));
+    size
+  }
+
+  fn paint(&self, _: &mut PaintingCtx) {}
+}
+
+#[cfg(test)]
+mod tests {
+  use futures::FutureExt;
+  use ribir_core::{reset_test_env, test_helper::*};
+  use winit::{
+    event::ElementState,
+    keyboard::{KeyCode, KeyLocation, PhysicalKey},
+  };
+
+  use super::*;
+
+  fn press(wnd: &TestWindow, code: KeyCode, key: NamedKey) {
+    wnd.processes_keyboard_event(
+      PhysicalKey::Code(code),
+      VirtualKey::Named(key),
+      false,
+      KeyLocation::Standard,
+      ElementState::Pressed,
+    );
+  }
+
+  fn empty_wnd() -> TestWindow {
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! { @MockBox { size: Size::new(400., 400.), tab_index: 0i16 } },
+      Size::new(400., 400.),
+    );
+    wnd.draw_frame();
+    wnd
+  }
+
+  #[test]
+  fn alert_dialog_choice() {
+    reset_test_env!();
+
+    let mut wnd = empty_wnd();
+
+    let result = AlertDialog::new("Delete?")
+      .with_actions(["Cancel", "Delete"])
+      .show(wnd.0.clone());
+    wnd.draw_frame();
+    let closer = result.closer();
+    let overlay = closer.0.borrow().overlay.clone().unwrap();
+    let overlay = overlay.upgrade().unwrap();
+    assert!(overlay.is_showing());
+
+    // The focus cycles within the dialog: the dialog, `Cancel`, `Delete`, and
+    // `Cancel` again.
+    for _ in 0..3 {
+      wnd.request_next_focus();
+      wnd.draw_frame();
+    }
+    press(&wnd, KeyCode::Enter, NamedKey::Enter);
+    wnd.draw_frame();
+
+    assert_eq!(result.now_or_never(), Some(Some(0)));
+    assert!(!overlay.is_showing());
+  }
+
+  #[test]
+  fn dialog_shrink_to_content() {
+    reset_test_env!();
+
+    let mut wnd = empty_wnd();
+    let (width, w_width) = split_value(0.);
+    let content = move |text: &'static str| {
+      let w_width = w_width.clone_writer();
+      move |_: &DialogCloser<()>| {
+        let w_width = w_width.clone_writer();
+        fn_widget! {
+          @Column {
+            on_performed_layout: move |e| *$w_width.write() = e.box_size().unwrap().width,
+            @Row {
+              h_align: HAlign::Right,
+              @Text { text }
+            }
+          }
+        }
+        .into_widget()
+      }
+    };
+
+    // As narrow as the min width, the padding of the dialog is 24.
+    let result = Dialog::new(content("OK")).show(wnd.0.clone());
+    wnd.draw_frame();
+    assert_eq!(*width.read(), 280. - 48.);
+    result.closer().dismiss();
+    wnd.draw_frame();
+
+    // As wide as the text, but limited by the window narrower than the max width.
+    let long = "A long message that not fit in the dialog without wrapping.";
+    let _result = Dialog::new(content(long)).show(wnd.0.clone());
+    wnd.draw_frame();
+    assert_eq!(*width.read(), 400. - 48.);
+  }
+
+  #[test]
+  fn dismiss_dialog() {
+    reset_test_env!();
+
+    let mut wnd = empty_wnd();
+    let content = |_: &DialogCloser<()>| fn_widget! { @Text { text: "content" } }.into_widget();
+
+    let result = Dialog::new(content)
+      .with_dismissible(false)
+      .show(wnd.0.clone());
+    wnd.draw_frame();
+    press(&wnd, KeyCode::Escape, NamedKey::Escape);
+    wnd.draw_frame();
+    let closer = result.closer();
+    assert!(!closer.is_closed());
+    closer.dismiss();
+    assert_eq!(result.now_or_never(), Some(None));
+
+    let result = BottomSheet::new(content).show(wnd.0.clone());
+    wnd.draw_frame();
+    press(&wnd, KeyCode::Escape, NamedKey::Escape);
+    wnd.draw_frame();
+    assert_eq!(result.now_or_never(), Some(None));
+  }
+
+  #[test]
+  fn snackbar() {
+    reset_test_env!();
+
+    let mut wnd = empty_wnd();
+    let result = Snackbar::new("Saved")
+      .with_action("Undo")
+      .show(wnd.0.clone());
+    wnd.draw_frame();
+
+    // The snackbar doesn't take the focus.
+    wnd.request_next_focus();
+    wnd.draw_frame();
+    let focusing = wnd.focusing();
+    assert!(focusing.is_some());
+    wnd.request_next_focus();
+    wnd.draw_frame();
+    assert_eq!(wnd.focusing(), focusing);
+
+    result.closer().close(());
+    assert_eq!(result.now_or_never(), Some(Some(())));
+  }
+
+  #[test]
+  fn queue_snackbars() {
+    reset_test_env!();
+
+    let mut wnd = empty_wnd();
+    let showing = |result: &DialogResult<()>| {
+      let overlay = result.closer().0.borrow().overlay.clone();
+      overlay
+        .and_then(|o| o.upgrade())
+        .is_some_and(|o| o.is_showing())
+    };
+
+    let first = Snackbar::new("First").show(wnd.0.clone());
+    let second = Snackbar::new("Second").show(wnd.0.clone());
+    wnd.draw_frame();
+    let first_overlay = first.closer().0.borrow().overlay.clone().unwrap();
+    assert!(showing(&first));
+    assert!(!showing(&second));
+
+    first.closer().dismiss();
+    wnd.draw_frame();
+    assert!(!showing(&first));
+    assert!(showing(&second));
+
+    second.closer().dismiss();
+    wnd.draw_frame();
+    assert!(!showing(&second));
+    // The closed overlay is released, its closer doesn't keep it alive.
+    assert!(first_overlay.upgrade().is_none());
+  }
+}
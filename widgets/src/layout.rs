@@ -26,9 +26,13 @@ mod sized_box;
 pub use flex::*;
 pub use sized_box::SizedBox;
 pub mod expanded;
-pub use expanded::Expanded;
+pub use expanded::{Expanded, FlexBasis};
+pub mod grid;
+pub use grid::*;
 mod stack;
 pub use stack::*;
+pub mod intrinsic;
+pub use intrinsic::*;
 pub mod only_sized_by_parent;
 pub use only_sized_by_parent::OnlySizedByParent;
 pub use ribir_core::builtin_widgets::container::Container;
@@ -3,10 +3,31 @@ use ribir_core::prelude::*;
 /// A widget that expanded a child of `Flex`, so that the child fills the
 /// available space. If multiple children are expanded, the available space is
 /// divided among them according to the flex factor.
+///
+/// The child starts from its `basis` size, grows by its share of the free
+/// space of the line, or shrinks if the children overflow the line.
 #[derive(Clone, PartialEq, Declare)]
 pub struct Expanded {
   #[declare(default = 1.)]
   pub flex: f32,
+  /// The main size of the child before it grows or shrinks. The default is
+  /// zero, so the child only takes its share of the free space.
+  #[declare(default = FlexBasis::Fixed(0.))]
+  pub basis: FlexBasis,
+  /// The factor of the child to shrink if the children overflow the line. The
+  /// overflow size is divided among the expanded children in proportion to
+  /// `shrink * basis`.
+  #[declare(default = 1.)]
+  pub shrink: f32,
+}
+
+/// The main size of an expanded child before it grows or shrinks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FlexBasis {
+  /// The size of the content of the child.
+  Content,
+  /// A fixed size.
+  Fixed(f32),
 }
 
 impl<'c> ComposeChild<'c> for Expanded {
@@ -119,4 +140,47 @@ mod tests {
     LayoutCase::new(&[0, 5]).with_rect(ribir_geom::rect(100., 50., 50., 50.)),
     LayoutCase::new(&[0, 6]).with_rect(ribir_geom::rect(150., 50., 200., 50.))
   );
+
+  widget_layout_test!(
+    grow_from_basis,
+    WidgetTester::new(fn_widget! {
+      @Row {
+        @Expanded {
+          basis: FlexBasis::Content,
+          @SizedBox { size: Size::new(100., 50.) }
+        }
+        @Expanded {
+          basis: FlexBasis::Fixed(50.),
+          @SizedBox { size: Size::new(100., 50.) }
+        }
+        @SizedBox { size: Size::new(100., 50.) }
+      }
+    })
+    .with_wnd_size(Size::new(450., 500.)),
+    LayoutCase::new(&[0, 0]).with_rect(ribir_geom::rect(0., 0., 200., 50.)),
+    LayoutCase::new(&[0, 1]).with_rect(ribir_geom::rect(200., 0., 150., 50.)),
+    LayoutCase::new(&[0, 2]).with_rect(ribir_geom::rect(350., 0., 100., 50.))
+  );
+
+  widget_layout_test!(
+    shrink_overflow,
+    WidgetTester::new(fn_widget! {
+      @Row {
+        @Expanded {
+          basis: FlexBasis::Fixed(200.),
+          @SizedBox { size: Size::new(100., 50.) }
+        }
+        @Expanded {
+          basis: FlexBasis::Fixed(100.),
+          shrink: 2.,
+          @SizedBox { size: Size::new(100., 50.) }
+        }
+        @SizedBox { size: Size::new(100., 50.) }
+      }
+    })
+    .with_wnd_size(Size::new(300., 500.)),
+    LayoutCase::new(&[0, 0]).with_rect(ribir_geom::rect(0., 0., 150., 50.)),
+    LayoutCase::new(&[0, 1]).with_rect(ribir_geom::rect(150., 0., 50., 50.)),
+    LayoutCase::new(&[0, 2]).with_rect(ribir_geom::rect(200., 0., 100., 50.))
+  );
 }
@@ -1,6 +1,6 @@
 use ribir_core::prelude::{log::warn, *};
 
-use super::{Direction, Expanded};
+use super::{Direction, Expanded, FlexBasis};
 
 /// How the children should be placed along the main axis in a flex layout.
 #[derive(Debug, Copy, Clone, PartialEq, Default)]
@@ -48,6 +48,10 @@ pub struct Flex {
   /// How the children should be placed along the main axis in a flex layout.
   #[declare(default)]
   pub justify_content: JustifyContent,
+  /// How the lines should be placed along the cross axis if the flex wraps. If
+  /// it's `None`, the lines are packed together and placed by `align_items`.
+  #[declare(default)]
+  pub align_content: Option<JustifyContent>,
   /// Define item between gap in main axis
   #[declare(default)]
   pub item_gap: f32,
@@ -90,6 +94,7 @@ impl Render for Flex {
       dir: direction,
       align_items: self.align_items,
       justify_content: self.justify_content,
+      align_content: self.align_content,
       wrap: self.wrap,
       main_axis_gap: self.item_gap,
       cross_axis_gap: self.line_gap,
@@ -99,6 +104,29 @@ impl Render for Flex {
     layouter.layout(ctx)
   }
 
+  fn intrinsic_size(&self, intrinsic: Intrinsic, extent: f32, ctx: &mut LayoutCtx) -> f32 {
+    if intrinsic.is_width() != self.direction.is_horizontal() {
+      // The main size of the children are unknown, so not limit it.
+      return ctx.children_max_intrinsic_size(intrinsic, f32::INFINITY);
+    }
+    if self.wrap && matches!(intrinsic, Intrinsic::MinWidth | Intrinsic::MinHeight) {
+      // Every child can be placed in its own line.
+      return ctx.children_max_intrinsic_size(intrinsic, extent);
+    }
+
+    let children: Vec<_> = ctx.children().collect();
+    let gap = if children.is_empty() || FlexLayouter::is_space_layout(self.justify_content) {
+      0.
+    } else {
+      self.item_gap * (children.len() - 1) as f32
+    };
+    children
+      .into_iter()
+      .map(|c| ctx.child_intrinsic_size(c, intrinsic, extent))
+      .sum::<f32>()
+      + gap
+  }
+
   #[inline]
   fn paint(&self, _: &mut PaintingCtx) {}
 }
@@ -141,6 +169,7 @@ struct FlexLayouter {
   dir: Direction,
   align_items: Align,
   justify_content: JustifyContent,
+  align_content: Option<JustifyContent>,
   wrap: bool,
   current_line: MainLineInfo,
   lines: Vec<MainLineInfo>,
@@ -152,12 +181,22 @@ impl FlexLayouter {
   fn layout(&mut self, ctx: &mut LayoutCtx) -> Size {
     self.perform_children_layout(ctx);
     self.flex_children_layout(ctx);
+    if self.align_items == Align::Baseline && self.dir == Direction::Horizontal {
+      self.baseline_align_lines(ctx);
+    }
 
     // cross direction need calculate cross_axis_gap but last line don't need.
-    let cross = self
+    let mut cross = self
       .lines
       .iter()
       .fold(-self.cross_axis_gap, |sum, l| sum + l.cross_line_height + self.cross_axis_gap);
+    if self
+      .align_content
+      .is_some_and(FlexLayouter::is_space_layout)
+      && self.max.cross.is_finite()
+    {
+      cross = self.max.cross;
+    }
     let main = match self.justify_content {
       JustifyContent::Start | JustifyContent::Center | JustifyContent::End => self
         .lines
@@ -186,10 +225,13 @@ impl FlexLayouter {
       FlexSize::zero()
     };
 
+    // The basis of the flex-items not take the space of the fixed widget, the
+    // flex-items shrink if they overflow.
+    let mut bases = 0.;
     while let Some(mut l) = layouter {
       let mut max = max;
       if !wrap {
-        max.main -= self.current_line.main_width;
+        max.main -= self.current_line.main_width - bases;
       }
 
       let clamp = BoxClamp { max: max.to_size(dir), min: min.to_size(dir) };
@@ -199,7 +241,7 @@ impl FlexLayouter {
 
       let flex = l
         .query::<Expanded>()
-        .map(|expanded| expanded.flex);
+        .map(|expanded| FlexItem::new(&expanded, size.main));
 
       layouter = l.into_next_sibling();
       let gap = if layouter.is_some() && !FlexLayouter::is_space_layout(self.justify_content) {
@@ -208,31 +250,38 @@ impl FlexLayouter {
         0.
       };
 
-      // flex-item need use empty space to resize after all fixed widget performed
-      // layout.
+      // flex-item only take its basis size, it need use empty space to resize after
+      // all fixed widget performed layout.
+      let main = flex.as_ref().map_or(size.main, |f| f.basis);
+      if flex.is_some() {
+        bases += main;
+      }
+      let line = &mut self.current_line;
+      if flex.is_some() && main <= 0. {
+        line.main_width += gap;
+      } else if wrap && !line.is_empty() && line.main_width + main > max.main {
+        self.place_line();
+      } else {
+        line.main_width += gap;
+      }
+
       let line = &mut self.current_line;
-      if let Some(flex) = flex {
+      line.main_width += main;
+      if let Some(flex) = flex.as_ref() {
         // expanded child size is zero, it don't need calculate
         if size.main > 0. {
-          line.flex_sum += flex;
+          line.flex_sum += flex.grow;
         }
-        line.main_width += gap;
       } else {
-        if wrap && !line.is_empty() && line.main_width + size.main > max.main {
-          self.place_line();
-        } else {
-          line.main_width += gap;
-        }
-
-        let line = &mut self.current_line;
-        line.main_width += size.main;
         line.cross_line_height = line.cross_line_height.max(size.cross);
       }
 
-      self
-        .current_line
-        .items_info
-        .push(FlexLayoutInfo { size, flex, pos: <_>::default() });
+      self.current_line.items_info.push(FlexLayoutInfo {
+        size,
+        flex,
+        pos: <_>::default(),
+        baseline: 0.,
+      });
     }
     self.place_line();
   }
@@ -248,23 +297,36 @@ impl FlexLayouter {
     let mut layouter = ctx.first_child_layouter();
     self.lines.iter_mut().for_each(|line| {
       let flex_sum = if line.flex_sum.is_normal() { line.flex_sum } else { 1. };
-      let flex_unit = (self.max.main - line.main_width) / flex_sum;
+      let free = self.max.main - line.main_width;
+      let shrink_sum: f32 = line
+        .items_info
+        .iter()
+        .filter(|info| info.size.main > 0.)
+        .filter_map(|info| info.flex.as_ref())
+        .map(|flex| flex.shrink * flex.basis)
+        .sum();
       line.items_info.iter_mut().for_each(|info| {
         let mut l = layouter.take().unwrap();
         if info.size.main > 0. {
-          if let Some(flex) = info.flex {
+          if let Some(flex) = info.flex.as_ref() {
             let &mut Self { mut max, mut min, dir, .. } = self;
             // If the maximum size is not specified, we are unable to calculate the flex
             // size.
-            if flex_unit.is_normal() {
-              let main = flex_unit * flex;
+            if free.is_finite() {
+              let main = if free >= 0. {
+                flex.basis + free * flex.grow / flex_sum
+              } else if shrink_sum > 0. {
+                (flex.basis + free * flex.shrink * flex.basis / shrink_sum).max(0.)
+              } else {
+                flex.basis
+              };
               max.main = main;
               min.main = main;
               let clamp = BoxClamp { max: max.to_size(dir), min: min.to_size(dir) };
               let size = l.perform_widget_layout(clamp);
               info.size = FlexSize::from_size(size, dir);
             }
-            line.main_width += info.size.main;
+            line.main_width += info.size.main - flex.basis;
             line.cross_line_height = line.cross_line_height.max(info.size.cross);
           }
         }
@@ -274,29 +336,63 @@ impl FlexLayouter {
     });
   }
 
+  /// Align the children of every line by their baseline, the line is expanded
+  /// to hold the highest ascent and the deepest descent.
+  fn baseline_align_lines(&mut self, ctx: &mut LayoutCtx) {
+    let children: Vec<_> = ctx.children().collect();
+    let mut children = children.into_iter();
+    self.lines.iter_mut().for_each(|line| {
+      let (mut ascent, mut descent) = (0f32, 0f32);
+      line.items_info.iter_mut().for_each(|info| {
+        let child = children.next().unwrap();
+        info.baseline = ctx
+          .widget_baseline(child)
+          .unwrap_or(info.size.cross);
+        ascent = ascent.max(info.baseline);
+        descent = descent.max(info.size.cross - info.baseline);
+      });
+      line.ascent = ascent;
+      line.cross_line_height = line.cross_line_height.max(ascent + descent);
+    });
+  }
+
   fn update_children_position(&mut self, bound: FlexSize, ctx: &mut LayoutCtx) {
-    let Self { reverse, dir, align_items, justify_content, lines, .. } = self;
+    let Self { reverse, dir, align_items, justify_content, align_content, lines, .. } = self;
 
-    let cross_size = lines.iter().map(|l| l.cross_line_height).sum();
+    let cross_size: f32 = lines.iter().map(|l| l.cross_line_height).sum();
     // cross gap don't use calc offset
     let cross_gap_count =
       if !lines.is_empty() { (lines.len() - 1) as f32 * self.cross_axis_gap } else { 0. };
-    let cross_offset = align_items.align_value(cross_size, bound.cross - cross_gap_count);
+    let (cross_offset, cross_step) = match align_content {
+      Some(align_content) => {
+        let gap = if FlexLayouter::is_space_layout(*align_content) { 0. } else { cross_gap_count };
+        let args =
+          JustifyArgs { size: cross_size + gap, count: lines.len(), gap: self.cross_axis_gap };
+        args.place(bound.cross, *align_content)
+      }
+      None => {
+        (align_items.align_value(cross_size, bound.cross - cross_gap_count), self.cross_axis_gap)
+      }
+    };
+    let baseline = *align_items == Align::Baseline && *dir == Direction::Horizontal;
 
     macro_rules! update_position {
       ($($rev: ident)?) => {
-        let mut cross = cross_offset - self.cross_axis_gap;
+        let mut cross = cross_offset;
         lines.iter_mut()$(.$rev())?.for_each(|line| {
           let (mut main, step) = line.place_args(bound.main, *justify_content, self.main_axis_gap);
           line.items_info.iter_mut()$(.$rev())?.for_each(|item| {
-            let item_cross_offset =
-              align_items.align_value(item.size.cross, line.cross_line_height);
+            let item_cross_offset = if baseline {
+              line.ascent - item.baseline
+            } else {
+              align_items.align_value(item.size.cross, line.cross_line_height)
+            };
 
-            item.pos.cross = cross + item_cross_offset + self.cross_axis_gap;
+            item.pos.cross = cross + item_cross_offset;
             item.pos.main = main;
             main = main + item.size.main + step;
           });
-          cross += line.cross_line_height + self.cross_axis_gap;
+          cross += line.cross_line_height + cross_step;
         });
       };
     }
@@ -331,43 +427,82 @@ struct MainLineInfo {
   items_info: Vec<FlexLayoutInfo>,
   flex_sum: f32,
   cross_line_height: f32,
+  /// The max distance from the top to the baseline of the children, only
+  /// used by the baseline alignment.
+  ascent: f32,
 }
 
 struct FlexLayoutInfo {
   pos: FlexSize,
   size: FlexSize,
-  flex: Option<f32>,
+  flex: Option<FlexItem>,
+  baseline: f32,
 }
 
-impl MainLineInfo {
-  fn is_empty(&self) -> bool { self.items_info.is_empty() }
+struct FlexItem {
+  grow: f32,
+  shrink: f32,
+  basis: f32,
+}
 
-  fn place_args(&self, main_max: f32, justify_content: JustifyContent, gap: f32) -> (f32, f32) {
-    if self.items_info.is_empty() {
+impl FlexItem {
+  fn new(expanded: &Expanded, content: f32) -> Self {
+    let basis = match expanded.basis {
+      FlexBasis::Content => content,
+      FlexBasis::Fixed(basis) => basis,
+    };
+    Self { grow: expanded.flex, shrink: expanded.shrink, basis }
+  }
+}
+
+/// The arguments to place items along an axis by the `JustifyContent`.
+struct JustifyArgs {
+  /// The total size of the items, includes the gaps between them.
+  size: f32,
+  count: usize,
+  gap: f32,
+}
+
+impl JustifyArgs {
+  /// Return the offset of the first item and the step between the items.
+  fn place(&self, max: f32, justify: JustifyContent) -> (f32, f32) {
+    let &Self { size, count, gap } = self;
+    if count == 0 {
       return (0., 0.);
     }
 
-    let item_cnt = self.items_info.len() as f32;
-    match justify_content {
+    let cnt = count as f32;
+    match justify {
       JustifyContent::Start => (0., gap),
-      JustifyContent::Center => ((main_max - self.main_width) / 2., gap),
-      JustifyContent::End => (main_max - self.main_width, gap),
+      JustifyContent::Center => ((max - size) / 2., gap),
+      JustifyContent::End => (max - size, gap),
       JustifyContent::SpaceAround => {
-        let step = (main_max - self.main_width) / item_cnt;
+        let step = (max - size) / cnt;
         (step / 2., step)
       }
+      // A single item is flush with the start edge.
+      JustifyContent::SpaceBetween if count == 1 => (0., gap),
       JustifyContent::SpaceBetween => {
-        let step = (main_max - self.main_width) / (item_cnt - 1.);
+        let step = (max - size) / (cnt - 1.);
         (0., step)
       }
       JustifyContent::SpaceEvenly => {
-        let step = (main_max - self.main_width) / (item_cnt + 1.);
+        let step = (max - size) / (cnt + 1.);
         (step, step)
       }
     }
   }
 }
 
+impl MainLineInfo {
+  fn is_empty(&self) -> bool { self.items_info.is_empty() }
+
+  fn place_args(&self, main_max: f32, justify_content: JustifyContent, gap: f32) -> (f32, f32) {
+    let args = JustifyArgs { size: self.main_width, count: self.items_info.len(), gap };
+    args.place(main_max, justify_content)
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use ribir_core::test_helper::*;
@@ -557,6 +692,75 @@ mod tests {
     LayoutCase::new(&[0, 2]).with_rect(ribir_geom::rect(200., 0., 100., 40.))
   );
 
+  widget_layout_test!(
+    baseline_cross_align,
+    WidgetTester::new(fn_widget! {
+      let style = |font_size: f32| TextStyle {
+        font_size: FontSize::Pixel(font_size.into()),
+        ..<_>::default()
+      };
+      @Row {
+        align_items: Align::Baseline,
+        @Text { text: "small", text_style: style(12.) }
+        @Text { text: "large", text_style: style(24.) }
+        @SizedBox { size: Size::new(10., 10.) }
+      }
+    })
+    .with_wnd_size(Size::new(500., 500.)),
+    // The baselines are at the ascent of the font, 0.987em of Lato.
+    LayoutCase::new(&[0, 0]).with_y(11.844),
+    LayoutCase::new(&[0, 1]).with_y(0.),
+    LayoutCase::new(&[0, 2]).with_y(13.688)
+  );
+
+  fn lines_align(align_content: JustifyContent) -> WidgetTester {
+    WidgetTester::new(fn_widget! {
+      @SizedBox {
+        size: Size::new(200., 200.),
+        @Flex {
+          wrap: true,
+          line_gap: 10.,
+          align_content,
+          @{ (0..3).map(|_| SizedBox { size: Size::new(100., 20.) }) }
+        }
+      }
+    })
+    .with_wnd_size(Size::new(500., 500.))
+  }
+
+  widget_layout_test!(
+    center_lines_align,
+    lines_align(JustifyContent::Center),
+    LayoutCase::new(&[0, 0, 0]).with_y(75.),
+    LayoutCase::new(&[0, 0, 1]).with_y(75.),
+    LayoutCase::new(&[0, 0, 2]).with_y(105.)
+  );
+
+  widget_layout_test!(
+    space_between_lines_align,
+    lines_align(JustifyContent::SpaceBetween),
+    LayoutCase::new(&[0, 0]).with_size(Size::new(200., 200.)),
+    LayoutCase::new(&[0, 0, 0]).with_y(0.),
+    LayoutCase::new(&[0, 0, 2]).with_y(180.)
+  );
+
+  widget_layout_test!(
+    space_between_single_line_align,
+    WidgetTester::new(fn_widget! {
+      @SizedBox {
+        size: Size::new(200., 200.),
+        @Flex {
+          wrap: true,
+          align_content: JustifyContent::SpaceBetween,
+          @{ (0..2).map(|_| SizedBox { size: Size::new(50., 20.) }) }
+        }
+      }
+    })
+    .with_wnd_size(Size::new(500., 500.)),
+    LayoutCase::new(&[0, 0, 0]).with_pos(Point::zero()),
+    LayoutCase::new(&[0, 0, 1]).with_pos(Point::new(50., 0.))
+  );
+
   fn main_align(justify_content: JustifyContent) -> WidgetTester {
     WidgetTester::new(fn_widget! {
       let item_size = Size::new(100., 20.);
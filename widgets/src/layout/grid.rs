@@ -0,0 +1,627 @@
+use std::ops::Range;
+
+use ribir_core::prelude::{log::warn, *};
+
+/// The sizing function of a row or a column of a [`Grid`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum GridTrack {
+  /// A fixed size in pixels.
+  Fixed(f32),
+  /// A share of the free space of the grid, like the `fr` unit of CSS. If the
+  /// grid has no limit in this axis, the track is sized as `Auto`.
+  Fraction(f32),
+  /// Sized to fit the largest child in the track.
+  #[default]
+  Auto,
+  /// Sized to fit the largest child in the track, but not less than the first
+  /// value and not greater than the second value.
+  MinMax(f32, f32),
+}
+
+/// The named areas of a [`Grid`], like the `grid-template-areas` of CSS.
+///
+/// Every string describes a row, the names of its cells are separated by
+/// whitespace and `.` is a cell without a name. An area is the rectangle of
+/// the cells with the same name.
+///
+/// # Example
+///
+/// ```
+/// use ribir_widgets::prelude::*;
+///
+/// let areas = GridAreas::from(["header header", "sidebar main"]);
+/// assert_eq!(areas.area("header"), Some((0..1, 0..2)));
+/// assert_eq!(areas.area("main"), Some((1..2, 1..2)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GridAreas(Vec<Vec<String>>);
+
+impl GridAreas {
+  pub fn new<'a>(rows: impl IntoIterator<Item = &'a str>) -> Self {
+    let rows = rows
+      .into_iter()
+      .map(|row| row.split_whitespace().map(String::from).collect())
+      .collect();
+    Self(rows)
+  }
+
+  #[inline]
+  pub fn row_count(&self) -> usize { self.0.len() }
+
+  pub fn column_count(&self) -> usize { self.0.iter().map(Vec::len).max().unwrap_or(0) }
+
+  /// Return the rows and the columns covered by the area of `name`.
+  pub fn area(&self, name: &str) -> Option<(Range<usize>, Range<usize>)> {
+    let mut area: Option<(Range<usize>, Range<usize>)> = None;
+    for (row, names) in self.0.iter().enumerate() {
+      for (col, _) in names
+        .iter()
+        .enumerate()
+        .filter(|(_, n)| *n == name)
+      {
+        let (rows, cols) = area.get_or_insert((row..row + 1, col..col + 1));
+        rows.start = rows.start.min(row);
+        rows.end = rows.end.max(row + 1);
+        cols.start = cols.start.min(col);
+        cols.end = cols.end.max(col + 1);
+      }
+    }
+    area
+  }
+}
+
+impl<'a, const N: usize> From<[&'a str; N]> for GridAreas {
+  fn from(rows: [&'a str; N]) -> Self { Self::new(rows) }
+}
+
+/// A widget that places its child in the cells of a [`Grid`].
+///
+/// The child is placed in the named `area` of the grid if it's set, otherwise
+/// at the `row` and the `column`. The children without a position are placed
+/// in the first free cells one by one, row by row.
+#[derive(Clone, PartialEq, Declare)]
+pub struct GridItem {
+  /// The first row of the child, starts from 0.
+  #[declare(default)]
+  pub row: Option<usize>,
+  /// The first column of the child, starts from 0.
+  #[declare(default)]
+  pub column: Option<usize>,
+  /// The number of rows the child spans.
+  #[declare(default = 1usize)]
+  pub row_span: usize,
+  /// The number of columns the child spans.
+  #[declare(default = 1usize)]
+  pub column_span: usize,
+  /// The name of the area in `Grid::areas` the child placed in, it overrides
+  /// the position and the spans of the child.
+  #[declare(default)]
+  pub area: CowArc<str>,
+}
+
+impl<'c> ComposeChild<'c> for GridItem {
+  type Child = Widget<'c>;
+  #[inline]
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    let data: Box<dyn Query> = match this.try_into_value() {
+      Ok(data) => Box::new(Queryable(data)),
+      Err(data) => Box::new(data),
+    };
+    Provider::new(data)
+      .with_child(fn_widget! { @{ child } })
+      .into_widget()
+  }
+}
+
+/// A layout that places its children in the cells of rows and columns, like
+/// the CSS grid layout.
+///
+/// The `rows` and the `columns` define the sizes of the tracks, the children
+/// placed beyond them create new tracks sized by `auto_rows` and
+/// `auto_columns`. Use [`GridItem`] to place a child in specified cells or a
+/// named area.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let _dashboard = fn_widget! {
+///   @Grid {
+///     columns: vec![GridTrack::Fixed(200.), GridTrack::Fraction(1.)],
+///     rows: vec![GridTrack::Auto, GridTrack::Fraction(1.)],
+///     areas: ["header header", "sidebar main"],
+///     column_gap: 8.,
+///     row_gap: 8.,
+///     @GridItem { area: "header", @Text { text: "Dashboard" } }
+///     @GridItem { area: "sidebar", @Text { text: "Menu" } }
+///     @GridItem { area: "main", @Text { text: "Content" } }
+///   }
+/// };
+/// ```
+#[derive(Default, MultiChild, Declare, Clone, PartialEq)]
+pub struct Grid {
+  /// The sizes of the rows.
+  #[declare(default)]
+  pub rows: Vec<GridTrack>,
+  /// The sizes of the columns.
+  #[declare(default)]
+  pub columns: Vec<GridTrack>,
+  /// The size of the rows beyond `rows`.
+  #[declare(default)]
+  pub auto_rows: GridTrack,
+  /// The size of the columns beyond `columns`.
+  #[declare(default)]
+  pub auto_columns: GridTrack,
+  /// The named areas to place the children by name.
+  #[declare(default)]
+  pub areas: GridAreas,
+  /// The gap between two rows.
+  #[declare(default)]
+  pub row_gap: f32,
+  /// The gap between two columns.
+  #[declare(default)]
+  pub column_gap: f32,
+  /// How the children placed in the horizontal axis of their cells.
+  #[declare(default = Align::Stretch)]
+  pub justify_items: Align,
+  /// How the children placed in the vertical axis of their cells.
+  #[declare(default = Align::Stretch)]
+  pub align_items: Align,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Placement {
+  rows: Range<usize>,
+  columns: Range<usize>,
+}
+
+impl Render for Grid {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let children: Vec<_> = ctx.children().collect();
+    let placements = self.place_children(&children, ctx);
+    let row_cnt = placements
+      .iter()
+      .map(|p| p.rows.end)
+      .chain([self.rows.len(), self.areas.row_count()])
+      .max()
+      .unwrap_or(0);
+    let col_cnt = placements
+      .iter()
+      .map(|p| p.columns.end)
+      .chain([self.columns.len(), self.areas.column_count()])
+      .max()
+      .unwrap_or(0);
+    let rows = tracks(&self.rows, self.auto_rows, row_cnt);
+    let columns = tracks(&self.columns, self.auto_columns, col_cnt);
+    let max = clamp.max;
+
+    // Size the columns by the children without the limit of the height.
+    let mut contents = vec![];
+    for (child, p) in children.iter().zip(placements.iter()) {
+      if is_content_sized(&columns, &p.columns, max.width) {
+        let clamp = BoxClamp { min: Size::zero(), max: Size::splat(f32::INFINITY) };
+        let size = ctx
+          .child_layouter(*child)
+          .perform_widget_layout(clamp);
+        contents.push((p.columns.clone(), size.width));
+      }
+    }
+    let col_sizes = track_sizes(&columns, self.column_gap, max.width, &contents);
+    let col_starts = track_starts(&col_sizes, self.column_gap);
+
+    // Size the rows by the children in the width of their cells.
+    contents.clear();
+    for (child, p) in children.iter().zip(placements.iter()) {
+      if is_content_sized(&rows, &p.rows, max.height) {
+        let width = span_size(&col_sizes, &p.columns, self.column_gap);
+        let (min, max) = cell_clamp(self.justify_items, width);
+        let clamp = BoxClamp { min: Size::new(min, 0.), max: Size::new(max, f32::INFINITY) };
+        let size = ctx
+          .child_layouter(*child)
+          .perform_widget_layout(clamp);
+        contents.push((p.rows.clone(), size.height));
+      }
+    }
+    let row_sizes = track_sizes(&rows, self.row_gap, max.height, &contents);
+    let row_starts = track_starts(&row_sizes, self.row_gap);
+
+    // Layout the children in their cells.
+    for (child, p) in children.iter().zip(placements.iter()) {
+      let cell = Size::new(
+        span_size(&col_sizes, &p.columns, self.column_gap),
+        span_size(&row_sizes, &p.rows, self.row_gap),
+      );
+      let (min_w, max_w) = cell_clamp(self.justify_items, cell.width);
+      let (min_h, max_h) = cell_clamp(self.align_items, cell.height);
+      let clamp = BoxClamp { min: Size::new(min_w, min_h), max: Size::new(max_w, max_h) };
+      let mut l = ctx.child_layouter(*child);
+      let size = l.perform_widget_layout(clamp);
+      let x =
+        col_starts[p.columns.start] + align_offset(self.justify_items, cell.width, size.width);
+      let y = row_starts[p.rows.start] + align_offset(self.align_items, cell.height, size.height);
+      l.update_position(Point::new(x, y));
+    }
+
+    let size = Size::new(
+      span_size(&col_sizes, &(0..col_cnt), self.column_gap),
+      span_size(&row_sizes, &(0..row_cnt), self.row_gap),
+    );
+    clamp.clamp(size)
+  }
+
+  #[inline]
+  fn paint(&self, _: &mut PaintingCtx) {}
+}
+
+impl Grid {
+  /// Resolve the cells of every child, the children with a position are
+  /// placed first, and then the others are placed in the free cells.
+  fn place_children(&self, children: &[WidgetId], ctx: &LayoutCtx) -> Vec<Placement> {
+    let items: Vec<_> = children
+      .iter()
+      .map(|child| {
+        let item = ctx.query_of_widget::<GridItem>(*child);
+        item.map(|item| self.explicit_item(item.clone()))
+      })
+      .collect();
+
+    let col_cnt = items
+      .iter()
+      .flatten()
+      .map(|item| item.column.unwrap_or(0) + item.column_span.max(1))
+      .chain([self.columns.len(), self.areas.column_count(), 1])
+      .max()
+      .unwrap_or(1);
+    let mut cells = Cells { col_cnt, occupied: vec![] };
+    let mut placements: Vec<Option<Placement>> = vec![None; items.len()];
+
+    // The children with both the row and the column.
+    for (item, placement) in items.iter().zip(placements.iter_mut()) {
+      if let Some(GridItem { row: Some(row), column: Some(col), row_span, column_span, .. }) = item
+      {
+        let p = Placement {
+          rows: *row..row + row_span.max(&1),
+          columns: *col..col + column_span.max(&1),
+        };
+        cells.occupy(&p);
+        *placement = Some(p);
+      }
+    }
+
+    // The children only with the row.
+    for (item, placement) in items.iter().zip(placements.iter_mut()) {
+      if let Some(GridItem { row: Some(row), column: None, row_span, column_span, .. }) = item {
+        let (rs, cs) = ((*row_span).max(1), (*column_span).max(1));
+        // Append implicit columns if the row is full.
+        let col = (0..)
+          .find(|col| cells.is_vacant(*row, *col, rs, cs))
+          .unwrap();
+        let p = Placement { rows: *row..row + rs, columns: col..col + cs };
+        cells.occupy(&p);
+        *placement = Some(p);
+      }
+    }
+
+    // The others are placed by the cursor, row by row.
+    let (mut cursor_row, mut cursor_col) = (0, 0);
+    for (item, placement) in items.iter().zip(placements.iter_mut()) {
+      if placement.is_some() {
+        continue;
+      }
+      let (column, rs, cs) = item
+        .as_ref()
+        .map_or((None, 1, 1), |item| (item.column, item.row_span.max(1), item.column_span.max(1)));
+      let p = if let Some(col) = column {
+        if col < cursor_col {
+          cursor_row += 1;
+        }
+        while !cells.is_free(cursor_row, col, rs, cs) {
+          cursor_row += 1;
+        }
+        Placement { rows: cursor_row..cursor_row + rs, columns: col..col + cs }
+      } else {
+        loop {
+          if cursor_col + cs > cells.col_cnt {
+            cursor_row += 1;
+            cursor_col = 0;
+          }
+          if cells.is_free(cursor_row, cursor_col, rs, cs) {
+            break;
+          }
+          cursor_col += 1;
+        }
+        Placement { rows: cursor_row..cursor_row + rs, columns: cursor_col..cursor_col + cs }
+      };
+      cursor_col = p.columns.end;
+      cells.occupy(&p);
+      *placement = Some(p);
+    }
+
+    placements
+      .into_iter()
+      .map(Option::unwrap)
+      .collect()
+  }
+
+  /// Convert the area of the item to its position and spans.
+  fn explicit_item(&self, mut item: GridItem) -> GridItem {
+    if !item.area.is_empty() {
+      if let Some((rows, cols)) = self.areas.area(&item.area) {
+        item.row = Some(rows.start);
+        item.column = Some(cols.start);
+        item.row_span = rows.len();
+        item.column_span = cols.len();
+      } else {
+        warn!("The area `{}` is not defined in the grid.", &*item.area);
+      }
+    }
+    item
+  }
+}
+
+struct Cells {
+  col_cnt: usize,
+  occupied: Vec<Vec<bool>>,
+}
+
+impl Cells {
+  fn is_free(&self, row: usize, col: usize, row_span: usize, col_span: usize) -> bool {
+    col + col_span <= self.col_cnt && self.is_vacant(row, col, row_span, col_span)
+  }
+
+  /// If none of the cells is occupied, the cells beyond the columns are
+  /// vacant.
+  fn is_vacant(&self, row: usize, col: usize, row_span: usize, col_span: usize) -> bool {
+    !(row..row + row_span).any(|r| {
+      self
+        .occupied
+        .get(r)
+        .is_some_and(|cells| cells.iter().skip(col).take(col_span).any(|c| *c))
+    })
+  }
+
+  fn occupy(&mut self, p: &Placement) {
+    let col_cnt = self.col_cnt.max(p.columns.end);
+    if col_cnt > self.col_cnt {
+      self.col_cnt = col_cnt;
+      self
+        .occupied
+        .iter_mut()
+        .for_each(|cells| cells.resize(col_cnt, false));
+    }
+    if self.occupied.len() < p.rows.end {
+      self
+        .occupied
+        .resize(p.rows.end, vec![false; col_cnt]);
+    }
+    for row in p.rows.clone() {
+      self.occupied[row][p.columns.clone()].fill(true);
+    }
+  }
+}
+
+fn tracks(defined: &[GridTrack], auto: GridTrack, cnt: usize) -> Vec<GridTrack> {
+  (0..cnt)
+    .map(|i| defined.get(i).copied().unwrap_or(auto))
+    .collect()
+}
+
+/// If the track is sized by its content.
+fn is_content_track(track: GridTrack, max: f32) -> bool {
+  match track {
+    GridTrack::Fixed(_) => false,
+    GridTrack::Fraction(_) => !max.is_finite(),
+    GridTrack::Auto | GridTrack::MinMax(..) => true,
+  }
+}
+
+/// If the children in the tracks need to be measured to size the tracks. The
+/// children spanning a flexible track not contribute to the sizes.
+fn is_content_sized(tracks: &[GridTrack], span: &Range<usize>, max: f32) -> bool {
+  let tracks = &tracks[span.clone()];
+  let flexible = |t: &GridTrack| matches!(t, GridTrack::Fraction(_)) && max.is_finite();
+  tracks.iter().any(|t| is_content_track(*t, max)) && !tracks.iter().any(flexible)
+}
+
+fn track_sizes(
+  tracks: &[GridTrack], gap: f32, max: f32, contents: &[(Range<usize>, f32)],
+) -> Vec<f32> {
+  let mut sizes: Vec<f32> = tracks
+    .iter()
+    .map(|t| match *t {
+      GridTrack::Fixed(v) | GridTrack::MinMax(v, _) => v,
+      GridTrack::Fraction(_) | GridTrack::Auto => 0.,
+    })
+    .collect();
+  let limit = |i: usize| match tracks[i] {
+    GridTrack::MinMax(_, max) => max,
+    _ => f32::INFINITY,
+  };
+
+  // Grow the tracks to fit the children, the children spanning fewer tracks
+  // first.
+  let mut contents: Vec<_> = contents.iter().collect();
+  contents.sort_by_key(|(span, _)| span.len());
+  for (span, size) in contents {
+    let mut extra = size - span_size(&sizes, span, gap);
+    let mut growing: Vec<_> = span
+      .clone()
+      .filter(|i| is_content_track(tracks[*i], max) && sizes[*i] < limit(*i))
+      .collect();
+    while extra > 0. && !growing.is_empty() {
+      let share = extra / growing.len() as f32;
+      growing.retain(|i| {
+        let grow = share.min(limit(*i) - sizes[*i]);
+        sizes[*i] += grow;
+        extra -= grow;
+        sizes[*i] < limit(*i)
+      });
+    }
+  }
+
+  // Share the free space to the flexible tracks.
+  if max.is_finite() {
+    let fr_sum: f32 = tracks
+      .iter()
+      .map(|t| if let GridTrack::Fraction(fr) = t { *fr } else { 0. })
+      .sum();
+    if fr_sum > 0. {
+      let free = (max - span_size(&sizes, &(0..sizes.len()), gap)).max(0.);
+      // Like CSS, the tracks less than `1fr` in total not fill all the space.
+      let unit = free / fr_sum.max(1.);
+      for (t, size) in tracks.iter().zip(sizes.iter_mut()) {
+        if let GridTrack::Fraction(fr) = t {
+          *size = unit * fr;
+        }
+      }
+    }
+  }
+  sizes
+}
+
+fn span_size(sizes: &[f32], span: &Range<usize>, gap: f32) -> f32 {
+  if span.is_empty() {
+    0.
+  } else {
+    sizes[span.clone()].iter().sum::<f32>() + gap * (span.len() - 1) as f32
+  }
+}
+
+fn track_starts(sizes: &[f32], gap: f32) -> Vec<f32> {
+  let mut start = 0.;
+  sizes
+    .iter()
+    .map(|size| {
+      let s = start;
+      start += size + gap;
+      s
+    })
+    .collect()
+}
+
+fn cell_clamp(align: Align, cell: f32) -> (f32, f32) {
+  if align == Align::Stretch { (cell, cell) } else { (0., cell) }
+}
+
+fn align_offset(align: Align, cell: f32, size: f32) -> f32 {
+  match align {
+    Align::Start | Align::Stretch | Align::Baseline => 0.,
+    Align::Center => (cell - size) / 2.,
+    Align::End => cell - size,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::test_helper::*;
+  use ribir_dev_helper::*;
+
+  use super::*;
+  use crate::prelude::*;
+
+  widget_layout_test!(
+    fixed_and_fraction_tracks,
+    WidgetTester::new(fn_widget! {
+      let size = Size::new(50., 30.);
+      @Grid {
+        columns: vec![GridTrack::Fixed(100.), GridTrack::Fraction(1.), GridTrack::Fraction(3.)],
+        column_gap: 10.,
+        row_gap: 10.,
+        @SizedBox { size }
+        @SizedBox { size }
+        @SizedBox { size }
+        @SizedBox { size }
+      }
+    })
+    .with_wnd_size(Size::new(500., 500.)),
+    LayoutCase::default().with_size(Size::new(500., 70.)),
+    LayoutCase::new(&[0, 0]).with_rect(ribir_geom::rect(0., 0., 100., 30.)),
+    LayoutCase::new(&[0, 1]).with_rect(ribir_geom::rect(110., 0., 95., 30.)),
+    LayoutCase::new(&[0, 2]).with_rect(ribir_geom::rect(215., 0., 285., 30.)),
+    LayoutCase::new(&[0, 3]).with_rect(ribir_geom::rect(0., 40., 100., 30.))
+  );
+
+  widget_layout_test!(
+    named_areas,
+    WidgetTester::new(fn_widget! {
+      let size = Size::new(10., 10.);
+      @Grid {
+        columns: vec![GridTrack::Fixed(100.), GridTrack::Fraction(1.)],
+        rows: vec![GridTrack::Fixed(50.), GridTrack::Fraction(1.)],
+        areas: ["header header", "side main"],
+        @GridItem { area: "main", @SizedBox { size } }
+        @GridItem { area: "header", @SizedBox { size } }
+        @GridItem { area: "side", @SizedBox { size } }
+      }
+    })
+    .with_wnd_size(Size::new(400., 300.)),
+    LayoutCase::new(&[0, 0]).with_rect(ribir_geom::rect(100., 50., 300., 250.)),
+    LayoutCase::new(&[0, 1]).with_rect(ribir_geom::rect(0., 0., 400., 50.)),
+    LayoutCase::new(&[0, 2]).with_rect(ribir_geom::rect(0., 50., 100., 250.))
+  );
+
+  widget_layout_test!(
+    span_and_auto_placement,
+    WidgetTester::new(fn_widget! {
+      let size = Size::new(20., 20.);
+      @Grid {
+        columns: vec![GridTrack::Fixed(50.); 3],
+        auto_rows: GridTrack::Fixed(50.),
+        justify_items: Align::Center,
+        align_items: Align::End,
+        @GridItem { row: 0usize, column: 1usize, column_span: 2usize, @SizedBox { size } }
+        @SizedBox { size }
+        @SizedBox { size }
+        @GridItem { row_span: 2usize, @SizedBox { size } }
+      }
+    })
+    .with_wnd_size(Size::new(400., 400.)),
+    LayoutCase::default().with_size(Size::new(150., 150.)),
+    LayoutCase::new(&[0, 0]).with_rect(ribir_geom::rect(90., 30., 20., 20.)),
+    LayoutCase::new(&[0, 1]).with_rect(ribir_geom::rect(15., 30., 20., 20.)),
+    LayoutCase::new(&[0, 2]).with_rect(ribir_geom::rect(15., 80., 20., 20.)),
+    LayoutCase::new(&[0, 3]).with_rect(ribir_geom::rect(65., 130., 20., 20.))
+  );
+
+  widget_layout_test!(
+    content_sized_tracks,
+    WidgetTester::new(fn_widget! {
+      @Grid {
+        columns: vec![GridTrack::Auto, GridTrack::MinMax(10., 40.)],
+        @SizedBox { size: Size::new(30., 10.) }
+        @SizedBox { size: Size::new(100., 10.) }
+        @GridItem { column_span: 2usize, @SizedBox { size: Size::new(90., 20.) } }
+      }
+    })
+    .with_wnd_size(Size::new(400., 400.)),
+    LayoutCase::default().with_size(Size::new(90., 30.)),
+    LayoutCase::new(&[0, 0]).with_rect(ribir_geom::rect(0., 0., 50., 10.)),
+    LayoutCase::new(&[0, 1]).with_rect(ribir_geom::rect(50., 0., 40., 10.)),
+    LayoutCase::new(&[0, 2]).with_rect(ribir_geom::rect(0., 10., 90., 20.))
+  );
+
+  widget_layout_test!(
+    row_item_in_full_row,
+    WidgetTester::new(fn_widget! {
+      let size = Size::new(10., 10.);
+      @Grid {
+        columns: vec![GridTrack::Fixed(50.); 2],
+        auto_columns: GridTrack::Fixed(30.),
+        auto_rows: GridTrack::Fixed(20.),
+        justify_items: Align::Start,
+        align_items: Align::Start,
+        @GridItem { row: 0usize, column: 0usize, @SizedBox { size } }
+        @GridItem { row: 0usize, column: 1usize, @SizedBox { size } }
+        @GridItem { row: 0usize, @SizedBox { size } }
+        @SizedBox { size }
+      }
+    })
+    .with_wnd_size(Size::new(400., 400.)),
+    LayoutCase::default().with_size(Size::new(130., 40.)),
+    // The row is full, an implicit column is appended.
+    LayoutCase::new(&[0, 2]).with_rect(ribir_geom::rect(100., 0., 10., 10.)),
+    LayoutCase::new(&[0, 3]).with_rect(ribir_geom::rect(0., 20., 10., 10.))
+  );
+}
@@ -0,0 +1,108 @@
+use ribir_core::prelude::*;
+
+/// A widget that sizes its child to the child's max intrinsic width, for
+/// example, a column of buttons stretched to the width of the widest one.
+///
+/// It queries the intrinsic size of the whole subtree, so it's more expensive
+/// than a fixed size, use it only when needed.
+#[derive(SingleChild, Declare)]
+pub struct IntrinsicWidth {}
+
+/// A widget that sizes its child to the child's max intrinsic height, for
+/// example, a row of cards stretched to the height of the tallest one.
+///
+/// It queries the intrinsic size of the whole subtree, so it's more expensive
+/// than a fixed size, use it only when needed.
+#[derive(SingleChild, Declare)]
+pub struct IntrinsicHeight {}
+
+impl Render for IntrinsicWidth {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let Some(child) = ctx.single_child() else {
+      return ZERO_SIZE;
+    };
+    let width = ctx
+      .child_intrinsic_size(child, Intrinsic::MaxWidth, clamp.max.height)
+      .clamp(clamp.min.width, clamp.max.width);
+    ctx.assert_perform_single_child_layout(clamp.with_fixed_width(width))
+  }
+
+  fn intrinsic_size(&self, intrinsic: Intrinsic, extent: f32, ctx: &mut LayoutCtx) -> f32 {
+    if intrinsic.is_width() {
+      ctx.children_max_intrinsic_size(Intrinsic::MaxWidth, extent)
+    } else {
+      let width = ctx.children_max_intrinsic_size(Intrinsic::MaxWidth, f32::INFINITY);
+      ctx.children_max_intrinsic_size(intrinsic, width.min(extent))
+    }
+  }
+
+  #[inline]
+  fn paint(&self, _: &mut PaintingCtx) {}
+}
+
+impl Render for IntrinsicHeight {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let Some(child) = ctx.single_child() else {
+      return ZERO_SIZE;
+    };
+    let height = ctx
+      .child_intrinsic_size(child, Intrinsic::MaxHeight, clamp.max.width)
+      .clamp(clamp.min.height, clamp.max.height);
+    ctx.assert_perform_single_child_layout(clamp.with_fixed_height(height))
+  }
+
+  fn intrinsic_size(&self, intrinsic: Intrinsic, extent: f32, ctx: &mut LayoutCtx) -> f32 {
+    if intrinsic.is_width() {
+      let height = ctx.children_max_intrinsic_size(Intrinsic::MaxHeight, f32::INFINITY);
+      ctx.children_max_intrinsic_size(intrinsic, height.min(extent))
+    } else {
+      ctx.children_max_intrinsic_size(Intrinsic::MaxHeight, extent)
+    }
+  }
+
+  #[inline]
+  fn paint(&self, _: &mut PaintingCtx) {}
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::test_helper::*;
+  use ribir_dev_helper::*;
+
+  use super::*;
+  use crate::prelude::*;
+
+  widget_layout_test!(
+    stretch_to_widest,
+    WidgetTester::new(fn_widget! {
+      @IntrinsicWidth {
+        @Column {
+          align_items: Align::Stretch,
+          @SizedBox { size: Size::new(100., 20.) }
+          @SizedBox { size: Size::new(50., 20.) }
+        }
+      }
+    })
+    .with_wnd_size(Size::new(500., 500.)),
+    LayoutCase::default().with_size(Size::new(100., 40.)),
+    LayoutCase::new(&[0, 0, 1]).with_rect(ribir_geom::rect(0., 20., 100., 20.))
+  );
+
+  widget_layout_test!(
+    stretch_to_tallest,
+    WidgetTester::new(fn_widget! {
+      @IntrinsicHeight {
+        @Row {
+          item_gap: 10.,
+          align_items: Align::Stretch,
+          @SizedBox { size: Size::new(20., 30.) }
+          @SizedBox { size: Size::new(20., 50.) }
+        }
+      }
+    })
+    .with_wnd_size(Size::new(500., 500.)),
+    LayoutCase::default().with_height(50.),
+    LayoutCase::new(&[0, 0, 0]).with_rect(ribir_geom::rect(0., 0., 20., 50.)),
+    LayoutCase::new(&[0, 0, 1]).with_rect(ribir_geom::rect(30., 0., 20., 50.))
+  );
+}
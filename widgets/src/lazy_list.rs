@@ -0,0 +1,405 @@
+use std::{cell::RefCell, rc::Rc};
+
+use ribir_core::prelude::*;
+
+use crate::{layout::Direction, scrollbar::Scrollbar};
+
+/// The builder of the items of a [`LazyList`], it's called with the index of
+/// the item to build.
+#[derive(Clone)]
+pub struct LazyItemBuilder(Rc<dyn Fn(usize) -> Widget<'static>>);
+
+impl<F: Fn(usize) -> Widget<'static> + 'static> From<F> for LazyItemBuilder {
+  fn from(f: F) -> Self { Self(Rc::new(f)) }
+}
+
+/// A scrollable list that only builds, lays out and paints the items
+/// intersecting its viewport, so it can hold a huge number of items.
+///
+/// Every item has the same extent in the main axis, so the list knows which
+/// items are visible without building them. The widgets of the items are
+/// recycled as the list scrolls: the list holds a fixed number of slots, an
+/// item scrolled out of the viewport hands its slot to the item scrolled in,
+/// and the items that stay in the viewport are not rebuilt.
+///
+/// If `cross_axis_count` is greater than 1, the list is a grid that places
+/// `cross_axis_count` items in every line.
+///
+/// Like the `Scrollbar`, the list provides the inner `ScrollableWidget` to
+/// its descendants.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let _logs = fn_widget! {
+///   @LazyList {
+///     item_count: 100_000usize,
+///     item_extent: 24.,
+///     item_builder: |idx: usize| {
+///       fn_widget! { @Text { text: format!("line {idx}") } }.into_widget()
+///     },
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct LazyList {
+  /// The number of the items.
+  pub item_count: usize,
+  /// The extent of every item in the main axis.
+  pub item_extent: f32,
+  /// The number of the items in every line, the items share the cross extent
+  /// of the list equally.
+  #[declare(default = 1usize)]
+  pub cross_axis_count: usize,
+  /// The main axis of the list, also the direction it scrolls.
+  #[declare(default = Direction::Vertical)]
+  pub direction: Direction,
+  /// The number of lines built beyond each edge of the viewport, to avoid
+  /// showing blank when scrolling quickly.
+  #[declare(default = 1usize)]
+  pub overscan: usize,
+  pub item_builder: LazyItemBuilder,
+  #[declare(skip, default = Stateful::new(ScrollableWidget::default()))]
+  scroll: Stateful<ScrollableWidget>,
+}
+
+impl LazyList {
+  /// Return the `ScrollableWidget` of the list. You can utilize it to scroll
+  /// the list or access scroll information.
+  pub fn inner_scrollable_widget(&self) -> &Stateful<ScrollableWidget> { &self.scroll }
+
+  /// Scroll the list to let the item of `index` at the start of the viewport.
+  pub fn jump_to_item(&self, index: usize) {
+    let line = index / self.cross_axis_count.max(1);
+    let offset = line as f32 * self.item_extent;
+    let mut scroll = self.scroll.write();
+    let pos = scroll.get_scroll_pos();
+    let pos = match self.direction {
+      Direction::Horizontal => Point::new(offset, pos.y),
+      Direction::Vertical => Point::new(pos.x, offset),
+    };
+    scroll.jump_to(pos);
+  }
+
+  fn sync_scrollable(&self) {
+    let scrollable = match self.direction {
+      Direction::Horizontal => Scrollable::X,
+      Direction::Vertical => Scrollable::Y,
+    };
+    if self.scroll.read().scrollable != scrollable {
+      self.scroll.write().scrollable = scrollable;
+    }
+  }
+}
+
+impl Compose for LazyList {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    let scroll = this.read().scroll.clone_writer();
+    fn_widget! {
+      $this.sync_scrollable();
+      let content = Stateful::new(LazyContent::new(&$this, &$scroll));
+      let slot_count = Stateful::new($content.slot_count);
+      let slots: Rc<RefCell<Vec<Stateful<Option<usize>>>>> = <_>::default();
+
+      let c_slots = slots.clone();
+      let u = watch!((
+        ScrollableWidget::get_scroll_pos(&$scroll),
+        ScrollableWidget::scroll_view_size(&$scroll),
+        $this.item_count,
+        $this.item_extent,
+        $this.cross_axis_count,
+        $this.direction,
+        $this.overscan,
+      ))
+      .subscribe(move |_| {
+        let list = $this;
+        list.sync_scrollable();
+
+        let new = LazyContent::new(&list, &$scroll);
+        if *$content != new {
+          *$content.write() = new;
+        }
+        let n = $content.slot_count;
+        if *$slot_count != n {
+          *$slot_count.write() = n;
+        } else {
+          // The count of the slots not changed, only the slots that hold a
+          // different item rebuild their widget.
+          for (slot, idx) in c_slots.borrow().iter().enumerate() {
+            let new_idx = $content.item_of_slot(slot);
+            if *idx.read() != new_idx {
+              *idx.write() = new_idx;
+            }
+          }
+        }
+      });
+
+      let items = pipe!(*$slot_count).map(move |n| {
+        let mut slots = slots.borrow_mut();
+        slots.clear();
+        (0..n)
+          .map(|slot| {
+            let idx = Stateful::new($content.item_of_slot(slot));
+            slots.push(idx.clone_writer());
+            let builder = $this.item_builder.clone();
+            pipe!(*$idx).map(move |idx| idx.map(|idx| (builder.0)(idx)))
+          })
+          .collect::<Vec<_>>()
+      });
+
+      @Scrollbar {
+        scroll: scroll.clone_writer(),
+        on_disposed: move |_| u.unsubscribe(),
+        @ $content { @ { items } }
+      }
+    }
+    .into_widget()
+  }
+}
+
+/// The content of the `LazyList`, it has the size of all the items, but only
+/// the items in the window (`first..end`) of the list are its children. The
+/// item of index `i` is held by the child slot `i % slot_count`.
+#[derive(MultiChild, PartialEq)]
+struct LazyContent {
+  direction: Direction,
+  item_extent: f32,
+  cross_axis_count: usize,
+  item_count: usize,
+  first: usize,
+  end: usize,
+  slot_count: usize,
+}
+
+impl LazyContent {
+  fn new(list: &LazyList, scroll: &ScrollableWidget) -> Self {
+    let LazyList { item_count, item_extent, cross_axis_count, direction, overscan, .. } = *list;
+    let cross_axis_count = cross_axis_count.max(1);
+    let pos = scroll.get_scroll_pos();
+    let view = scroll.scroll_view_size();
+    let (offset, view) = match direction {
+      Direction::Horizontal => (pos.x, view.width),
+      Direction::Vertical => (pos.y, view.height),
+    };
+
+    let (mut first, mut end, mut slot_count) = (0, 0, 0);
+    if item_extent > 0. && view > 0. {
+      let lines = item_count.div_ceil(cross_axis_count);
+      let first_line = ((offset / item_extent) as usize).saturating_sub(overscan);
+      let end_line = ((offset + view) / item_extent).ceil() as usize + overscan;
+      let view_lines = (view / item_extent).ceil() as usize + 1 + 2 * overscan;
+      end = (end_line.min(lines) * cross_axis_count).min(item_count);
+      first = (first_line * cross_axis_count).min(end);
+      slot_count = (view_lines * cross_axis_count).min(item_count);
+    }
+
+    Self { direction, item_extent, cross_axis_count, item_count, first, end, slot_count }
+  }
+
+  fn item_of_slot(&self, slot: usize) -> Option<usize> {
+    let Self { first, end, slot_count, .. } = *self;
+    if slot >= slot_count {
+      return None;
+    }
+    let idx = first + (slot + slot_count - first % slot_count) % slot_count;
+    (idx < end).then_some(idx)
+  }
+}
+
+impl Render for LazyContent {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let Self { direction, item_extent, cross_axis_count, item_count, .. } = *self;
+    let lines = item_count.div_ceil(cross_axis_count);
+    let main = lines as f32 * item_extent;
+    let max_cross = match direction {
+      Direction::Horizontal => clamp.max.height,
+      Direction::Vertical => clamp.max.width,
+    };
+    // Without a limit in the cross axis, the items are squares.
+    let item_cross =
+      if max_cross.is_finite() { max_cross / cross_axis_count as f32 } else { item_extent };
+
+    let mut slot = 0;
+    let mut layouter = ctx.first_child_layouter();
+    while let Some(mut l) = layouter {
+      if let Some(idx) = self.item_of_slot(slot) {
+        let line = (idx / cross_axis_count) as f32;
+        let col = (idx % cross_axis_count) as f32;
+        let (size, pos) = match direction {
+          Direction::Horizontal => {
+            (Size::new(item_extent, item_cross), Point::new(line * item_extent, col * item_cross))
+          }
+          Direction::Vertical => {
+            (Size::new(item_cross, item_extent), Point::new(col * item_cross, line * item_extent))
+          }
+        };
+        l.perform_widget_layout(BoxClamp::fixed_size(size));
+        l.update_position(pos);
+      } else {
+        l.perform_widget_layout(BoxClamp::fixed_size(Size::zero()));
+      }
+      slot += 1;
+      layouter = l.into_next_sibling();
+    }
+
+    let cross = item_cross * cross_axis_count as f32;
+    let size = match direction {
+      Direction::Horizontal => Size::new(main, cross),
+      Direction::Vertical => Size::new(cross, main),
+    };
+    clamp.clamp(size)
+  }
+
+  #[inline]
+  fn paint(&self, _: &mut PaintingCtx) {}
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+
+  use super::*;
+
+  #[test]
+  fn only_build_visible_items() {
+    reset_test_env!();
+
+    let built = Stateful::new(vec![]);
+    let c_built = built.clone_writer();
+    let (scroll, w_scroll) = split_value(None);
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        let built = c_built.clone_writer();
+        let list = @LazyList {
+          item_count: 100_000usize,
+          item_extent: 10.,
+          item_builder: move |idx: usize| {
+            built.write().push(idx);
+            fn_widget!{ @MockBox { size: Size::zero() } }.into_widget()
+          },
+        };
+        *$w_scroll.write() = Some($list.inner_scrollable_widget().clone_writer());
+        list
+      },
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+    wnd.draw_frame();
+
+    // 10 lines in the viewport and one line beyond its bottom edge.
+    let mut expect: Vec<_> = (0..11).collect();
+    assert_eq!(*built.read(), expect);
+
+    let scroll = scroll.read().as_ref().unwrap().clone_writer();
+    scroll.write().jump_to(Point::new(0., 500_000.));
+    wnd.draw_frame();
+    built.write().sort();
+    expect.extend(49_999..50_011);
+    assert_eq!(*built.read(), expect);
+
+    // Scroll one line only build the new line.
+    built.write().clear();
+    scroll.write().scroll(0., 10.);
+    wnd.draw_frame();
+    assert_eq!(*built.read(), vec![50_011]);
+
+    let content = scroll.read().scroll_content_size();
+    assert_eq!(content, Size::new(100., 1_000_000.));
+  }
+
+  type ListWnd = (TestWindow, Stateful<LazyList>, Stateful<Vec<usize>>, Stateful<Vec<usize>>);
+
+  /// A list of 100 items in a 100x100 window, it records the items built and
+  /// disposed.
+  fn list_wnd() -> ListWnd {
+    let built = Stateful::new(vec![]);
+    let w_built = built.clone_writer();
+    let disposed = Stateful::new(vec![]);
+    let w_disposed = disposed.clone_writer();
+    let (list, w_list) = split_value(None);
+    let wnd = TestWindow::new_with_size(
+      fn_widget! {
+        let w_built = w_built.clone_writer();
+        let w_disposed = w_disposed.clone_writer();
+        let list = @LazyList {
+          item_count: 100usize,
+          item_extent: 10.,
+          item_builder: move |idx: usize| {
+            w_built.write().push(idx);
+            let w_disposed = w_disposed.clone_writer();
+            fn_widget! {
+              @MockBox {
+                size: Size::zero(),
+                on_disposed: move |_| $w_disposed.write().push(idx),
+              }
+            }
+            .into_widget()
+          },
+        };
+        *$w_list.write() = Some(list.clone_writer());
+        list
+      },
+      Size::new(100., 100.),
+    );
+    let list = list.read().as_ref().unwrap().clone_writer();
+    (wnd, list, built, disposed)
+  }
+
+  #[test]
+  fn recycle_items_on_scroll() {
+    reset_test_env!();
+
+    let (mut wnd, list, built, disposed) = list_wnd();
+    // The viewport is sized in the first frame.
+    wnd.draw_frame();
+    wnd.draw_frame();
+    assert_eq!(*built.read(), (0..11).collect::<Vec<_>>());
+
+    // The items scrolled out hand their slots to the items scrolled in.
+    built.write().clear();
+    let scroll = list
+      .read()
+      .inner_scrollable_widget()
+      .clone_writer();
+    scroll.write().scroll(0., 30.);
+    wnd.draw_frame();
+    built.write().sort();
+    disposed.write().sort();
+    assert_eq!(*built.read(), vec![11, 12, 13]);
+    assert_eq!(*disposed.read(), vec![0, 1]);
+
+    // Scroll back rebuilds the items.
+    built.write().clear();
+    disposed.write().clear();
+    scroll.write().scroll(0., -30.);
+    wnd.draw_frame();
+    built.write().sort();
+    disposed.write().sort();
+    assert_eq!(*built.read(), vec![0, 1]);
+    assert_eq!(*disposed.read(), vec![11, 12, 13]);
+  }
+
+  #[test]
+  fn change_item_extent() {
+    reset_test_env!();
+
+    let (mut wnd, list, built, _) = list_wnd();
+    wnd.draw_frame();
+    wnd.draw_frame();
+    assert_eq!(built.read().len(), 11);
+
+    built.write().clear();
+    list.write().item_extent = 20.;
+    wnd.draw_frame();
+    // Fewer items fill the viewport, and the content grows.
+    assert_eq!(*built.read(), (0..6).collect::<Vec<_>>());
+    let scroll = list
+      .read()
+      .inner_scrollable_widget()
+      .clone_writer();
+    assert_eq!(scroll.read().scroll_content_size(), Size::new(100., 2000.));
+  }
+}
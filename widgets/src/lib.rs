@@ -2,24 +2,36 @@ pub mod avatar;
 pub mod buttons;
 pub mod checkbox;
 pub mod common_widget;
+pub mod data_table;
+pub mod dialog;
 pub mod divider;
 pub mod grid_view;
 pub mod icon;
 pub mod input;
 pub mod label;
 pub mod layout;
+pub mod lazy_list;
 pub mod link;
 pub mod lists;
+pub mod menu;
 pub mod path;
+pub mod radio;
 pub mod scrollbar;
+pub mod segmented_button;
+pub mod select;
+pub mod slider;
+pub mod switch;
 pub mod tabs;
 pub mod text;
 pub mod text_field;
+pub mod tooltip;
 pub mod transform_box;
+pub mod tree_view;
 pub mod prelude {
   pub use super::{
-    avatar::*, buttons::*, checkbox::*, common_widget::*, divider::*, grid_view::*, icon::*,
-    input::*, label::*, layout::*, link::*, lists::*, path::*, scrollbar::*, tabs::*, text::*,
-    text_field::*, transform_box::*,
+    avatar::*, buttons::*, checkbox::*, common_widget::*, data_table::*, dialog::*, divider::*,
+    grid_view::*, icon::*, input::*, label::*, layout::*, lazy_list::*, link::*, lists::*, menu::*,
+    path::*, radio::*, scrollbar::*, segmented_button::*, select::*, slider::*, switch::*, tabs::*,
+    text::*, text_field::*, tooltip::*, transform_box::*, tree_view::*,
   };
 }
@@ -0,0 +1,549 @@
+use std::{cell::RefCell, rc::Rc};
+
+use ribir_core::{
+  overlay::{AutoClosePolicy, Placement, PlacementSide},
+  prelude::*,
+};
+
+use crate::prelude::*;
+
+/// An item of a [`Menu`], it opens a submenu if it has children.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MenuItem {
+  pub label: CowArc<str>,
+  /// A disabled item can't be chosen.
+  pub enabled: bool,
+  pub children: Vec<MenuItem>,
+}
+
+impl MenuItem {
+  pub fn new(label: impl Into<CowArc<str>>) -> Self {
+    Self { label: label.into(), enabled: true, children: vec![] }
+  }
+
+  pub fn with_children(mut self, children: Vec<MenuItem>) -> Self {
+    self.children = children;
+    self
+  }
+
+  pub fn with_enabled(mut self, enabled: bool) -> Self {
+    self.enabled = enabled;
+    self
+  }
+}
+
+#[derive(Clone)]
+pub struct MenuStyle {
+  pub background: Brush,
+  pub foreground: Brush,
+  pub disabled_foreground: Brush,
+  /// The background of the highlighted item.
+  pub active_background: Brush,
+  pub text_style: CowArc<TextStyle>,
+  pub radius: f32,
+  pub padding: EdgeInsets,
+  pub item_height: f32,
+  pub item_padding: EdgeInsets,
+  pub min_width: f32,
+}
+
+impl CustomStyle for MenuStyle {
+  fn default_style(ctx: &impl ProviderCtx) -> Self {
+    let palette = Palette::of(ctx);
+    Self {
+      background: palette.surface_container().into(),
+      foreground: palette.on_surface().into(),
+      disabled_foreground: palette.on_surface().with_alpha(0.38).into(),
+      active_background: palette.on_surface().with_alpha(0.08).into(),
+      text_style: TypographyTheme::of(ctx).label_large.text.clone(),
+      radius: 4.,
+      padding: EdgeInsets::vertical(8.),
+      item_height: 48.,
+      item_padding: EdgeInsets::horizontal(12.),
+      min_width: 112.,
+    }
+  }
+}
+
+/// A menu shows a list of items beside a target widget, the items with
+/// children open submenus.
+///
+/// The menu is navigated by the arrow keys: `Up` and `Down` move the highlight,
+/// `Right` opens the submenu, `Left` closes it, `Enter` or `Space` chooses the
+/// highlighted item and `Escape` closes the menu. Its result is the path of
+/// the chosen item.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let _w = fn_widget! {
+///   @FilledButton {
+///     on_tap: move |e| {
+///       let items = vec![
+///         MenuItem::new("Open"),
+///         MenuItem::new("Export").with_children(vec![
+///           MenuItem::new("PNG"),
+///           MenuItem::new("SVG"),
+///         ]),
+///       ];
+///       let result = Menu::new(items).show(e.current_target(), e.window());
+///       let _ = AppCtx::spawn_local(async move {
+///         println!("{:?}", result.await);
+///       });
+///     },
+///     @{ Label::new("File") }
+///   }
+/// };
+/// ```
+pub struct Menu {
+  items: Vec<MenuItem>,
+  placement: Placement,
+  min_width: f32,
+  active: Option<usize>,
+}
+
+impl Menu {
+  pub fn new(items: Vec<MenuItem>) -> Self {
+    Self { items, placement: Placement::new(PlacementSide::Bottom), min_width: 0., active: None }
+  }
+
+  /// Set where the menu is placed around the target, default is below the
+  /// target.
+  pub fn with_placement(mut self, placement: Placement) -> Self {
+    self.placement = placement;
+    self
+  }
+
+  /// Set the min width of the menu, the menu is at least as wide as the
+  /// `MenuStyle::min_width`.
+  pub fn with_min_width(mut self, min_width: f32) -> Self {
+    self.min_width = min_width;
+    self
+  }
+
+  /// Set the item highlighted when the menu shows.
+  pub fn with_active(mut self, index: usize) -> Self {
+    self.active = Some(index);
+    self
+  }
+
+  /// Show the menu beside the `target` widget, the result is the path of the
+  /// chosen item.
+  pub fn show(self, target: WidgetId, wnd: Rc<Window>) -> DialogResult<Vec<usize>> {
+    let Self { items, placement, min_width, active } = self;
+    let menu =
+      Rc::new(MenuCtx { items, min_width, closer: DialogCloser::new(), levels: <_>::default() });
+    menu.show(target, placement, active, wnd)
+  }
+}
+
+struct MenuCtx {
+  items: Vec<MenuItem>,
+  min_width: f32,
+  closer: DialogCloser<Vec<usize>>,
+  levels: RefCell<Vec<MenuLevel>>,
+}
+
+/// A showing menu panel, the root menu is the first level.
+struct MenuLevel {
+  overlay: Overlay,
+  focus: Option<Box<dyn Fn()>>,
+}
+
+impl MenuCtx {
+  /// Show the root menu panel, the menu panels hold the context until the
+  /// root panel disposed.
+  fn show(
+    self: &Rc<Self>, target: WidgetId, placement: Placement, active: Option<usize>, wnd: Rc<Window>,
+  ) -> DialogResult<Vec<usize>> {
+    let overlay = self.level_overlay(vec![], active, true);
+    overlay.set_auto_close_policy(AutoClosePolicy::TAP_OUTSIDE);
+    let result = self.closer.attach(&overlay, &wnd);
+    self
+      .levels
+      .borrow_mut()
+      .push(MenuLevel { overlay: overlay.clone(), focus: None });
+    overlay.show_anchored(target, placement, wnd);
+    result
+  }
+
+  fn items_at(&self, path: &[usize]) -> &[MenuItem] {
+    path
+      .iter()
+      .fold(&self.items[..], |items, idx| &items[*idx].children)
+  }
+
+  fn level_overlay(
+    self: &Rc<Self>, path: Vec<usize>, active: Option<usize>, focus: bool,
+  ) -> Overlay {
+    let menu = self.clone();
+    Overlay::new(move |_: &mut BuildCtx| menu_panel(menu.clone(), path.clone(), active, focus))
+  }
+
+  /// Open the submenu of the item at `path` beside the item widget `anchor`,
+  /// the submenu takes the focus if `focus` is true.
+  fn open_submenu(
+    self: &Rc<Self>, path: Vec<usize>, anchor: WidgetId, focus: bool, wnd: Rc<Window>,
+  ) {
+    let depth = path.len();
+    self.close_from(depth);
+    let active = focus
+      .then(|| next_enabled(self.items_at(&path), None, false))
+      .flatten();
+    let overlay = self.level_overlay(path, active, focus);
+    overlay.set_auto_close_policy(AutoClosePolicy::NONE);
+    overlay.set_modal(false);
+    self
+      .levels
+      .borrow_mut()
+      .push(MenuLevel { overlay: overlay.clone(), focus: None });
+    overlay.show_anchored(anchor, Placement::new(PlacementSide::End), wnd);
+  }
+
+  /// Close the menu panels from the `depth` level.
+  fn close_from(&self, depth: usize) {
+    let levels = {
+      let mut levels = self.levels.borrow_mut();
+      let depth = depth.min(levels.len());
+      levels.split_off(depth)
+    };
+    levels
+      .into_iter()
+      .rev()
+      .for_each(|l| l.overlay.close());
+  }
+
+  fn focus_level(&self, depth: usize) {
+    if let Some(focus) = self
+      .levels
+      .borrow()
+      .get(depth)
+      .and_then(|l| l.focus.as_ref())
+    {
+      focus();
+    }
+  }
+}
+
+fn menu_panel(
+  menu: Rc<MenuCtx>, path: Vec<usize>, active: Option<usize>, focus: bool,
+) -> Widget<'static> {
+  fn_widget! {
+    let style = MenuStyle::of(ctx!());
+    let depth = path.len();
+    let items = menu.items_at(&path).to_vec();
+    let active = Stateful::new(active);
+    let item_ids: Rc<RefCell<Vec<Option<WidgetId>>>> =
+      Rc::new(RefCell::new(vec![None; items.len()]));
+
+    // Choose the item at `idx`, or open its submenu.
+    let activate = {
+      let menu = menu.clone();
+      let item_ids = item_ids.clone();
+      let items = items.clone();
+      let path = path.clone();
+      move |idx: usize, focus: bool, wnd: Rc<Window>| {
+        let item = &items[idx];
+        if !item.enabled {
+          return;
+        }
+        let mut item_path = path.clone();
+        item_path.push(idx);
+        if item.children.is_empty() {
+          menu.closer.close(item_path);
+        } else if let Some(anchor) = item_ids.borrow()[idx] {
+          menu.open_submenu(item_path, anchor, focus, wnd);
+        }
+      }
+    };
+
+    let rows = items.iter().enumerate().map(|(idx, item)| {
+      let activate = activate.clone();
+      let activate2 = activate.clone();
+      let item_ids = item_ids.clone();
+      let menu = menu.clone();
+      let has_children = !item.children.is_empty();
+      let enabled = item.enabled;
+      let background = {
+        let style = style.clone();
+        pipe!(*$active == Some(idx)).map(move |active| {
+          if active { style.active_background.clone() } else { Color::TRANSPARENT.into() }
+        })
+      };
+      let foreground = if enabled {
+        style.foreground.clone()
+      } else {
+        style.disabled_foreground.clone()
+      };
+      let chevron = has_children.then(|| @Icon {
+        size: IconSize::of(ctx!()).small,
+        @ { svgs::CHEVRON_RIGHT }
+      });
+      @Row {
+        align_items: Align::Center,
+        padding: style.item_padding,
+        background,
+        on_mounted: move |e| item_ids.borrow_mut()[idx] = Some(e.current_target()),
+        on_pointer_enter: move |e| {
+          if enabled {
+            *$active.write() = Some(idx);
+          }
+          if has_children {
+            activate(idx, false, e.window());
+          } else {
+            menu.close_from(depth + 1);
+          }
+        },
+        on_tap: move |e| activate2(idx, true, e.window()),
+        @ConstrainedBox {
+          clamp: BoxClamp::min_height(style.item_height),
+          @Expanded {
+            v_align: VAlign::Center,
+            @Text {
+              text: item.label.clone(),
+              foreground: foreground.clone(),
+              text_style: style.text_style.clone(),
+            }
+          }
+        }
+        @{ chevron }
+      }
+    }).collect::<Vec<_>>();
+
+    let min_width = style.min_width.max(menu.min_width);
+    let (menu2, menu3) = (menu.clone(), menu.clone());
+    let mut panel = @Column {
+      padding: style.padding,
+      background: style.background.clone(),
+      border_radius: Radius::all(style.radius),
+      auto_focus: focus,
+      on_key_down: move |e| {
+        let key = e.key().clone();
+        let current = *$active;
+        match key {
+          VirtualKey::Named(NamedKey::ArrowDown) => {
+            *$active.write() = next_enabled(&items, current, false);
+          }
+          VirtualKey::Named(NamedKey::ArrowUp) => {
+            *$active.write() = next_enabled(&items, current, true);
+          }
+          VirtualKey::Named(NamedKey::Home) => {
+            *$active.write() = next_enabled(&items, None, false);
+          }
+          VirtualKey::Named(NamedKey::End) => {
+            *$active.write() = next_enabled(&items, None, true);
+          }
+          VirtualKey::Named(NamedKey::Enter | NamedKey::Space) => {
+            if let Some(idx) = current {
+              activate(idx, true, e.window());
+            }
+          }
+          VirtualKey::Named(NamedKey::ArrowRight) => {
+            if let Some(idx) = current.filter(|idx| !items[*idx].children.is_empty()) {
+              activate(idx, true, e.window());
+            }
+          }
+          VirtualKey::Named(NamedKey::ArrowLeft) if depth > 0 => {
+            menu2.close_from(depth);
+            menu2.focus_level(depth - 1);
+          }
+          VirtualKey::Named(NamedKey::Escape) => {
+            if depth > 0 {
+              menu2.close_from(depth);
+              menu2.focus_level(depth - 1);
+            } else {
+              menu2.closer.dismiss();
+            }
+          }
+          _ => return,
+        }
+        e.stop_propagation();
+      },
+      on_disposed: move |_| {
+        // The root menu closes all its submenus, and releases its own level to
+        // break the cycle between the level overlay and the menu.
+        if depth == 0 {
+          menu3.close_from(1);
+          menu3.closer.dismiss();
+          let levels = std::mem::take(&mut *menu3.levels.borrow_mut());
+          drop(levels);
+        }
+      },
+    };
+
+    if let Some(level) = menu.levels.borrow_mut().get_mut(depth) {
+      level.focus = Some(Box::new(move || $panel.request_focus()));
+    }
+    @ $panel {
+      @ConstrainedBox {
+        clamp: BoxClamp::min_width(min_width),
+        @Column {
+          align_items: Align::Stretch,
+          @{ rows }
+        }
+      }
+    }
+  }
+  .into_widget()
+}
+
+/// Return the next enabled item after the `current` item, or before it if
+/// `backward`, it wraps around the ends.
+fn next_enabled(items: &[MenuItem], current: Option<usize>, backward: bool) -> Option<usize> {
+  let len = items.len();
+  (1..=len)
+    .map(|offset| match (current, backward) {
+      (None, false) => offset - 1,
+      (None, true) => len - offset,
+      (Some(c), false) => (c + offset) % len,
+      (Some(c), true) => (c + len - offset % len) % len,
+    })
+    .find(|idx| items[*idx].enabled)
+}
+
+#[cfg(test)]
+mod tests {
+  use futures::FutureExt;
+  use ribir_core::{reset_test_env, test_helper::*};
+  use winit::{
+    event::ElementState,
+    keyboard::{KeyCode, KeyLocation, PhysicalKey},
+  };
+
+  use super::*;
+
+  fn press(wnd: &mut TestWindow, code: KeyCode, key: NamedKey) {
+    wnd.processes_keyboard_event(
+      PhysicalKey::Code(code),
+      VirtualKey::Named(key),
+      false,
+      KeyLocation::Standard,
+      ElementState::Pressed,
+    );
+    wnd.draw_frame();
+  }
+
+  fn items() -> Vec<MenuItem> {
+    vec![
+      MenuItem::new("Open"),
+      MenuItem::new("Recent").with_enabled(false),
+      MenuItem::new("Export").with_children(vec![MenuItem::new("PNG"), MenuItem::new("SVG")]),
+    ]
+  }
+
+  #[test]
+  fn skip_disabled() {
+    let items = items();
+    assert_eq!(next_enabled(&items, None, false), Some(0));
+    assert_eq!(next_enabled(&items, Some(0), false), Some(2));
+    assert_eq!(next_enabled(&items, Some(2), false), Some(0));
+    assert_eq!(next_enabled(&items, Some(0), true), Some(2));
+    assert_eq!(next_enabled(&items, None, true), Some(2));
+  }
+
+  #[test]
+  fn keyboard_submenu() {
+    reset_test_env!();
+
+    let (target, w_target) = split_value(None);
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockBox {
+          size: Size::new(40., 20.),
+          on_mounted: move |e| *$w_target.write() = Some(e.current_target()),
+        }
+      },
+      Size::new(400., 400.),
+    );
+    wnd.draw_frame();
+
+    let target = target.read().unwrap();
+    let result = Menu::new(items()).show(target, wnd.0.clone());
+    wnd.draw_frame();
+
+    // Down to `Open`, skip the disabled `Recent` to `Export`, open the submenu,
+    // then down to `SVG` and choose it.
+    press(&mut wnd, KeyCode::ArrowDown, NamedKey::ArrowDown);
+    press(&mut wnd, KeyCode::ArrowDown, NamedKey::ArrowDown);
+    press(&mut wnd, KeyCode::ArrowRight, NamedKey::ArrowRight);
+    wnd.draw_frame();
+    press(&mut wnd, KeyCode::ArrowDown, NamedKey::ArrowDown);
+    press(&mut wnd, KeyCode::Enter, NamedKey::Enter);
+
+    assert_eq!(result.now_or_never(), Some(Some(vec![2, 1])));
+  }
+
+  #[test]
+  fn escape_closes_submenu_first() {
+    reset_test_env!();
+
+    let (target, w_target) = split_value(None);
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockBox {
+          size: Size::new(40., 20.),
+          on_mounted: move |e| *$w_target.write() = Some(e.current_target()),
+        }
+      },
+      Size::new(400., 400.),
+    );
+    wnd.draw_frame();
+
+    let target = target.read().unwrap();
+    let result = Menu::new(items())
+      .with_active(2)
+      .show(target, wnd.0.clone());
+    let closer = result.closer();
+    wnd.draw_frame();
+
+    press(&mut wnd, KeyCode::Enter, NamedKey::Enter);
+    wnd.draw_frame();
+    press(&mut wnd, KeyCode::Escape, NamedKey::Escape);
+    assert!(!closer.is_closed());
+
+    // Back to the root menu, choose `Open`.
+    press(&mut wnd, KeyCode::Home, NamedKey::Home);
+    press(&mut wnd, KeyCode::Enter, NamedKey::Enter);
+    assert_eq!(result.now_or_never(), Some(Some(vec![0])));
+  }
+
+  #[test]
+  fn release_menu_after_closed() {
+    reset_test_env!();
+
+    let (target, w_target) = split_value(None);
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockBox {
+          size: Size::new(40., 20.),
+          on_mounted: move |e| *$w_target.write() = Some(e.current_target()),
+        }
+      },
+      Size::new(400., 400.),
+    );
+    wnd.draw_frame();
+
+    let target = target.read().unwrap();
+    let menu = Rc::new(MenuCtx {
+      items: items(),
+      min_width: 0.,
+      closer: DialogCloser::new(),
+      levels: <_>::default(),
+    });
+    let result = menu.show(target, Placement::new(PlacementSide::Bottom), Some(2), wnd.0.clone());
+    wnd.draw_frame();
+    // Open the submenu, then choose its first item.
+    press(&mut wnd, KeyCode::Enter, NamedKey::Enter);
+    wnd.draw_frame();
+    assert_eq!(menu.levels.borrow().len(), 2);
+    press(&mut wnd, KeyCode::Enter, NamedKey::Enter);
+    wnd.draw_frame();
+
+    assert_eq!(result.now_or_never(), Some(Some(vec![2, 0])));
+    assert!(menu.levels.borrow().is_empty());
+    assert_eq!(Rc::strong_count(&menu), 1);
+  }
+}
@@ -0,0 +1,337 @@
+use std::{cell::RefCell, rc::Rc};
+
+use ribir_core::prelude::*;
+
+use crate::{
+  common_widget::{Leading, Trailing},
+  prelude::{Label, Row, Text},
+};
+
+/// A radio button, it's one of the mutually exclusive options of its ancestor
+/// [`RadioGroup`].
+///
+/// In a group, the radio is selected when its `value` equals the value of the
+/// group, and selecting it changes the value of the group. The arrow keys move
+/// the selection to the previous or the next radio of the group. Out of a
+/// group, the radio is selected once it's tapped.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let _w = fn_widget! {
+///   let group = @RadioGroup { value: Some("Tea") };
+///   watch!($group.value).subscribe(|v| println!("{v:?}"));
+///   @$group {
+///     @Column {
+///       @Radio { value: "Tea", @Trailing(Label::new("Tea")) }
+///       @Radio { value: "Coffee", @Trailing(Label::new("Coffee")) }
+///     }
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct Radio<V: PartialEq + Clone + 'static> {
+  #[declare(strict)]
+  pub value: V,
+  #[declare(default)]
+  pub selected: bool,
+  #[declare(default=Palette::of(ctx!()).primary())]
+  pub color: Color,
+}
+
+/// A group of the mutually exclusive [`Radio`]s, its `value` is the value of
+/// the selected radio.
+#[derive(Declare)]
+pub struct RadioGroup<V: PartialEq + Clone + 'static> {
+  #[declare(default, strict)]
+  pub value: Option<V>,
+}
+
+#[derive(Clone)]
+pub struct RadioStyle {
+  /// The diameter of the radio.
+  pub size: f32,
+  /// The diameter of the dot of the selected radio.
+  pub dot_size: f32,
+  pub border_width: f32,
+  /// The color of the ring of the unselected radio.
+  pub unselected: Brush,
+  /// The text style of the radio label.
+  pub label_style: CowArc<TextStyle>,
+  /// The radio foreground
+  pub label_color: Brush,
+}
+
+#[derive(Clone, Declare)]
+pub struct RadioDecorator {
+  #[declare(default=Palette::of(ctx!()).primary())]
+  pub color: Color,
+}
+
+impl ComposeDecorator for RadioDecorator {
+  fn compose_decorator(_: State<Self>, host: Widget) -> Widget { host }
+}
+
+#[derive(Template)]
+pub enum RadioTemplate {
+  Before(Leading<Label>),
+  After(Trailing<Label>),
+}
+
+/// The radios of a group, provided to the descendants of the group.
+struct GroupRadios<V> {
+  select: Box<dyn Fn(V)>,
+  value: RefCell<Option<V>>,
+  radios: RefCell<Vec<GroupRadio<V>>>,
+}
+
+struct GroupRadio<V> {
+  id: WidgetId,
+  value: V,
+  set_selected: Box<dyn Fn(bool)>,
+  focus: Box<dyn Fn()>,
+}
+
+impl<V: PartialEq + Clone> GroupRadios<V> {
+  fn is_selected(&self, value: &V) -> bool { self.value.borrow().as_ref() == Some(value) }
+
+  fn sync(&self, value: Option<V>) {
+    *self.value.borrow_mut() = value;
+    for radio in self.radios.borrow().iter() {
+      (radio.set_selected)(self.is_selected(&radio.value));
+    }
+  }
+
+  /// Select the radio after the radio `id`, or before it if `backward`, and
+  /// focus it.
+  fn step(&self, id: WidgetId, backward: bool) {
+    let next = {
+      let radios = self.radios.borrow();
+      let len = radios.len();
+      let Some(idx) = radios.iter().position(|r| r.id == id) else { return };
+      let next = if backward { (idx + len - 1) % len } else { (idx + 1) % len };
+      let radio = &radios[next];
+      (radio.focus)();
+      radio.value.clone()
+    };
+    (self.select)(next);
+  }
+}
+
+impl<'c, V: PartialEq + Clone + 'static> ComposeChild<'c> for RadioGroup<V> {
+  type Child = Widget<'c>;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    let writer = this.clone_writer();
+    let group = Rc::new(GroupRadios {
+      select: Box::new(move |v| writer.write().value = Some(v)),
+      value: RefCell::new(this.read().value.clone()),
+      radios: <_>::default(),
+    });
+    let group2 = group.clone();
+    let w = fn_widget! {
+      let u = watch!($this.value.clone()).subscribe(move |v| group2.sync(v));
+      @ $child { on_disposed: move |_| u.unsubscribe() }
+    };
+
+    // The radios find their group by the provider.
+    Provider::new(Box::new(Queryable(group)))
+      .with_child(w)
+      .into_widget()
+  }
+}
+
+impl<V: PartialEq + Clone + 'static> ComposeChild<'static> for Radio<V> {
+  type Child = Option<RadioTemplate>;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'static> {
+    fn_widget! {
+      let RadioStyle {
+        size, dot_size, border_width, unselected, label_style, label_color,
+      } = RadioStyle::of(ctx!());
+      let group = Provider::of::<Rc<GroupRadios<V>>>(ctx!()).map(|g| g.clone());
+      if let Some(group) = &group {
+        let selected = group.is_selected(&$this.value);
+        if $this.selected != selected {
+          $this.write().selected = selected;
+        }
+      }
+
+      let icon = @RadioDecorator {
+        color: pipe!($this.color),
+        @Container {
+          size: Size::splat(size),
+          border_radius: Radius::all(size / 2.),
+          border: pipe!{
+            let color = if $this.selected { $this.color.into() } else { unselected.clone() };
+            Border::all(BorderSide::new(border_width, color))
+          },
+          @Container {
+            size: Size::splat(dot_size),
+            h_align: HAlign::Center,
+            v_align: VAlign::Center,
+            border_radius: Radius::all(dot_size / 2.),
+            background: pipe!($this.color),
+            visible: pipe!($this.selected),
+          }
+        }
+      }
+      .into_widget();
+
+      let radio = if let Some(child) = child {
+        let label = |label: Label| @Text {
+          text: label.0,
+          foreground: label_color,
+          text_style: label_style,
+        };
+        @Row {
+          align_items: Align::Center,
+          @ {
+            match child {
+              RadioTemplate::Before(w) => [label(w.0).into_widget(), icon],
+              RadioTemplate::After(w) => [icon, label(w.0).into_widget()],
+            }
+          }
+        }
+        .into_widget()
+      } else {
+        icon
+      };
+
+      let mut radio = @ $radio {};
+      let focus = radio.get_request_focus_widget().clone_writer();
+      let (group2, group3, group4) = (group.clone(), group.clone(), group.clone());
+      let select = Rc::new(move || match &group {
+        Some(group) => (group.select)($this.value.clone()),
+        None => $this.write().selected = true,
+      });
+      let select2 = select.clone();
+      let this2 = this.clone_writer();
+      @ $radio {
+        cursor: CursorIcon::Pointer,
+        on_tap: move |_| select(),
+        on_key_down: move |e| {
+          let backward = match e.key() {
+            VirtualKey::Named(NamedKey::ArrowUp | NamedKey::ArrowLeft) => true,
+            VirtualKey::Named(NamedKey::ArrowDown | NamedKey::ArrowRight) => false,
+            VirtualKey::Named(NamedKey::Space) => {
+              select2();
+              return;
+            }
+            _ => return,
+          };
+          if let Some(group) = &group2 {
+            group.step(e.current_target(), backward);
+            e.stop_propagation();
+          }
+        },
+        on_mounted: move |e| if let Some(group) = &group3 {
+          let this = this2.clone_writer();
+          let focus = focus.clone_writer();
+          group.radios.borrow_mut().push(GroupRadio {
+            id: e.current_target(),
+            value: this2.read().value.clone(),
+            set_selected: Box::new(move |selected| if this.read().selected != selected {
+              this.write().selected = selected;
+            }),
+            focus: Box::new(move || focus.read().request_focus()),
+          });
+        },
+        on_disposed: move |e| if let Some(group) = &group4 {
+          group.radios.borrow_mut().retain(|r| r.id != e.current_target());
+        },
+      }
+    }
+    .into_widget()
+  }
+}
+
+impl CustomStyle for RadioStyle {
+  fn default_style(ctx: &impl ProviderCtx) -> Self {
+    let palette = Palette::of(ctx);
+    RadioStyle {
+      size: 20.,
+      dot_size: 10.,
+      border_width: 2.,
+      unselected: palette.on_surface_variant().into(),
+      label_style: TypographyTheme::of(ctx).body_large.text.clone(),
+      label_color: palette.on_surface().into(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+  use winit::{
+    event::ElementState,
+    keyboard::{KeyCode, KeyLocation, PhysicalKey},
+  };
+
+  use super::*;
+  use crate::prelude::*;
+
+  fn press(wnd: &mut TestWindow, code: KeyCode, key: NamedKey) {
+    wnd.processes_keyboard_event(
+      PhysicalKey::Code(code),
+      VirtualKey::Named(key),
+      false,
+      KeyLocation::Standard,
+      ElementState::Pressed,
+    );
+    wnd.draw_frame();
+  }
+
+  #[test]
+  fn mutual_exclusion() {
+    reset_test_env!();
+
+    let group = Stateful::new(RadioGroup { value: Some(2) });
+    type SelectedOf = Box<dyn Fn() -> bool>;
+    let radios: Rc<RefCell<Vec<SelectedOf>>> = <_>::default();
+    let (c_group, c_radios) = (group.clone_writer(), radios.clone());
+    let mut wnd = TestWindow::new(fn_widget! {
+      let radios = c_radios.clone();
+      let group = c_group.clone_writer();
+      @ $group {
+        @Column {
+          @ {
+            (1..=3).map(move |v| {
+              let radio = @Radio { value: v };
+              let r = radio.clone_writer();
+              radios.borrow_mut().push(Box::new(move || r.read().selected));
+              radio
+            })
+          }
+        }
+      }
+    });
+    wnd.draw_frame();
+    let selected = || {
+      radios
+        .borrow()
+        .iter()
+        .map(|r| r())
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(selected(), [false, true, false]);
+
+    group.write().value = Some(3);
+    wnd.draw_frame();
+    assert_eq!(selected(), [false, false, true]);
+
+    // Focus the first radio, the arrow keys move the selection and wrap around.
+    wnd.request_next_focus();
+    press(&mut wnd, KeyCode::Space, NamedKey::Space);
+    assert_eq!(group.read().value, Some(1));
+    press(&mut wnd, KeyCode::ArrowDown, NamedKey::ArrowDown);
+    assert_eq!(group.read().value, Some(2));
+    press(&mut wnd, KeyCode::ArrowUp, NamedKey::ArrowUp);
+    press(&mut wnd, KeyCode::ArrowUp, NamedKey::ArrowUp);
+    assert_eq!(group.read().value, Some(3));
+    assert_eq!(selected(), [false, false, true]);
+  }
+}
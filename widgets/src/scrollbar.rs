@@ -27,7 +27,7 @@ use crate::layout::Stack;
 #[derive(Declare)]
 pub struct Scrollbar {
   #[declare(default=Stateful::new(ScrollableWidget::default()))]
-  scroll: Stateful<ScrollableWidget>,
+  pub(crate) scroll: Stateful<ScrollableWidget>,
 }
 
 class_names! {
@@ -0,0 +1,272 @@
+use std::cell::RefCell;
+
+use ribir_core::prelude::*;
+
+use crate::prelude::{Icon, JustifyContent, Row, Text};
+
+/// A row of connected segments to select an option, or to select many options
+/// if `multi_select` is true.
+///
+/// A selected segment shows a check mark. A single-select segmented button
+/// keeps one segment selected, a multi-select one toggles the tapped segment.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let _w = fn_widget! {
+///   let views = @SegmentedButton {
+///     segments: vec!["Day".into(), "Week".into(), "Month".into()],
+///     selected: vec![0],
+///   };
+///   watch!($views.selected.clone()).subscribe(|s| println!("{s:?}"));
+///   views
+/// };
+/// ```
+#[derive(Declare)]
+pub struct SegmentedButton {
+  pub segments: Vec<CowArc<str>>,
+  /// The indices of the selected segments, in ascending order.
+  #[declare(default)]
+  pub selected: Vec<usize>,
+  #[declare(default)]
+  pub multi_select: bool,
+}
+
+#[derive(Clone)]
+pub struct SegmentedButtonStyle {
+  pub height: f32,
+  pub padding: EdgeInsets,
+  /// The radius of the outer corners of the first and the last segments.
+  pub radius: f32,
+  pub border_width: f32,
+  pub border: Brush,
+  pub foreground: Brush,
+  pub selected_background: Brush,
+  pub selected_foreground: Brush,
+  pub label_style: CowArc<TextStyle>,
+  /// The size of the check icon of the selected segments.
+  pub icon_size: Size,
+  /// The gap between the check icon and the label.
+  pub icon_gap: f32,
+}
+
+/// The decorator of a segment, a theme can override it to show the interactive
+/// states of the segment.
+#[derive(Clone, Declare)]
+pub struct SegmentDecorator {
+  pub color: Color,
+  /// The border radius of the segment.
+  pub radius: Radius,
+}
+
+impl ComposeDecorator for SegmentDecorator {
+  fn compose_decorator(_: State<Self>, host: Widget) -> Widget { host }
+}
+
+impl SegmentedButton {
+  pub fn is_selected(&self, idx: usize) -> bool { self.selected.contains(&idx) }
+
+  /// Select the segment at `idx`, or toggle it if the button is multi-select.
+  pub fn select(&mut self, idx: usize) {
+    if !self.multi_select {
+      self.selected = vec![idx];
+    } else if let Some(pos) = self.selected.iter().position(|s| *s == idx) {
+      self.selected.remove(pos);
+    } else {
+      let pos = self.selected.partition_point(|s| *s < idx);
+      self.selected.insert(pos, idx);
+    }
+  }
+}
+
+impl Compose for SegmentedButton {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    fn_widget! {
+      let style = SegmentedButtonStyle::of(ctx!());
+      let this2 = this.clone_writer();
+      let (height, border_width, radius) = (style.height, style.border_width, style.radius);
+      let border = style.border.clone();
+      // The segments are separated by the dividers and bordered by the row.
+      //
+      // Only rebuild the segments when they change, not when the selection
+      // changes, to keep the focus of the segment.
+      let last = RefCell::new($this.segments.clone());
+      let segments = pipe!($this.segments.clone())
+        .value_chain(move |s| {
+          s.filter(move |(_, segments)| {
+            let changed = *segments != *last.borrow();
+            if changed {
+              *last.borrow_mut() = segments.clone();
+            }
+            changed
+          })
+          .box_it()
+        })
+        .map(move |segments| {
+          let len = segments.len();
+          let mut children = vec![];
+          for (idx, label) in segments.into_iter().enumerate() {
+            if idx > 0 {
+              let divider = @Container {
+                size: Size::new(style.border_width, style.height - 2. * style.border_width),
+                background: style.border.clone(),
+              };
+              children.push(divider.into_widget());
+            }
+            children.push(segment(this2.clone_writer(), idx, len, label, &style));
+          }
+          children
+        });
+      @Row {
+        align_items: Align::Center,
+        clamp: BoxClamp::fixed_height(height),
+        padding: EdgeInsets::all(border_width),
+        border: Border::all(BorderSide::new(border_width, border)),
+        border_radius: Radius::all(radius),
+        @ { segments }
+      }
+    }
+    .into_widget()
+  }
+}
+
+fn segment(
+  this: impl StateWriter<Value = SegmentedButton>, idx: usize, len: usize, label: CowArc<str>,
+  style: &SegmentedButtonStyle,
+) -> Widget<'static> {
+  let style = style.clone();
+  fn_widget! {
+    let SegmentedButtonStyle {
+      height, padding, radius, border_width, foreground, selected_background,
+      selected_foreground, label_style, icon_size, icon_gap, ..
+    } = style;
+    // The outer corners of the first and the last segments follow the border.
+    let inner = radius - border_width;
+    let radius = Radius::horizontal(
+      if idx == 0 { inner } else { 0. },
+      if idx + 1 == len { inner } else { 0. },
+    );
+    let check = @Icon {
+      size: icon_size,
+      margin: EdgeInsets::only_right(icon_gap),
+      visible: pipe!($this.is_selected(idx)),
+      @ { svgs::DONE }
+    };
+    let segment = @Row {
+      align_items: Align::Center,
+      justify_content: JustifyContent::Center,
+      clamp: BoxClamp::fixed_height(height - 2. * border_width),
+      padding,
+      border_radius: radius,
+      background: pipe!{
+        if $this.is_selected(idx) { selected_background.clone() } else { Color::TRANSPARENT.into() }
+      },
+      cursor: CursorIcon::Pointer,
+      on_tap: move |_| $this.write().select(idx),
+      on_key_down: move |e| if matches!(
+        e.key(),
+        VirtualKey::Named(NamedKey::Enter | NamedKey::Space)
+      ) {
+        $this.write().select(idx);
+      },
+      @ { check }
+      @Text {
+        text: label,
+        foreground: pipe!{
+          if $this.is_selected(idx) { selected_foreground.clone() } else { foreground.clone() }
+        },
+        text_style: label_style,
+      }
+    };
+
+    @SegmentDecorator {
+      color: Palette::of(ctx!()).on_surface(),
+      radius,
+      @ { segment }
+    }
+  }
+  .into_widget()
+}
+
+impl CustomStyle for SegmentedButtonStyle {
+  fn default_style(ctx: &impl ProviderCtx) -> Self {
+    let palette = Palette::of(ctx);
+    SegmentedButtonStyle {
+      height: 40.,
+      padding: EdgeInsets::horizontal(12.),
+      radius: 20.,
+      border_width: 1.,
+      border: palette.outline().into(),
+      foreground: palette.on_surface().into(),
+      selected_background: palette.secondary_container().into(),
+      selected_foreground: palette.on_secondary_container().into(),
+      label_style: TypographyTheme::of(ctx).label_large.text.clone(),
+      icon_size: Size::splat(18.),
+      icon_gap: 8.,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+  use winit::{
+    event::ElementState,
+    keyboard::{KeyCode, KeyLocation, PhysicalKey},
+  };
+
+  use super::*;
+
+  fn press(wnd: &mut TestWindow, code: KeyCode, key: NamedKey) {
+    wnd.processes_keyboard_event(
+      PhysicalKey::Code(code),
+      VirtualKey::Named(key),
+      false,
+      KeyLocation::Standard,
+      ElementState::Pressed,
+    );
+    wnd.draw_frame();
+  }
+
+  #[test]
+  fn select_segment() {
+    let mut single = SegmentedButton { segments: vec![], selected: vec![0], multi_select: false };
+    single.select(2);
+    assert_eq!(single.selected, [2]);
+    single.select(2);
+    assert_eq!(single.selected, [2]);
+
+    let mut multi = SegmentedButton { segments: vec![], selected: vec![], multi_select: true };
+    multi.select(2);
+    multi.select(0);
+    assert_eq!(multi.selected, [0, 2]);
+    multi.select(2);
+    assert_eq!(multi.selected, [0]);
+  }
+
+  #[test]
+  fn keyboard_select() {
+    reset_test_env!();
+
+    let button = Stateful::new(SegmentedButton {
+      segments: vec!["Day".into(), "Week".into(), "Month".into()],
+      selected: vec![0],
+      multi_select: false,
+    });
+    let c_button = button.clone_writer();
+    let mut wnd = TestWindow::new(fn_widget! { @ { c_button.clone_writer() } });
+    wnd.draw_frame();
+
+    wnd.request_next_focus();
+    wnd.request_next_focus();
+    press(&mut wnd, KeyCode::Enter, NamedKey::Enter);
+    assert_eq!(button.read().selected, [1]);
+
+    wnd.request_next_focus();
+    press(&mut wnd, KeyCode::Space, NamedKey::Space);
+    assert_eq!(button.read().selected, [2]);
+  }
+}
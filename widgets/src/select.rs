@@ -0,0 +1,242 @@
+use std::rc::Rc;
+
+use ribir_core::{
+  overlay::{Placement, PlacementSide},
+  prelude::*,
+};
+
+use crate::prelude::*;
+
+#[derive(Clone)]
+pub struct SelectStyle {
+  pub foreground: Brush,
+  pub placeholder_foreground: Brush,
+  pub text_style: CowArc<TextStyle>,
+  pub border: Border,
+  pub radius: f32,
+  pub padding: EdgeInsets,
+  pub min_width: f32,
+}
+
+impl CustomStyle for SelectStyle {
+  fn default_style(ctx: &impl ProviderCtx) -> Self {
+    let palette = Palette::of(ctx);
+    Self {
+      foreground: palette.on_surface().into(),
+      placeholder_foreground: palette.on_surface_variant().into(),
+      text_style: TypographyTheme::of(ctx).body_large.text.clone(),
+      border: Border::all(BorderSide::new(1., palette.outline().into())),
+      radius: 4.,
+      padding: EdgeInsets::new(8., 8., 8., 16.),
+      min_width: 112.,
+    }
+  }
+}
+
+/// A dropdown to choose one of the options, the options show in a [`Menu`]
+/// below it.
+///
+/// The menu opens by a tap, or by the `Enter`, `Space` or `Down` key when the
+/// select is focused.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let _w = fn_widget! {
+///   @Select {
+///     options: vec!["Small".into(), "Medium".into(), "Large".into()],
+///     placeholder: "Size",
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct Select {
+  pub options: Vec<CowArc<str>>,
+  /// The index of the selected option.
+  #[declare(default)]
+  pub selected: Option<usize>,
+  /// The text shows when no option is selected.
+  #[declare(default)]
+  pub placeholder: CowArc<str>,
+}
+
+impl Select {
+  /// Return the label of the selected option.
+  pub fn selected_option(&self) -> Option<&CowArc<str>> {
+    self
+      .selected
+      .and_then(|idx| self.options.get(idx))
+  }
+
+  /// Open the menu below the `target`, the `focus` restores the focus to the
+  /// select after the menu closed.
+  fn open_menu(
+    this: &impl StateWriter<Value = Self>, target: WidgetId, wnd: Rc<Window>, focus: Rc<dyn Fn()>,
+  ) {
+    let (items, selected) = {
+      let this = this.read();
+      let items = this
+        .options
+        .iter()
+        .map(|o| MenuItem::new(o.clone()))
+        .collect();
+      (items, this.selected)
+    };
+    let width = wnd.layout_size(target).map_or(0., |s| s.width);
+    let mut menu = Menu::new(items)
+      .with_placement(Placement::new(PlacementSide::Bottom))
+      .with_min_width(width);
+    if let Some(selected) = selected {
+      menu = menu.with_active(selected);
+    }
+    let result = menu.show(target, wnd);
+    let this = this.clone_writer();
+    let _ = AppCtx::spawn_local(async move {
+      if let Some(path) = result.await {
+        this.write().selected = path.first().copied();
+      }
+      focus();
+    });
+  }
+}
+
+impl Compose for Select {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    fn_widget! {
+      let style = SelectStyle::of(ctx!());
+      let (this2, this3) = (this.clone_writer(), this.clone_writer());
+      let label = {
+        let style = style.clone();
+        pipe!($this.selected_option().cloned()).map(move |option| {
+          let (text, foreground) = match option {
+            Some(option) => (option, style.foreground.clone()),
+            None => (this3.read().placeholder.clone(), style.placeholder_foreground.clone()),
+          };
+          @Text { text, foreground, text_style: style.text_style.clone() }
+        })
+      };
+
+      let mut row = @Row {
+        align_items: Align::Center,
+        padding: style.padding,
+        border: style.border.clone(),
+        border_radius: Radius::all(style.radius),
+        cursor: CursorIcon::Pointer,
+        tab_index: 0i16,
+      };
+      let focus: Rc<dyn Fn()> = Rc::new(move || $row.request_focus());
+      let focus2 = focus.clone();
+      @ $row {
+        on_tap: move |e| {
+          Select::open_menu(&this, e.current_target(), e.window(), focus.clone())
+        },
+        on_key_down: move |e| {
+          if let VirtualKey::Named(NamedKey::Enter | NamedKey::Space | NamedKey::ArrowDown) =
+            e.key()
+          {
+            Select::open_menu(&this2, e.current_target(), e.window(), focus2.clone());
+            e.stop_propagation();
+          }
+        },
+        @ConstrainedBox {
+          clamp: BoxClamp::min_width(style.min_width),
+          @Expanded { @ { label } }
+        }
+        @Icon {
+          size: IconSize::of(ctx!()).small,
+          @ { svgs::ARROW_DROP_DOWN }
+        }
+      }
+    }
+    .into_widget()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+  use winit::{
+    event::ElementState,
+    keyboard::{KeyCode, KeyLocation, PhysicalKey},
+  };
+
+  use super::*;
+
+  fn press(wnd: &mut TestWindow, code: KeyCode, key: NamedKey) {
+    wnd.processes_keyboard_event(
+      PhysicalKey::Code(code),
+      VirtualKey::Named(key),
+      false,
+      KeyLocation::Standard,
+      ElementState::Pressed,
+    );
+    wnd.draw_frame();
+  }
+
+  #[test]
+  fn choose_option() {
+    reset_test_env!();
+
+    let select = Stateful::new(Select {
+      options: vec!["Small".into(), "Medium".into(), "Large".into()],
+      selected: Some(0),
+      placeholder: "Size".into(),
+    });
+    let c_select = select.clone_writer();
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! { @ { c_select.clone_writer() } },
+      Size::new(400., 400.),
+    );
+    wnd.draw_frame();
+    wnd.request_next_focus();
+    wnd.draw_frame();
+
+    // Open the menu with the first option highlighted, then choose the next one.
+    press(&mut wnd, KeyCode::Enter, NamedKey::Enter);
+    press(&mut wnd, KeyCode::ArrowDown, NamedKey::ArrowDown);
+    press(&mut wnd, KeyCode::Enter, NamedKey::Enter);
+
+    assert_eq!(select.read().selected, Some(1));
+    assert_eq!(select.read().selected_option().map(|s| &**s), Some("Medium"));
+  }
+
+  #[test]
+  fn restore_focus() {
+    reset_test_env!();
+
+    let select = Stateful::new(Select {
+      options: vec!["Small".into(), "Large".into()],
+      selected: None,
+      placeholder: "Size".into(),
+    });
+    let c_select = select.clone_writer();
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! { @ { c_select.clone_writer() } },
+      Size::new(400., 400.),
+    );
+    wnd.draw_frame();
+    wnd.request_next_focus();
+    wnd.draw_frame();
+    let trigger = wnd.focusing();
+    assert!(trigger.is_some());
+
+    // The menu takes the focus, and gives it back after chosen.
+    press(&mut wnd, KeyCode::Enter, NamedKey::Enter);
+    assert_ne!(wnd.focusing(), trigger);
+    press(&mut wnd, KeyCode::ArrowDown, NamedKey::ArrowDown);
+    press(&mut wnd, KeyCode::Enter, NamedKey::Enter);
+    wnd.draw_frame();
+    assert_eq!(select.read().selected, Some(0));
+    assert_eq!(wnd.focusing(), trigger);
+
+    // So does the menu dismissed.
+    press(&mut wnd, KeyCode::Space, NamedKey::Space);
+    assert_ne!(wnd.focusing(), trigger);
+    press(&mut wnd, KeyCode::Escape, NamedKey::Escape);
+    wnd.draw_frame();
+    assert_eq!(wnd.focusing(), trigger);
+  }
+}
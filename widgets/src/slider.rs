@@ -0,0 +1,588 @@
+use std::rc::Rc;
+
+use ribir_core::prelude::*;
+
+use crate::{layout::Stack, prelude::Text};
+
+#[derive(Clone)]
+pub struct SliderStyle {
+  /// The height of the slider, it's also the height of the area that responds
+  /// to the pointer.
+  pub height: f32,
+  pub track_height: f32,
+  pub track_radius: f32,
+  pub thumb_size: Size,
+  /// The diameter of the tick marks of a discrete slider.
+  pub tick_size: f32,
+  pub active_track: Brush,
+  pub inactive_track: Brush,
+  pub thumb: Color,
+  pub active_tick: Brush,
+  pub inactive_tick: Brush,
+  pub label_background: Brush,
+  pub label_foreground: Brush,
+  pub label_style: CowArc<TextStyle>,
+  pub label_padding: EdgeInsets,
+  pub label_radius: f32,
+  /// The gap between the value label and the thumb.
+  pub label_gap: f32,
+}
+
+impl CustomStyle for SliderStyle {
+  fn default_style(ctx: &impl ProviderCtx) -> Self {
+    let palette = Palette::of(ctx);
+    Self {
+      height: 32.,
+      track_height: 4.,
+      track_radius: 2.,
+      thumb_size: Size::splat(16.),
+      tick_size: 2.,
+      active_track: palette.primary().into(),
+      inactive_track: palette.primary().with_alpha(0.24).into(),
+      thumb: palette.primary(),
+      active_tick: palette.on_primary().into(),
+      inactive_tick: palette.primary().into(),
+      label_background: palette.primary().into(),
+      label_foreground: palette.on_primary().into(),
+      label_style: TypographyTheme::of(ctx).label_medium.text.clone(),
+      label_padding: EdgeInsets::symmetrical(4., 8.),
+      label_radius: 4.,
+      label_gap: 4.,
+    }
+  }
+}
+
+/// The decorator of the slider thumbs, a theme can override it to show the
+/// interactive states of the thumbs.
+#[derive(Clone, Declare)]
+pub struct SliderThumbDecorator {
+  pub color: Color,
+}
+
+impl ComposeDecorator for SliderThumbDecorator {
+  fn compose_decorator(_: State<Self>, host: Widget) -> Widget { host }
+}
+
+/// A slider to pick a value from a range by dragging its thumb.
+///
+/// The slider is continuous by default, set the `divisions` to make it
+/// discrete, then its value snaps to the divisions and the tick marks show on
+/// its track. When the thumb is focused, the arrow keys change the value by a
+/// step, `PageUp` and `PageDown` by a tenth of the range, `Home` and `End` move
+/// it to the ends. The value label shows above the thumb when the thumb is
+/// dragging or focused.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let _w = fn_widget! {
+///   @Slider { value: 20., max: 100., divisions: Some(10) }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct Slider {
+  pub value: f32,
+  #[declare(default = 0.)]
+  pub min: f32,
+  #[declare(default = 1.)]
+  pub max: f32,
+  /// The number of the discrete intervals, `None` for a continuous slider.
+  #[declare(default)]
+  pub divisions: Option<usize>,
+}
+
+/// A slider with two thumbs to pick a range of values, the start value is
+/// never greater than the end value.
+///
+/// It works as the [`Slider`], and every thumb can be focused and changed by
+/// the keyboard separately. A pointer press moves the nearest thumb.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let _w = fn_widget! {
+///   @RangeSlider { start: 20., end: 80., max: 100. }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct RangeSlider {
+  pub start: f32,
+  pub end: f32,
+  #[declare(default = 0.)]
+  pub min: f32,
+  #[declare(default = 1.)]
+  pub max: f32,
+  /// The number of the discrete intervals, `None` for a continuous slider.
+  #[declare(default)]
+  pub divisions: Option<usize>,
+}
+
+impl Slider {
+  /// Set the value, the value is clamped in the range and snapped to the
+  /// divisions.
+  pub fn set_value(&mut self, value: f32) { self.value = self.bounds().snap(value); }
+
+  fn bounds(&self) -> SliderBounds {
+    SliderBounds { min: self.min, max: self.max, divisions: self.divisions }
+  }
+}
+
+impl RangeSlider {
+  /// Set the start value, it's clamped between the `min` and the end value.
+  pub fn set_start(&mut self, start: f32) { self.start = self.bounds().snap(start).min(self.end); }
+
+  /// Set the end value, it's clamped between the start value and the `max`.
+  pub fn set_end(&mut self, end: f32) { self.end = self.bounds().snap(end).max(self.start); }
+
+  fn bounds(&self) -> SliderBounds {
+    SliderBounds { min: self.min, max: self.max, divisions: self.divisions }
+  }
+}
+
+impl Compose for Slider {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> { slider_widget(this) }
+}
+
+impl Compose for RangeSlider {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> { slider_widget(this) }
+}
+
+#[derive(Clone, Copy)]
+struct SliderBounds {
+  min: f32,
+  max: f32,
+  divisions: Option<usize>,
+}
+
+impl SliderBounds {
+  fn snap(&self, value: f32) -> f32 {
+    let value = value.clamp(self.min, self.max);
+    match self.divisions.filter(|d| *d > 0) {
+      Some(_) => {
+        let step = self.step();
+        (self.min + ((value - self.min) / step).round() * step).min(self.max)
+      }
+      None => value,
+    }
+  }
+
+  /// The value changed by an arrow key.
+  fn step(&self) -> f32 {
+    let divisions = self.divisions.filter(|d| *d > 0).unwrap_or(100);
+    (self.max - self.min) / divisions as f32
+  }
+
+  /// The value changed by a page key.
+  fn page(&self) -> f32 {
+    let steps = self.divisions.map_or(10, |d| (d / 10).max(1));
+    self.step() * steps as f32
+  }
+
+  fn ratio(&self, value: f32) -> f32 {
+    let len = self.max - self.min;
+    if len > 0. { ((value - self.min) / len).clamp(0., 1.) } else { 0. }
+  }
+
+  fn value_at(&self, ratio: f32) -> f32 { self.min + ratio * (self.max - self.min) }
+
+  fn label(&self, value: f32) -> String {
+    let step = self.step();
+    let decimals =
+      if step >= 1. || step <= 0. { 0 } else { (-step.log10()).ceil().min(3.) as usize };
+    format!("{value:.decimals$}")
+  }
+}
+
+/// The common part of the [`Slider`] and the [`RangeSlider`].
+trait SliderValues: 'static {
+  const THUMBS: usize;
+
+  fn bounds(&self) -> SliderBounds;
+
+  fn value_of(&self, thumb: usize) -> f32;
+
+  fn set_value_of(&mut self, thumb: usize, value: f32);
+
+  /// The values the active track is between.
+  fn active(&self) -> (f32, f32);
+
+  /// The thumb that moves to the `value` when the pointer presses.
+  fn nearest_thumb(&self, value: f32) -> usize;
+}
+
+impl SliderValues for Slider {
+  const THUMBS: usize = 1;
+
+  fn bounds(&self) -> SliderBounds { self.bounds() }
+
+  fn value_of(&self, _: usize) -> f32 { self.value }
+
+  fn set_value_of(&mut self, _: usize, value: f32) { self.set_value(value) }
+
+  fn active(&self) -> (f32, f32) { (self.min, self.value) }
+
+  fn nearest_thumb(&self, _: f32) -> usize { 0 }
+}
+
+impl SliderValues for RangeSlider {
+  const THUMBS: usize = 2;
+
+  fn bounds(&self) -> SliderBounds { self.bounds() }
+
+  fn value_of(&self, thumb: usize) -> f32 { if thumb == 0 { self.start } else { self.end } }
+
+  fn set_value_of(&mut self, thumb: usize, value: f32) {
+    if thumb == 0 { self.set_start(value) } else { self.set_end(value) }
+  }
+
+  fn active(&self) -> (f32, f32) { (self.start, self.end) }
+
+  fn nearest_thumb(&self, value: f32) -> usize {
+    let to_start = (value - self.start).abs();
+    let to_end = (value - self.end).abs();
+    // The thumbs overlap, pick the one in the direction of the pointer.
+    if to_start < to_end || (to_start == to_end && value < self.start) { 0 } else { 1 }
+  }
+}
+
+/// The x position of the center of the thumb at `ratio` of the track.
+fn thumb_x(ratio: f32, width: f32, style: &SliderStyle) -> f32 {
+  let half = style.thumb_size.width / 2.;
+  half + ratio * (width - style.thumb_size.width).max(0.)
+}
+
+/// The ratio of the track at the `x` position.
+fn ratio_at(x: f32, width: f32, style: &SliderStyle) -> f32 {
+  let len = width - style.thumb_size.width;
+  if len > 0. { ((x - style.thumb_size.width / 2.) / len).clamp(0., 1.) } else { 0. }
+}
+
+fn slider_widget<T: SliderValues>(this: impl StateWriter<Value = T>) -> Widget<'static> {
+  fn_widget! {
+    let style = SliderStyle::of(ctx!());
+    let mut container = @Stack {
+      clamp: BoxClamp::EXPAND_X.with_fixed_height(style.height),
+      cursor: CursorIcon::Pointer,
+    };
+    // The thumb dragged by the pointer.
+    let dragging = Stateful::new(None::<usize>);
+
+    let track = {
+      let style2 = style.clone();
+      @Container {
+        size: pipe!{
+          let width = $container.layout_width();
+          let len = thumb_x(1., width, &style2) - thumb_x(0., width, &style2);
+          Size::new(len, style2.track_height)
+        },
+        anchor: Anchor::left_top(
+          style.thumb_size.width / 2.,
+          (style.height - style.track_height) / 2.
+        ),
+        background: style.inactive_track.clone(),
+        border_radius: Radius::all(style.track_radius),
+      }
+    };
+
+    let active_track = {
+      let (style, style2) = (style.clone(), style.clone());
+      let active_x = move |this: &T, width: f32| {
+        let bounds = this.bounds();
+        let (start, end) = this.active();
+        let start = thumb_x(bounds.ratio(start), width, &style);
+        let end = thumb_x(bounds.ratio(end), width, &style);
+        (start, end)
+      };
+      let active_x2 = active_x.clone();
+      @Container {
+        size: pipe!{
+          let (start, end) = active_x(&$this, $container.layout_width());
+          Size::new(end - start, style2.track_height)
+        },
+        anchor: pipe!{
+          let (start, _) = active_x2(&$this, $container.layout_width());
+          Anchor::left_top(start, (style2.height - style2.track_height) / 2.)
+        },
+        background: style2.active_track.clone(),
+        border_radius: Radius::all(style2.track_radius),
+      }
+    };
+
+    let ticks = {
+      let style = style.clone();
+      let this2 = this.clone_writer();
+      let layout_box = container.get_layout_box_widget().clone_watcher();
+      pipe!($this.bounds().divisions).map(move |divisions| {
+        let divisions = divisions.unwrap_or(0);
+        (0..=divisions).filter(|_| divisions > 0).map(|idx| {
+          let ratio = idx as f32 / divisions as f32;
+          let (style, style2) = (style.clone(), style.clone());
+          let size = style.tick_size;
+          let this = this2.clone_writer();
+          let layout_box = layout_box.clone_watcher();
+          @Container {
+            size: Size::splat(size),
+            anchor: pipe!{
+              let x = thumb_x(ratio, LayoutBox::layout_width(&$layout_box), &style);
+              Anchor::left_top(x - size / 2., (style.height - size) / 2.)
+            },
+            background: pipe!{
+              let bounds = $this.bounds();
+              let (start, end) = $this.active();
+              if bounds.ratio(start) <= ratio && ratio <= bounds.ratio(end) {
+                style2.active_tick.clone()
+              } else {
+                style2.inactive_tick.clone()
+              }
+            },
+            border_radius: Radius::all(size / 2.),
+          }
+        })
+        .collect::<Vec<_>>()
+      })
+    };
+
+    let mut thumbs = vec![];
+    let mut labels = vec![];
+    let mut focuses = vec![];
+    for idx in 0..T::THUMBS {
+      let mut thumb = @Container {
+        size: style.thumb_size,
+        background: style.thumb,
+        border_radius: Radius::all(style.thumb_size.width.min(style.thumb_size.height) / 2.),
+        tab_index: 0i16,
+        on_key_down: move |e| {
+          let bounds = $this.bounds();
+          let value = $this.value_of(idx);
+          let value = match e.key() {
+            VirtualKey::Named(NamedKey::ArrowRight | NamedKey::ArrowUp) => value + bounds.step(),
+            VirtualKey::Named(NamedKey::ArrowLeft | NamedKey::ArrowDown) => value - bounds.step(),
+            VirtualKey::Named(NamedKey::PageUp) => value + bounds.page(),
+            VirtualKey::Named(NamedKey::PageDown) => value - bounds.page(),
+            VirtualKey::Named(NamedKey::Home) => bounds.min,
+            VirtualKey::Named(NamedKey::End) => bounds.max,
+            _ => return,
+          };
+          $this.write().set_value_of(idx, value);
+          e.stop_propagation();
+        },
+      };
+
+      let mut label = @Text {
+        text: pipe!($this.bounds().label($this.value_of(idx))),
+        padding: style.label_padding,
+        background: style.label_background.clone(),
+        border_radius: Radius::all(style.label_radius),
+        foreground: style.label_foreground.clone(),
+        text_style: style.label_style.clone(),
+        visible: pipe!(*$dragging == Some(idx) || $thumb.has_focus()),
+      };
+      let label_style = style.clone();
+      let label = @IgnorePointer {
+        @ $label {
+          anchor: pipe!{
+            let ratio = $this.bounds().ratio($this.value_of(idx));
+            let x = thumb_x(ratio, $container.layout_width(), &label_style);
+            let size = $label.layout_size();
+            let thumb_top = (label_style.height - label_style.thumb_size.height) / 2.;
+            Anchor::left_top(x - size.width / 2., thumb_top - label_style.label_gap - size.height)
+          }
+        }
+      };
+      labels.push(label);
+
+      let focus = thumb.get_request_focus_widget().clone_writer();
+      focuses.push(focus);
+
+      let thumb = @SliderThumbDecorator { color: style.thumb, @ $thumb {} };
+      let mut thumb = @ $thumb {};
+      let thumb_style = style.clone();
+      let thumb = @ $thumb {
+        anchor: pipe!{
+          let ratio = $this.bounds().ratio($this.value_of(idx));
+          let x = thumb_x(ratio, $container.layout_width(), &thumb_style);
+          let size = $thumb.layout_size();
+          Anchor::left_top(x - size.width / 2., (thumb_style.height - size.height) / 2.)
+        }
+      };
+      thumbs.push(thumb);
+    }
+
+    let focuses = Rc::new(focuses);
+    let (style2, this2) = (style.clone(), this.clone_writer());
+    let move_to = Rc::new(move |x: f32, width: f32, thumb: usize| {
+      let bounds = this2.read().bounds();
+      let value = bounds.snap(bounds.value_at(ratio_at(x, width, &style2)));
+      if this2.read().value_of(thumb) != value {
+        this2.write().set_value_of(thumb, value);
+      }
+    });
+    let move_to2 = move_to.clone();
+    @ $container {
+      on_pointer_down: move |e| if e.is_primary {
+        let width = $container.layout_width();
+        let bounds = $this.bounds();
+        let value = bounds.value_at(ratio_at(e.position().x, width, &style));
+        let thumb = $this.nearest_thumb(value);
+        *$dragging.write() = Some(thumb);
+        // Keep dragging the thumb when the pointer moves out of the slider.
+        e.set_pointer_capture(e.id);
+        focuses[thumb].read().request_focus();
+        move_to(e.position().x, width, thumb);
+      },
+      on_pointer_move: move |e| if let Some(thumb) = *$dragging {
+        move_to2(e.position().x, $container.layout_width(), thumb);
+      },
+      on_pointer_up: move |_| if $dragging.is_some() {
+        *$dragging.write() = None;
+      },
+      @{ track }
+      @{ active_track }
+      @{ ticks }
+      @{ thumbs }
+      @{ labels }
+    }
+  }
+  .into_widget()
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+  use winit::{
+    dpi::LogicalPosition,
+    event::{DeviceId, ElementState, MouseButton, WindowEvent},
+    keyboard::{KeyCode, KeyLocation, PhysicalKey},
+  };
+
+  use super::*;
+
+  fn press(wnd: &mut TestWindow, code: KeyCode, key: NamedKey) {
+    wnd.processes_keyboard_event(
+      PhysicalKey::Code(code),
+      VirtualKey::Named(key),
+      false,
+      KeyLocation::Standard,
+      ElementState::Pressed,
+    );
+    wnd.draw_frame();
+  }
+
+  fn cursor_move(wnd: &TestWindow, x: f32, y: f32) {
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::CursorMoved {
+      device_id: unsafe { DeviceId::dummy() },
+      position: LogicalPosition::new(x, y).to_physical(1.),
+    });
+  }
+
+  fn mouse_input(wnd: &TestWindow, state: ElementState) {
+    let device_id = unsafe { DeviceId::dummy() };
+    wnd.process_mouse_input(device_id, state, MouseButton::Left);
+  }
+
+  #[test]
+  fn snap_to_divisions() {
+    let bounds = SliderBounds { min: 0., max: 100., divisions: Some(4) };
+    assert_eq!(bounds.snap(30.), 25.);
+    assert_eq!(bounds.snap(40.), 50.);
+    assert_eq!(bounds.snap(120.), 100.);
+    assert_eq!(bounds.step(), 25.);
+    assert_eq!(bounds.page(), 25.);
+    assert_eq!(bounds.label(25.), "25");
+
+    let bounds = SliderBounds { min: 0., max: 1., divisions: None };
+    assert_eq!(bounds.snap(0.333), 0.333);
+    assert_eq!(bounds.label(0.333), "0.33");
+  }
+
+  #[test]
+  fn drag_slider() {
+    reset_test_env!();
+
+    let slider = Stateful::new(Slider { value: 0., min: 0., max: 100., divisions: None });
+    let c_slider = slider.clone_writer();
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! { @ { c_slider.clone_writer() } },
+      Size::new(216., 100.),
+    );
+    wnd.draw_frame();
+
+    // The track is from 8 to 208 with the default thumb size.
+    cursor_move(&wnd, 108., 16.);
+    mouse_input(&wnd, ElementState::Pressed);
+    wnd.draw_frame();
+    assert_eq!(slider.read().value, 50.);
+
+    cursor_move(&wnd, 158., 16.);
+    wnd.draw_frame();
+    assert_eq!(slider.read().value, 75.);
+
+    // The slider captures the pointer, it keeps dragging out of the slider.
+    cursor_move(&wnd, 158., 80.);
+    wnd.draw_frame();
+    assert_eq!(slider.read().value, 75.);
+    cursor_move(&wnd, 300., 80.);
+    wnd.draw_frame();
+    assert_eq!(slider.read().value, 100.);
+    cursor_move(&wnd, 158., 16.);
+    wnd.draw_frame();
+    assert_eq!(slider.read().value, 75.);
+
+    mouse_input(&wnd, ElementState::Released);
+    cursor_move(&wnd, 58., 16.);
+    wnd.draw_frame();
+    assert_eq!(slider.read().value, 75.);
+
+    // The pressed thumb is focused.
+    press(&mut wnd, KeyCode::ArrowRight, NamedKey::ArrowRight);
+    assert_eq!(slider.read().value, 76.);
+    press(&mut wnd, KeyCode::PageDown, NamedKey::PageDown);
+    assert_eq!(slider.read().value, 66.);
+    press(&mut wnd, KeyCode::End, NamedKey::End);
+    assert_eq!(slider.read().value, 100.);
+  }
+
+  #[test]
+  fn range_slider() {
+    reset_test_env!();
+
+    let range =
+      Stateful::new(RangeSlider { start: 20., end: 60., min: 0., max: 100., divisions: Some(10) });
+    let c_range = range.clone_writer();
+    let mut wnd =
+      TestWindow::new_with_size(fn_widget! { @ { c_range.clone_writer() } }, Size::new(216., 100.));
+    wnd.draw_frame();
+
+    // Press near the end thumb moves it.
+    cursor_move(&wnd, 188., 16.);
+    mouse_input(&wnd, ElementState::Pressed);
+    mouse_input(&wnd, ElementState::Released);
+    wnd.draw_frame();
+    assert_eq!((range.read().start, range.read().end), (20., 90.));
+
+    // Press near the start thumb moves it, and the keyboard acts on it.
+    cursor_move(&wnd, 8., 16.);
+    mouse_input(&wnd, ElementState::Pressed);
+    mouse_input(&wnd, ElementState::Released);
+    wnd.draw_frame();
+    assert_eq!((range.read().start, range.read().end), (0., 90.));
+    press(&mut wnd, KeyCode::ArrowUp, NamedKey::ArrowUp);
+    assert_eq!((range.read().start, range.read().end), (10., 90.));
+
+    // The start never passes the end.
+    press(&mut wnd, KeyCode::End, NamedKey::End);
+    assert_eq!((range.read().start, range.read().end), (90., 90.));
+
+    // Tab to the end thumb.
+    wnd.request_next_focus();
+    press(&mut wnd, KeyCode::End, NamedKey::End);
+    assert_eq!((range.read().start, range.read().end), (90., 100.));
+  }
+}
@@ -0,0 +1,166 @@
+use ribir_core::prelude::*;
+
+use crate::layout::Stack;
+
+/// A toggle to turn an option on or off, its thumb slides to the end when it's
+/// checked.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let _w = fn_widget! {
+///   let switch = @Switch { checked: true };
+///   watch!($switch.checked).subscribe(|checked| println!("{checked}"));
+///   switch
+/// };
+/// ```
+#[derive(Clone, Declare)]
+pub struct Switch {
+  #[declare(default)]
+  pub checked: bool,
+  /// The color of the track when the switch is checked.
+  #[declare(default=Palette::of(ctx!()).primary())]
+  pub color: Color,
+}
+
+#[derive(Clone)]
+pub struct SwitchStyle {
+  pub track_size: Size,
+  /// The diameter of the thumb when the switch is unchecked.
+  pub thumb_size: f32,
+  /// The diameter of the thumb when the switch is checked.
+  pub checked_thumb_size: f32,
+  pub border_width: f32,
+  /// The track of the unchecked switch.
+  pub track: Brush,
+  /// The border of the unchecked switch.
+  pub border: Brush,
+  /// The thumb of the unchecked switch.
+  pub thumb: Brush,
+  /// The thumb of the checked switch.
+  pub checked_thumb: Brush,
+}
+
+/// The decorator of the switch thumb, a theme can override it to show the
+/// interactive states of the switch.
+#[derive(Clone, Declare)]
+pub struct SwitchThumbDecorator {
+  pub color: Color,
+}
+
+impl ComposeDecorator for SwitchThumbDecorator {
+  fn compose_decorator(_: State<Self>, host: Widget) -> Widget { host }
+}
+
+impl Switch {
+  pub fn switch_check(&mut self) { self.checked = !self.checked; }
+}
+
+impl Compose for Switch {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    fn_widget! {
+      let SwitchStyle {
+        track_size, thumb_size, checked_thumb_size, border_width, track, border, thumb,
+        checked_thumb,
+      } = SwitchStyle::of(ctx!());
+
+      let diameter = move |checked: bool| if checked { checked_thumb_size } else { thumb_size };
+      let knob = @Container {
+        size: pipe!(Size::splat(diameter($this.checked))),
+        border_radius: pipe!(Radius::all(diameter($this.checked) / 2.)),
+        background: pipe!(if $this.checked { checked_thumb.clone() } else { thumb.clone() }),
+      };
+      let knob = @SwitchThumbDecorator {
+        color: pipe!($this.color),
+        @ $knob {}
+      };
+
+      // The thumb is centered at the start or the end of the track.
+      let mut knob = @ $knob {};
+      let mut knob = @ $knob {
+        anchor: pipe!{
+          let half = track_size.height / 2.;
+          let center_x = if $this.checked { track_size.width - half } else { half };
+          let size = $knob.layout_size();
+          Anchor::left_top(center_x - size.width / 2., half - size.height / 2.)
+        }
+      };
+      knob
+        .get_relative_anchor_widget()
+        .map_writer(|w| PartData::from_ref_mut(&mut w.anchor))
+        .transition(transitions::EASE_IN.of(ctx!()), ctx!());
+
+      @Stack {
+        clamp: BoxClamp::fixed_size(track_size),
+        border_radius: Radius::all(track_size.height / 2.),
+        background: pipe!(if $this.checked { $this.color.into() } else { track.clone() }),
+        border: pipe!{
+          let color = if $this.checked { $this.color.into() } else { border.clone() };
+          Border::all(BorderSide::new(border_width, color))
+        },
+        cursor: CursorIcon::Pointer,
+        on_tap: move |_| $this.write().switch_check(),
+        on_key_up: move |k| if *k.key() == VirtualKey::Named(NamedKey::Space) {
+          $this.write().switch_check()
+        },
+        @ { knob }
+      }
+    }
+    .into_widget()
+  }
+}
+
+impl CustomStyle for SwitchStyle {
+  fn default_style(ctx: &impl ProviderCtx) -> Self {
+    let palette = Palette::of(ctx);
+    SwitchStyle {
+      track_size: Size::new(52., 32.),
+      thumb_size: 16.,
+      checked_thumb_size: 24.,
+      border_width: 2.,
+      track: palette.surface_container_highest().into(),
+      border: palette.outline().into(),
+      thumb: palette.outline().into(),
+      checked_thumb: palette.on_primary().into(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+  use winit::{
+    event::ElementState,
+    keyboard::{KeyCode, KeyLocation, PhysicalKey},
+  };
+
+  use super::*;
+
+  #[test]
+  fn toggle_switch() {
+    reset_test_env!();
+
+    let switch = Stateful::new(Switch { checked: false, color: Color::RED });
+    let c_switch = switch.clone_writer();
+    let mut wnd = TestWindow::new(fn_widget! { @ { c_switch.clone_writer() } });
+    wnd.draw_frame();
+    // The unchecked thumb is at the start of the track.
+    wnd.assert_root_size(Size::new(52., 32.));
+    LayoutCase::expect_pos(&wnd, &[0, 0, 0, 0, 0], Point::new(8., 8.));
+
+    wnd.request_next_focus();
+    wnd.processes_keyboard_event(
+      PhysicalKey::Code(KeyCode::Space),
+      VirtualKey::Named(NamedKey::Space),
+      false,
+      KeyLocation::Standard,
+      ElementState::Released,
+    );
+    wnd.draw_frame();
+    assert!(switch.read().checked);
+    LayoutCase::expect_pos(&wnd, &[0, 0, 0, 0, 0], Point::new(24., 4.));
+  }
+}
@@ -24,12 +24,25 @@ impl VisualText for Text {
 }
 
 impl Render for Text {
-  fn perform_layout(&self, clamp: BoxClamp, _: &mut LayoutCtx) -> Size {
-    self
-      .text_layout(AppCtx::typography_store(), clamp.max)
-      .visual_rect()
-      .size
-      .cast_unit()
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let glyphs = self.text_layout(AppCtx::typography_store(), clamp.max);
+    if let Some(baseline) = glyphs.first_baseline() {
+      ctx.set_baseline(baseline);
+    }
+    glyphs.visual_rect().size.cast_unit()
+  }
+
+  fn intrinsic_size(&self, intrinsic: Intrinsic, extent: f32, _: &mut LayoutCtx) -> f32 {
+    let bounds = match intrinsic {
+      // Wrap the text at every word.
+      Intrinsic::MinWidth => Size::new(0., extent),
+      Intrinsic::MaxWidth => Size::new(f32::INFINITY, extent),
+      Intrinsic::MinHeight | Intrinsic::MaxHeight => Size::new(extent, f32::INFINITY),
+    };
+    let rect = self
+      .text_layout(AppCtx::typography_store(), bounds)
+      .visual_rect();
+    intrinsic.axis_of(rect.size.cast_unit())
   }
 
   #[inline]
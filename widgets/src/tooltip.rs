@@ -0,0 +1,125 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use ribir_core::{
+  overlay::{AutoClosePolicy, Placement, PlacementAlign, PlacementSide},
+  prelude::*,
+};
+
+use crate::prelude::*;
+
+#[derive(Clone)]
+pub struct TooltipStyle {
+  pub background: Brush,
+  pub foreground: Brush,
+  pub text_style: CowArc<TextStyle>,
+  pub padding: EdgeInsets,
+  pub radius: f32,
+  /// The gap between the tooltip and its host.
+  pub gap: f32,
+  /// How long the pointer hovers before the tooltip shows.
+  pub delay: Duration,
+}
+
+impl CustomStyle for TooltipStyle {
+  fn default_style(ctx: &impl ProviderCtx) -> Self {
+    let palette = Palette::of(ctx);
+    Self {
+      background: palette.inverse_surface().into(),
+      foreground: palette.inverse_on_surface().into(),
+      text_style: TypographyTheme::of(ctx).body_small.text.clone(),
+      padding: EdgeInsets::symmetrical(4., 8.),
+      radius: 4.,
+      gap: 4.,
+      delay: Duration::from_millis(500),
+    }
+  }
+}
+
+/// Shows a brief text beside its child when the pointer hovers over the child,
+/// the text flips to the other side if there is not enough space.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let _w = fn_widget! {
+///   @Tooltip {
+///     text: "Delete the file",
+///     @Icon { @{ svgs::DELETE } }
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct Tooltip {
+  pub text: CowArc<str>,
+  /// The preferred side of the child to show the text.
+  #[declare(default = PlacementSide::Top)]
+  pub side: PlacementSide,
+}
+
+impl<'c> ComposeChild<'c> for Tooltip {
+  type Child = Widget<'c>;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    fn_widget! {
+      let style = TooltipStyle::of(ctx!());
+      let overlay = {
+        let style = style.clone();
+        let this = this.clone_watcher();
+        Overlay::new(move |_: &mut BuildCtx| {
+          let style = style.clone();
+          let this = this.clone_watcher();
+          fn_widget! {
+            @IgnorePointer {
+              @Text {
+                text: pipe!($this.text.clone()),
+                padding: style.padding,
+                background: style.background.clone(),
+                border_radius: Radius::all(style.radius),
+                foreground: style.foreground.clone(),
+                text_style: style.text_style.clone(),
+              }
+            }
+          }
+          .into_widget()
+        })
+      };
+      overlay.set_auto_close_policy(AutoClosePolicy::NONE);
+      overlay.set_modal(false);
+
+      let pending: Rc<RefCell<Option<SubscriptionGuard<BoxSubscription<'static>>>>> =
+        <_>::default();
+      let hide = {
+        let overlay = overlay.clone();
+        let pending = pending.clone();
+        move || {
+          pending.borrow_mut().take();
+          overlay.close();
+        }
+      };
+      let (hide2, hide3) = (hide.clone(), hide.clone());
+
+      @$child {
+        on_pointer_enter: move |e| {
+          let target = e.current_target();
+          let wnd = e.window();
+          let overlay = overlay.clone();
+          let placement = Placement::new($this.side)
+            .with_align(PlacementAlign::Center)
+            .with_gap(style.gap);
+          let show = observable::timer((), style.delay, AppCtx::scheduler())
+            .box_it()
+            .subscribe(move |_| overlay.show_anchored(target, placement, wnd.clone()))
+            .unsubscribe_when_dropped();
+          *pending.borrow_mut() = Some(show);
+        },
+        on_pointer_leave: move |_| hide(),
+        on_pointer_down: move |_| hide2(),
+        on_disposed: move |_| hide3(),
+      }
+    }
+    .into_widget()
+  }
+}
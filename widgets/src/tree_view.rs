@@ -0,0 +1,665 @@
+use std::collections::HashSet;
+
+use ribir_core::prelude::*;
+
+use crate::{
+  icon::Icon,
+  layout::{Column, Row, SizedBox},
+  prelude::Text,
+};
+
+/// The path of a node in a [`TreeView`], the indices of the node and its
+/// ancestors among their siblings, from the root to the node.
+pub type TreePath = Vec<usize>;
+
+/// A node of the [`TreeView`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TreeNode {
+  pub label: CowArc<str>,
+  pub children: Vec<TreeNode>,
+}
+
+impl TreeNode {
+  pub fn new(label: impl Into<CowArc<str>>) -> Self {
+    Self { label: label.into(), children: vec![] }
+  }
+
+  pub fn with_children(mut self, children: Vec<TreeNode>) -> Self {
+    self.children = children;
+    self
+  }
+}
+
+#[derive(Clone)]
+pub struct TreeViewStyle {
+  pub text_style: CowArc<TextStyle>,
+  pub foreground: Brush,
+  pub selected_background: Brush,
+  /// The brush of the indentation guides.
+  pub guide: Brush,
+  pub row_padding: EdgeInsets,
+}
+
+impl CustomStyle for TreeViewStyle {
+  fn default_style(ctx: &impl ProviderCtx) -> Self {
+    let palette = Palette::of(ctx);
+    Self {
+      text_style: TypographyTheme::of(ctx).body_medium.text.clone(),
+      foreground: palette.on_surface().into(),
+      selected_background: palette.secondary_container().into(),
+      guide: palette.outline_variant().into(),
+      row_padding: EdgeInsets::vertical(4.),
+    }
+  }
+}
+
+/// A widget displays a hierarchy of [`TreeNode`]s, every level is indented
+/// with a guide line, and the nodes with children can be expanded or
+/// collapsed. The toggle and the children are animated by the `EASE_IN_OUT`
+/// transition of the `TransitionTheme`. The children of a node are built the
+/// first time it's expanded.
+///
+/// - Tap a node to select it, with the command key to add it to or remove it
+///   from the selection, with the shift key to select the nodes from the last
+///   selected one to it.
+/// - Tap the toggle or double tap a node to expand or collapse it.
+/// - Every node is a focus node, the focus moves with the arrow keys, `Home`
+///   and `End`. The `ArrowRight` expands the node or moves to its first child,
+///   the `ArrowLeft` collapses the node or moves to its parent. The focus
+///   moving without the command key selects the node, and with the shift key
+///   extends the selection. `Space` selects the focused node and `Enter`
+///   expands or collapses it.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let _tree = fn_widget! {
+///   @TreeView {
+///     nodes: vec![
+///       TreeNode::new("src").with_children(vec![
+///         TreeNode::new("lib.rs"),
+///         TreeNode::new("main.rs"),
+///       ]),
+///       TreeNode::new("Cargo.toml"),
+///     ],
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct TreeView {
+  nodes: Vec<TreeNode>,
+  /// The indentation of every level.
+  #[declare(default = 24.)]
+  pub indent: f32,
+  /// Whether more than one node can be selected.
+  #[declare(default = true)]
+  pub multi_select: bool,
+  #[declare(skip)]
+  expanded: HashSet<TreePath>,
+  #[declare(skip)]
+  selected: Vec<TreePath>,
+  /// The node the selection range starts from.
+  #[declare(skip)]
+  range_anchor: Option<TreePath>,
+  /// The node has the focus, or will get the focus when the tree is focused.
+  #[declare(skip)]
+  focus_path: Option<TreePath>,
+  /// Increased when the nodes are updated to rebuild the tree.
+  #[declare(skip)]
+  generation: usize,
+}
+
+impl TreeView {
+  pub fn nodes(&self) -> &[TreeNode] { &self.nodes }
+
+  /// Update the nodes and rebuild the tree. The expanded and selected states
+  /// are reset.
+  pub fn update_nodes(&mut self, f: impl FnOnce(&mut Vec<TreeNode>)) {
+    f(&mut self.nodes);
+    self.expanded.clear();
+    self.selected.clear();
+    self.range_anchor = None;
+    self.focus_path = None;
+    self.generation += 1;
+  }
+
+  pub fn node(&self, path: &[usize]) -> Option<&TreeNode> {
+    let (first, rest) = path.split_first()?;
+    rest
+      .iter()
+      .try_fold(self.nodes.get(*first)?, |node, idx| node.children.get(*idx))
+  }
+
+  pub fn is_expanded(&self, path: &[usize]) -> bool { self.expanded.contains(path) }
+
+  /// Expand or collapse the node. If the focused node is hidden by
+  /// collapsing, the focus moves to the collapsed node.
+  pub fn set_expanded(&mut self, path: &[usize], expanded: bool) {
+    let has_children = self
+      .node(path)
+      .is_some_and(|n| !n.children.is_empty());
+    if expanded && has_children {
+      self.expanded.insert(path.to_vec());
+    } else if !expanded && self.expanded.remove(path) {
+      let hidden = self
+        .focus_path
+        .as_ref()
+        .is_some_and(|c| c.len() > path.len() && c.starts_with(path));
+      if hidden {
+        self.focus_path = Some(path.to_vec());
+      }
+    }
+  }
+
+  pub fn toggle_expanded(&mut self, path: &[usize]) {
+    let expanded = self.is_expanded(path);
+    self.set_expanded(path, !expanded);
+  }
+
+  /// The paths of the nodes not hidden by a collapsed ancestor, in the order
+  /// they are displayed.
+  pub fn visible_paths(&self) -> Vec<TreePath> {
+    fn collect(
+      nodes: &[TreeNode], path: &mut TreePath, expanded: &HashSet<TreePath>,
+      paths: &mut Vec<TreePath>,
+    ) {
+      for (idx, node) in nodes.iter().enumerate() {
+        path.push(idx);
+        paths.push(path.clone());
+        if expanded.contains(path) {
+          collect(&node.children, path, expanded, paths);
+        }
+        path.pop();
+      }
+    }
+
+    let mut paths = vec![];
+    collect(&self.nodes, &mut vec![], &self.expanded, &mut paths);
+    paths
+  }
+
+  /// The selected nodes, in the order they are selected.
+  pub fn selected(&self) -> &[TreePath] { &self.selected }
+
+  pub fn is_selected(&self, path: &[usize]) -> bool { self.selected.iter().any(|p| p == path) }
+
+  /// Select only the node.
+  pub fn select(&mut self, path: &[usize]) {
+    self.selected = vec![path.to_vec()];
+    self.range_anchor = Some(path.to_vec());
+  }
+
+  /// Add the node to the selection or remove it from the selection.
+  pub fn toggle_selected(&mut self, path: &[usize]) {
+    if !self.multi_select {
+      self.select(path);
+    } else if let Some(idx) = self.selected.iter().position(|p| p == path) {
+      self.selected.remove(idx);
+    } else {
+      self.selected.push(path.to_vec());
+      self.range_anchor = Some(path.to_vec());
+    }
+  }
+
+  /// Select the visible nodes from the node the last selection started from
+  /// to the node.
+  pub fn select_range(&mut self, path: &[usize]) {
+    let visible = self.visible_paths();
+    let to = visible.iter().position(|p| p == path);
+    let from = self
+      .range_anchor
+      .as_ref()
+      .and_then(|a| visible.iter().position(|p| p == a));
+    match (from, to) {
+      (Some(from), Some(to)) if self.multi_select => {
+        let range = from.min(to)..=from.max(to);
+        self.selected = visible[range].to_vec();
+      }
+      _ => self.select(path),
+    }
+  }
+
+  pub fn clear_selection(&mut self) {
+    self.selected.clear();
+    self.range_anchor = None;
+  }
+
+  /// The node has the focus, or will get the focus when the tree is focused.
+  pub fn focused(&self) -> Option<&[usize]> { self.focus_path.as_deref() }
+
+  /// Move the focus to the node.
+  pub fn focus(&mut self, path: &[usize]) { self.focus_path = Some(path.to_vec()); }
+
+  /// Only one node is reachable by the `Tab` key, the others are focused by the
+  /// arrow keys.
+  fn is_tab_stop(&self, path: &[usize]) -> bool {
+    match &self.focus_path {
+      Some(cursor) => cursor == path,
+      None => path == [0],
+    }
+  }
+
+  /// The node the focus moves to when pressing `key`, or `None` if the key
+  /// doesn't move the focus.
+  fn navigate(&self, key: &VirtualKey) -> Option<TreePath> {
+    let visible = self.visible_paths();
+    let current = self
+      .focus_path
+      .as_ref()
+      .and_then(|c| visible.iter().position(|p| p == c));
+    let idx = match key {
+      VirtualKey::Named(NamedKey::ArrowDown) => current.map_or(0, |i| i + 1),
+      VirtualKey::Named(NamedKey::ArrowUp) => current.map_or(0, |i| i.saturating_sub(1)),
+      VirtualKey::Named(NamedKey::Home) => 0,
+      VirtualKey::Named(NamedKey::End) => visible.len().checked_sub(1)?,
+      VirtualKey::Named(NamedKey::ArrowRight) => {
+        let cursor = self.focus_path.as_ref()?;
+        if !self.is_expanded(cursor) {
+          return None;
+        }
+        current? + 1
+      }
+      VirtualKey::Named(NamedKey::ArrowLeft) => {
+        let cursor = self.focus_path.as_ref()?;
+        if self.is_expanded(cursor) || cursor.len() < 2 {
+          return None;
+        }
+        return Some(cursor[..cursor.len() - 1].to_vec());
+      }
+      _ => return None,
+    };
+    visible.get(idx).cloned()
+  }
+
+  fn on_key(&mut self, key: &VirtualKey, shift: bool, command: bool) {
+    if let Some(path) = self.navigate(key) {
+      self.focus_path = Some(path.clone());
+      if shift {
+        self.select_range(&path);
+      } else if !command {
+        self.select(&path);
+      }
+      return;
+    }
+    let Some(cursor) = self.focus_path.clone() else { return };
+    match key {
+      VirtualKey::Named(NamedKey::ArrowRight) => self.set_expanded(&cursor, true),
+      VirtualKey::Named(NamedKey::ArrowLeft) => self.set_expanded(&cursor, false),
+      VirtualKey::Named(NamedKey::Enter) => self.toggle_expanded(&cursor),
+      VirtualKey::Named(NamedKey::Space) if command => self.toggle_selected(&cursor),
+      VirtualKey::Named(NamedKey::Space) => self.select(&cursor),
+      _ => {}
+    }
+  }
+}
+
+impl Compose for TreeView {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    fn_widget! {
+      let style = TreeViewStyle::of(ctx!());
+      // Only rebuild the nodes when they are updated, not for the expanded or
+      // selected states.
+      let generation = Stateful::new($this.generation);
+      let u = watch!($this.generation).subscribe(move |g| {
+        if *$generation != g {
+          *$generation.write() = g;
+        }
+      });
+
+      let this2 = this.clone_writer();
+      let nodes = pipe!(*$generation).map(move |_| {
+        let count = this2.read().nodes.len();
+        (0..count)
+          .map(|idx| tree_node(this2.clone_writer(), vec![idx], &style))
+          .collect::<Vec<_>>()
+      });
+      @Column {
+        align_items: Align::Stretch,
+        on_disposed: move |_| u.unsubscribe(),
+        @ { nodes }
+      }
+    }
+    .into_widget()
+  }
+}
+
+fn tree_node(
+  this: impl StateWriter<Value = TreeView>, path: TreePath, style: &TreeViewStyle,
+) -> Widget<'static> {
+  let style = style.clone();
+  fn_widget! {
+    let has_children = $this.node(&path).is_some_and(|n| !n.children.is_empty());
+    let row = tree_row(this.clone_writer(), path.clone(), has_children, &style);
+    let children = has_children.then(|| {
+      // Build the children the first time the node is expanded.
+      let built = Stateful::new($this.is_expanded(&path));
+      let p = path.clone();
+      let u = watch!($this.is_expanded(&p))
+        .filter(|expanded| *expanded)
+        .take(1)
+        .subscribe(move |_| *$built.write() = true);
+
+      let this2 = this.clone_writer();
+      let style = style.clone();
+      let path2 = path.clone();
+      let children = pipe!(*$built).map(move |built| {
+        let count = if built {
+          this2.read().node(&path2).map_or(0, |n| n.children.len())
+        } else {
+          0
+        };
+        (0..count)
+          .map(|idx| {
+            let mut path = path2.clone();
+            path.push(idx);
+            tree_node(this2.clone_writer(), path, &style)
+          })
+          .collect::<Vec<_>>()
+      });
+
+      let p = path.clone();
+      let collapsible = @Collapsible {
+        factor: pipe!(if $this.is_expanded(&p) { 1. } else { 0. }),
+        on_disposed: move |_| u.unsubscribe(),
+      };
+      collapsible
+        .clone_writer()
+        .map_writer(|w| PartData::from_ref(&w.factor))
+        .transition(transitions::EASE_IN_OUT.of(ctx!()), ctx!());
+
+      @ $collapsible {
+        @Column {
+          align_items: Align::Stretch,
+          @ { children }
+        }
+      }
+    });
+
+    @Column {
+      align_items: Align::Stretch,
+      @ { row }
+      @ { children }
+    }
+  }
+  .into_widget()
+}
+
+fn tree_row(
+  this: impl StateWriter<Value = TreeView>, path: TreePath, has_children: bool,
+  style: &TreeViewStyle,
+) -> Widget<'static> {
+  let style = style.clone();
+  fn_widget! {
+    let label = $this.node(&path).map(|n| n.label.clone()).unwrap_or_default();
+    let toggle = if has_children {
+      let p = path.clone();
+      let angle = Stateful::new(if $this.is_expanded(&p) { 90f32 } else { 0. });
+      let u = watch!($this.is_expanded(&p))
+        .distinct_until_changed()
+        .subscribe(move |expanded| *$angle.write() = if expanded { 90. } else { 0. });
+      angle
+        .clone_writer()
+        .transition(transitions::EASE_IN_OUT.of(ctx!()), ctx!());
+
+      let p = path.clone();
+      let size = IconSize::of(ctx!()).small;
+      let icon = @Icon {
+        size,
+        transform: pipe!(rotation_at_center(*$angle, size)),
+        on_tap: move |e| {
+          $this.write().toggle_expanded(&p);
+          e.stop_propagation();
+        },
+        on_disposed: move |_| u.unsubscribe(),
+        @ { svgs::CHEVRON_RIGHT }
+      };
+      icon.into_widget()
+    } else {
+      @SizedBox { size: IconSize::of(ctx!()).small }.into_widget()
+    };
+
+    let (p1, p2, p3, p4, p5) =
+      (path.clone(), path.clone(), path.clone(), path.clone(), path.clone());
+    let mut row = @IndentGuides {
+      depth: path.len() - 1,
+      indent: pipe!($this.indent),
+      brush: style.guide.clone(),
+      tab_index: pipe!(if $this.is_tab_stop(&p1) { 0i16 } else { -1 }),
+      background: {
+        let style = style.clone();
+        distinct_pipe!($this.is_selected(&p2)).map(move |selected| {
+          selected.then(|| style.selected_background.clone())
+        })
+      },
+      on_tap: move |e| {
+        let mut tree = $this.write();
+        tree.focus(&p3);
+        if e.with_shift_key() {
+          tree.select_range(&p3);
+        } else if e.with_command_key() {
+          tree.toggle_selected(&p3);
+        } else {
+          tree.select(&p3);
+        }
+      },
+      on_double_tap: move |_| $this.write().toggle_expanded(&p4),
+      on_key_down: move |e| {
+        let handled = matches!(
+          e.key(),
+          VirtualKey::Named(
+            NamedKey::ArrowDown | NamedKey::ArrowUp | NamedKey::ArrowLeft
+              | NamedKey::ArrowRight | NamedKey::Home | NamedKey::End
+              | NamedKey::Enter | NamedKey::Space
+          )
+        );
+        if handled {
+          let (shift, command) = (e.with_shift_key(), e.with_command_key());
+          $this.write().on_key(e.key(), shift, command);
+          e.stop_propagation();
+        }
+      },
+      on_focus: move |_| {
+        if $this.focused() != Some(&p5) {
+          $this.write().focus(&p5);
+        }
+      },
+    };
+
+    let u = watch!($this.focused() == Some(&path))
+      .distinct_until_changed()
+      .filter(|focused| *focused)
+      .subscribe(move |_| $row.request_focus());
+    @ $row {
+      on_disposed: move |_| u.unsubscribe(),
+      @Row {
+        align_items: Align::Center,
+        padding: style.row_padding,
+        @ { toggle }
+        @Text {
+          text: label,
+          text_style: style.text_style.clone(),
+          foreground: style.foreground.clone(),
+        }
+      }
+    }
+  }
+  .into_widget()
+}
+
+fn rotation_at_center(degrees: f32, size: Size) -> Transform {
+  let (x, y) = (size.width / 2., size.height / 2.);
+  Transform::translation(-x, -y)
+    .then_rotate(Angle::degrees(degrees))
+    .then_translate(Vector::new(x, y))
+}
+
+/// Paints the indentation guides of the `depth` levels before the child.
+#[derive(Declare, SingleChild)]
+struct IndentGuides {
+  depth: usize,
+  indent: f32,
+  brush: Brush,
+}
+
+impl Render for IndentGuides {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let offset = self.depth as f32 * self.indent;
+    let thickness = Size::new(offset, 0.);
+    let zero = Size::zero();
+    let child_clamp =
+      BoxClamp { min: (clamp.min - thickness).max(zero), max: (clamp.max - thickness).max(zero) };
+    let mut l = ctx.assert_single_child_layouter();
+    let size = l.perform_widget_layout(child_clamp);
+    l.update_position(Point::new(offset, 0.));
+    clamp.clamp(size + thickness)
+  }
+
+  fn paint(&self, ctx: &mut PaintingCtx) {
+    let Some(size) = ctx.box_size() else { return };
+    if self.depth == 0 {
+      return;
+    }
+    let painter = ctx.painter();
+    painter.set_brush(self.brush.clone());
+    for level in 0..self.depth {
+      let x = (level as f32 + 0.5) * self.indent - 0.5;
+      painter.rect(&Rect::new(Point::new(x, 0.), Size::new(1., size.height)));
+    }
+    painter.fill();
+  }
+}
+
+/// Shows the part of the child, `factor` is the rate of the height of the child
+/// to show, so the child is hidden if it's zero.
+#[derive(Declare, SingleChild)]
+struct Collapsible {
+  factor: f32,
+}
+
+impl Render for Collapsible {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let child_clamp = BoxClamp { min: clamp.min, max: Size::new(clamp.max.width, f32::INFINITY) };
+    let size = ctx.assert_perform_single_child_layout(child_clamp);
+    clamp.clamp(Size::new(size.width, size.height * self.factor))
+  }
+
+  fn paint(&self, ctx: &mut PaintingCtx) {
+    if let Some(size) = ctx.box_size() {
+      ctx
+        .painter()
+        .clip(Path::rect(&Rect::from_size(size)));
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+  use winit::{
+    event::ElementState,
+    keyboard::{KeyCode, KeyLocation, PhysicalKey},
+  };
+
+  use super::*;
+
+  fn nodes() -> Vec<TreeNode> {
+    vec![
+      TreeNode::new("a").with_children(vec![
+        TreeNode::new("a0"),
+        TreeNode::new("a1").with_children(vec![TreeNode::new("a10")]),
+      ]),
+      TreeNode::new("b"),
+    ]
+  }
+
+  fn tree_wnd() -> (TestWindow, Stateful<TreeView>) {
+    let (tree, w_tree) = split_value(None);
+    let wnd = TestWindow::new_with_size(
+      fn_widget! {
+        let tree = @TreeView { nodes: nodes() };
+        *$w_tree.write() = Some(tree.clone_writer());
+        tree
+      },
+      Size::new(200., 400.),
+    );
+    let tree = tree.read().as_ref().unwrap().clone_writer();
+    (wnd, tree)
+  }
+
+  #[test]
+  fn expand_and_select() {
+    reset_test_env!();
+
+    let (mut wnd, tree) = tree_wnd();
+    wnd.draw_frame();
+    assert_eq!(tree.read().visible_paths(), vec![vec![0], vec![1]]);
+
+    tree.write().set_expanded(&[0], true);
+    tree.write().set_expanded(&[0, 1], true);
+    // A node without children can't be expanded.
+    tree.write().set_expanded(&[1], true);
+    wnd.draw_frame();
+    assert_eq!(
+      tree.read().visible_paths(),
+      vec![vec![0], vec![0, 0], vec![0, 1], vec![0, 1, 0], vec![1]]
+    );
+
+    let mut t = tree.write();
+    t.select(&[0, 0]);
+    t.select_range(&[0, 1, 0]);
+    assert_eq!(t.selected(), &[vec![0, 0], vec![0, 1], vec![0, 1, 0]]);
+    t.toggle_selected(&[0, 1]);
+    t.toggle_selected(&[1]);
+    assert_eq!(t.selected(), &[vec![0, 0], vec![0, 1, 0], vec![1]]);
+
+    t.focus(&[0, 1, 0]);
+    t.set_expanded(&[0], false);
+    assert_eq!(t.focused(), Some(&[0][..]));
+    assert_eq!(t.visible_paths(), vec![vec![0], vec![1]]);
+  }
+
+  #[test]
+  fn keyboard_navigation() {
+    reset_test_env!();
+
+    let (mut wnd, tree) = tree_wnd();
+    wnd.draw_frame();
+    wnd.request_next_focus();
+    wnd.draw_frame();
+
+    let press = |wnd: &mut TestWindow, key: NamedKey| {
+      wnd.processes_keyboard_event(
+        PhysicalKey::Code(KeyCode::Enter),
+        VirtualKey::Named(key),
+        false,
+        KeyLocation::Standard,
+        ElementState::Pressed,
+      );
+      wnd.draw_frame();
+    };
+
+    press(&mut wnd, NamedKey::ArrowDown);
+    assert_eq!(tree.read().focused(), Some(&[1][..]));
+    assert_eq!(tree.read().selected(), &[vec![1]]);
+
+    press(&mut wnd, NamedKey::ArrowUp);
+    press(&mut wnd, NamedKey::ArrowRight);
+    assert!(tree.read().is_expanded(&[0]));
+    press(&mut wnd, NamedKey::ArrowRight);
+    assert_eq!(tree.read().focused(), Some(&[0, 0][..]));
+    press(&mut wnd, NamedKey::ArrowLeft);
+    assert_eq!(tree.read().focused(), Some(&[0][..]));
+    press(&mut wnd, NamedKey::ArrowLeft);
+    assert!(!tree.read().is_expanded(&[0]));
+
+    press(&mut wnd, NamedKey::End);
+    assert_eq!(tree.read().focused(), Some(&[1][..]));
+    // The key events go to the focused node.
+    let focusing = wnd.focusing();
+    press(&mut wnd, NamedKey::Home);
+    assert_ne!(wnd.focusing(), focusing);
+  }
+}